@@ -154,7 +154,7 @@ export const BulkDataSetUploader: React.FC<BulkDataSetUploaderProps> = ({
           <input
             ref={fileInputRef}
             type="file"
-            accept=".csv,.tsv,.json"
+            accept=".csv,.tsv,.json,.dot,.gv"
             multiple
             onChange={handleFileInputChange}
             className="hidden"
@@ -126,7 +126,7 @@ export const DataSetUploader: React.FC<DataSetUploaderProps> = ({
     if (format === FileFormat.CSV || format === FileFormat.TSV) {
       return [DataType.NODES, DataType.EDGES, DataType.LAYERS]
     }
-    if (format === FileFormat.JSON) {
+    if (format === FileFormat.JSON || format === FileFormat.DOT) {
       return [DataType.GRAPH]
     }
     return []
@@ -395,6 +395,7 @@ export const DataSetUploader: React.FC<DataSetUploaderProps> = ({
       case FileFormat.TSV:
         return <IconFileTypeCsv size={24} color="green" />
       case FileFormat.JSON:
+      case FileFormat.DOT:
         return <IconFileText size={24} color="blue" />
       default:
         return <IconFile size={24} color="gray" />
@@ -481,7 +482,7 @@ export const DataSetUploader: React.FC<DataSetUploaderProps> = ({
             <IconAlertCircle className="h-4 w-4 text-orange-600" />
             <AlertTitle>Unsupported File Format</AlertTitle>
             <AlertDescription>
-              Please upload a CSV (.csv), TSV (.tsv), or JSON (.json) file.
+              Please upload a CSV (.csv), TSV (.tsv), JSON (.json), or Graphviz DOT (.dot, .gv) file.
             </AlertDescription>
           </Alert>
         )}
@@ -528,6 +529,7 @@ export const DataSetUploader: React.FC<DataSetUploaderProps> = ({
                   <SelectItem value={FileFormat.CSV}>{getFileFormatDisplayName(FileFormat.CSV)}</SelectItem>
                   <SelectItem value={FileFormat.TSV}>{getFileFormatDisplayName(FileFormat.TSV)}</SelectItem>
                   <SelectItem value={FileFormat.JSON}>{getFileFormatDisplayName(FileFormat.JSON)}</SelectItem>
+                  <SelectItem value={FileFormat.DOT}>{getFileFormatDisplayName(FileFormat.DOT)}</SelectItem>
                 </SelectContent>
               </Select>
               {selectedFile.formatOverride && (
@@ -690,7 +692,7 @@ export const DataSetUploader: React.FC<DataSetUploaderProps> = ({
         <input
           ref={bulkInputRef}
           type="file"
-          accept=".csv,.tsv,.json"
+          accept=".csv,.tsv,.json,.dot,.gv"
           multiple
           onChange={(e) => {
             const files = Array.from(e.target.files || [])
@@ -870,7 +872,7 @@ export const DataSetUploader: React.FC<DataSetUploaderProps> = ({
       <input
         ref={fileInputRef}
         type="file"
-        accept=".csv,.tsv,.json"
+        accept=".csv,.tsv,.json,.dot,.gv"
         style={{ display: 'none' }}
         onChange={handleFileInputChange}
       />
@@ -261,7 +261,7 @@ export const GraphArtefactNodeConfigForm: React.FC<GraphArtefactNodeConfigFormPr
               value={localConfig.renderConfig?.builtInStyles || 'light'}
               onValueChange={(value) => setLocalConfig(prev => ({
                 ...prev,
-                renderConfig: { ...(prev.renderConfig ?? {}), builtInStyles: value as 'none' | 'light' | 'dark' }
+                renderConfig: { ...(prev.renderConfig ?? {}), builtInStyles: value as 'none' | 'light' | 'dark' | 'blueprint' }
               }))}
             >
               <SelectTrigger id="built-in-style">
@@ -271,6 +271,7 @@ export const GraphArtefactNodeConfigForm: React.FC<GraphArtefactNodeConfigFormPr
                 <SelectItem value="none">None (engine defaults)</SelectItem>
                 <SelectItem value="light">Light</SelectItem>
                 <SelectItem value="dark">Dark</SelectItem>
+                <SelectItem value="blueprint">Blueprint (PlantUML)</SelectItem>
               </SelectContent>
             </Select>
             <p className="text-sm text-muted-foreground">
@@ -385,6 +385,7 @@ export enum FileFormat {
   CSV = 'CSV',
   TSV = 'TSV',
   JSON = 'JSON',
+  DOT = 'DOT',
 }
 
 // Data type enum (semantic meaning)
@@ -580,6 +581,9 @@ export const getFileFormatDisplayName = (format: string): string => {
     case 'JSON':
     case 'json':
       return 'JSON'
+    case 'DOT':
+    case 'dot':
+      return 'Graphviz DOT'
     default:
       return 'Unknown'
   }
@@ -625,6 +629,7 @@ export const detectFileFormat = (filename: string): FileFormat | null => {
   if (lower.endsWith('.csv')) return FileFormat.CSV
   if (lower.endsWith('.tsv')) return FileFormat.TSV
   if (lower.endsWith('.json')) return FileFormat.JSON
+  if (lower.endsWith('.dot') || lower.endsWith('.gv')) return FileFormat.DOT
   return null
 }
 
@@ -634,7 +639,7 @@ export const isValidFormatTypeCombination = (format: FileFormat, type: DataType)
       (type === DataType.NODES || type === DataType.EDGES || type === DataType.LAYERS)) {
     return true
   }
-  if (format === FileFormat.JSON && type === DataType.GRAPH) {
+  if ((format === FileFormat.JSON || format === FileFormat.DOT) && type === DataType.GRAPH) {
     return true
   }
   return false
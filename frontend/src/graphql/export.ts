@@ -7,6 +7,7 @@ export const EXPORT_NODE_OUTPUT: TypedDocumentNode<
     nodeId: string
     planId?: number | null
     renderConfig?: Record<string, unknown> | null
+    compress?: boolean | null
   }
 > = gql`
   mutation ExportNodeOutput(
@@ -14,18 +15,21 @@ export const EXPORT_NODE_OUTPUT: TypedDocumentNode<
     $nodeId: String!
     $planId: Int
     $renderConfig: RenderConfigInput
+    $compress: Boolean
   ) {
     exportNodeOutput(
       projectId: $projectId
       planId: $planId
       nodeId: $nodeId
       renderConfigOverride: $renderConfig
+      compress: $compress
     ) {
       success
       message
       content
       filename
       mimeType
+      contentEncoding
     }
   }
 `
@@ -36,4 +40,5 @@ export interface ExportNodeOutputResult {
   content: string // Base64 encoded content
   filename: string
   mimeType: string
+  contentEncoding?: string | null // 'gzip' when content is compressed
 }
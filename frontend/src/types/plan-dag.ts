@@ -140,7 +140,7 @@ export interface GraphArtefactNodeConfig {
     applyLayers?: boolean;
     useNodeWeight?: boolean;
     useEdgeWeight?: boolean;
-    builtInStyles?: 'none' | 'light' | 'dark';
+    builtInStyles?: 'none' | 'light' | 'dark' | 'blueprint';
     targetOptions?: RenderTargetOptions;
     addNodeCommentsAsNotes?: boolean;
     notePosition?: 'left' | 'right' | 'top' | 'bottom';
@@ -170,7 +170,7 @@ export interface TreeArtefactNodeConfig {
     applyLayers?: boolean;
     useNodeWeight?: boolean;
     useEdgeWeight?: boolean;
-    builtInStyles?: 'none' | 'light' | 'dark';
+    builtInStyles?: 'none' | 'light' | 'dark' | 'blueprint';
     targetOptions?: RenderTargetOptions;
     addNodeCommentsAsNotes?: boolean;
     notePosition?: 'left' | 'right' | 'top' | 'bottom';
@@ -216,7 +216,7 @@ export interface SequenceArtefactNodeConfig {
   outputPath: string;
   renderConfig?: {
     containNodes?: 'one' | 'all';
-    builtInStyles?: 'none' | 'light' | 'dark';
+    builtInStyles?: 'none' | 'light' | 'dark' | 'blueprint';
     showNotes?: boolean;
     renderAllSequences?: boolean;
     enabledSequenceIds?: number[];
@@ -442,6 +442,6 @@ export interface ValidationError {
 export interface ValidationWarning {
   nodeId?: string;
   edgeId?: string;
-  type: 'UnusedOutput' | 'PerformanceImpact' | 'ConfigurationSuggestion';
+  type: 'UnusedOutput' | 'PerformanceImpact' | 'ConfigurationSuggestion' | 'UnreachableNode';
   message: string;
 }
@@ -94,6 +94,7 @@ impl ConsoleContext {
     pub async fn list_projects(&self) -> Result<()> {
         let mut rows = Vec::new();
         let records = projects::Entity::find()
+            .filter(projects::Column::DeletedAt.is_null())
             .order_by_asc(projects::Column::Id)
             .all(&self.db)
             .await?;
@@ -0,0 +1,61 @@
+//! `layercake export` — run one exporter against a graph without a plan.
+//!
+//! The input is a graph JSON file or a directory of nodes/edges/layers CSV
+//! files; `-` reads graph JSON from stdin. The output goes to a file, or to
+//! stdout when it is `-` or omitted.
+
+use anyhow::{anyhow, Result};
+use layercake_core::plan::ExportFileType;
+use layercake_core::plan_execution;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Render `input` with the exporter named `format` and write it to `output`.
+pub fn run(input: &str, format: &str, output: Option<&str>) -> Result<()> {
+    let exporter = parse_format(format)?;
+
+    let graph = if input == "-" {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        plan_execution::graph_from_json(&content)?
+    } else {
+        plan_execution::load_graph(Path::new(input))?
+    };
+
+    let rendered = plan_execution::render_adhoc_export(&graph, &exporter)?;
+
+    match output {
+        None | Some("-") => {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(&rendered)?;
+            stdout.flush()?;
+        }
+        Some(path) => layercake_core::common::write_bytes_to_file(path, &rendered)?,
+    }
+
+    Ok(())
+}
+
+/// Match a built-in exporter by format id, ignoring case.
+fn parse_format(format: &str) -> Result<ExportFileType> {
+    let formats = ExportFileType::built_in();
+    formats
+        .iter()
+        .find(|candidate| {
+            candidate
+                .format_info()
+                .is_some_and(|info| info.id.eq_ignore_ascii_case(format))
+        })
+        .cloned()
+        .ok_or_else(|| {
+            let available: Vec<&str> = formats
+                .iter()
+                .filter_map(|candidate| candidate.format_info().map(|info| info.id))
+                .collect();
+            anyhow!(
+                "Unknown export format '{}'. Available formats: {}",
+                format,
+                available.join(", ")
+            )
+        })
+}
@@ -14,6 +14,7 @@ mod api;
 mod db_info;
 mod doc;
 mod doctor;
+mod export;
 mod query;
 mod query_payloads;
 mod schema_dump;
@@ -39,11 +40,27 @@ enum Commands {
         plan: String,
         #[clap(short, long)]
         watch: bool,
+        /// Milliseconds of quiet after a change before re-running in watch mode
+        #[clap(long, default_value = "300")]
+        debounce_ms: u64,
     },
     Init {
         #[clap(short, long)]
         plan: String,
     },
+    /// Render a graph with a single exporter, without writing a plan
+    Export {
+        /// Graph JSON file, directory of nodes/edges/layers CSV files, or `-`
+        /// for graph JSON on stdin
+        #[clap(short, long)]
+        input: String,
+        /// Export format id, e.g. DOT, Mermaid, PlantUML (case-insensitive)
+        #[clap(short, long)]
+        format: String,
+        /// Output file; `-` or omitted writes to stdout
+        #[clap(short, long)]
+        output: Option<String>,
+    },
     Generate {
         #[clap(subcommand)]
         command: GenerateCommands,
@@ -94,6 +111,9 @@ enum Commands {
         /// Show what would be done without making changes
         #[clap(long)]
         dry_run: bool,
+        /// Install without verifying the download's published SHA-256
+        #[clap(long)]
+        skip_verify: bool,
     },
     /// Print embedded agent-facing documentation (workflows, commands, guides)
     Doc {
@@ -266,9 +286,17 @@ async fn main() -> Result<()> {
     setup_logging(&args.log_level);
 
     match args.command {
-        Commands::Run { plan, watch } => {
+        Commands::Run {
+            plan,
+            watch,
+            debounce_ms,
+        } => {
             info!("Running plan: {}", plan);
-            plan_execution::execute_plan(plan, watch)?;
+            plan_execution::execute_plan_with_debounce(
+                plan,
+                watch,
+                std::time::Duration::from_millis(debounce_ms),
+            )?;
         }
         Commands::Init { plan } => {
             info!("Initializing plan: {}", plan);
@@ -277,6 +305,13 @@ async fn main() -> Result<()> {
             let serialized_plan = serde_yaml::to_string(&plan)?;
             common::write_string_to_file(&plan_file_path, &serialized_plan)?;
         }
+        Commands::Export {
+            input,
+            format,
+            output,
+        } => {
+            export::run(&input, &format, output.as_deref())?;
+        }
         Commands::Generate { command } => match command {
             GenerateCommands::Template { name } => {
                 info!("Generating template: {}", name);
@@ -330,6 +365,7 @@ async fn main() -> Result<()> {
             backup,
             rollback,
             dry_run,
+            skip_verify,
         } => {
             let update_cmd = update::command::UpdateCommand {
                 check_only: check,
@@ -340,7 +376,7 @@ async fn main() -> Result<()> {
                 backup,
                 rollback,
                 dry_run,
-                skip_verify: false,
+                skip_verify,
             };
             update_cmd.execute().await?;
         }
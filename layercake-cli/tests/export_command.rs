@@ -0,0 +1,93 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const GRAPH_JSON: &str = r#"{
+  "nodes": [
+    { "id": "api", "label": "API", "layer": "service", "is_partition": false, "weight": 1 },
+    { "id": "db", "label": "Database", "layer": "storage", "is_partition": false, "weight": 1 }
+  ],
+  "edges": [
+    { "id": "api_db", "source": "api", "target": "db", "label": "reads", "layer": "service", "weight": 1 }
+  ],
+  "layers": [
+    { "id": "service", "label": "Service", "background_color": "ffffff", "text_color": "000000", "border_color": "000000" },
+    { "id": "storage", "label": "Storage", "background_color": "eeeeee", "text_color": "000000", "border_color": "000000" }
+  ]
+}"#;
+
+fn layercake() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_layercake"))
+}
+
+fn assert_dot(dot: &str) {
+    assert!(dot.contains("digraph"), "not DOT output:\n{dot}");
+    assert!(dot.contains("api"), "missing node api:\n{dot}");
+    assert!(dot.contains("db"), "missing node db:\n{dot}");
+    assert!(dot.contains("api -> db"), "missing edge api -> db:\n{dot}");
+}
+
+#[test]
+fn export_converts_graph_json_file_to_dot_file() {
+    let dir = std::env::temp_dir().join(format!("layercake-export-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("graph.json");
+    let output = dir.join("graph.dot");
+    std::fs::write(&input, GRAPH_JSON).unwrap();
+
+    let status = layercake()
+        .args(["export", "-i"])
+        .arg(&input)
+        .args(["-f", "dot", "-o"])
+        .arg(&output)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let dot = std::fs::read_to_string(&output).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_dot(&dot);
+}
+
+#[test]
+fn export_reads_stdin_and_writes_stdout() {
+    let mut child = layercake()
+        .args(["export", "-i", "-", "-f", "DOT", "-o", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(GRAPH_JSON.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert_dot(&String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn export_rejects_unknown_format_and_lists_available_ones() {
+    let mut child = layercake()
+        .args(["export", "-i", "-", "-f", "bogus"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(GRAPH_JSON.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Unknown export format 'bogus'"), "{stderr}");
+    assert!(stderr.contains("Available formats"), "{stderr}");
+    assert!(stderr.contains("DOT"), "{stderr}");
+}
@@ -1,16 +1,33 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
+use super::DatasetSchemaSummary;
 use super::{AppContext, DataSetSummary, DataSetValidationSummary, GraphValidationSummary};
 use super::{BulkDataSetUpload, DataSetEmptyCreateRequest, DataSetFileCreateRequest};
 use super::{DataSetExportFormat, DataSetExportRequest, DataSetExportResult, DataSetUpdateRequest};
 use super::{DataSetImportFormat, DataSetImportOutcome, DataSetImportRequest};
 use crate::auth::Actor;
 use crate::database::entities::data_sets;
+use crate::database::entities::graph_data;
+use crate::dataset_schema::DatasetSchema;
 use crate::errors::{CoreError, CoreResult};
+use crate::graph_merge::{
+    graph_from_data_set_json, merge_graphs, ConflictResolution, MergeStrategy,
+};
+use crate::services::graph_data_service::GraphDataService;
+
+/// Default grace period before an unreferenced DataSet counts as orphaned.
+const ORPHANED_DATA_SET_GRACE_HOURS: i64 = 24;
+
+fn orphan_cutoff(min_age_hours: Option<i64>) -> chrono::DateTime<chrono::Utc> {
+    let hours = min_age_hours
+        .unwrap_or(ORPHANED_DATA_SET_GRACE_HOURS)
+        .max(0);
+    chrono::Utc::now() - chrono::Duration::hours(hours)
+}
 
 impl AppContext {
     pub async fn list_data_sets(&self, project_id: i32) -> CoreResult<Vec<DataSetSummary>> {
@@ -55,12 +72,13 @@ impl AppContext {
             file_format,
             tabular_data_type,
             file_bytes,
+            strict,
         } = request;
         self.authorize_project_write(actor, project_id).await?;
 
-        let created = self
+        let (created, _) = self
             .data_set_service
-            .create_from_file(
+            .create_from_file_with_validation(
                 project_id,
                 name,
                 description,
@@ -68,6 +86,7 @@ impl AppContext {
                 file_format,
                 file_bytes,
                 tabular_data_type,
+                strict,
             )
             .await?;
 
@@ -185,6 +204,85 @@ impl AppContext {
             .map_err(|e| CoreError::internal(format!("Failed to validate data set {}: {}", id, e)))
     }
 
+    /// Check a DataSet against `schema`. The DataSet is not modified.
+    pub async fn validate_data_set_against_schema(
+        &self,
+        id: i32,
+        schema: &DatasetSchema,
+    ) -> CoreResult<DataSetValidationSummary> {
+        self.data_set_service
+            .validate_against_schema(id, schema)
+            .await
+    }
+
+    /// Check a DataSet against a schema saved in the same project.
+    pub async fn validate_data_set_against_saved_schema(
+        &self,
+        actor: &Actor,
+        id: i32,
+        schema_id: i32,
+    ) -> CoreResult<DataSetValidationSummary> {
+        let data_set = self
+            .get_data_set(id)
+            .await?
+            .ok_or_else(|| CoreError::not_found("DataSet", id.to_string()))?;
+        let schema = self.get_dataset_schema(actor, schema_id).await?;
+        if schema.project_id != data_set.project_id {
+            return Err(CoreError::validation(
+                "the dataset schema belongs to a different project than the data set",
+            ));
+        }
+        self.validate_data_set_against_schema(id, &schema.schema)
+            .await
+    }
+
+    pub async fn list_dataset_schemas(
+        &self,
+        actor: &Actor,
+        project_id: i32,
+    ) -> CoreResult<Vec<DatasetSchemaSummary>> {
+        self.authorize_project_read(actor, project_id).await?;
+        self.dataset_schema_service
+            .list(project_id)
+            .await?
+            .into_iter()
+            .map(DatasetSchemaSummary::from_model)
+            .collect()
+    }
+
+    pub async fn get_dataset_schema(
+        &self,
+        actor: &Actor,
+        id: i32,
+    ) -> CoreResult<DatasetSchemaSummary> {
+        let model = self.dataset_schema_service.get(id).await?;
+        self.authorize_project_read(actor, model.project_id).await?;
+        DatasetSchemaSummary::from_model(model)
+    }
+
+    /// Save `schema` under `name`, replacing an existing schema of that name.
+    pub async fn save_dataset_schema(
+        &self,
+        actor: &Actor,
+        project_id: i32,
+        name: &str,
+        schema: &DatasetSchema,
+    ) -> CoreResult<DatasetSchemaSummary> {
+        self.authorize_project_write(actor, project_id).await?;
+        let model = self
+            .dataset_schema_service
+            .save(project_id, name, schema)
+            .await?;
+        DatasetSchemaSummary::from_model(model)
+    }
+
+    pub async fn delete_dataset_schema(&self, actor: &Actor, id: i32) -> CoreResult<()> {
+        let model = self.dataset_schema_service.get(id).await?;
+        self.authorize_project_write(actor, model.project_id)
+            .await?;
+        self.dataset_schema_service.delete(id).await
+    }
+
     pub async fn validate_graph(&self, graph_id: i32) -> CoreResult<GraphValidationSummary> {
         self.graph_service
             .validate_graph(graph_id)
@@ -199,6 +297,34 @@ impl AppContext {
         self.data_set_service.delete(id).await
     }
 
+    /// DataSets no plan DAG node in the project references. DataSets younger
+    /// than `min_age_hours` (default 24) are left out.
+    pub async fn orphaned_data_sets(
+        &self,
+        project_id: i32,
+        min_age_hours: Option<i64>,
+    ) -> CoreResult<Vec<DataSetSummary>> {
+        let data_sets = self
+            .data_set_service
+            .find_orphaned(project_id, orphan_cutoff(min_age_hours))
+            .await?;
+
+        Ok(data_sets.into_iter().map(DataSetSummary::from).collect())
+    }
+
+    /// Delete the DataSets `orphaned_data_sets` reports, returning their IDs.
+    pub async fn delete_orphaned_data_sets(
+        &self,
+        actor: &Actor,
+        project_id: i32,
+        min_age_hours: Option<i64>,
+    ) -> CoreResult<Vec<i32>> {
+        self.authorize_project_write(actor, project_id).await?;
+        self.data_set_service
+            .delete_orphaned(project_id, orphan_cutoff(min_age_hours))
+            .await
+    }
+
     pub async fn merge_data_sets(
         &self,
         actor: &Actor,
@@ -375,6 +501,84 @@ impl AppContext {
             .map_err(|e| CoreError::internal(format!("Failed to serialize merged data: {}", e)))
     }
 
+    /// Join the graphs of several distinct data sets on node id and persist
+    /// the result as a new computed graph. Source order matters for the
+    /// `Left`/`Right` strategies and the `PreferFirst`/`PreferLast` conflict
+    /// policies.
+    pub async fn merge_data_sets_into_graph(
+        &self,
+        actor: &Actor,
+        project_id: i32,
+        source_ids: Vec<i32>,
+        strategy: MergeStrategy,
+        resolution: ConflictResolution,
+    ) -> CoreResult<graph_data::Model> {
+        self.authorize_project_write(actor, project_id).await?;
+        if source_ids.len() < 2 {
+            return Err(CoreError::validation(
+                "At least 2 data sets are required for merging",
+            ));
+        }
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = source_ids.iter().find(|id| !seen.insert(**id)) {
+            return Err(CoreError::validation(format!(
+                "Data set {} is listed more than once for merging",
+                duplicate
+            )));
+        }
+
+        let models = data_sets::Entity::find()
+            .filter(data_sets::Column::Id.is_in(source_ids.clone()))
+            .filter(data_sets::Column::ProjectId.eq(project_id))
+            .all(&self.db)
+            .await
+            .map_err(|e| {
+                CoreError::internal(format!("Failed to load data sets for merging: {}", e))
+            })?;
+
+        let mut graphs = Vec::with_capacity(source_ids.len());
+        let mut names = Vec::with_capacity(source_ids.len());
+        for id in &source_ids {
+            let model = models.iter().find(|m| m.id == *id).ok_or_else(|| {
+                CoreError::validation(format!(
+                    "Data set {} was not found or doesn't belong to project {}",
+                    id, project_id
+                ))
+            })?;
+            let graph = graph_from_data_set_json(&model.graph_json).map_err(|e| {
+                CoreError::validation(format!("Data set {} has invalid graph data: {}", id, e))
+            })?;
+            graphs.push(graph);
+            names.push(model.name.clone());
+        }
+
+        let name = format!("Merged: {}", names.join(" + "));
+        let merged = merge_graphs(&name, &graphs, strategy, resolution).map_err(|conflict| {
+            let mut fields = BTreeMap::new();
+            fields.insert(
+                "conflictingNodeIds".to_string(),
+                conflict.node_ids.join(","),
+            );
+            fields.insert(
+                "conflictingEdgeIds".to_string(),
+                conflict.edge_ids.join(","),
+            );
+            CoreError::conflict(conflict.to_string()).with_fields(fields)
+        })?;
+
+        GraphDataService::new(self.db.clone())
+            .create_computed_from_graph(
+                project_id,
+                &merged,
+                Some(json!({
+                    "mergedDataSetIds": source_ids,
+                    "mergeStrategy": strategy,
+                    "conflictResolution": resolution,
+                })),
+            )
+            .await
+    }
+
     pub async fn export_data_sets(
         &self,
         actor: &Actor,
@@ -384,6 +588,7 @@ impl AppContext {
             project_id,
             data_set_ids,
             format,
+            group_by_layer,
         } = request;
         self.authorize_project_read(actor, project_id).await?;
 
@@ -407,6 +612,18 @@ impl AppContext {
         }
 
         let bytes = match format {
+            DataSetExportFormat::Xlsx if group_by_layer => self
+                .data_set_bulk_service
+                .export_to_xlsx_by_layer(&data_set_ids)
+                .await
+                .map_err(|e| {
+                    CoreError::internal(format!("Failed to export datasets to XLSX: {}", e))
+                })?,
+            DataSetExportFormat::Ods if group_by_layer => {
+                return Err(CoreError::validation(
+                    "Grouping by layer is only supported for XLSX exports",
+                ))
+            }
             DataSetExportFormat::Xlsx => self
                 .data_set_bulk_service
                 .export_to_xlsx(&data_set_ids)
@@ -1,7 +1,11 @@
 use super::{AppContext, GraphNodeUpdateRequest};
 use crate::auth::Actor;
 use crate::errors::{CoreError, CoreResult};
-use crate::services::graph_analysis_service::GraphConnectivityReport;
+use crate::graph::{PathAlgorithm, WeightNormalization};
+use crate::services::graph_analysis_service::{
+    BetweennessReport, ComponentSummary, GraphConnectivityReport, GraphSummaryMetrics,
+    LayerConnectivityReport, ShortestPathReport, TopologyReport,
+};
 use crate::services::graph_edit_service::ReplaySummary as GraphEditReplaySummary;
 use serde_json::{json, Value};
 impl AppContext {
@@ -160,6 +164,31 @@ impl AppContext {
             )
             .await
     }
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_project_layer(
+        &self,
+        actor: &Actor,
+        project_id: i32,
+        layer_id: String,
+        name: Option<String>,
+        background_color: Option<String>,
+        text_color: Option<String>,
+        border_color: Option<String>,
+        alias: Option<String>,
+    ) -> CoreResult<Vec<crate::database::entities::project_layers::Model>> {
+        self.authorize_project_write(actor, project_id).await?;
+        self.graph_service
+            .update_project_layer(
+                project_id,
+                &layer_id,
+                name,
+                background_color,
+                text_color,
+                border_color,
+                alias,
+            )
+            .await
+    }
     pub async fn delete_project_layer(
         &self,
         actor: &Actor,
@@ -479,6 +508,49 @@ impl AppContext {
         service.prune_orphaned_graphs(project_id).await
     }
 
+    /// Store a named copy of a graph's current contents; needs write access.
+    pub async fn snapshot_graph(
+        &self,
+        actor: &Actor,
+        graph_data_id: i32,
+        label: String,
+    ) -> CoreResult<crate::database::entities::graph_snapshots::Model> {
+        self.authorize_graph_write(actor, graph_data_id).await?;
+        self.graph_snapshot_service
+            .create(graph_data_id, &label)
+            .await
+    }
+
+    pub async fn list_graph_snapshots(
+        &self,
+        actor: &Actor,
+        graph_data_id: i32,
+    ) -> CoreResult<Vec<crate::database::entities::graph_snapshots::Model>> {
+        let project_id = self.project_id_for_graph(graph_data_id).await?;
+        self.authorize_project_read(actor, project_id).await?;
+        self.graph_snapshot_service.list(graph_data_id).await
+    }
+
+    /// Put a snapshot's contents back into the graph it was taken from.
+    pub async fn restore_graph_snapshot(
+        &self,
+        actor: &Actor,
+        snapshot_id: i32,
+    ) -> CoreResult<crate::database::entities::graph_data::Model> {
+        let snapshot = self.graph_snapshot_service.get(snapshot_id).await?;
+        self.authorize_graph_write(actor, snapshot.graph_data_id)
+            .await?;
+        self.graph_snapshot_service.restore(snapshot_id).await
+    }
+
+    /// Remove a snapshot; blobs it shared with other snapshots are kept.
+    pub async fn delete_graph_snapshot(&self, actor: &Actor, snapshot_id: i32) -> CoreResult<()> {
+        let snapshot = self.graph_snapshot_service.get(snapshot_id).await?;
+        self.authorize_graph_write(actor, snapshot.graph_data_id)
+            .await?;
+        self.graph_snapshot_service.delete(snapshot_id).await
+    }
+
     pub async fn replay_graph_data_edits(
         &self,
         actor: &Actor,
@@ -592,4 +664,130 @@ impl AppContext {
             .find_paths(graph_id, &source_node, &target_node, max_paths)
             .await
     }
+    /// Diff two computed graphs of the same project; read access is
+    /// authorized on that project.
+    pub async fn diff_computed_graphs(
+        &self,
+        actor: &Actor,
+        graph_id_a: i32,
+        graph_id_b: i32,
+    ) -> CoreResult<crate::graph_diff::GraphDiff> {
+        let project_id = self.project_id_for_graph(graph_id_a).await?;
+        if self.project_id_for_graph(graph_id_b).await? != project_id {
+            return Err(CoreError::validation(
+                "the two graphs belong to different projects",
+            ));
+        }
+        self.authorize_project_read(actor, project_id).await?;
+        self.graph_analysis_service
+            .diff_graphs(graph_id_a, graph_id_b)
+            .await
+    }
+    /// Materialise PageRank scores as a new computed graph in the source
+    /// graph's project, which requires write access to it.
+    pub async fn annotate_graph_pagerank(
+        &self,
+        actor: &Actor,
+        graph_id: i32,
+        damping: f64,
+        iterations: usize,
+    ) -> CoreResult<crate::database::entities::graph_data::Model> {
+        let project_id = self.project_id_for_graph(graph_id).await?;
+        self.authorize_project_write(actor, project_id).await?;
+        self.graph_analysis_service
+            .annotate_pagerank(graph_id, damping, iterations)
+            .await
+    }
+    /// Materialise normalized edge weights as a new computed graph in the
+    /// source graph's project, which requires write access to it.
+    pub async fn normalize_graph_edge_weights(
+        &self,
+        actor: &Actor,
+        graph_id: i32,
+        method: WeightNormalization,
+        range: (i32, i32),
+        preserve_zero: bool,
+    ) -> CoreResult<crate::database::entities::graph_data::Model> {
+        let project_id = self.project_id_for_graph(graph_id).await?;
+        self.authorize_project_write(actor, project_id).await?;
+        self.graph_analysis_service
+            .normalize_edge_weights(graph_id, method, range, preserve_zero)
+            .await
+    }
+    pub async fn graph_summary_metrics(
+        &self,
+        actor: &Actor,
+        graph_id: i32,
+    ) -> CoreResult<GraphSummaryMetrics> {
+        let project_id = self.project_id_for_graph(graph_id).await?;
+        self.authorize_project_read(actor, project_id).await?;
+        self.graph_analysis_service.summary_metrics(graph_id).await
+    }
+    pub async fn graph_connected_components(
+        &self,
+        actor: &Actor,
+        graph_id: i32,
+        directed: bool,
+    ) -> CoreResult<ComponentSummary> {
+        let project_id = self.project_id_for_graph(graph_id).await?;
+        self.authorize_project_read(actor, project_id).await?;
+        self.graph_analysis_service
+            .connected_components(graph_id, directed)
+            .await
+    }
+    pub async fn graph_topology(
+        &self,
+        actor: &Actor,
+        graph_id: i32,
+        directed: bool,
+    ) -> CoreResult<TopologyReport> {
+        let project_id = self.project_id_for_graph(graph_id).await?;
+        self.authorize_project_read(actor, project_id).await?;
+        self.graph_analysis_service
+            .topology(graph_id, directed)
+            .await
+    }
+    pub async fn graph_betweenness_centrality(
+        &self,
+        actor: &Actor,
+        graph_id: i32,
+        weighted: bool,
+        directed: bool,
+        normalized: bool,
+    ) -> CoreResult<BetweennessReport> {
+        let project_id = self.project_id_for_graph(graph_id).await?;
+        self.authorize_project_read(actor, project_id).await?;
+        self.graph_analysis_service
+            .betweenness_centrality(graph_id, weighted, directed, normalized)
+            .await
+    }
+    pub async fn shortest_graph_path(
+        &self,
+        actor: &Actor,
+        graph_id: i32,
+        source_node: String,
+        target_node: String,
+        algorithm: PathAlgorithm,
+        weighted: bool,
+    ) -> CoreResult<ShortestPathReport> {
+        let project_id = self.project_id_for_graph(graph_id).await?;
+        self.authorize_project_read(actor, project_id).await?;
+        self.graph_analysis_service
+            .shortest_path(graph_id, &source_node, &target_node, algorithm, weighted)
+            .await
+    }
+
+    pub async fn graph_layer_connectivity(
+        &self,
+        actor: &Actor,
+        graph_id: i32,
+        layer_a: String,
+        layer_b: String,
+    ) -> CoreResult<LayerConnectivityReport> {
+        let project_id = self.project_id_for_graph(graph_id).await?;
+        self.authorize_project_read(actor, project_id).await?;
+        self.graph_analysis_service
+            .layer_connectivity(graph_id, &layer_a, &layer_b)
+            .await
+    }
 }
@@ -933,7 +933,7 @@ impl AppContext {
         })?;
 
         // Remove the existing project and re-import using the same ID
-        self.delete_project(actor, project_id).await?;
+        self.purge_project(actor, project_id).await?;
 
         let archive_bytes = archive_directory(Path::new(&path))
             .map_err(|e| CoreError::internal(format!("Failed to archive directory: {}", e)))?;
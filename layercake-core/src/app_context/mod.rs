@@ -5,14 +5,19 @@ use sea_orm::{DatabaseConnection, EntityTrait};
 use serde::{Deserialize, Serialize};
 
 use crate::auth::{Actor, AllowAllAuthorizer, Authorizer};
-use crate::database::entities::{data_sets, graph_data, plans, projects};
+use crate::database::entities::{data_sets, dataset_schemas, graph_data, plans, projects};
+use crate::dataset_schema::DatasetSchema;
 use crate::errors::{CoreError, CoreResult};
+use crate::pipeline::PlanExecutionRegistry;
+use crate::services::dataset_schema_service::parse_schema;
 use crate::services::graph_analysis_service::GraphAnalysisService;
 use crate::services::graph_edit_service::GraphEditService;
+use crate::services::graph_snapshot_service::GraphSnapshotService;
 use crate::services::plan_service::PlanService;
 use crate::services::{
     data_set_service::DataSetService, dataset_bulk_service::DataSetBulkService,
-    AuthorizationService, ExportService, GraphService, ImportService, PlanDagService, ProjectRole,
+    dataset_schema_service::DatasetSchemaService, AuthorizationService, ExportService,
+    GraphService, ImportService, PlanDagService, ProjectRole,
 };
 mod data_set_operations;
 mod graph_operations;
@@ -33,10 +38,13 @@ pub struct AppContext {
     graph_service: Arc<GraphService>,
     data_set_service: Arc<DataSetService>,
     data_set_bulk_service: Arc<DataSetBulkService>,
+    dataset_schema_service: Arc<DatasetSchemaService>,
     plan_dag_service: Arc<PlanDagService>,
     plan_service: Arc<PlanService>,
     graph_edit_service: Arc<GraphEditService>,
     graph_analysis_service: Arc<GraphAnalysisService>,
+    graph_snapshot_service: Arc<GraphSnapshotService>,
+    plan_executions: Arc<PlanExecutionRegistry>,
 }
 
 impl AppContext {
@@ -55,8 +63,10 @@ impl AppContext {
         let plan_service = Arc::new(PlanService::new(db.clone()));
         let graph_edit_service = Arc::new(GraphEditService::new(db.clone()));
         let graph_analysis_service = Arc::new(GraphAnalysisService::new(db.clone()));
+        let graph_snapshot_service = Arc::new(GraphSnapshotService::new(db.clone()));
         let data_set_service = Arc::new(DataSetService::new(db.clone()));
         let data_set_bulk_service = Arc::new(DataSetBulkService::new(db.clone()));
+        let dataset_schema_service = Arc::new(DatasetSchemaService::new(db.clone()));
 
         Self {
             db,
@@ -66,10 +76,13 @@ impl AppContext {
             graph_service,
             data_set_service,
             data_set_bulk_service,
+            dataset_schema_service,
             plan_dag_service,
             plan_service,
             graph_edit_service,
             graph_analysis_service,
+            graph_snapshot_service,
+            plan_executions: Arc::new(PlanExecutionRegistry::new()),
         }
     }
 
@@ -200,6 +213,11 @@ impl AppContext {
         &self.plan_service
     }
 
+    /// Registry of running plan executions, used to cancel them.
+    pub fn plan_executions(&self) -> &Arc<PlanExecutionRegistry> {
+        &self.plan_executions
+    }
+
     /// Public authorization gate for project write access. Callers that run
     /// operations directly (e.g. DAG execution mutations that bypass the
     /// service layer) must call this before mutating project state.
@@ -234,6 +252,7 @@ pub struct ProjectSummary {
     pub import_export_path: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<projects::Model> for ProjectSummary {
@@ -247,10 +266,21 @@ impl From<projects::Model> for ProjectSummary {
             import_export_path: model.import_export_path,
             created_at: model.created_at,
             updated_at: model.updated_at,
+            deleted_at: model.deleted_at,
         }
     }
 }
 
+/// How a project tag filter combines multiple requested tags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TagMatchMode {
+    /// Match projects carrying at least one of the requested tags.
+    #[default]
+    Any,
+    /// Match only projects carrying every requested tag.
+    All,
+}
+
 #[derive(Clone)]
 pub struct ProjectArchiveFile {
     pub filename: String,
@@ -394,6 +424,32 @@ pub struct DataSetValidationSummary {
     pub checked_at: DateTime<Utc>,
 }
 
+/// A dataset schema saved under a name in a project
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetSchemaSummary {
+    pub id: i32,
+    pub project_id: i32,
+    pub name: String,
+    pub schema: DatasetSchema,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DatasetSchemaSummary {
+    fn from_model(model: dataset_schemas::Model) -> CoreResult<Self> {
+        let schema = parse_schema(&model)?;
+        Ok(Self {
+            id: model.id,
+            project_id: model.project_id,
+            name: model.name,
+            schema,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        })
+    }
+}
+
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GraphValidationSummary {
@@ -421,6 +477,9 @@ pub struct DataSetFileCreateRequest {
     pub file_format: DataSetFileFormat,
     pub tabular_data_type: Option<DataSetDataType>,
     pub file_bytes: Vec<u8>,
+    /// Validate delimited rows: `Some(true)` rejects the file on any bad row,
+    /// `Some(false)` skips bad rows and records them as an annotation.
+    pub strict: Option<bool>,
 }
 
 #[derive(Clone)]
@@ -472,6 +531,8 @@ pub struct DataSetExportRequest {
     pub project_id: i32,
     pub data_set_ids: Vec<i32>,
     pub format: DataSetExportFormat,
+    /// XLSX only: one sheet per layer plus `edges` and `summary` sheets.
+    pub group_by_layer: bool,
 }
 
 #[derive(Clone)]
@@ -571,7 +632,6 @@ pub struct GraphNodeUpdateRequest {
     pub belongs_to: Option<String>,
 }
 
-
 pub fn summarize_graph_counts(graph_json: &str) -> (Option<usize>, Option<usize>, Option<usize>) {
     serde_json::from_str::<Value>(graph_json)
         .ok()
@@ -77,6 +77,17 @@ impl AppContext {
         Ok(PlanSummary::from(plan))
     }
 
+    /// Signal every running execution of the project's plans to stop at the
+    /// next node boundary. Returns the ids of the plans that were signalled.
+    pub async fn cancel_plan_execution(
+        &self,
+        actor: &Actor,
+        project_id: i32,
+    ) -> CoreResult<Vec<i32>> {
+        self.authorize_project_write(actor, project_id).await?;
+        Ok(self.plan_executions.cancel_project(project_id))
+    }
+
     pub async fn resolve_plan_model(
         &self,
         project_id: i32,
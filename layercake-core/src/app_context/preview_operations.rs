@@ -10,7 +10,10 @@ use crate::sequence_context::{apply_render_config, build_story_context};
 fn apply_preview_limit(content: String, format: ExportFileType, max_rows: Option<usize>) -> String {
     match (format, max_rows) {
         (
-            ExportFileType::CSVNodes | ExportFileType::CSVEdges | ExportFileType::CSVMatrix,
+            ExportFileType::CSVNodes
+            | ExportFileType::CSVEdges
+            | ExportFileType::CSVMatrix
+            | ExportFileType::CSVAdjacency,
             Some(limit),
         ) => {
             let mut limited_lines = Vec::new();
@@ -1,15 +1,28 @@
-use chrono::Utc;
-use sea_orm::{ActiveModelTrait, EntityTrait, QueryOrder, Set};
+use std::collections::HashMap;
 
-use super::{AppContext, ProjectSummary, ProjectUpdate};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+use serde_json::{json, Value};
+
+use super::{AppContext, ProjectSummary, ProjectUpdate, TagMatchMode};
 use crate::auth::Actor;
-use crate::database::entities::projects;
+use crate::database::entities::{
+    data_sets, dataset_schemas, graph_data, graph_data_edges, graph_data_nodes, graph_edits,
+    layer_aliases, plan_dag_annotations, plan_dag_edges, plan_dag_nodes, plans, project_layers,
+    projects,
+};
 use crate::errors::{CoreError, CoreResult};
+use crate::services::graph_snapshot_service::GraphSnapshotService;
+use crate::services::plan_service::PlanService;
 
 impl AppContext {
     // ----- Project helpers -------------------------------------------------
     pub async fn list_projects(&self) -> CoreResult<Vec<ProjectSummary>> {
         let projects = projects::Entity::find()
+            .filter(projects::Column::DeletedAt.is_null())
             .order_by_desc(projects::Column::UpdatedAt)
             .all(&self.db)
             .await
@@ -21,32 +34,42 @@ impl AppContext {
     pub async fn list_projects_filtered(
         &self,
         tags: Option<Vec<String>>,
+        tag_match: TagMatchMode,
     ) -> CoreResult<Vec<ProjectSummary>> {
         let projects = projects::Entity::find()
+            .filter(projects::Column::DeletedAt.is_null())
             .order_by_desc(projects::Column::UpdatedAt)
             .all(&self.db)
             .await
             .map_err(|e| CoreError::internal(format!("Failed to list projects: {}", e)))?;
 
-        // If tags filter is provided, filter projects by tags
-        let filtered_projects = if let Some(filter_tags) = tags {
-            if filter_tags.is_empty() {
-                projects
-            } else {
-                projects
-                    .into_iter()
-                    .filter(|project| {
-                        let project_tags: Vec<String> =
-                            serde_json::from_str(&project.tags).unwrap_or_default();
-                        // Check if any filter tag matches any project tag
-                        filter_tags
-                            .iter()
-                            .any(|filter_tag| project_tags.contains(filter_tag))
-                    })
-                    .collect()
-            }
+        // Tags are compared trimmed and case-insensitively; blank filter tags are ignored
+        let filter_tags: Vec<String> = tags
+            .unwrap_or_default()
+            .iter()
+            .map(|tag| tag.trim().to_lowercase())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+
+        let filtered_projects = if filter_tags.is_empty() {
+            projects
         } else {
             projects
+                .into_iter()
+                .filter(|project| {
+                    let project_tags: Vec<String> =
+                        serde_json::from_str::<Vec<String>>(&project.tags)
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|tag| tag.trim().to_lowercase())
+                            .collect();
+                    let has_tag = |filter_tag: &String| project_tags.contains(filter_tag);
+                    match tag_match {
+                        TagMatchMode::Any => filter_tags.iter().any(has_tag),
+                        TagMatchMode::All => filter_tags.iter().all(has_tag),
+                    }
+                })
+                .collect()
         };
 
         Ok(filtered_projects
@@ -55,6 +78,20 @@ impl AppContext {
             .collect())
     }
 
+    /// Projects in the trash, most recently deleted first.
+    pub async fn list_deleted_projects(&self) -> CoreResult<Vec<ProjectSummary>> {
+        let projects = projects::Entity::find()
+            .filter(projects::Column::DeletedAt.is_not_null())
+            .order_by_desc(projects::Column::DeletedAt)
+            .all(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to list deleted projects: {}", e)))?;
+
+        Ok(projects.into_iter().map(ProjectSummary::from).collect())
+    }
+
+    /// Look up a project by ID, including soft-deleted ones (check
+    /// `deleted_at`) so they can still be shown and restored.
     pub async fn get_project(&self, id: i32) -> CoreResult<Option<ProjectSummary>> {
         let project = projects::Entity::find_by_id(id)
             .one(&self.db)
@@ -185,6 +222,377 @@ impl AppContext {
         Ok(ProjectSummary::from(project))
     }
 
+    /// Deep-copy a project into a new one: datasets and dataset schemas,
+    /// plans with their DAGs and annotations, computed graphs (with edit
+    /// history), the layer palette and layer aliases. Plan DAG node ids are
+    /// regenerated and every reference to a copied dataset, layer, graph or
+    /// DAG node is remapped. Collaborators are not copied, and neither are
+    /// graph snapshots: they record the source project's history and their
+    /// blobs name its datasets. The copy happens in a single transaction.
+    pub async fn clone_project(
+        &self,
+        actor: &Actor,
+        source_id: i32,
+        new_name: String,
+    ) -> CoreResult<ProjectSummary> {
+        self.authorize_project_read(actor, source_id).await?;
+        self.authorize(actor, "write:project")?;
+
+        let new_name = new_name.trim().to_string();
+        if new_name.is_empty() {
+            return Err(CoreError::validation("Project name cannot be empty"));
+        }
+
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to begin transaction: {}", e)))?;
+
+        let source = projects::Entity::find_by_id(source_id)
+            .one(&txn)
+            .await
+            .map_err(|e| {
+                CoreError::internal(format!("Failed to load project {}: {}", source_id, e))
+            })?
+            .ok_or_else(|| CoreError::not_found("Project", source_id.to_string()))?;
+
+        let now = Utc::now();
+        let project = projects::ActiveModel {
+            name: Set(new_name),
+            description: Set(source.description.clone()),
+            tags: Set(source.tags.clone()),
+            import_export_path: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| CoreError::internal(format!("Failed to create project: {}", e)))?;
+
+        // Datasets
+        let mut data_set_ids = HashMap::new();
+        let source_data_sets = data_sets::Entity::find()
+            .filter(data_sets::Column::ProjectId.eq(source_id))
+            .all(&txn)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load data sets: {}", e)))?;
+        for data_set in source_data_sets {
+            let old_id = data_set.id;
+            let mut model: data_sets::ActiveModel = data_set.into();
+            model.id = NotSet;
+            model.project_id = Set(project.id);
+            model.created_at = Set(now);
+            model.updated_at = Set(now);
+            let copy = model.insert(&txn).await.map_err(|e| {
+                CoreError::internal(format!("Failed to copy data set {}: {}", old_id, e))
+            })?;
+            data_set_ids.insert(old_id, copy.id);
+        }
+
+        let source_schemas = dataset_schemas::Entity::find()
+            .filter(dataset_schemas::Column::ProjectId.eq(source_id))
+            .all(&txn)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load dataset schemas: {}", e)))?;
+        for schema in source_schemas {
+            let old_id = schema.id;
+            let mut model: dataset_schemas::ActiveModel = schema.into();
+            model.id = NotSet;
+            model.project_id = Set(project.id);
+            model.created_at = Set(now);
+            model.updated_at = Set(now);
+            model.insert(&txn).await.map_err(|e| {
+                CoreError::internal(format!("Failed to copy dataset schema {}: {}", old_id, e))
+            })?;
+        }
+
+        // Layer palette and aliases
+        let mut layer_ids = HashMap::new();
+        let source_layers = project_layers::Entity::find()
+            .filter(project_layers::Column::ProjectId.eq(source_id))
+            .all(&txn)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load project layers: {}", e)))?;
+        for layer in source_layers {
+            let old_id = layer.id;
+            let source_dataset_id = layer
+                .source_dataset_id
+                .map(|id| data_set_ids.get(&id).copied().unwrap_or(id));
+            let mut model: project_layers::ActiveModel = layer.into();
+            model.id = NotSet;
+            model.project_id = Set(project.id);
+            model.source_dataset_id = Set(source_dataset_id);
+            model.created_at = Set(now);
+            model.updated_at = Set(now);
+            let copy = model.insert(&txn).await.map_err(|e| {
+                CoreError::internal(format!("Failed to copy project layer {}: {}", old_id, e))
+            })?;
+            layer_ids.insert(old_id, copy.id);
+        }
+
+        let source_aliases = layer_aliases::Entity::find()
+            .filter(layer_aliases::Column::ProjectId.eq(source_id))
+            .all(&txn)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load layer aliases: {}", e)))?;
+        for alias in source_aliases {
+            let Some(target_layer_id) = layer_ids.get(&alias.target_layer_id).copied() else {
+                continue;
+            };
+            layer_aliases::ActiveModel {
+                id: NotSet,
+                project_id: Set(project.id),
+                alias_layer_id: Set(alias.alias_layer_id.clone()),
+                target_layer_id: Set(target_layer_id),
+                created_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| {
+                CoreError::internal(format!(
+                    "Failed to copy layer alias {}: {}",
+                    alias.alias_layer_id, e
+                ))
+            })?;
+        }
+
+        // Plans and their DAGs
+        let mut node_ids: HashMap<String, String> = HashMap::new();
+        let mut edge_ids: HashMap<String, String> = HashMap::new();
+        let source_plans = plans::Entity::find()
+            .filter(plans::Column::ProjectId.eq(source_id))
+            .order_by_asc(plans::Column::Id)
+            .all(&txn)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load plans: {}", e)))?;
+        for plan in source_plans {
+            let plan_copy = plans::ActiveModel {
+                id: NotSet,
+                project_id: Set(project.id),
+                name: Set(plan.name.clone()),
+                description: Set(plan.description.clone()),
+                tags: Set(plan.tags.clone()),
+                yaml_content: Set(plan.yaml_content.clone()),
+                dependencies: Set(plan.dependencies.clone()),
+                status: Set(plan.status.clone()),
+                version: Set(1),
+                created_at: Set(now),
+                updated_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to copy plan {}: {}", plan.id, e)))?;
+
+            let nodes = plan_dag_nodes::Entity::find()
+                .filter(plan_dag_nodes::Column::PlanId.eq(plan.id))
+                .all(&txn)
+                .await
+                .map_err(|e| CoreError::internal(format!("Failed to load plan nodes: {}", e)))?;
+            for node in nodes {
+                let new_id = PlanService::generate_node_id(&node.node_type);
+                node_ids.insert(node.id.clone(), new_id.clone());
+
+                let config_json = match serde_json::from_str::<Value>(&node.config_json) {
+                    Ok(mut config) => {
+                        remap_data_set_id(&mut config, &data_set_ids);
+                        config.to_string()
+                    }
+                    Err(_) => node.config_json.clone(),
+                };
+
+                plan_dag_nodes::ActiveModel {
+                    id: Set(new_id),
+                    plan_id: Set(plan_copy.id),
+                    node_type: Set(node.node_type.clone()),
+                    position_x: Set(node.position_x),
+                    position_y: Set(node.position_y),
+                    source_position: Set(node.source_position.clone()),
+                    target_position: Set(node.target_position.clone()),
+                    metadata_json: Set(node.metadata_json.clone()),
+                    config_json: Set(config_json),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&txn)
+                .await
+                .map_err(|e| {
+                    CoreError::internal(format!("Failed to copy node {}: {}", node.id, e))
+                })?;
+            }
+
+            let edges = plan_dag_edges::Entity::find()
+                .filter(plan_dag_edges::Column::PlanId.eq(plan.id))
+                .all(&txn)
+                .await
+                .map_err(|e| CoreError::internal(format!("Failed to load plan edges: {}", e)))?;
+            for edge in edges {
+                let new_id = PlanService::generate_edge_id();
+                edge_ids.insert(edge.id.clone(), new_id.clone());
+
+                plan_dag_edges::ActiveModel {
+                    id: Set(new_id),
+                    plan_id: Set(plan_copy.id),
+                    source_node_id: Set(node_ids
+                        .get(&edge.source_node_id)
+                        .cloned()
+                        .unwrap_or_else(|| edge.source_node_id.clone())),
+                    target_node_id: Set(node_ids
+                        .get(&edge.target_node_id)
+                        .cloned()
+                        .unwrap_or_else(|| edge.target_node_id.clone())),
+                    metadata_json: Set(edge.metadata_json.clone()),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&txn)
+                .await
+                .map_err(|e| {
+                    CoreError::internal(format!("Failed to copy edge {}: {}", edge.id, e))
+                })?;
+            }
+
+            let annotations = plan_dag_annotations::Entity::find()
+                .filter(plan_dag_annotations::Column::PlanId.eq(plan.id))
+                .all(&txn)
+                .await
+                .map_err(|e| {
+                    CoreError::internal(format!("Failed to load plan annotations: {}", e))
+                })?;
+            for annotation in annotations {
+                let old_id = annotation.id;
+                let target_ids = if annotation.target_type == "edge" {
+                    &edge_ids
+                } else {
+                    &node_ids
+                };
+                let target_id = target_ids
+                    .get(&annotation.target_id)
+                    .cloned()
+                    .unwrap_or_else(|| annotation.target_id.clone());
+                let mut model: plan_dag_annotations::ActiveModel = annotation.into();
+                model.id = NotSet;
+                model.project_id = Set(project.id);
+                model.plan_id = Set(plan_copy.id);
+                model.target_id = Set(target_id);
+                model.created_at = Set(now);
+                model.updated_at = Set(now);
+                model.insert(&txn).await.map_err(|e| {
+                    CoreError::internal(format!("Failed to copy annotation {}: {}", old_id, e))
+                })?;
+            }
+        }
+
+        // Graphs, keyed to the regenerated DAG node ids
+        let mut graph_ids = HashMap::new();
+        let mut graph_copies = Vec::new();
+        let source_graphs = graph_data::Entity::find()
+            .filter(graph_data::Column::ProjectId.eq(source_id))
+            .all(&txn)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load graphs: {}", e)))?;
+        for graph in source_graphs {
+            let old_id = graph.id;
+            let dag_node_id = graph
+                .dag_node_id
+                .as_ref()
+                .map(|id| node_ids.get(id).cloned().unwrap_or_else(|| id.clone()));
+            let mut metadata = graph.metadata.clone();
+            if let Some(metadata) = metadata.as_mut() {
+                remap_data_set_id(metadata, &data_set_ids);
+            }
+            let mut model: graph_data::ActiveModel = graph.into();
+            model.id = NotSet;
+            model.project_id = Set(project.id);
+            model.dag_node_id = Set(dag_node_id);
+            model.metadata = Set(metadata);
+            model.created_at = Set(now);
+            model.updated_at = Set(now);
+            let copy = model.insert(&txn).await.map_err(|e| {
+                CoreError::internal(format!("Failed to copy graph {}: {}", old_id, e))
+            })?;
+            graph_ids.insert(old_id, copy.id);
+
+            let nodes = graph_data_nodes::Entity::find()
+                .filter(graph_data_nodes::Column::GraphDataId.eq(old_id))
+                .all(&txn)
+                .await
+                .map_err(|e| CoreError::internal(format!("Failed to load graph nodes: {}", e)))?;
+            for node in nodes {
+                let source_dataset_id = node
+                    .source_dataset_id
+                    .map(|id| data_set_ids.get(&id).copied().unwrap_or(id));
+                let mut model: graph_data_nodes::ActiveModel = node.into();
+                model.id = NotSet;
+                model.graph_data_id = Set(copy.id);
+                model.source_dataset_id = Set(source_dataset_id);
+                model.insert(&txn).await.map_err(|e| {
+                    CoreError::internal(format!("Failed to copy graph node: {}", e))
+                })?;
+            }
+
+            let edges = graph_data_edges::Entity::find()
+                .filter(graph_data_edges::Column::GraphDataId.eq(old_id))
+                .all(&txn)
+                .await
+                .map_err(|e| CoreError::internal(format!("Failed to load graph edges: {}", e)))?;
+            for edge in edges {
+                let source_dataset_id = edge
+                    .source_dataset_id
+                    .map(|id| data_set_ids.get(&id).copied().unwrap_or(id));
+                let mut model: graph_data_edges::ActiveModel = edge.into();
+                model.id = NotSet;
+                model.graph_data_id = Set(copy.id);
+                model.source_dataset_id = Set(source_dataset_id);
+                model.insert(&txn).await.map_err(|e| {
+                    CoreError::internal(format!("Failed to copy graph edge: {}", e))
+                })?;
+            }
+
+            let edits = graph_edits::Entity::find()
+                .filter(graph_edits::Column::GraphId.eq(old_id))
+                .all(&txn)
+                .await
+                .map_err(|e| CoreError::internal(format!("Failed to load graph edits: {}", e)))?;
+            for edit in edits {
+                let mut model: graph_edits::ActiveModel = edit.into();
+                model.id = NotSet;
+                model.graph_id = Set(copy.id);
+                model.insert(&txn).await.map_err(|e| {
+                    CoreError::internal(format!("Failed to copy graph edit: {}", e))
+                })?;
+            }
+            graph_copies.push(copy);
+        }
+
+        // Computed graphs name their upstream graph in metadata; that id is
+        // only known once every graph has been copied.
+        for copy in graph_copies {
+            let Some(mut metadata) = copy.metadata.clone() else {
+                continue;
+            };
+            if !remap_upstream_graph_id(&mut metadata, &graph_ids) {
+                continue;
+            }
+            let copy_id = copy.id;
+            let mut model: graph_data::ActiveModel = copy.into();
+            model.metadata = Set(Some(metadata));
+            model.update(&txn).await.map_err(|e| {
+                CoreError::internal(format!("Failed to update graph {}: {}", copy_id, e))
+            })?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to commit transaction: {}", e)))?;
+
+        // A source project without plans still gets the default plan
+        let _ = self.plan_service.ensure_default_plan(project.id).await;
+
+        Ok(ProjectSummary::from(project))
+    }
+
     pub async fn update_project(
         &self,
         actor: &Actor,
@@ -222,17 +630,117 @@ impl AppContext {
         Ok(ProjectSummary::from(project))
     }
 
+    /// Soft-delete a project: it is hidden from project lists but keeps all of
+    /// its rows until it is restored or purged.
     pub async fn delete_project(&self, actor: &Actor, id: i32) -> CoreResult<()> {
+        self.authorize_project_admin(actor, id).await?;
+        let project = self.load_project_for_trash(id).await?;
+        if project.deleted_at.is_some() {
+            return Err(CoreError::not_found("Project", id.to_string()));
+        }
+
+        let mut active: projects::ActiveModel = project.into();
+        active.deleted_at = Set(Some(Utc::now()));
+        active
+            .update(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to delete project {}: {}", id, e)))?;
+
+        Ok(())
+    }
+
+    /// Bring a soft-deleted project back into the project lists.
+    pub async fn restore_project(&self, actor: &Actor, id: i32) -> CoreResult<ProjectSummary> {
+        self.authorize_project_admin(actor, id).await?;
+        let project = self.load_project_for_trash(id).await?;
+        if project.deleted_at.is_none() {
+            return Err(CoreError::validation(format!(
+                "Project {} is not deleted",
+                id
+            )));
+        }
+
+        let mut active: projects::ActiveModel = project.into();
+        active.deleted_at = Set(None);
+        let project = active
+            .update(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to restore project {}: {}", id, e)))?;
+
+        Ok(ProjectSummary::from(project))
+    }
+
+    /// Permanently delete a project and, through cascades, everything it owns.
+    /// Works whether or not the project was soft-deleted first.
+    pub async fn purge_project(&self, actor: &Actor, id: i32) -> CoreResult<()> {
         self.authorize_project_admin(actor, id).await?;
         let result = projects::Entity::delete_by_id(id)
             .exec(&self.db)
             .await
-            .map_err(|e| CoreError::internal(format!("Failed to delete project {}: {}", id, e)))?;
+            .map_err(|e| CoreError::internal(format!("Failed to purge project {}: {}", id, e)))?;
 
         if result.rows_affected == 0 {
             return Err(CoreError::not_found("Project", id.to_string()));
         }
+        GraphSnapshotService::delete_unreferenced_blobs(&self.db).await?;
 
         Ok(())
     }
+
+    /// Permanently delete projects soft-deleted before `cutoff`, returning how
+    /// many were removed. Used by the background retention task.
+    pub async fn purge_projects_deleted_before(&self, cutoff: DateTime<Utc>) -> CoreResult<u64> {
+        let result = projects::Entity::delete_many()
+            .filter(projects::Column::DeletedAt.lt(cutoff))
+            .exec(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to purge deleted projects: {}", e)))?;
+        if result.rows_affected > 0 {
+            GraphSnapshotService::delete_unreferenced_blobs(&self.db).await?;
+        }
+
+        Ok(result.rows_affected)
+    }
+
+    async fn load_project_for_trash(&self, id: i32) -> CoreResult<projects::Model> {
+        projects::Entity::find_by_id(id)
+            .one(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load project {}: {}", id, e)))?
+            .ok_or_else(|| CoreError::not_found("Project", id.to_string()))
+    }
+}
+
+/// Point an `upstreamGraphId` field at the cloned graph, if it refers to one.
+/// Returns whether the value changed.
+fn remap_upstream_graph_id(value: &mut Value, graph_ids: &HashMap<i32, i32>) -> bool {
+    let Some(object) = value.as_object_mut() else {
+        return false;
+    };
+    let Some(new_id) = object
+        .get("upstreamGraphId")
+        .and_then(Value::as_i64)
+        .and_then(|id| graph_ids.get(&(id as i32)))
+    else {
+        return false;
+    };
+    object.insert("upstreamGraphId".to_string(), json!(new_id));
+    true
+}
+
+/// Point a `dataSetId` field at the cloned dataset, if it refers to one.
+fn remap_data_set_id(value: &mut Value, data_set_ids: &HashMap<i32, i32>) {
+    let Some(object) = value.as_object_mut() else {
+        return;
+    };
+    let Some(old_id) = object
+        .get("dataSetId")
+        .and_then(Value::as_i64)
+        .map(|id| id as i32)
+    else {
+        return;
+    };
+    if let Some(new_id) = data_set_ids.get(&old_id) {
+        object.insert("dataSetId".to_string(), json!(new_id));
+    }
 }
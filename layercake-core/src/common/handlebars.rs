@@ -18,10 +18,14 @@ pub fn create_path_if_not_exists(path: &str) -> anyhow::Result<()> {
 }
 
 pub fn write_string_to_file(filename: &str, content: &str) -> anyhow::Result<()> {
+    write_bytes_to_file(filename, content.as_bytes())
+}
+
+pub fn write_bytes_to_file(filename: &str, content: &[u8]) -> anyhow::Result<()> {
     create_path_if_not_exists(filename)?;
     let path = Path::new(filename);
     let mut file = File::create(path)?;
-    file.write_all(content.as_bytes())?;
+    file.write_all(content)?;
     Ok(())
 }
 
@@ -261,6 +265,41 @@ pub fn get_handlebars() -> Handlebars<'static> {
     handlebars.register_helper("puml_render_tree", Box::new(puml_render_tree));
 
     handlebars_helper!(mermaid_render_tree: |node: Value, layermap: Value, style_config: Value| {
+        fn depth_of(node: &Value) -> i64 {
+            node.get("depth").and_then(|v| v.as_i64()).unwrap_or(0)
+        }
+
+        fn deepest(node: &Value) -> i64 {
+            node.get("children")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .map(deepest)
+                .fold(depth_of(node), i64::max)
+        }
+
+        // Levels below a partition, from the `build_tree` depth of its deepest
+        // descendant; plain nodes span none.
+        fn levels_spanned(node: &Value) -> i64 {
+            let is_partition = node.get("is_partition").and_then(|v| v.as_bool()).unwrap_or(false);
+            if is_partition {
+                deepest(node) - depth_of(node)
+            } else {
+                0
+            }
+        }
+
+        // Siblings are grouped by how many hierarchy levels they span: the
+        // deepest partitions are declared first, then shallower ones, then plain
+        // nodes. Partitions of equal depth end up adjacent, so Mermaid's layered
+        // layout ranks each hierarchy level as one band; nodes outside any
+        // partition end up last in the implicit root.
+        fn ranked(nodes: &[Value]) -> Vec<&Value> {
+            let mut ordered: Vec<&Value> = nodes.iter().collect();
+            ordered.sort_by_key(|node| std::cmp::Reverse(levels_spanned(node)));
+            ordered
+        }
+
         fn render_tree(
             node: Value,
             _layermap: &serde_json::Map<String, Value>,
@@ -279,8 +318,9 @@ pub fn get_handlebars() -> Handlebars<'static> {
 
                 // Only render as subgraph if this is a partition node
                 if is_partition && !children.is_empty() {
-                    result += &format!("{}subgraph \"{}\"\n", indent, label);
-                    let children_rendered: Vec<String> = children.iter().map(|child| {
+                    // Keyed by ID so partitions sharing a label stay separate.
+                    result += &format!("{}subgraph {}[\"{}\"]\n", indent, id, label);
+                    let children_rendered: Vec<String> = ranked(children).into_iter().map(|child| {
                         render_tree(child.clone(), _layermap, acc + 1, _apply_layers)
                     }).collect();
                     result += &children_rendered.join("");
@@ -322,7 +362,13 @@ pub fn get_handlebars() -> Handlebars<'static> {
             .and_then(|v| v.as_bool())
             .unwrap_or(true);
 
-        render_tree(node, &layermap, 0, apply_layers)
+        match node {
+            Value::Array(roots) => ranked(&roots)
+                .into_iter()
+                .map(|root| render_tree(root.clone(), &layermap, 0, apply_layers))
+                .collect::<String>(),
+            node => render_tree(node, &layermap, 0, apply_layers),
+        }
     });
     handlebars.register_helper("mermaid_render_tree", Box::new(mermaid_render_tree));
 
@@ -434,6 +480,9 @@ pub fn get_handlebars() -> Handlebars<'static> {
                             bordercolor = border_color.to_string();
                         }
                     }
+                    if let Some(fill_color) = map.get("fill_color").and_then(|v| v.as_str()) {
+                        fillcolor = fill_color.to_string();
+                    }
 
                     let escaped_comment = comment.replace('"', "\\\"");
                     let comment_attr = if has_comment {
@@ -474,9 +523,14 @@ pub fn get_handlebars() -> Handlebars<'static> {
                     } else {
                         String::new()
                     };
+                    let fill_attr = map
+                        .get("fill_color")
+                        .and_then(|v| v.as_str())
+                        .map(|fill| format!(",filled\", fillcolor=\"#{}", fill))
+                        .unwrap_or_default();
                     result += &format!(
-                        "{}{} [label=\"{}\", layer=\"{}\", style=\"rounded\"{}];\n",
-                        indent, id, label, layer, comment_attr
+                        "{}{} [label=\"{}\", layer=\"{}\", style=\"rounded{}\"{}];\n",
+                        indent, id, label, layer, fill_attr, comment_attr
                     );
 
                     // If this non-partition node has children, render them separately
@@ -566,6 +620,78 @@ pub fn get_handlebars() -> Handlebars<'static> {
     });
     handlebars.register_helper("puml_link_color", Box::new(puml_link));
 
+    // Global `skinparam` lines for PlantUML diagrams: the `built_in_styles`
+    // preset, with `target_options.plantuml` overrides replacing preset values.
+    handlebars_helper!(puml_skinparams: |config: Value| {
+        let preset: &[(&str, &str)] = match config.get("built_in_styles").and_then(Value::as_str) {
+            Some("none") => &[("defaultFontName", "Lato"), ("shadowing", "false")],
+            Some("dark") => &[
+                ("backgroundColor", "#1e1e1e"),
+                ("defaultFontColor", "#f5f5f5"),
+                ("ArrowColor", "#dfe3ec"),
+                ("RectangleBorderColor", "#777777"),
+                ("defaultFontName", "Lato"),
+                ("shadowing", "false"),
+            ],
+            Some("blueprint") => &[
+                ("backgroundColor", "#0b3d91"),
+                ("defaultFontColor", "#ffffff"),
+                ("ArrowColor", "#dbe9ff"),
+                ("RectangleBackgroundColor", "#134a9e"),
+                ("RectangleBorderColor", "#ffffff"),
+                ("defaultFontName", "Courier"),
+                ("shadowing", "false"),
+                ("roundCorner", "0"),
+            ],
+            _ => &[
+                ("backgroundColor", "#ffffff"),
+                ("defaultFontColor", "#1f2933"),
+                ("ArrowColor", "#2B303A"),
+                ("RectangleBorderColor", "#555555"),
+                ("defaultFontName", "Lato"),
+                ("shadowing", "false"),
+            ],
+        };
+        let mut params: Vec<(String, String)> = preset
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        let overrides = config.pointer("/target_options/plantuml");
+        let text = |key: &str| {
+            overrides
+                .and_then(|o| o.get(key))
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+        };
+        let color = |key: &str| text(key).map(|c| format!("#{}", c.trim_start_matches('#')));
+        let round_corner = overrides
+            .and_then(|o| o.get("round_corner"))
+            .and_then(Value::as_u64)
+            .map(|radius| radius.to_string());
+        for (name, value) in [
+            ("backgroundColor", color("background_color")),
+            ("defaultFontName", text("font_name").map(str::to_string)),
+            ("ArrowColor", color("arrow_color")),
+            ("roundCorner", round_corner),
+        ] {
+            if let Some(value) = value {
+                match params.iter_mut().find(|(existing, _)| existing == name) {
+                    Some(param) => param.1 = value,
+                    None => params.push((name.to_string(), value)),
+                }
+            }
+        }
+
+        params
+            .iter()
+            .map(|(name, value)| format!("skinparam {} {}", name, value))
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+    handlebars.register_helper("puml_skinparams", Box::new(puml_skinparams));
+
     handlebars_helper!(layer_bg_color: |layermap: Value, layer_id: String| {
         if let Value::Object(map) = layermap {
             if let Some(layer_obj) = map.get(&layer_id) {
@@ -583,5 +709,83 @@ pub fn get_handlebars() -> Handlebars<'static> {
     });
     handlebars.register_helper("layer_bg_color", Box::new(layer_bg_color));
 
+    // General-purpose helpers for user templates (custom exports): string
+    // casing, truncation to a character count with an optional `suffix=`,
+    // shading hex colours by a 0-1 fraction, and embedding any value as JSON
+    // (use `{{{json value}}}` so the output is not HTML-escaped).
+    handlebars_helper!(uppercase: |v: Value| value_text(v).to_uppercase());
+    handlebars.register_helper("uppercase", Box::new(uppercase));
+
+    handlebars_helper!(lowercase: |v: Value| value_text(v).to_lowercase());
+    handlebars.register_helper("lowercase", Box::new(lowercase));
+
+    handlebars_helper!(truncate: |v: Value, length: u64, {suffix: str = ""}| {
+        let text = value_text(v);
+        if text.chars().count() > length as usize {
+            format!("{}{}", text.chars().take(length as usize).collect::<String>(), suffix)
+        } else {
+            text
+        }
+    });
+    handlebars.register_helper("truncate", Box::new(truncate));
+
+    handlebars_helper!(lighten: |color: String, amount: f64| shade_hex(&color, amount, true));
+    handlebars.register_helper("lighten", Box::new(lighten));
+
+    handlebars_helper!(darken: |color: String, amount: f64| shade_hex(&color, amount, false));
+    handlebars.register_helper("darken", Box::new(darken));
+
+    handlebars_helper!(json: |v: Value| v.to_string());
+    handlebars.register_helper("json", Box::new(json));
+
     handlebars
 }
+
+fn value_text(v: Value) -> String {
+    match v {
+        Value::String(s) => s,
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Move each channel of a `rrggbb` / `rgb` hex colour (with or without `#`)
+/// towards white or black by `amount` (0-1). The `#` prefix is kept as given;
+/// anything that is not a hex colour is returned unchanged.
+fn shade_hex(color: &str, amount: f64, lighten: bool) -> String {
+    let trimmed = color.trim();
+    let (prefix, hex) = match trimmed.strip_prefix('#') {
+        Some(hex) => ("#", hex),
+        None => ("", trimmed),
+    };
+    let expanded: String = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return color.to_string(),
+    };
+    let channels: Option<Vec<u8>> = (0..3)
+        .map(|i| {
+            expanded
+                .get(i * 2..i * 2 + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+        })
+        .collect();
+    let Some(channels) = channels else {
+        return color.to_string();
+    };
+
+    let amount = amount.clamp(0.0, 1.0);
+    let shaded: String = channels
+        .into_iter()
+        .map(|channel| {
+            let channel = channel as f64;
+            let value = if lighten {
+                channel + (255.0 - channel) * amount
+            } else {
+                channel * (1.0 - amount)
+            };
+            format!("{:02x}", value.round() as u8)
+        })
+        .collect();
+    format!("{}{}", prefix, shaded)
+}
@@ -8,4 +8,4 @@ pub mod error_helpers;
 pub mod handlebars;
 
 // Re-export commonly used items for convenience
-pub use handlebars::{get_handlebars, write_string_to_file};
+pub use handlebars::{get_handlebars, write_bytes_to_file, write_string_to_file};
@@ -1,11 +1,21 @@
 use anyhow::Result;
 use csv::StringRecord;
+use indexmap::{map::Entry, IndexMap, IndexSet};
+use serde::Serialize;
+use serde_json::Value;
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
+use crate::graph::{Edge, Graph, Layer, Node};
+
+/// Header prefix marking a delimited-file column as an attribute, so extra
+/// columns in existing files are not picked up and keys cannot clash with the
+/// fixed headers
+pub const ATTRIBUTE_COLUMN_PREFIX: &str = "attr_";
+
 pub struct DfNodeLoadProfile {
     pub id_column: usize,
     pub label_column: usize,
@@ -54,6 +64,9 @@ pub struct DfEdgeLoadProfile {
     pub layer_column: usize,
     pub weight_column: usize,
     pub comment_column: usize,
+    /// `attr_<key>` columns, loaded as edge attributes under `<key>` with
+    /// [`parse_attribute_cell`].
+    pub attribute_columns: Vec<(usize, String)>,
 }
 
 impl Default for DfEdgeLoadProfile {
@@ -66,6 +79,7 @@ impl Default for DfEdgeLoadProfile {
             layer_column: 4,
             weight_column: 5,
             comment_column: 6,
+            attribute_columns: Vec::new(),
         }
     }
 }
@@ -74,7 +88,7 @@ impl Display for DfEdgeLoadProfile {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Edge column offsets: id:{}, source:{}, target:{}, label:{}, layer:{}, weight:{}, comment:{}",
+            "Edge column offsets: id:{}, source:{}, target:{}, label:{}, layer:{}, weight:{}, comment:{}, attributes:{:?}",
             self.id_column,
             self.source_column,
             self.target_column,
@@ -82,6 +96,7 @@ impl Display for DfEdgeLoadProfile {
             self.layer_column,
             self.weight_column,
             self.comment_column,
+            self.attribute_columns,
         )
     }
 }
@@ -114,12 +129,29 @@ pub fn create_df_edge_load_profile(headers: &[String]) -> DfEdgeLoadProfile {
             "layer" => profile.layer_column = i,
             "weight" => profile.weight_column = i,
             "comment" => profile.comment_column = i,
-            _ => {}
+            other => {
+                if let Some(key) = other
+                    .strip_prefix(ATTRIBUTE_COLUMN_PREFIX)
+                    .filter(|key| !key.is_empty())
+                {
+                    profile.attribute_columns.push((i, key.to_string()));
+                }
+            }
         }
     }
     profile
 }
 
+/// Attribute value of a delimited-file cell: JSON numbers, booleans, arrays,
+/// objects and quoted strings are decoded, anything else is taken as a plain
+/// string. The inverse of `csv_common::attribute_cell`.
+pub fn parse_attribute_cell(cell: &str) -> Value {
+    match serde_json::from_str::<Value>(cell) {
+        Ok(Value::Null) | Err(_) => Value::String(cell.to_string()),
+        Ok(value) => value,
+    }
+}
+
 pub fn get_headers_from_file(filename: &str, separator: u8) -> anyhow::Result<Vec<String>> {
     let file = File::open(filename)?;
     let reader = BufReader::new(file);
@@ -220,5 +252,1074 @@ pub fn verify_id_column(records: &[StringRecord], id_column_index: usize) -> any
     Ok(())
 }
 
+/// A row rejected by [`validate_delimited_rows`]. `line` is the 1-based line
+/// in the original file, so the header is line 1 and the first row line 2.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RowError {
+    pub line: u64,
+    pub column: Option<String>,
+    pub message: String,
+}
+
+impl Display for RowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.column {
+            Some(column) => write!(
+                f,
+                "line {}, column '{}': {}",
+                self.line, column, self.message
+            ),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+pub struct ValidatedRows {
+    pub headers: StringRecord,
+    /// Rows that passed validation, in file order.
+    pub rows: Vec<StringRecord>,
+    pub errors: Vec<RowError>,
+}
+
+/// Read delimited `content` and check every row instead of stopping at the
+/// first bad one: `required_columns` must be present and non-empty, `weight`
+/// must be an integer and `id` must be unique. A missing required header is
+/// always an error. With `strict`, any row error fails the whole read;
+/// otherwise the invalid rows are left out and returned in `errors`.
+pub fn validate_delimited_rows(
+    content: &[u8],
+    delimiter: u8,
+    required_columns: &[&str],
+    strict: bool,
+) -> anyhow::Result<ValidatedRows> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(content);
+    let headers = reader.headers()?.clone();
+    let column_index = |name: &str| headers.iter().position(|h| h.trim() == name);
+
+    let mut required = Vec::with_capacity(required_columns.len());
+    for &column in required_columns {
+        let index = column_index(column)
+            .ok_or_else(|| anyhow::anyhow!("Missing required column '{}'", column))?;
+        required.push((column, index));
+    }
+    let id_column = column_index("id");
+    let weight_column = column_index("weight");
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen_ids: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
+    for result in reader.records() {
+        let record = match result {
+            Ok(record) => record,
+            Err(e) => {
+                errors.push(RowError {
+                    line: e.position().map_or(0, |p| p.line()),
+                    column: None,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = record.position().map_or(0, |p| p.line());
+        let mut row_errors = Vec::new();
+
+        for &(column, index) in &required {
+            if record
+                .get(index)
+                .is_none_or(|value| value.trim().is_empty())
+            {
+                row_errors.push(RowError {
+                    line,
+                    column: Some(column.to_string()),
+                    message: "Missing required value".to_string(),
+                });
+            }
+        }
+        if let Some(weight) = weight_column
+            .and_then(|index| record.get(index))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            if weight.parse::<i32>().is_err() {
+                row_errors.push(RowError {
+                    line,
+                    column: Some("weight".to_string()),
+                    message: format!("Weight '{}' is not an integer", weight),
+                });
+            }
+        }
+        if row_errors.is_empty() {
+            if let Some(id) = id_column
+                .and_then(|index| record.get(index))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+            {
+                if let Some(first_line) = seen_ids.get(id) {
+                    row_errors.push(RowError {
+                        line,
+                        column: Some("id".to_string()),
+                        message: format!(
+                            "Duplicate id '{}' (first seen on line {})",
+                            id, first_line
+                        ),
+                    });
+                } else {
+                    seen_ids.insert(id.to_string(), line);
+                }
+            }
+        }
+
+        if row_errors.is_empty() {
+            rows.push(record);
+        } else {
+            errors.extend(row_errors);
+        }
+    }
+
+    if strict && !errors.is_empty() {
+        let details: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        return Err(anyhow::anyhow!(
+            "{} invalid row(s): {}",
+            errors.len(),
+            details.join("; ")
+        ));
+    }
+
+    Ok(ValidatedRows {
+        headers,
+        rows,
+        errors,
+    })
+}
+
+/// Parse the weighted adjacency list written by `export::to_csv_adjacency`.
+///
+/// Each row is `node_id,neighbour:weight,...`; a bare id is an isolated node.
+/// Neighbours without a row of their own still become nodes. Edge ids are
+/// `source->target`, suffixed with a counter for parallel edges.
+pub fn parse_csv_adjacency(content: &str) -> anyhow::Result<Graph> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let mut node_ids: Vec<String> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut edges = Vec::new();
+    let mut edge_ids: HashSet<String> = HashSet::new();
+
+    for (row_idx, record) in reader.records().enumerate() {
+        let record = record?;
+        let Some(source) = record.get(0).map(str::trim).filter(|id| !id.is_empty()) else {
+            continue;
+        };
+        if seen.insert(source.to_string()) {
+            node_ids.push(source.to_string());
+        }
+
+        for entry in record
+            .iter()
+            .skip(1)
+            .filter(|entry| !entry.trim().is_empty())
+        {
+            let (target, weight) = entry.rsplit_once(':').ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Row {}: expected 'neighbour:weight', got '{}'",
+                    row_idx + 1,
+                    entry
+                )
+            })?;
+            let weight: i32 = weight.trim().parse().map_err(|_| {
+                anyhow::anyhow!("Row {}: invalid weight in '{}'", row_idx + 1, entry)
+            })?;
+            let target = target.trim().to_string();
+            if seen.insert(target.clone()) {
+                node_ids.push(target.clone());
+            }
+
+            let base_id = format!("{}->{}", source, target);
+            let mut id = base_id.clone();
+            let mut counter = 2;
+            while !edge_ids.insert(id.clone()) {
+                id = format!("{}#{}", base_id, counter);
+                counter += 1;
+            }
+            edges.push(Edge {
+                id,
+                source: source.to_string(),
+                target,
+                label: String::new(),
+                layer: String::new(),
+                weight,
+                comment: None,
+                dataset: None,
+                attributes: None,
+            });
+        }
+    }
+
+    Ok(Graph {
+        name: String::new(),
+        nodes: node_ids
+            .into_iter()
+            .map(|id| Node {
+                label: id.clone(),
+                id,
+                weight: 1,
+                ..Default::default()
+            })
+            .collect(),
+        edges,
+        layers: Vec::new(),
+        annotations: None,
+    })
+}
+
+/// Load a weighted adjacency list file, see [`parse_csv_adjacency`].
+pub fn load_csv_adjacency(filename: &str) -> anyhow::Result<Graph> {
+    let content = std::fs::read_to_string(filename)?;
+    parse_csv_adjacency(&content)
+}
+
+/// Parse a Graphviz DOT graph.
+///
+/// Both `digraph` and `graph` are accepted; undirected edges keep the
+/// endpoint order they were written in. Each `subgraph cluster_*` becomes a
+/// layer (id without the `cluster_` prefix) and a partition node (id is the
+/// subgraph name) that its members belong to; the cluster's `label`,
+/// `fillcolor`/`bgcolor`, `fontcolor` and `color` name and colour the layer.
+/// Nodes and edges map `label`, `weight`, `layer` and `comment` onto their
+/// fields, `node [...]`/`edge [...]` defaults apply within their subgraph,
+/// and every other attribute (`color`, `shape`, ...) lands in `attributes`.
+/// Edge ids come from an `id` attribute or are `source->target` (`--` for
+/// undirected graphs), suffixed with a counter for parallel edges. Ports are
+/// ignored.
+pub fn parse_dot(content: &str) -> anyhow::Result<Graph> {
+    let mut parser = DotParser {
+        tokens: tokenize_dot(content)?,
+        pos: 0,
+        directed: true,
+        name: String::new(),
+        nodes: IndexMap::new(),
+        edges: Vec::new(),
+        edge_ids: HashSet::new(),
+        layers: Vec::new(),
+    };
+    parser.graph()?;
+
+    Ok(Graph {
+        name: parser.name,
+        nodes: parser.nodes.into_values().collect(),
+        edges: parser.edges,
+        layers: parser.layers,
+        annotations: None,
+    })
+}
+
+/// Load a Graphviz DOT file, see [`parse_dot`].
+pub fn load_dot(filename: &str) -> anyhow::Result<Graph> {
+    let content = std::fs::read_to_string(filename)?;
+    parse_dot(&content)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DotToken {
+    /// Identifier, numeral, quoted or HTML string; quoted ids are never
+    /// keywords.
+    Id {
+        text: String,
+        quoted: bool,
+    },
+    /// `->` when true, `--` otherwise.
+    EdgeOp(bool),
+    Punct(char),
+}
+
+/// Split DOT source into tokens paired with the line they start on,
+/// dropping comments and `#` preprocessor lines.
+fn tokenize_dot(content: &str) -> anyhow::Result<Vec<(DotToken, usize)>> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+    let mut line = 1;
+
+    while let Some(c) = chars.next() {
+        let start = line;
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|&c| c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if prev == '*' => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            prev = c;
+                        }
+                        None => anyhow::bail!("Line {}: unterminated comment", start),
+                    }
+                }
+            }
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('"') => text.push('"'),
+                            // Backslash-newline continues the string
+                            Some('\n') => line += 1,
+                            Some(other) => {
+                                text.push('\\');
+                                text.push(other);
+                            }
+                            None => anyhow::bail!("Line {}: unterminated string", start),
+                        },
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            text.push(c);
+                        }
+                        None => anyhow::bail!("Line {}: unterminated string", start),
+                    }
+                }
+                tokens.push((DotToken::Id { text, quoted: true }, start));
+            }
+            '<' => {
+                let mut text = String::new();
+                let mut depth = 1;
+                loop {
+                    let Some(c) = chars.next() else {
+                        anyhow::bail!("Line {}: unterminated HTML string", start);
+                    };
+                    match c {
+                        '<' => depth += 1,
+                        '>' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        '\n' => line += 1,
+                        _ => {}
+                    }
+                    text.push(c);
+                }
+                tokens.push((DotToken::Id { text, quoted: true }, start));
+            }
+            '-' if matches!(chars.peek(), Some('>') | Some('-')) => {
+                let directed = chars.next() == Some('>');
+                tokens.push((DotToken::EdgeOp(directed), start));
+            }
+            '{' | '}' | '[' | ']' | '=' | ';' | ',' | ':' => {
+                tokens.push((DotToken::Punct(c), start));
+            }
+            c if c.is_alphanumeric() || matches!(c, '_' | '.' | '-') || !c.is_ascii() => {
+                let mut text = c.to_string();
+                while let Some(next) = chars
+                    .next_if(|&c| c.is_alphanumeric() || matches!(c, '_' | '.') || !c.is_ascii())
+                {
+                    text.push(next);
+                }
+                tokens.push((
+                    DotToken::Id {
+                        text,
+                        quoted: false,
+                    },
+                    start,
+                ));
+            }
+            other => anyhow::bail!("Line {}: unexpected character '{}'", start, other),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Attribute defaults and cluster membership in force inside one (sub)graph.
+#[derive(Clone, Default)]
+struct DotScope {
+    node_defaults: Vec<(String, String)>,
+    edge_defaults: Vec<(String, String)>,
+    /// Partition node id and layer id of the innermost enclosing cluster.
+    cluster: Option<(String, String)>,
+    /// True in the cluster's own body, where graph attributes style its layer.
+    is_cluster: bool,
+}
+
+struct DotParser {
+    tokens: Vec<(DotToken, usize)>,
+    pos: usize,
+    directed: bool,
+    name: String,
+    nodes: IndexMap<String, Node>,
+    edges: Vec<Edge>,
+    edge_ids: HashSet<String>,
+    layers: Vec<Layer>,
+}
+
+impl DotParser {
+    fn peek_at(&self, offset: usize) -> Option<&DotToken> {
+        self.tokens.get(self.pos + offset).map(|(token, _)| token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn keyword(&self, keyword: &str) -> bool {
+        matches!(
+            self.peek_at(0),
+            Some(DotToken::Id { text, quoted: false }) if text.eq_ignore_ascii_case(keyword)
+        )
+    }
+
+    fn eat_punct(&mut self, punct: char) -> bool {
+        let found = self.peek_at(0) == Some(&DotToken::Punct(punct));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_punct(&mut self, punct: char) -> anyhow::Result<()> {
+        if self.eat_punct(punct) {
+            Ok(())
+        } else {
+            anyhow::bail!("Line {}: expected '{}'", self.line(), punct)
+        }
+    }
+
+    fn expect_id(&mut self) -> anyhow::Result<String> {
+        match self.peek_at(0) {
+            Some(DotToken::Id { text, .. }) => {
+                let text = text.clone();
+                self.pos += 1;
+                Ok(text)
+            }
+            _ => anyhow::bail!("Line {}: expected an identifier", self.line()),
+        }
+    }
+
+    fn graph(&mut self) -> anyhow::Result<()> {
+        if self.keyword("strict") {
+            self.pos += 1;
+        }
+        self.directed = if self.keyword("digraph") {
+            true
+        } else if self.keyword("graph") {
+            false
+        } else {
+            anyhow::bail!("Line {}: expected 'graph' or 'digraph'", self.line());
+        };
+        self.pos += 1;
+        if matches!(self.peek_at(0), Some(DotToken::Id { .. })) {
+            self.name = self.expect_id()?;
+        }
+        self.expect_punct('{')?;
+        self.statements(&mut DotScope::default(), &mut IndexSet::new())?;
+        if self.pos < self.tokens.len() {
+            anyhow::bail!("Line {}: unexpected content after the graph", self.line());
+        }
+        Ok(())
+    }
+
+    /// Statements up to and including the closing `}`, collecting the ids of
+    /// nodes they mention into `members`.
+    fn statements(
+        &mut self,
+        scope: &mut DotScope,
+        members: &mut IndexSet<String>,
+    ) -> anyhow::Result<()> {
+        while !self.eat_punct('}') {
+            if self.peek_at(0).is_none() {
+                anyhow::bail!("Line {}: expected '}}'", self.line());
+            }
+            self.statement(scope, members)?;
+            self.eat_punct(';');
+        }
+        Ok(())
+    }
+
+    fn statement(
+        &mut self,
+        scope: &mut DotScope,
+        members: &mut IndexSet<String>,
+    ) -> anyhow::Result<()> {
+        for kind in ["node", "edge", "graph"] {
+            if self.keyword(kind) {
+                self.pos += 1;
+                let attrs = self.attr_lists()?;
+                match kind {
+                    "node" => scope.node_defaults.extend(attrs),
+                    "edge" => scope.edge_defaults.extend(attrs),
+                    _ => {
+                        for (key, value) in attrs {
+                            self.graph_attr(scope, &key, value);
+                        }
+                    }
+                }
+                return Ok(());
+            }
+        }
+
+        if matches!(self.peek_at(0), Some(DotToken::Id { .. }))
+            && self.peek_at(1) == Some(&DotToken::Punct('='))
+        {
+            let key = self.expect_id()?;
+            self.pos += 1;
+            let value = self.expect_id()?;
+            self.graph_attr(scope, &key, value);
+            return Ok(());
+        }
+
+        let (first, is_subgraph) = self.operand(scope, members)?;
+        if !matches!(self.peek_at(0), Some(DotToken::EdgeOp(_))) {
+            let attrs = self.attr_lists()?;
+            if !is_subgraph {
+                self.touch_node(&first[0], &attrs, scope);
+            }
+            return Ok(());
+        }
+
+        let mut operands = vec![first];
+        while let Some(DotToken::EdgeOp(directed)) = self.peek_at(0).cloned() {
+            if directed != self.directed {
+                anyhow::bail!(
+                    "Line {}: '{}' edges are not allowed in a {}",
+                    self.line(),
+                    if directed { "->" } else { "--" },
+                    if self.directed { "digraph" } else { "graph" }
+                );
+            }
+            self.pos += 1;
+            operands.push(self.operand(scope, members)?.0);
+        }
+        let attrs: Vec<(String, String)> = scope
+            .edge_defaults
+            .iter()
+            .cloned()
+            .chain(self.attr_lists()?)
+            .collect();
+        for pair in operands.windows(2) {
+            for source in &pair[0] {
+                for target in &pair[1] {
+                    self.add_edge(source, target, &attrs);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// A node id (with optional port, ignored) or a subgraph; returns the
+    /// node ids it stands for and whether it was a subgraph.
+    fn operand(
+        &mut self,
+        scope: &DotScope,
+        members: &mut IndexSet<String>,
+    ) -> anyhow::Result<(Vec<String>, bool)> {
+        if self.keyword("subgraph") || self.peek_at(0) == Some(&DotToken::Punct('{')) {
+            return Ok((self.subgraph(scope, members)?, true));
+        }
+
+        let id = self.expect_id()?;
+        if self.eat_punct(':') {
+            self.expect_id()?;
+            if self.eat_punct(':') {
+                self.expect_id()?;
+            }
+        }
+        self.touch_node(&id, &[], scope);
+        members.insert(id.clone());
+        Ok((vec![id], false))
+    }
+
+    fn subgraph(
+        &mut self,
+        scope: &DotScope,
+        members: &mut IndexSet<String>,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut name = None;
+        if self.keyword("subgraph") {
+            self.pos += 1;
+            if matches!(self.peek_at(0), Some(DotToken::Id { .. })) {
+                name = Some(self.expect_id()?);
+            }
+        }
+        self.expect_punct('{')?;
+
+        let mut inner = DotScope {
+            is_cluster: false,
+            ..scope.clone()
+        };
+        if let Some(name) = name.filter(|name| name.starts_with("cluster")) {
+            let layer_id = name
+                .strip_prefix("cluster_")
+                .or_else(|| name.strip_prefix("cluster"))
+                .filter(|id| !id.is_empty())
+                .unwrap_or(&name)
+                .to_string();
+            let partition = self.nodes.entry(name.clone()).or_insert_with(|| Node {
+                id: name.clone(),
+                label: layer_id.clone(),
+                weight: 1,
+                ..Default::default()
+            });
+            partition.is_partition = true;
+            partition.layer = layer_id.clone();
+            if partition.belongs_to.is_none() {
+                partition.belongs_to = scope.cluster.as_ref().map(|(parent, _)| parent.clone());
+            }
+            if !self.layers.iter().any(|layer| layer.id == layer_id) {
+                self.layers.push(Layer::new(
+                    &layer_id, &layer_id, "222222", "ffffff", "dddddd",
+                ));
+            }
+            inner.cluster = Some((name, layer_id));
+            inner.is_cluster = true;
+        }
+
+        let mut inner_members = IndexSet::new();
+        self.statements(&mut inner, &mut inner_members)?;
+        members.extend(inner_members.iter().cloned());
+        Ok(inner_members.into_iter().collect())
+    }
+
+    /// Zero or more `[key=value, ...]` groups; `,` and `;` separators are
+    /// optional.
+    fn attr_lists(&mut self) -> anyhow::Result<Vec<(String, String)>> {
+        let mut attrs = Vec::new();
+        while self.eat_punct('[') {
+            while !self.eat_punct(']') {
+                let key = self.expect_id()?;
+                self.expect_punct('=')?;
+                attrs.push((key, self.expect_id()?));
+                if !self.eat_punct(',') {
+                    self.eat_punct(';');
+                }
+            }
+        }
+        Ok(attrs)
+    }
+
+    fn graph_attr(&mut self, scope: &DotScope, key: &str, value: String) {
+        match (&scope.cluster, scope.is_cluster) {
+            (Some((partition, layer_id)), true) => {
+                let Some(layer) = self.layers.iter_mut().find(|layer| &layer.id == layer_id) else {
+                    return;
+                };
+                match key {
+                    "label" => {
+                        layer.label = value.clone();
+                        if let Some(node) = self.nodes.get_mut(partition) {
+                            node.label = value;
+                        }
+                    }
+                    "fillcolor" | "bgcolor" => set_dot_color(&mut layer.background_color, &value),
+                    "fontcolor" => set_dot_color(&mut layer.text_color, &value),
+                    "color" | "pencolor" => set_dot_color(&mut layer.border_color, &value),
+                    _ => {}
+                }
+            }
+            (None, _) if key == "label" && self.name.is_empty() => self.name = value,
+            _ => {}
+        }
+    }
+
+    /// Create `id` on first mention (with the scope's node defaults and
+    /// cluster), then apply `attrs`. Nodes first mentioned outside any
+    /// cluster join the first cluster that lists them.
+    fn touch_node(&mut self, id: &str, attrs: &[(String, String)], scope: &DotScope) {
+        let node = match self.nodes.entry(id.to_string()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let node = entry.insert(Node {
+                    id: id.to_string(),
+                    label: id.to_string(),
+                    weight: 1,
+                    ..Default::default()
+                });
+                for (key, value) in &scope.node_defaults {
+                    apply_dot_node_attr(node, key, value);
+                }
+                node
+            }
+        };
+        if let Some((partition, layer_id)) = &scope.cluster {
+            if node.belongs_to.is_none() && !node.is_partition {
+                node.belongs_to = Some(partition.clone());
+                if node.layer.is_empty() {
+                    node.layer = layer_id.clone();
+                }
+            }
+        }
+        for (key, value) in attrs {
+            apply_dot_node_attr(node, key, value);
+        }
+    }
+
+    fn add_edge(&mut self, source: &str, target: &str, attrs: &[(String, String)]) {
+        let mut edge = Edge {
+            id: String::new(),
+            source: source.to_string(),
+            target: target.to_string(),
+            weight: 1,
+            ..Default::default()
+        };
+        let mut explicit_id = None;
+        for (key, value) in attrs {
+            match key.as_str() {
+                "id" => explicit_id = Some(value.clone()),
+                "label" => edge.label = value.clone(),
+                "layer" => edge.layer = value.clone(),
+                "comment" => edge.comment = Some(value.clone()),
+                "weight" => match value.trim().parse::<f64>() {
+                    Ok(weight) => edge.weight = weight.round() as i32,
+                    Err(_) => set_dot_attribute(&mut edge.attributes, key, value),
+                },
+                _ => set_dot_attribute(&mut edge.attributes, key, value),
+            }
+        }
+
+        let base_id = explicit_id.unwrap_or_else(|| {
+            format!(
+                "{}{}{}",
+                source,
+                if self.directed { "->" } else { "--" },
+                target
+            )
+        });
+        let mut id = base_id.clone();
+        let mut counter = 2;
+        while !self.edge_ids.insert(id.clone()) {
+            id = format!("{}#{}", base_id, counter);
+            counter += 1;
+        }
+        edge.id = id;
+        self.edges.push(edge);
+    }
+}
+
+fn apply_dot_node_attr(node: &mut Node, key: &str, value: &str) {
+    match key {
+        "label" => node.label = value.to_string(),
+        "layer" => node.layer = value.to_string(),
+        "comment" => node.comment = Some(value.to_string()),
+        "weight" => match value.trim().parse::<f64>() {
+            Ok(weight) => node.weight = weight.round() as i32,
+            Err(_) => set_dot_attribute(&mut node.attributes, key, value),
+        },
+        _ => set_dot_attribute(&mut node.attributes, key, value),
+    }
+}
+
+fn set_dot_attribute(attributes: &mut Option<Value>, key: &str, value: &str) {
+    if let Some(map) = attributes
+        .get_or_insert_with(|| Value::Object(Default::default()))
+        .as_object_mut()
+    {
+        map.insert(key.to_string(), Value::String(value.to_string()));
+    }
+}
+
+/// Layer colours are stored as bare hex; named DOT colours keep the default.
+fn set_dot_color(target: &mut String, value: &str) {
+    let hex = value.trim().trim_start_matches('#');
+    if matches!(hex.len(), 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        *target = hex[..6].to_string();
+    }
+}
+
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use crate::plan::{
+        NotePosition, RenderConfig, RenderConfigBuiltInStyle, RenderConfigOrientation,
+        RenderTargetOptions,
+    };
+
+    fn render_config(use_edge_weight: bool) -> RenderConfig {
+        RenderConfig {
+            contain_nodes: false,
+            orientation: RenderConfigOrientation::TB,
+            apply_layers: true,
+            built_in_styles: RenderConfigBuiltInStyle::Light,
+            target_options: RenderTargetOptions::default(),
+            add_node_comments_as_notes: false,
+            note_position: NotePosition::Left,
+            use_node_weight: true,
+            use_edge_weight,
+            normalize_edge_weights: false,
+            render_partitions_as_nodes: false,
+            directed: None,
+            precompute_layout: false,
+            prettify: false,
+            timestamp_format: crate::plan::TimestampFormat::Rfc3339,
+            layer_source_styles: vec![],
+            color_by_attribute: None,
+        }
+    }
+
+    #[test]
+    fn csv_edges_round_trip_attribute_columns() {
+        let edge = |id: &str, attributes: Option<serde_json::Value>| Edge {
+            id: id.to_string(),
+            source: "a".to_string(),
+            target: "b".to_string(),
+            label: "calls".to_string(),
+            layer: "app".to_string(),
+            weight: 4,
+            attributes,
+            ..Default::default()
+        };
+        let graph = Graph {
+            name: "Attributes".to_string(),
+            nodes: Vec::new(),
+            edges: vec![
+                edge(
+                    "e2",
+                    Some(serde_json::json!({ "protocol": "http, tls", "note": "say \"hi\"" })),
+                ),
+                // A key shadowing a fixed header, typed values and a string
+                // that looks like a number.
+                edge(
+                    "e1",
+                    Some(serde_json::json!({
+                        "owner": "ops",
+                        "label": "shadow",
+                        "retries": 3,
+                        "secure": true,
+                        "port": "443"
+                    })),
+                ),
+                edge("e3", None),
+            ],
+            layers: Vec::new(),
+            annotations: None,
+        };
+
+        let csv = crate::export::to_csv_edges::render(&graph, &render_config(true)).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "id,source,target,label,layer,weight,comment,attr_label,attr_note,attr_owner,\
+                 attr_port,attr_protocol,attr_retries,attr_secure"
+            )
+        );
+        assert_eq!(
+            lines.next(),
+            Some(r#"e1,a,b,calls,app,4,,shadow,,ops,"""443""",,3,true"#)
+        );
+        assert!(csv.contains(r#",,"say ""hi""",,,"http, tls",,"#), "{csv}");
+
+        let mut reader = csv::ReaderBuilder::new().from_reader(csv.as_bytes());
+        let headers: Vec<String> = reader.headers().unwrap().iter().map(String::from).collect();
+        let profile = create_df_edge_load_profile(&headers);
+        let loaded: Vec<Edge> = reader
+            .records()
+            .map(|record| Edge::from_row(&record.unwrap(), &profile).unwrap())
+            .collect();
+
+        let by_id = |id: &str| graph.edges.iter().find(|e| e.id == id).unwrap();
+        assert_eq!(loaded.len(), 3);
+        for edge in &loaded {
+            let original = by_id(&edge.id);
+            assert_eq!(edge.weight, original.weight);
+            assert_eq!(edge.attributes, original.attributes, "{}", edge.id);
+        }
+    }
+
+    #[test]
+    fn csv_edges_only_load_prefixed_columns_as_attributes() {
+        let csv = "id,source,target,label,layer,weight,comment,extra,attr_team\n\
+                   e1,a,b,calls,app,1,,ignored,ops\n";
+        let mut reader = csv::ReaderBuilder::new().from_reader(csv.as_bytes());
+        let headers: Vec<String> = reader.headers().unwrap().iter().map(String::from).collect();
+        let profile = create_df_edge_load_profile(&headers);
+        let record = reader.records().next().unwrap().unwrap();
+        let edge = Edge::from_row(&record, &profile).unwrap();
+        assert_eq!(edge.attributes, Some(serde_json::json!({ "team": "ops" })));
+    }
+
+    #[test]
+    fn validate_delimited_rows_reports_file_lines() {
+        let content = b"id,label,weight\n\
+a,A,1\n\
+b,,2\n\
+c,C,heavy\n\
+a,Again,3\n\
+\"d\",\"D\nmultiline\",4\n\
+e\n";
+
+        let validated = validate_delimited_rows(content, b',', &["id", "label"], false).unwrap();
+        let ids: Vec<&str> = validated.rows.iter().map(|r| &r[0]).collect();
+        assert_eq!(ids, vec!["a", "d"]);
+        let errors: Vec<(u64, Option<&str>)> = validated
+            .errors
+            .iter()
+            .map(|e| (e.line, e.column.as_deref()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (3, Some("label")),
+                (4, Some("weight")),
+                (5, Some("id")),
+                (8, Some("label")),
+            ]
+        );
+        assert!(validated.errors[2].message.contains("first seen on line 2"));
+
+        let err = validate_delimited_rows(content, b',', &["id", "label"], true)
+            .err()
+            .expect("strict mode rejects invalid rows");
+        assert!(err.to_string().starts_with("4 invalid row(s)"), "{err}");
+        assert!(validate_delimited_rows(content, b',', &["source"], false).is_err());
+    }
+
+    #[test]
+    fn csv_adjacency_round_trips_a_directed_graph() {
+        let node = |id: &str| Node {
+            id: id.to_string(),
+            label: id.to_string(),
+            layer: "app".to_string(),
+            weight: 1,
+            ..Default::default()
+        };
+        let edge = |source: &str, target: &str, weight: i32| Edge {
+            id: format!("{}->{}", source, target),
+            source: source.to_string(),
+            target: target.to_string(),
+            layer: "app".to_string(),
+            weight,
+            ..Default::default()
+        };
+        let graph = Graph {
+            name: "Directed".to_string(),
+            nodes: vec![node("a"), node("b"), node("c"), node("lonely")],
+            edges: vec![edge("a", "b", 3), edge("a", "c", 1), edge("c", "b", 7)],
+            layers: Vec::new(),
+            annotations: None,
+        };
+
+        let csv = crate::export::to_csv_adjacency::render(&graph, &render_config(true)).unwrap();
+        assert_eq!(csv, "a,b:3,c:1\nb\nc,b:7\nlonely\n");
+
+        let unweighted =
+            crate::export::to_csv_adjacency::render(&graph, &render_config(false)).unwrap();
+        assert_eq!(unweighted, "a,b:1,c:1\nb\nc,b:1\nlonely\n");
+
+        let parsed = parse_csv_adjacency(&csv).unwrap();
+        let ids = |g: &Graph| g.nodes.iter().map(|n| n.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&parsed), ids(&graph));
+        let edges = |g: &Graph| {
+            g.edges
+                .iter()
+                .map(|e| (e.id.clone(), e.source.clone(), e.target.clone(), e.weight))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(edges(&parsed), edges(&graph));
+    }
+
+    #[test]
+    fn dot_clusters_become_layers_with_their_members() {
+        let dot = r##"
+            // Exported from a whiteboard
+            digraph "Payments Platform" {
+                node [shape=box];
+                "Web App" -> gateway [label="HTTPS", weight=3, color="#ff0000"];
+                subgraph cluster_backend {
+                    label = "Back end";
+                    fillcolor = "#ddeeff";
+                    color = "#334455";
+                    gateway [label="API Gateway"];
+                    "Ledger DB" [comment="primary"];
+                    gateway -> "Ledger DB" -> gateway;
+                }
+                /* not in any cluster */
+                audit;
+            }
+        "##;
+
+        let graph = parse_dot(dot).unwrap();
+        assert_eq!(graph.name, "Payments Platform");
+
+        assert_eq!(graph.layers.len(), 1);
+        let layer = &graph.layers[0];
+        assert_eq!(
+            (layer.id.as_str(), layer.label.as_str()),
+            ("backend", "Back end")
+        );
+        assert_eq!(
+            (
+                layer.background_color.as_str(),
+                layer.text_color.as_str(),
+                layer.border_color.as_str()
+            ),
+            ("ddeeff", "ffffff", "334455")
+        );
+
+        let node = |id: &str| graph.nodes.iter().find(|n| n.id == id).unwrap();
+        let cluster = node("cluster_backend");
+        assert!(cluster.is_partition);
+        assert_eq!(cluster.label, "Back end");
+        let members: Vec<&str> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.belongs_to.as_deref() == Some("cluster_backend"))
+            .map(|n| n.id.as_str())
+            .collect();
+        assert_eq!(members, vec!["gateway", "Ledger DB"]);
+        assert_eq!(node("gateway").layer, "backend");
+        assert_eq!(node("gateway").label, "API Gateway");
+        assert_eq!(node("Ledger DB").comment.as_deref(), Some("primary"));
+        assert_eq!(node("Web App").layer, "");
+        assert_eq!(node("audit").belongs_to, None);
+        assert_eq!(
+            node("Web App").attributes,
+            Some(serde_json::json!({ "shape": "box" }))
+        );
+
+        let ids: Vec<&str> = graph.edges.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "Web App->gateway",
+                "gateway->Ledger DB",
+                "Ledger DB->gateway"
+            ]
+        );
+        let first = &graph.edges[0];
+        assert_eq!((first.label.as_str(), first.weight), ("HTTPS", 3));
+        assert_eq!(
+            first.attributes,
+            Some(serde_json::json!({ "color": "#ff0000" }))
+        );
+    }
+
+    #[test]
+    fn dot_undirected_graphs_expand_subgraph_operands() {
+        let graph = parse_dot("graph { a -- { b c }; a -- b }").unwrap();
+        let ids: Vec<&str> = graph.edges.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["a--b", "a--c", "a--b#2"]);
+        assert!(graph.layers.is_empty());
+
+        let err = parse_dot("graph { a -> b }").unwrap_err();
+        assert!(err.to_string().contains("not allowed in a graph"), "{err}");
+        assert!(parse_dot("digraph { a -> ").is_err());
+    }
+}
@@ -18,6 +18,7 @@ use strum::{AsRefStr, Display, EnumIter, EnumString};
 /// - `Ods`: OpenDocument Spreadsheet format, open alternative to Excel
 /// - `Pdf`: Portable Document Format, for read-only exports
 /// - `Xml`: Extensible Markup Language, for structured data exchange
+/// - `Dot`: Graphviz DOT (`.dot` or `.gv`), imported as a complete graph
 #[derive(
     Debug,
     Clone,
@@ -42,6 +43,8 @@ pub enum FileFormat {
     Ods,
     Pdf,
     Xml,
+    #[strum(to_string = "dot", serialize = "gv")]
+    Dot,
 }
 
 impl FileFormat {
@@ -230,7 +233,8 @@ impl DataType {
             | (FileFormat::Tsv, DataType::Nodes)
             | (FileFormat::Tsv, DataType::Edges)
             | (FileFormat::Tsv, DataType::Layers)
-            | (FileFormat::Json, DataType::Graph) => true,
+            | (FileFormat::Json, DataType::Graph)
+            | (FileFormat::Dot, DataType::Graph) => true,
             _ => false,
         }
     }
@@ -250,6 +254,7 @@ mod tests {
         assert_eq!(FileFormat::Ods.as_ref(), "ods");
         assert_eq!(FileFormat::Pdf.as_ref(), "pdf");
         assert_eq!(FileFormat::Xml.as_ref(), "xml");
+        assert_eq!(FileFormat::Dot.as_ref(), "dot");
     }
 
     #[test]
@@ -268,6 +273,7 @@ mod tests {
         assert_eq!("ods".parse::<FileFormat>().ok(), Some(FileFormat::Ods));
         assert_eq!("pdf".parse::<FileFormat>().ok(), Some(FileFormat::Pdf));
         assert_eq!("xml".parse::<FileFormat>().ok(), Some(FileFormat::Xml));
+        assert_eq!("gv".parse::<FileFormat>().ok(), Some(FileFormat::Dot));
         assert!("unknown".parse::<FileFormat>().is_err());
     }
 
@@ -294,6 +300,14 @@ mod tests {
             FileFormat::from_extension("sheet.ods"),
             Some(FileFormat::Ods)
         );
+        assert_eq!(
+            FileFormat::from_extension("network.dot"),
+            Some(FileFormat::Dot)
+        );
+        assert_eq!(
+            FileFormat::from_extension("network.GV"),
+            Some(FileFormat::Dot)
+        );
         assert_eq!(FileFormat::from_extension("noext"), None);
         assert_eq!(FileFormat::from_extension("unknown.xyz"), None);
     }
@@ -309,7 +323,7 @@ mod tests {
     #[test]
     fn test_file_format_iter() {
         let formats: Vec<_> = FileFormat::iter().collect();
-        assert_eq!(formats.len(), 7);
+        assert_eq!(formats.len(), 8);
         assert!(formats.contains(&FileFormat::Csv));
         assert!(formats.contains(&FileFormat::Json));
         assert!(formats.contains(&FileFormat::Xlsx));
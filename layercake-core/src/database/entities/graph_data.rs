@@ -133,6 +133,9 @@ pub enum GraphDataStatus {
     Active,
     #[serde(rename = "error")]
     Error,
+    /// The run was cancelled before this node executed.
+    #[serde(rename = "cancelled")]
+    Cancelled,
 }
 
 impl GraphDataStatus {
@@ -141,6 +144,7 @@ impl GraphDataStatus {
             Self::Processing => "processing",
             Self::Active => "active",
             Self::Error => "error",
+            Self::Cancelled => "cancelled",
         }
     }
 }
@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A named, immutable copy of a graph_data entry's contents. Nodes, edges and
+/// the project's layers are stored as content-addressed blobs (see
+/// `graph_snapshot_blobs`) referenced by hash.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "graph_snapshots")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub graph_data_id: i32,
+    pub label: String,
+    pub nodes_hash: String,
+    pub edges_hash: String,
+    pub layers_hash: String,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub annotations: Option<serde_json::Value>,
+    pub node_count: i32,
+    pub edge_count: i32,
+    pub created_at: ChronoDateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::graph_data::Entity",
+        from = "Column::GraphDataId",
+        to = "super::graph_data::Column::Id"
+    )]
+    GraphData,
+}
+
+impl Related<super::graph_data::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GraphData.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,5 +1,7 @@
+pub mod collaboration_events;
 pub mod common_types;
 pub mod data_sets;
+pub mod dataset_schemas;
 pub mod library_items;
 pub mod plan_dag_annotations;
 pub mod plan_dag_edges;
@@ -29,6 +31,8 @@ pub mod layer_aliases;
 pub mod graph_data;
 pub mod graph_data_edges;
 pub mod graph_data_nodes;
+pub mod graph_snapshot_blobs;
+pub mod graph_snapshots;
 
 // Re-export specific entities to avoid naming conflicts
 pub use execution_state::ExecutionState;
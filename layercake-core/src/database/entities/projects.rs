@@ -14,6 +14,8 @@ pub struct Model {
     pub import_export_path: Option<String>,
     pub created_at: ChronoDateTimeUtc,
     pub updated_at: ChronoDateTimeUtc,
+    /// Set when the project is soft-deleted; cleared again on restore.
+    pub deleted_at: Option<ChronoDateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -48,6 +50,7 @@ impl ActiveModel {
             import_export_path: Set(None),
             created_at: Set(chrono::Utc::now()),
             updated_at: Set(chrono::Utc::now()),
+            deleted_at: Set(None),
         }
     }
 
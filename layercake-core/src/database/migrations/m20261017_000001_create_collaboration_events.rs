@@ -0,0 +1,60 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+/// Persist collaboration events so subscribers that (re)join a plan can replay
+/// recent history. `plan_id` is the broadcast channel key (e.g. `project_1`),
+/// not a foreign key; `payload` holds the serialised event.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            r#"
+            CREATE TABLE collaboration_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                event_id TEXT NOT NULL UNIQUE,
+                plan_id TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                event_type TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )
+            "#
+            .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            "CREATE INDEX idx_collaboration_events_plan ON collaboration_events(plan_id, created_at)"
+                .to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            "CREATE INDEX idx_collaboration_events_created ON collaboration_events(created_at)"
+                .to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            "DROP TABLE IF EXISTS collaboration_events".to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+/// Named, immutable snapshots of a graph's contents. Node, edge and layer sets
+/// are stored once in `graph_snapshot_blobs`, keyed by the SHA-256 of their
+/// JSON, so snapshots of unchanged contents share storage.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        for statement in [
+            r#"
+            CREATE TABLE graph_snapshot_blobs (
+                hash TEXT PRIMARY KEY NOT NULL,
+                content TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )
+            "#,
+            r#"
+            CREATE TABLE graph_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                graph_data_id INTEGER NOT NULL REFERENCES graph_data(id) ON DELETE CASCADE,
+                label TEXT NOT NULL,
+                nodes_hash TEXT NOT NULL REFERENCES graph_snapshot_blobs(hash),
+                edges_hash TEXT NOT NULL REFERENCES graph_snapshot_blobs(hash),
+                layers_hash TEXT NOT NULL REFERENCES graph_snapshot_blobs(hash),
+                annotations TEXT,
+                node_count INTEGER NOT NULL,
+                edge_count INTEGER NOT NULL,
+                created_at TEXT NOT NULL
+            )
+            "#,
+            "CREATE INDEX idx_graph_snapshots_graph_data_id ON graph_snapshots(graph_data_id)",
+        ] {
+            db.execute(Statement::from_string(
+                manager.get_database_backend(),
+                statement.to_string(),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        for table in ["graph_snapshots", "graph_snapshot_blobs"] {
+            db.execute(Statement::from_string(
+                manager.get_database_backend(),
+                format!("DROP TABLE IF EXISTS {}", table),
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+}
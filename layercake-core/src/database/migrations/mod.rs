@@ -51,6 +51,10 @@ mod m20260709_000001_rebuild_graph_edits_drop_graphs_fk;
 mod m20260715_000001_drop_code_analysis_profiles;
 mod m20260715_000002_normalise_sequence_edge_order;
 mod m20260715_000003_add_enabled_graph_ids_to_stories;
+mod m20261017_000001_create_collaboration_events;
+mod m20261017_000002_create_dataset_schemas;
+mod m20261017_000003_add_deleted_at_to_projects;
+mod m20261017_000004_create_graph_snapshots;
 
 pub struct Migrator;
 
@@ -109,6 +113,10 @@ impl MigratorTrait for Migrator {
             Box::new(m20260715_000001_drop_code_analysis_profiles::Migration),
             Box::new(m20260715_000002_normalise_sequence_edge_order::Migration),
             Box::new(m20260715_000003_add_enabled_graph_ids_to_stories::Migration),
+            Box::new(m20261017_000001_create_collaboration_events::Migration),
+            Box::new(m20261017_000002_create_dataset_schemas::Migration),
+            Box::new(m20261017_000003_add_deleted_at_to_projects::Migration),
+            Box::new(m20261017_000004_create_graph_snapshots::Migration),
         ]
     }
 }
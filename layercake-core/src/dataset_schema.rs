@@ -0,0 +1,191 @@
+//! Contracts that uploaded datasets can be checked against.
+//!
+//! A `DatasetSchema` names the fields every node and edge must carry, the type
+//! each field must have, and the layers items may reference. Fields are either
+//! built-in columns (`id`, `label`, `layer`, `weight`, `comment`, `belongs_to`,
+//! `is_partition`, plus `source`/`target` on edges) or attribute keys.
+//! Validation only reads the graph; it never rewrites the dataset.
+
+use crate::graph::{Edge, Graph, Node};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatasetSchema {
+    #[serde(default)]
+    pub nodes: RecordSchema,
+    #[serde(default)]
+    pub edges: RecordSchema,
+    /// Layers nodes and edges may use. `None` allows any layer.
+    #[serde(default)]
+    pub allowed_layers: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordSchema {
+    /// Fields that must be present and non-empty on every row.
+    #[serde(default)]
+    pub required: Vec<String>,
+    /// Type constraints, checked only where the field has a value.
+    #[serde(default)]
+    pub fields: Vec<FieldConstraint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldConstraint {
+    pub name: String,
+    pub field_type: FieldType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    Integer,
+    Number,
+    Boolean,
+}
+
+impl FieldType {
+    fn as_str(self) -> &'static str {
+        match self {
+            FieldType::String => "string",
+            FieldType::Integer => "integer",
+            FieldType::Number => "number",
+            FieldType::Boolean => "boolean",
+        }
+    }
+
+    /// Tabular uploads store attribute cells as text, so numeric and boolean
+    /// fields also accept strings that parse as that type.
+    fn accepts(self, value: &Value) -> bool {
+        match (self, value) {
+            (FieldType::String, Value::String(_)) => true,
+            (FieldType::Integer, Value::Number(number)) => number.is_i64() || number.is_u64(),
+            (FieldType::Integer, Value::String(text)) => text.trim().parse::<i64>().is_ok(),
+            (FieldType::Number, Value::Number(_)) => true,
+            (FieldType::Number, Value::String(text)) => text.trim().parse::<f64>().is_ok(),
+            (FieldType::Boolean, Value::Bool(_)) => true,
+            (FieldType::Boolean, Value::String(text)) => {
+                matches!(text.trim().to_ascii_lowercase().as_str(), "true" | "false")
+            }
+            _ => false,
+        }
+    }
+}
+
+impl DatasetSchema {
+    /// Check every node and edge, returning one message per violation.
+    ///
+    /// Messages name the row as `nodes[i]`/`edges[i]` (zero-based, in stored
+    /// order) along with its ID, e.g.
+    /// `nodes[2] (id "n3"): missing required field "owner"`.
+    pub fn validate(&self, graph: &Graph) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        for (index, node) in graph.nodes.iter().enumerate() {
+            let row = format!("nodes[{}] (id {:?})", index, node.id);
+            self.check_row(
+                &self.nodes,
+                &row,
+                &node.layer,
+                |name| node_field(node, name),
+                &mut errors,
+            );
+        }
+        for (index, edge) in graph.edges.iter().enumerate() {
+            let row = format!("edges[{}] (id {:?})", index, edge.id);
+            self.check_row(
+                &self.edges,
+                &row,
+                &edge.layer,
+                |name| edge_field(edge, name),
+                &mut errors,
+            );
+        }
+
+        errors
+    }
+
+    fn check_row(
+        &self,
+        schema: &RecordSchema,
+        row: &str,
+        layer: &str,
+        field: impl Fn(&str) -> Option<Value>,
+        errors: &mut Vec<String>,
+    ) {
+        for name in &schema.required {
+            if field(name).is_none() {
+                errors.push(format!("{}: missing required field {:?}", row, name));
+            }
+        }
+
+        for constraint in &schema.fields {
+            if let Some(value) = field(&constraint.name) {
+                if !constraint.field_type.accepts(&value) {
+                    errors.push(format!(
+                        "{}: field {:?} expected {}, got {}",
+                        row,
+                        constraint.name,
+                        constraint.field_type.as_str(),
+                        value
+                    ));
+                }
+            }
+        }
+
+        if let Some(allowed) = &self.allowed_layers {
+            if !layer.is_empty() && !allowed.iter().any(|candidate| candidate == layer) {
+                errors.push(format!("{}: layer {:?} is not allowed", row, layer));
+            }
+        }
+    }
+}
+
+fn node_field(node: &Node, name: &str) -> Option<Value> {
+    let value = match name {
+        "id" => Value::from(node.id.as_str()),
+        "label" => Value::from(node.label.as_str()),
+        "layer" => Value::from(node.layer.as_str()),
+        "weight" => Value::from(node.weight),
+        "is_partition" => Value::from(node.is_partition),
+        "belongs_to" => Value::from(node.belongs_to.clone()),
+        "comment" => Value::from(node.comment.clone()),
+        _ => attribute(node.attributes.as_ref(), name),
+    };
+    present(value)
+}
+
+fn edge_field(edge: &Edge, name: &str) -> Option<Value> {
+    let value = match name {
+        "id" => Value::from(edge.id.as_str()),
+        "source" => Value::from(edge.source.as_str()),
+        "target" => Value::from(edge.target.as_str()),
+        "label" => Value::from(edge.label.as_str()),
+        "layer" => Value::from(edge.layer.as_str()),
+        "weight" => Value::from(edge.weight),
+        "comment" => Value::from(edge.comment.clone()),
+        _ => attribute(edge.attributes.as_ref(), name),
+    };
+    present(value)
+}
+
+fn attribute(attributes: Option<&Value>, name: &str) -> Value {
+    attributes
+        .and_then(|attrs| attrs.get(name))
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+/// Null and blank strings count as missing.
+fn present(value: Value) -> Option<Value> {
+    match &value {
+        Value::Null => None,
+        Value::String(text) if text.trim().is_empty() => None,
+        _ => Some(value),
+    }
+}
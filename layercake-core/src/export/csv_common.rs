@@ -3,6 +3,8 @@
 /// This module provides generic helpers to eliminate duplication across
 /// CSV export functions.
 use csv::Writer;
+use serde_json::Value;
+use std::collections::BTreeSet;
 use std::error::Error;
 
 /// Generic CSV exporter that handles the common pattern of:
@@ -90,6 +92,41 @@ where
     String::from_utf8(data).map_err(Into::into)
 }
 
+/// Sorted union of the attribute keys across all items, used as extra
+/// columns after an exporter's fixed ones.
+pub fn attribute_columns<'a>(
+    attributes: impl IntoIterator<Item = Option<&'a Value>>,
+) -> Vec<String> {
+    attributes
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object)
+        .flat_map(|map| map.keys().cloned())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Header of the column holding attribute `key`
+pub fn attribute_header(key: &str) -> String {
+    format!("{}{}", crate::data_loader::ATTRIBUTE_COLUMN_PREFIX, key)
+}
+
+/// Cell text for one attribute, blank when the item lacks the key. Strings
+/// are written as-is unless they would read back as another JSON value (e.g.
+/// `"42"` or `"true"`), in which case they are quoted; everything else is
+/// written as JSON, so `data_loader::parse_attribute_cell` restores the type.
+pub fn attribute_cell(attributes: Option<&Value>, key: &str) -> String {
+    match attributes.and_then(|value| value.get(key)) {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => match crate::data_loader::parse_attribute_cell(text) {
+            Value::String(_) => text.clone(),
+            _ => Value::String(text.clone()).to_string(),
+        },
+        Some(other) => other.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
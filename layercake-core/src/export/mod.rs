@@ -1,5 +1,6 @@
 mod csv_common;
 pub mod sequence_renderer;
+pub mod to_csv_adjacency;
 pub mod to_csv_edges;
 pub mod to_csv_matrix;
 pub mod to_csv_nodes;
@@ -9,20 +10,27 @@ pub mod to_dot_hierarchy;
 pub mod to_gml;
 pub mod to_jsgraph;
 pub mod to_json;
+pub mod to_json_hierarchy;
+pub mod to_jsonl;
 pub mod to_mermaid;
 pub mod to_mermaid_mindmap;
 pub mod to_mermaid_sequence;
 pub mod to_mermaid_treemap;
+#[cfg(feature = "parquet")]
+pub mod to_parquet;
 pub mod to_plantuml;
 pub mod to_plantuml_mindmap;
 pub mod to_plantuml_sequence;
 pub mod to_plantuml_wbs;
+pub mod to_svg;
 
 /// Common rendering function used by all exporters
 /// This helps eliminate duplication across export modules
 pub mod renderer {
     use crate::graph::{Edge, Graph, Layer, Node, TreeNode};
-    use crate::plan::{LayerSourceStyle, RenderConfig};
+    use crate::plan::{
+        ColorByAttribute, LayerSourceStyle, RenderConfig, RenderConfigBuiltInStyle,
+    };
     use indexmap::IndexMap;
     use serde_json::{json, Value};
     use std::collections::HashMap;
@@ -35,8 +43,15 @@ pub mod renderer {
         pub hierarchy_edges: Vec<Edge>,
         pub hierarchy_tree: Value,
         pub hierarchy_tree_edges: Vec<TreeNode>,
+        /// Partition nodes and their links to direct children; empty unless
+        /// `render_partitions_as_nodes` is set.
+        pub partition_nodes: Vec<Node>,
+        pub partition_edges: Vec<Edge>,
         pub layer_map: IndexMap<String, Layer>,
         pub layers: Vec<Layer>,
+        /// Background colour (hex, no `#`) per node id from
+        /// `color_by_attribute`; nodes without the attribute are absent.
+        pub node_colors: HashMap<String, String>,
     }
 
     pub fn prepare_graph_data(graph: &Graph, render_config: &RenderConfig) -> PreparedGraphData {
@@ -55,19 +70,40 @@ pub mod renderer {
         let mut hierarchy_tree_nodes = graph.build_tree();
         let mut hierarchy_tree_edges = graph.build_tree_from_edges();
 
+        apply_weight_config(&mut flow_nodes, &mut flow_edges, render_config);
+        apply_weight_config(&mut hierarchy_nodes, &mut hierarchy_edges, render_config);
         if !render_config.use_node_weight {
-            reset_node_weights(&mut flow_nodes);
-            reset_node_weights(&mut hierarchy_nodes);
             reset_tree_weights(&mut hierarchy_tree_nodes);
             reset_tree_weights(&mut hierarchy_tree_edges);
         }
 
-        if !render_config.use_edge_weight {
-            reset_edge_weights(&mut flow_edges);
-            reset_edge_weights(&mut hierarchy_edges);
+        let node_colors = render_config
+            .color_by_attribute
+            .as_ref()
+            .map(|spec| attribute_colors(&graph.nodes, spec))
+            .unwrap_or_default();
+
+        let mut hierarchy_tree = serde_json::to_value(&hierarchy_tree_nodes).unwrap_or(Value::Null);
+        if !node_colors.is_empty() {
+            insert_tree_fill_colors(&mut hierarchy_tree, &node_colors);
         }
 
-        let hierarchy_tree = serde_json::to_value(&hierarchy_tree_nodes).unwrap_or(Value::Null);
+        let (partition_nodes, partition_edges) = if render_config.render_partitions_as_nodes {
+            let partition_nodes: Vec<Node> = graph
+                .nodes
+                .iter()
+                .filter(|node| node.is_partition)
+                .cloned()
+                .collect();
+            let partition_edges = hierarchy_edges
+                .iter()
+                .filter(|edge| partition_nodes.iter().any(|node| node.id == edge.source))
+                .cloned()
+                .collect();
+            (partition_nodes, partition_edges)
+        } else {
+            (Vec::new(), Vec::new())
+        };
 
         let mut layer_map = graph.get_layer_map();
 
@@ -111,8 +147,11 @@ pub mod renderer {
             hierarchy_edges,
             hierarchy_tree,
             hierarchy_tree_edges,
+            partition_nodes,
+            partition_edges,
             layer_map,
             layers,
+            node_colors,
         }
     }
 
@@ -136,22 +175,28 @@ pub mod renderer {
 
         let flow_edges = with_relative_weight(&data.flow_edges);
         let hierarchy_edges = with_relative_weight(&data.hierarchy_edges);
+        let flow_nodes = with_fill_color(&data.flow_nodes, &data.node_colors);
+        let hierarchy_nodes = with_fill_color(&data.hierarchy_nodes, &data.node_colors);
 
         json!({
             "graph_name": &graph.name,
             "config": render_config,
-            "hierarchy_nodes": data.hierarchy_nodes,
+            "hierarchy_nodes": hierarchy_nodes,
             "hierarchy_edges": hierarchy_edges,
             "hierarchy_tree": data.hierarchy_tree,
             "hierarchy_tree_edges": data.hierarchy_tree_edges,
-            "flow_nodes": data.flow_nodes,
+            "flow_nodes": flow_nodes,
             "flow_edges": flow_edges,
+            "partition_nodes": data.partition_nodes,
+            "partition_edges": data.partition_edges,
+            "partition_style": partition_style(render_config),
             "layers": data.layers,
             "layer_map": data.layer_map,
         })
     }
 
-    fn with_relative_weight(edges: &[Edge]) -> Vec<Value> {
+    /// Scale edge weights linearly into the 1-6 range used for edge styling.
+    fn relative_weights(edges: &[Edge]) -> Vec<i32> {
         if edges.is_empty() {
             return Vec::new();
         }
@@ -170,8 +215,16 @@ pub mod renderer {
             .map(|edge| {
                 let weight = std::cmp::max(1, edge.weight) as f64;
                 let ratio = ((weight - min_w as f64) / range).clamp(0.0, 1.0);
-                let rel = (1.0 + (ratio * 5.0)).round() as i32; // 1-6 inclusive
+                (1.0 + (ratio * 5.0)).round() as i32 // 1-6 inclusive
+            })
+            .collect()
+    }
 
+    fn with_relative_weight(edges: &[Edge]) -> Vec<Value> {
+        edges
+            .iter()
+            .zip(relative_weights(edges))
+            .map(|(edge, rel)| {
                 let mut value = serde_json::to_value(edge).unwrap_or(Value::Null);
                 if let Some(map) = value.as_object_mut() {
                     map.insert("relative_weight".to_string(), Value::from(rel));
@@ -181,6 +234,126 @@ pub mod renderer {
             .collect()
     }
 
+    fn with_fill_color(nodes: &[Node], colors: &HashMap<String, String>) -> Vec<Value> {
+        nodes
+            .iter()
+            .map(|node| {
+                let mut value = serde_json::to_value(node).unwrap_or(Value::Null);
+                if let (Some(map), Some(color)) = (value.as_object_mut(), colors.get(&node.id)) {
+                    map.insert("fill_color".to_string(), Value::from(color.as_str()));
+                }
+                value
+            })
+            .collect()
+    }
+
+    fn insert_tree_fill_colors(tree: &mut Value, colors: &HashMap<String, String>) {
+        match tree {
+            Value::Array(items) => {
+                for item in items {
+                    insert_tree_fill_colors(item, colors);
+                }
+            }
+            Value::Object(map) => {
+                let color = map
+                    .get("id")
+                    .and_then(|id| id.as_str())
+                    .and_then(|id| colors.get(id));
+                if let Some(color) = color {
+                    map.insert("fill_color".to_string(), Value::from(color.as_str()));
+                }
+                if let Some(children) = map.get_mut("children") {
+                    insert_tree_fill_colors(children, colors);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Interpolate a colour for every node carrying a numeric `spec.key`
+    /// attribute, scaled across the min-max range of that attribute. A range
+    /// of zero maps every node to the midpoint colour; invalid hex colours
+    /// disable the override.
+    fn attribute_colors(nodes: &[Node], spec: &ColorByAttribute) -> HashMap<String, String> {
+        let (Some(low), Some(high)) = (parse_hex(&spec.min_color), parse_hex(&spec.max_color))
+        else {
+            return HashMap::new();
+        };
+
+        let values: Vec<(&str, f64)> = nodes
+            .iter()
+            .filter_map(|node| {
+                let value = node.attributes.as_ref()?.get(&spec.key)?;
+                let number = match value {
+                    Value::Number(number) => number.as_f64(),
+                    Value::String(text) => text.trim().parse::<f64>().ok(),
+                    _ => None,
+                }?;
+                number.is_finite().then_some((node.id.as_str(), number))
+            })
+            .collect();
+
+        let (min_v, max_v) = values.iter().fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(min_v, max_v), (_, v)| (min_v.min(*v), max_v.max(*v)),
+        );
+        let range = max_v - min_v;
+
+        values
+            .into_iter()
+            .map(|(id, value)| {
+                let ratio = if range > 0.0 {
+                    (value - min_v) / range
+                } else {
+                    0.5
+                };
+                let channel = |from: u8, to: u8| {
+                    (from as f64 + (to as f64 - from as f64) * ratio).round() as u8
+                };
+                let color = format!(
+                    "{:02x}{:02x}{:02x}",
+                    channel(low[0], high[0]),
+                    channel(low[1], high[1]),
+                    channel(low[2], high[2])
+                );
+                (id.to_string(), color)
+            })
+            .collect()
+    }
+
+    fn parse_hex(color: &str) -> Option<[u8; 3]> {
+        let hex = color.trim().trim_start_matches('#');
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some([channel(0)?, channel(2)?, channel(4)?])
+    }
+
+    /// Reset node and edge weights to 1 when the render config disables them,
+    /// or rescale edge weights to 1-6 when it asks for normalised weights.
+    pub fn apply_weight_config(
+        nodes: &mut [Node],
+        edges: &mut [Edge],
+        render_config: &RenderConfig,
+    ) {
+        if !render_config.use_node_weight {
+            reset_node_weights(nodes);
+        }
+        if !render_config.use_edge_weight {
+            reset_edge_weights(edges);
+        } else if render_config.normalize_edge_weights {
+            normalize_edge_weights(edges);
+        }
+    }
+
+    fn normalize_edge_weights(edges: &mut [Edge]) {
+        let weights = relative_weights(edges);
+        for (edge, weight) in edges.iter_mut().zip(weights) {
+            edge.weight = weight;
+        }
+    }
+
     fn reset_node_weights(nodes: &mut [Node]) {
         for node in nodes {
             node.weight = 1;
@@ -200,12 +373,35 @@ pub mod renderer {
         }
     }
 
-    fn apply_layer_style(layer: &mut Layer, mode: &LayerSourceStyle) {
-        let palette = match mode {
+    /// Background, text and border colours for a layer source style.
+    fn style_palette(mode: &LayerSourceStyle) -> (&'static str, &'static str, &'static str) {
+        match mode {
             LayerSourceStyle::Default => ("222222", "ffffff", "dddddd"),
             LayerSourceStyle::Light => ("f7f7f8", "0f172a", "e2e8f0"),
             LayerSourceStyle::Dark => ("1f2933", "f8fafc", "94a3b8"),
+        }
+    }
+
+    /// Colours for partition header nodes, taken from the palette matching
+    /// the active built-in style; `None` leaves them unstyled.
+    fn partition_style(render_config: &RenderConfig) -> Value {
+        let mode = match render_config.built_in_styles {
+            RenderConfigBuiltInStyle::None => return Value::Null,
+            RenderConfigBuiltInStyle::Light | RenderConfigBuiltInStyle::Blueprint => {
+                LayerSourceStyle::Light
+            }
+            RenderConfigBuiltInStyle::Dark => LayerSourceStyle::Dark,
         };
+        let (background_color, text_color, border_color) = style_palette(&mode);
+        json!({
+            "background_color": background_color,
+            "text_color": text_color,
+            "border_color": border_color,
+        })
+    }
+
+    fn apply_layer_style(layer: &mut Layer, mode: &LayerSourceStyle) {
+        let palette = style_palette(mode);
 
         layer.background_color = palette.0.to_string();
         layer.text_color = palette.1.to_string();
@@ -217,10 +413,35 @@ pub mod renderer {
     }
 }
 
+/// Render a binary export format
+///
+/// Text formats go through their `render` functions; this covers the formats
+/// whose `ExportFormatInfo::binary` flag is set.
+pub fn render_bytes(
+    graph: &crate::graph::Graph,
+    format: &crate::plan::ExportFileType,
+    render_config: &crate::plan::RenderConfig,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    use crate::plan::ExportFileType;
+
+    match format {
+        #[cfg(feature = "parquet")]
+        ExportFileType::ParquetNodes => to_parquet::render_nodes(graph, render_config),
+        #[cfg(feature = "parquet")]
+        ExportFileType::ParquetEdges => to_parquet::render_edges(graph, render_config),
+        #[cfg(not(feature = "parquet"))]
+        ExportFileType::ParquetNodes | ExportFileType::ParquetEdges => {
+            let _ = (graph, render_config);
+            Err("Parquet export requires the `parquet` feature".into())
+        }
+        other => Err(format!("{:?} is not a binary export format", other).into()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::renderer::prepare_graph_data;
-    use crate::graph::{Graph, Layer, Node};
+    use crate::graph::{Edge, Graph, Layer, Node};
     use crate::plan::{
         NotePosition, RenderConfig, RenderConfigBuiltInStyle, RenderConfigOrientation,
         RenderTargetOptions,
@@ -255,10 +476,68 @@ mod tests {
             note_position: NotePosition::Left,
             use_node_weight: true,
             use_edge_weight: true,
+            normalize_edge_weights: false,
+            render_partitions_as_nodes: false,
+            directed: None,
+            precompute_layout: false,
+            prettify: false,
+            timestamp_format: crate::plan::TimestampFormat::Rfc3339,
             layer_source_styles: vec![],
+            color_by_attribute: None,
+        }
+    }
+
+    fn create_edge(id: &str, source: &str, target: &str, weight: i32) -> Edge {
+        Edge {
+            id: id.to_string(),
+            source: source.to_string(),
+            target: target.to_string(),
+            label: String::new(),
+            layer: "l1".to_string(),
+            weight,
+            comment: None,
+            dataset: None,
+            attributes: None,
+        }
+    }
+
+    fn weighted_graph() -> Graph {
+        Graph {
+            name: "Weighted".to_string(),
+            nodes: vec![
+                create_node("a", "A", "l1"),
+                create_node("b", "B", "l1"),
+                create_node("c", "C", "l1"),
+            ],
+            edges: vec![
+                create_edge("e1", "a", "b", 10),
+                create_edge("e2", "b", "c", 100),
+            ],
+            layers: vec![create_layer("l1")],
+            annotations: None,
         }
     }
 
+    #[test]
+    fn test_normalized_edge_weights_drive_dot_penwidth() {
+        use crate::export::{to_dot, to_gml};
+
+        let mut config = create_test_config();
+        config.normalize_edge_weights = true;
+
+        let dot = to_dot::render(&weighted_graph(), &config).unwrap();
+        assert!(dot.contains("a -> b [penwidth=1.0]"), "{dot}");
+        assert!(dot.contains("b -> c [penwidth=6.0]"), "{dot}");
+
+        let gml = to_gml::render(&weighted_graph(), &config).unwrap();
+        assert!(gml.contains("weight 6"), "{gml}");
+        assert!(!gml.contains("weight 100"), "{gml}");
+
+        config.normalize_edge_weights = false;
+        let gml = to_gml::render(&weighted_graph(), &config).unwrap();
+        assert!(gml.contains("weight 100"), "{gml}");
+    }
+
     #[test]
     fn test_missing_layers_are_created_with_defaults() {
         // Graph with nodes referencing layers that don't exist
@@ -317,6 +596,37 @@ mod tests {
         assert!(!prepared.layer_map.contains_key(""));
     }
 
+    #[test]
+    fn test_gml_undirected_collapses_reciprocal_edges() {
+        use crate::export::to_gml;
+
+        let mut graph = weighted_graph();
+        graph.edges.push(create_edge("e3", "b", "a", 5));
+
+        let mut config = create_test_config();
+        let gml = to_gml::render(&graph, &config).unwrap();
+        assert!(!gml.contains("directed"), "{gml}");
+        assert_eq!(gml.matches("type \"flow\"\n      source").count(), 3);
+
+        config.directed = Some(true);
+        let gml = to_gml::render(&graph, &config).unwrap();
+        assert!(gml.contains("directed 1"), "{gml}");
+        assert_eq!(gml.matches("type \"flow\"\n      source").count(), 3);
+        assert!(gml.contains("source b\n      target a"), "{gml}");
+
+        // A second b->a edge folds into the same canonical a-b pair
+        graph.edges.push(create_edge("e4", "b", "a", 2));
+        config.directed = Some(false);
+        let gml = to_gml::render(&graph, &config).unwrap();
+        assert!(gml.contains("directed 0"), "{gml}");
+        assert_eq!(gml.matches("type \"flow\"\n      source").count(), 2);
+        assert!(gml.contains("id e1"), "{gml}");
+        assert!(!gml.contains("id e3"), "{gml}");
+        assert!(!gml.contains("id e4"), "{gml}");
+        assert!(gml.contains("weight 17"), "{gml}");
+        assert!(!gml.contains("source b\n      target a"), "{gml}");
+    }
+
     #[test]
     fn test_dot_render_includes_nodes_with_missing_layers() {
         use crate::export::to_dot;
@@ -348,6 +658,465 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_jsgraph_positions_only_when_precompute_layout_is_set() {
+        use crate::export::to_jsgraph;
+
+        let mut config = create_test_config();
+        let html = to_jsgraph::render(&weighted_graph(), &config).unwrap();
+        assert!(!html.contains("\"x\":"), "{html}");
+
+        config.precompute_layout = true;
+        let html = to_jsgraph::render(&weighted_graph(), &config).unwrap();
+        assert_eq!(html.matches("\"x\":").count(), 3, "{html}");
+        assert!(html.contains("\"x\": 240.0"), "{html}");
+    }
+
+    #[test]
+    fn test_color_by_attribute_interpolates_node_fill() {
+        use crate::export::{to_dot, to_mermaid};
+        use crate::plan::ColorByAttribute;
+        use serde_json::json;
+
+        let scored = |id: &str, score: serde_json::Value| {
+            let mut node = create_node(id, id, "l1");
+            node.attributes = Some(json!({ "score": score }));
+            node
+        };
+        let graph = Graph {
+            name: "Heatmap".to_string(),
+            nodes: vec![
+                scored("low", json!(0)),
+                scored("mid", json!("5")),
+                scored("high", json!(10.0)),
+                create_node("plain", "plain", "l1"),
+            ],
+            edges: vec![],
+            layers: vec![create_layer("l1")],
+            annotations: None,
+        };
+        let mut config = create_test_config();
+        config.color_by_attribute = Some(ColorByAttribute {
+            key: "score".to_string(),
+            min_color: "#000000".to_string(),
+            max_color: "c8c8c8".to_string(),
+        });
+
+        let prepared = prepare_graph_data(&graph, &config);
+        assert_eq!(prepared.node_colors["low"], "000000");
+        assert_eq!(prepared.node_colors["mid"], "646464");
+        assert_eq!(prepared.node_colors["high"], "c8c8c8");
+        assert!(!prepared.node_colors.contains_key("plain"));
+
+        let dot = to_dot::render(&graph, &config).unwrap();
+        assert!(
+            dot.contains("mid[label=\"mid\", fillcolor=\"#646464\"]"),
+            "{dot}"
+        );
+        assert!(dot.contains("plain[label=\"plain\"];"), "{dot}");
+
+        let mermaid = to_mermaid::render(&graph, &config).unwrap();
+        assert!(mermaid.contains("style mid fill:#646464;"), "{mermaid}");
+        assert!(!mermaid.contains("style plain"), "{mermaid}");
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_nodes_round_trip_with_typed_attribute_columns() {
+        use crate::export::to_parquet;
+        use arrow_array::cast::AsArray;
+        use arrow_array::types::Float64Type;
+        use arrow_array::Array;
+        use arrow_schema::DataType;
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use serde_json::json;
+
+        let with_attributes = |id: &str, attributes: serde_json::Value| {
+            let mut node = create_node(id, &id.to_uppercase(), "l1");
+            node.attributes = Some(attributes);
+            node
+        };
+        let graph = Graph {
+            name: "Parquet".to_string(),
+            nodes: vec![
+                with_attributes("b", json!({ "score": 2.5, "team": "core", "id": 7 })),
+                with_attributes("a", json!({ "score": 1, "active": true })),
+                create_node("c", "C", "l1"),
+            ],
+            edges: vec![create_edge("e1", "a", "b", 3)],
+            layers: vec![create_layer("l1")],
+            annotations: None,
+        };
+        let config = create_test_config();
+
+        let bytes = to_parquet::render_nodes(&graph, &config).unwrap();
+        let mut batches: Vec<_> =
+            ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(bytes))
+                .unwrap()
+                .build()
+                .unwrap()
+                .map(|batch| batch.unwrap())
+                .collect();
+        assert_eq!(batches.len(), 1);
+        let batch = batches.remove(0);
+        assert_eq!(batch.num_rows(), 3);
+
+        let schema = batch.schema();
+        let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["id", "label", "layer", "weight", "active", "attr_id", "score", "team"]
+        );
+        assert_eq!(schema.field(4).data_type(), &DataType::Boolean);
+        assert_eq!(schema.field(6).data_type(), &DataType::Float64);
+
+        assert_eq!(batch.column(0).as_string::<i32>().value(0), "a");
+        let scores = batch.column(6).as_primitive::<Float64Type>();
+        assert_eq!(scores.value(1), 2.5);
+        assert!(scores.is_null(2));
+
+        let bytes = to_parquet::render_edges(&graph, &config).unwrap();
+        let edges = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(bytes))
+            .unwrap()
+            .build()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(edges.num_rows(), 1);
+        assert_eq!(edges.num_columns(), 6);
+    }
+
+    #[test]
+    fn test_dot_contain_nodes_nests_partition_clusters() {
+        use crate::export::to_dot;
+
+        let partition = |id: &str, label: &str, parent: Option<&str>| {
+            let mut node = create_node(id, label, "l1");
+            node.is_partition = true;
+            node.belongs_to = parent.map(str::to_string);
+            node
+        };
+        let member = |id: &str, parent: Option<&str>| {
+            let mut node = create_node(id, &id.to_uppercase(), "l1");
+            node.belongs_to = parent.map(str::to_string);
+            node
+        };
+        let mut layer = create_layer("l1");
+        layer.background_color = "abcdef".to_string();
+        let mut graph = Graph {
+            name: "Clusters".to_string(),
+            nodes: vec![
+                partition("outer", "Outer", None),
+                partition("inner", "Inner", Some("outer")),
+                member("a", Some("inner")),
+                member("b", Some("outer")),
+                member("c", None),
+            ],
+            edges: vec![create_edge("e1", "a", "c", 1)],
+            layers: vec![layer],
+            annotations: None,
+        };
+
+        let mut config = create_test_config();
+        config.contain_nodes = true;
+        let dot = to_dot::render(&graph, &config).unwrap();
+
+        // Slice out each cluster up to its closing brace to check membership.
+        let block = |name: &str| {
+            let start = dot.find(&format!("subgraph cluster_{} {{", name)).unwrap();
+            let indent = dot[..start].len() - dot[..start].trim_end_matches(' ').len();
+            let close = format!("\n{}  }}\n", " ".repeat(indent));
+            let end = start + dot[start..].find(&close).unwrap();
+            dot[start..end].to_string()
+        };
+        let outer = block("outer");
+        let inner = block("inner");
+        assert!(outer.contains("label=\"Outer\""), "{dot}");
+        assert!(outer.contains("fillcolor=\"#abcdef\""), "{dot}");
+        assert!(outer.contains("subgraph cluster_inner {"), "{dot}");
+        assert!(outer.contains("b [label=\"B\""), "{dot}");
+        assert!(inner.contains("a [label=\"A\""), "{dot}");
+        assert!(!inner.contains("b [label="), "{dot}");
+        assert!(!outer.contains("c [label="), "{dot}");
+        assert!(dot.contains("c [label=\"C\""), "{dot}");
+
+        // A belongs_to cycle still renders each node exactly once.
+        graph.nodes[0].belongs_to = Some("inner".to_string());
+        let dot = to_dot::render(&graph, &config).unwrap();
+        assert_eq!(dot.matches("subgraph cluster_outer {").count(), 1, "{dot}");
+        assert_eq!(dot.matches("subgraph cluster_inner {").count(), 1, "{dot}");
+        assert_eq!(dot.matches("a [label=").count(), 1, "{dot}");
+    }
+
+    #[test]
+    fn test_dot_renders_partitions_as_nodes_when_enabled() {
+        use crate::export::to_dot;
+
+        let mut group = create_node("grp", "Group", "l1");
+        group.is_partition = true;
+        let mut child_a = create_node("a", "A", "l1");
+        child_a.belongs_to = Some("grp".to_string());
+        let mut child_b = create_node("b", "B", "l1");
+        child_b.belongs_to = Some("grp".to_string());
+        let graph = Graph {
+            name: "Partitions".to_string(),
+            nodes: vec![group, child_a, child_b],
+            edges: vec![create_edge("e1", "a", "b", 1)],
+            layers: vec![create_layer("l1")],
+            annotations: None,
+        };
+
+        let mut config = create_test_config();
+        let dot = to_dot::render(&graph, &config).unwrap();
+        assert!(!dot.contains("grp["), "{dot}");
+
+        config.render_partitions_as_nodes = true;
+        let dot = to_dot::render(&graph, &config).unwrap();
+        assert!(
+            dot.contains(r#"grp[label="Group", shape="tab", style="filled,bold""#),
+            "{dot}"
+        );
+        assert!(dot.contains(r##"fillcolor="#f7f7f8""##), "{dot}");
+        assert!(dot.contains(r#"grp -> a [style="dashed""#), "{dot}");
+        assert!(dot.contains("a[label=\"A\"]"), "children stay: {dot}");
+        assert!(dot.contains("b[label=\"B\"]"), "children stay: {dot}");
+
+        config.built_in_styles = RenderConfigBuiltInStyle::Dark;
+        let dot = to_dot::render(&graph, &config).unwrap();
+        assert!(
+            dot.contains(r##"fillcolor="#1f2933", fontcolor="#f8fafc", color="#94a3b8""##),
+            "{dot}"
+        );
+
+        config.built_in_styles = RenderConfigBuiltInStyle::None;
+        let dot = to_dot::render(&graph, &config).unwrap();
+        assert!(
+            dot.contains(r#"grp[label="Group", shape="tab", style="filled,bold"];"#),
+            "{dot}"
+        );
+    }
+
+    #[test]
+    fn test_partitions_as_nodes_is_rejected_outside_dot() {
+        use crate::plan::ExportFileType;
+
+        let mut config = create_test_config();
+        assert!(config.check_supported_by(&ExportFileType::Mermaid).is_ok());
+
+        config.render_partitions_as_nodes = true;
+        assert!(config.check_supported_by(&ExportFileType::DOT).is_ok());
+        let err = config
+            .check_supported_by(&ExportFileType::Mermaid)
+            .unwrap_err();
+        assert!(err.contains("render_partitions_as_nodes"), "{err}");
+    }
+
+    #[test]
+    fn test_json_hierarchy_nests_children_and_breaks_cycles() {
+        use crate::export::to_json_hierarchy;
+
+        let mut group = create_node("grp", "Group", "l1");
+        group.is_partition = true;
+        let mut child_a = create_node("a", "A", "l1");
+        child_a.belongs_to = Some("grp".to_string());
+        child_a.weight = 3;
+        let mut child_b = create_node("b", "B", "l1");
+        child_b.belongs_to = Some("grp".to_string());
+        let mut loop_x = create_node("x", "X", "l1");
+        loop_x.belongs_to = Some("y".to_string());
+        let mut loop_y = create_node("y", "Y", "l1");
+        loop_y.belongs_to = Some("x".to_string());
+        let graph = Graph {
+            name: "Hierarchy".to_string(),
+            nodes: vec![group, child_a, child_b, loop_x, loop_y],
+            edges: vec![],
+            layers: vec![create_layer("l1")],
+            annotations: None,
+        };
+
+        let mut config = create_test_config();
+        let output = to_json_hierarchy::render(&graph, &config).unwrap();
+        assert!(!output.contains('\n'), "compact by default: {output}");
+        let roots: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(
+            roots,
+            serde_json::json!([
+                {"id": "grp", "label": "Group", "weight": 1, "children": [
+                    {"id": "a", "label": "A", "weight": 3, "children": []},
+                    {"id": "b", "label": "B", "weight": 1, "children": []}
+                ]},
+                {"id": "x", "label": "X", "weight": 1, "children": [
+                    {"id": "y", "label": "Y", "weight": 1, "children": []}
+                ]}
+            ])
+        );
+
+        config.use_node_weight = false;
+        config.prettify = true;
+        let output = to_json_hierarchy::render(&graph, &config).unwrap();
+        assert!(output.contains("\n  {"), "{output}");
+        let roots: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(roots[0]["children"][0]["weight"], 1);
+    }
+
+    #[test]
+    fn test_json_timestamp_format_switches_to_epoch_millis() {
+        use crate::export::to_json;
+        use crate::plan::TimestampFormat;
+
+        let mut node = create_node("a", "2024-01-02T03:04:05Z", "l1");
+        node.attributes = Some(serde_json::json!({
+            "updated": "2024-01-02T03:04:05Z",
+            "owner": "ops",
+        }));
+        let graph = Graph {
+            name: "Timestamps".to_string(),
+            nodes: vec![node],
+            edges: vec![],
+            layers: vec![create_layer("l1")],
+            annotations: None,
+        };
+
+        let mut config = create_test_config();
+        let output: serde_json::Value =
+            serde_json::from_str(&to_json::render(&graph, &config).unwrap()).unwrap();
+        assert_eq!(
+            output["flow_nodes"][0]["attributes"]["updated"],
+            "2024-01-02T03:04:05Z"
+        );
+
+        config.timestamp_format = TimestampFormat::EpochMillis;
+        let output: serde_json::Value =
+            serde_json::from_str(&to_json::render(&graph, &config).unwrap()).unwrap();
+        let attributes = &output["flow_nodes"][0]["attributes"];
+        assert_eq!(attributes["updated"], 1_704_164_645_000_i64);
+        assert_eq!(attributes["owner"], "ops");
+        // Only attribute values are converted.
+        assert_eq!(output["flow_nodes"][0]["label"], "2024-01-02T03:04:05Z");
+    }
+
+    #[test]
+    fn test_jsonl_emits_one_typed_record_per_line() {
+        use crate::export::to_jsonl;
+
+        let graph = weighted_graph();
+        let mut config = create_test_config();
+        config.use_edge_weight = false;
+        let output = to_jsonl::render(&graph, &config).unwrap();
+
+        assert!(output.ends_with('\n') && !output.ends_with("\n\n"));
+        let records: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            records.len(),
+            1 + graph.layers.len() + graph.nodes.len() + graph.edges.len()
+        );
+        assert_eq!(records[0]["type"], "meta");
+        assert_eq!(records[0]["graph"], "Weighted");
+        assert_eq!(records[0]["counts"]["edges"], graph.edges.len());
+        let edges: Vec<_> = records.iter().filter(|r| r["type"] == "edge").collect();
+        assert_eq!(edges.len(), graph.edges.len());
+        assert!(edges.iter().all(|edge| edge["weight"] == 1));
+        assert!(records
+            .iter()
+            .any(|r| r["type"] == "layer" && r["id"] == "l1"));
+
+        let empty = to_jsonl::render(&Graph::default(), &config).unwrap();
+        assert_eq!(empty.lines().count(), 1);
+        assert!(empty.starts_with('{') && empty.ends_with("}\n"));
+        assert!(empty.contains(r#""type":"meta""#), "{empty}");
+    }
+
+    #[test]
+    fn test_svg_styles_layers_and_truncates_labels() {
+        use crate::export::to_svg;
+        use crate::plan::SvgRenderOptions;
+
+        let mut group = create_node("grp", "Group", "l1");
+        group.is_partition = true;
+        let mut child_a = create_node("a", "A", "l1");
+        child_a.belongs_to = Some("grp".to_string());
+        let mut child_b = create_node("b", "Averyverylongname & co", "l1");
+        child_b.belongs_to = Some("grp".to_string());
+        let graph = Graph {
+            name: "Preview".to_string(),
+            nodes: vec![group, child_a, child_b],
+            edges: vec![create_edge("e1", "a", "b", 1)],
+            layers: vec![create_layer("l1")],
+            annotations: None,
+        };
+
+        let mut config = create_test_config();
+        config.target_options.svg = Some(SvgRenderOptions {
+            label_max_width: 70,
+        });
+        let svg = to_svg::render(&graph, &config).unwrap();
+
+        // Two rows: the group centred over its children, sized to fit them.
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="181" height="152""#),
+            "{svg}"
+        );
+        assert!(svg.contains(">Averyvery…</text>"), "{svg}");
+        assert!(
+            svg.contains(r##"fill="#aabbcc" stroke="#445566""##),
+            "{svg}"
+        );
+        assert!(svg.contains(r##"fill="#112233">Group</text>"##), "{svg}");
+        assert_eq!(svg.matches("<rect").count(), 3);
+        // Two hierarchy links plus the flow edge.
+        assert_eq!(svg.matches("<line").count(), 3);
+    }
+
+    #[test]
+    fn test_plantuml_blueprint_preset_with_skinparam_overrides() {
+        use crate::export::to_plantuml;
+        use crate::plan::PlantUmlRenderOptions;
+
+        let graph = Graph {
+            name: "Themed".to_string(),
+            nodes: vec![create_node("a", "A", "l1"), create_node("b", "B", "l1")],
+            edges: vec![create_edge("e1", "a", "b", 1)],
+            layers: vec![create_layer("l1")],
+            annotations: None,
+        };
+
+        let mut config = create_test_config();
+        config.built_in_styles = RenderConfigBuiltInStyle::Blueprint;
+        config.target_options.plantuml = Some(PlantUmlRenderOptions {
+            background_color: None,
+            font_name: Some("Inter".to_string()),
+            arrow_color: Some("ff8800".to_string()),
+            round_corner: Some(12),
+        });
+        let puml = to_plantuml::render(&graph, &config).unwrap();
+
+        let skinparams: Vec<&str> = puml
+            .lines()
+            .filter(|line| line.starts_with("skinparam"))
+            .collect();
+        assert_eq!(
+            skinparams,
+            vec![
+                "skinparam backgroundColor #0b3d91",
+                "skinparam defaultFontColor #ffffff",
+                "skinparam ArrowColor #ff8800",
+                "skinparam RectangleBackgroundColor #134a9e",
+                "skinparam RectangleBorderColor #ffffff",
+                "skinparam defaultFontName Inter",
+                "skinparam shadowing false",
+                "skinparam roundCorner 12",
+            ]
+        );
+        // Layer styles still colour the nodes on top of the global theme.
+        assert!(puml.contains("BackgroundColor #aabbcc;"), "{puml}");
+        assert!(puml.contains("FontColor #112233;"), "{puml}");
+        assert!(puml.contains(r#"rectangle "A" as a <<l1>>"#), "{puml}");
+    }
+
     #[test]
     fn test_mermaid_render_includes_nodes_with_missing_layers() {
         use crate::export::to_mermaid;
@@ -389,6 +1158,106 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mermaid_contain_nodes_emits_partition_subgraphs_in_rank_order() {
+        use crate::export::to_mermaid;
+        use crate::plan::RenderConfigOrientation;
+
+        let partition = |id: &str, label: &str, parent: Option<&str>| {
+            let mut node = create_node(id, label, "l1");
+            node.is_partition = true;
+            node.belongs_to = parent.map(str::to_string);
+            node
+        };
+        let member = |id: &str, parent: Option<&str>| {
+            let mut node = create_node(id, &id.to_uppercase(), "l1");
+            node.belongs_to = parent.map(str::to_string);
+            node
+        };
+        // The loose node and the one-level partition are listed first to check
+        // that the two-level partition is still declared ahead of them.
+        let graph = Graph {
+            name: "Ranks".to_string(),
+            nodes: vec![
+                member("c", None),
+                partition("flat", "Flat", None),
+                member("d", Some("flat")),
+                member("b", Some("outer")),
+                partition("outer", "Group", None),
+                partition("inner", "Group", Some("outer")),
+                member("a", Some("inner")),
+            ],
+            edges: vec![create_edge("e1", "a", "c", 1)],
+            layers: vec![create_layer("l1")],
+            annotations: None,
+        };
+
+        let mut config = create_test_config();
+        config.contain_nodes = true;
+        config.orientation = RenderConfigOrientation::LR;
+        let result = to_mermaid::render(&graph, &config).unwrap();
+
+        assert!(result.contains("flowchart LR"), "{result}");
+        let expected = [
+            "subgraph outer[\"Group\"]",
+            "  subgraph inner[\"Group\"]",
+            "    a[\"A\"]",
+            "  end",
+            "  b[\"B\"]",
+            "end",
+            "subgraph flat[\"Flat\"]",
+            "  d[\"D\"]",
+            "end",
+            "c[\"C\"]",
+        ]
+        .join("\n");
+        assert!(result.contains(&expected), "{result}");
+
+        config.orientation = RenderConfigOrientation::TB;
+        let result = to_mermaid::render(&graph, &config).unwrap();
+        assert!(result.contains("flowchart TB"), "{result}");
+    }
+
+    #[test]
+    fn test_custom_template_uses_string_and_colour_helpers() {
+        use crate::export::to_custom;
+        use crate::plan::CustomExportProfile;
+
+        let dir = std::env::temp_dir().join(format!("layercake-custom-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let template = dir.join("nodes.hbs");
+        std::fs::write(
+            &template,
+            "{{#each flow_nodes}}{{uppercase id}}={{truncate label 5 suffix=\"~\"}};{{/each}}\n\
+             {{lighten \"#000000\" 0.5}} {{darken \"ffffff\" 0.25}} {{lowercase \"MiXeD\"}}\n\
+             {{{json config.apply_layers}}}",
+        )
+        .unwrap();
+
+        let graph = Graph {
+            name: "Custom".to_string(),
+            nodes: vec![
+                create_node("api", "Gateway Service", "l1"),
+                create_node("db", "Store", "l1"),
+            ],
+            edges: vec![create_edge("e1", "api", "db", 1)],
+            layers: vec![create_layer("l1")],
+            annotations: None,
+        };
+        let profile = CustomExportProfile {
+            template: template.to_string_lossy().to_string(),
+            partials: None,
+        };
+
+        let output = to_custom::render(&graph, &create_test_config(), &profile).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "API=Gatew~;DB=Store;");
+        assert_eq!(lines[1], "#808080 bfbfbf mixed");
+        assert_eq!(lines[2], "true");
+    }
+
     #[test]
     fn test_mermaid_frontmatter_title_with_colon_is_quoted() {
         use crate::export::to_mermaid;
@@ -0,0 +1,34 @@
+use crate::graph::Graph;
+use crate::plan::RenderConfig;
+use csv::WriterBuilder;
+use indexmap::IndexMap;
+use std::error::Error;
+
+/// Export the flow graph as a weighted adjacency list
+///
+/// One row per node: `node_id,neighbour:weight,...`, listing only outgoing
+/// edges in edge order. Isolated nodes get a row with just their id, so the
+/// output grows with edges rather than with the square of the node count.
+/// Weights are 1 when `use_edge_weight` is disabled.
+pub fn render(graph: &Graph, render_config: &RenderConfig) -> Result<String, Box<dyn Error>> {
+    let prepared = crate::export::renderer::prepare_graph_data(graph, render_config);
+
+    let mut rows: IndexMap<&str, Vec<String>> = prepared
+        .flow_nodes
+        .iter()
+        .map(|node| (node.id.as_str(), vec![node.id.clone()]))
+        .collect();
+    for edge in &prepared.flow_edges {
+        if let Some(row) = rows.get_mut(edge.source.as_str()) {
+            row.push(format!("{}:{}", edge.target, edge.weight));
+        }
+    }
+
+    let mut wtr = WriterBuilder::new().flexible(true).from_writer(vec![]);
+    for row in rows.values() {
+        wtr.write_record(row)?;
+    }
+
+    let data = wtr.into_inner()?;
+    Ok(String::from_utf8(data)?)
+}
@@ -2,25 +2,45 @@ use crate::graph::Graph;
 use crate::plan::RenderConfig;
 use std::error::Error;
 
-use super::csv_common::export_to_csv_sorted;
+use super::csv_common::{
+    attribute_cell, attribute_columns, attribute_header, export_to_csv_sorted,
+};
 
 /// Export graph edges to CSV format
 ///
-/// Edges are sorted by ID for consistent output.
+/// Edges are sorted by ID for consistent output. Edge attributes follow the
+/// fixed columns as `attr_<key>` columns, in alphabetical key order.
 pub fn render(graph: &Graph, _render_config: &RenderConfig) -> Result<String, Box<dyn Error>> {
+    let attribute_keys = attribute_columns(graph.edges.iter().map(|edge| edge.attributes.as_ref()));
+    let mut headers = vec![
+        "id", "source", "target", "label", "layer", "weight", "comment",
+    ];
+    let attribute_headers: Vec<String> = attribute_keys
+        .iter()
+        .map(|key| attribute_header(key))
+        .collect();
+    headers.extend(attribute_headers.iter().map(String::as_str));
+
     export_to_csv_sorted(
         &graph.edges,
-        &["id", "source", "target", "label", "layer", "comment"],
+        &headers,
         |edge| edge.id.clone(), // Clone for sorting (small cost for consistency)
         |edge| {
-            vec![
+            let mut row = vec![
                 edge.id.to_string(),
                 edge.source.clone(),
                 edge.target.clone(),
                 edge.label.clone(),
                 edge.layer.clone(),
+                edge.weight.to_string(),
                 edge.comment.as_deref().unwrap_or("").to_string(),
-            ]
+            ];
+            row.extend(
+                attribute_keys
+                    .iter()
+                    .map(|key| attribute_cell(edge.attributes.as_ref(), key)),
+            );
+            row
         },
     )
 }
@@ -4,6 +4,19 @@ use std::error::Error;
 use std::fs;
 use tracing::error;
 
+/// Render a user-supplied Handlebars template (plus optional partials).
+///
+/// The template context holds `config`, `flow_nodes`, `flow_edges`,
+/// `hierarchy_nodes`, `hierarchy_edges`, `hierarchy_tree`,
+/// `hierarchy_tree_edges`, `layers`/`layer_map` (layers keyed by ID) and
+/// `layers_array`. Besides the built-in helpers, templates can use every
+/// helper from [`crate::common::get_handlebars`], including:
+///
+/// - `{{uppercase label}}`, `{{lowercase label}}`
+/// - `{{truncate label 12}}`, `{{truncate label 12 suffix="…"}}`
+/// - `{{lighten "#336699" 0.2}}`, `{{darken background_color 0.5}}` for hex
+///   colours, by a 0-1 fraction
+/// - `{{{json attributes}}}` to embed any value as JSON
 pub fn render(
     graph: &Graph,
     render_config: &RenderConfig,
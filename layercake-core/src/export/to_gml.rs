@@ -1,5 +1,6 @@
 use crate::graph::Graph;
 use crate::plan::RenderConfig;
+use std::collections::HashMap;
 use std::error::Error;
 
 pub fn get_template() -> String {
@@ -7,5 +8,34 @@ pub fn get_template() -> String {
 }
 
 pub fn render(graph: &Graph, render_config: &RenderConfig) -> Result<String, Box<dyn Error>> {
-    crate::export::renderer::render_template(graph, render_config, &get_template())
+    if render_config.directed != Some(false) {
+        return crate::export::renderer::render_template(graph, render_config, &get_template());
+    }
+
+    // Undirected: order endpoints first, then fold every edge between the same
+    // pair (reciprocal or parallel) into the first one with the summed weight
+    let mut graph = graph.clone();
+    for edge in graph.edges.iter_mut() {
+        if edge.source > edge.target {
+            std::mem::swap(&mut edge.source, &mut edge.target);
+        }
+    }
+    let mut first: HashMap<(String, String), usize> = HashMap::new();
+    let mut folded = vec![0; graph.edges.len()];
+    for (idx, edge) in graph.edges.iter().enumerate() {
+        let kept = *first
+            .entry((edge.source.clone(), edge.target.clone()))
+            .or_insert(idx);
+        folded[kept] += edge.weight;
+    }
+    let mut idx = 0;
+    graph.edges.retain_mut(|edge| {
+        idx += 1;
+        let keep = first[&(edge.source.clone(), edge.target.clone())] == idx - 1;
+        if keep {
+            edge.weight = folded[idx - 1];
+        }
+        keep
+    });
+    crate::export::renderer::render_template(&graph, render_config, &get_template())
 }
@@ -2,9 +2,14 @@ use crate::graph::Graph;
 use crate::plan::RenderConfig;
 use serde::Serialize;
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
 
+/// Horizontal gap between nodes in a row, and between partition regions.
+const NODE_SPACING: f64 = 120.0;
+/// Vertical gap between hierarchy depths.
+const LEVEL_SPACING: f64 = 100.0;
+
 #[derive(Serialize)]
 struct JsGraph {
     nodes: Vec<JsNode>,
@@ -17,6 +22,12 @@ struct JsNode {
     name: String,
     layer: String,
     attrs: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    x: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    y: Option<f64>,
 }
 
 #[derive(Serialize)]
@@ -33,17 +44,32 @@ fn from_graph(graph: &Graph, render_config: &RenderConfig) -> JsGraph {
     let prepared = crate::export::renderer::prepare_graph_data(graph, render_config);
     let mut nodes = Vec::new();
     let mut edges = Vec::new();
+    let positions = if render_config.precompute_layout {
+        layout_positions(
+            graph,
+            prepared.flow_nodes.iter().map(|node| node.id.as_str()),
+        )
+    } else {
+        HashMap::new()
+    };
 
     for node in &prepared.flow_nodes {
         let mut attrs = HashMap::new();
         attrs.insert("is_partition".to_string(), node.is_partition.to_string());
         attrs.insert("weight".to_string(), node.weight.to_string());
         attrs.insert("type".to_string(), node.layer.to_string());
+        let position = positions.get(&node.id);
         nodes.push(JsNode {
             id: node.id.clone(),
             name: node.label.clone(),
             layer: node.layer.clone(),
             attrs,
+            color: prepared
+                .node_colors
+                .get(&node.id)
+                .map(|color| format!("#{}", color)),
+            x: position.map(|(x, _)| *x),
+            y: position.map(|(_, y)| *y),
         });
     }
 
@@ -67,6 +93,64 @@ fn from_graph(graph: &Graph, render_config: &RenderConfig) -> JsGraph {
     }
 }
 
+/// Place nodes in a layered grid: each top-level partition gets its own
+/// region, laid out left to right in id order with ungrouped nodes first, and
+/// within a region nodes sit in rows by `belongs_to` depth, sorted by id.
+/// The result depends only on ids and hierarchy, so reloads are stable.
+fn layout_positions<'a>(
+    graph: &Graph,
+    node_ids: impl Iterator<Item = &'a str>,
+) -> HashMap<String, (f64, f64)> {
+    let known: HashSet<&str> = graph.nodes.iter().map(|node| node.id.as_str()).collect();
+    let parent: HashMap<&str, &str> = graph
+        .nodes
+        .iter()
+        .filter_map(|node| {
+            let parent = node.belongs_to.as_deref()?;
+            known.contains(parent).then_some((node.id.as_str(), parent))
+        })
+        .collect();
+
+    // Region anchor (top-level ancestor) -> depth -> node ids
+    let mut regions: BTreeMap<Option<&str>, BTreeMap<usize, Vec<&str>>> = BTreeMap::new();
+    for id in node_ids {
+        let mut seen = HashSet::from([id]);
+        let mut current = id;
+        let mut anchor = None;
+        let mut depth = 0;
+        while let Some(&next) = parent.get(current) {
+            if !seen.insert(next) {
+                break; // belongs_to cycle
+            }
+            anchor = Some(next);
+            current = next;
+            depth += 1;
+        }
+        regions
+            .entry(anchor)
+            .or_default()
+            .entry(depth)
+            .or_default()
+            .push(id);
+    }
+
+    let mut positions = HashMap::new();
+    let mut offset = 0.0;
+    for rows in regions.values_mut() {
+        let mut width = 0;
+        for (depth, ids) in rows.iter_mut() {
+            ids.sort_unstable();
+            for (column, id) in ids.iter().enumerate() {
+                let x = offset + column as f64 * NODE_SPACING;
+                positions.insert(id.to_string(), (x, *depth as f64 * LEVEL_SPACING));
+            }
+            width = width.max(ids.len());
+        }
+        offset += (width + 1) as f64 * NODE_SPACING;
+    }
+    positions
+}
+
 pub fn render(graph: &Graph, render_config: &RenderConfig) -> Result<String, Box<dyn Error>> {
     let layers = graph.get_layer_map();
     let name = &graph.name;
@@ -91,3 +175,39 @@ pub fn render(graph: &Graph, render_config: &RenderConfig) -> Result<String, Box
 pub fn get_template() -> String {
     include_str!("to_jsgraph.hbs").to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+
+    #[test]
+    fn layout_groups_partitions_into_stable_regions() {
+        let node = |id: &str, parent: Option<&str>, is_partition: bool| Node {
+            id: id.to_string(),
+            label: id.to_string(),
+            layer: "l1".to_string(),
+            is_partition,
+            belongs_to: parent.map(str::to_string),
+            ..Default::default()
+        };
+        let graph = Graph {
+            name: "Layout".to_string(),
+            nodes: vec![
+                node("zone", None, true),
+                node("b", Some("zone"), false),
+                node("a", Some("zone"), false),
+                node("loose", None, false),
+            ],
+            ..Default::default()
+        };
+
+        let positions = layout_positions(&graph, ["b", "a", "loose"].into_iter());
+        assert_eq!(positions["loose"], (0.0, 0.0));
+        assert_eq!(positions["a"], (2.0 * NODE_SPACING, LEVEL_SPACING));
+        assert_eq!(positions["b"], (3.0 * NODE_SPACING, LEVEL_SPACING));
+
+        let reordered = layout_positions(&graph, ["loose", "a", "b"].into_iter());
+        assert_eq!(positions, reordered);
+    }
+}
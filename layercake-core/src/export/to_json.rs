@@ -1,5 +1,6 @@
 use crate::graph::Graph;
-use crate::plan::RenderConfig;
+use crate::plan::{RenderConfig, TimestampFormat};
+use serde_json::Value;
 use std::error::Error;
 
 pub fn render(graph: &Graph, render_config: &RenderConfig) -> Result<String, Box<dyn Error>> {
@@ -7,7 +8,7 @@ pub fn render(graph: &Graph, render_config: &RenderConfig) -> Result<String, Box
 
     let prepared = crate::export::renderer::prepare_graph_data(graph, render_config);
 
-    let res = json!({
+    let mut res = json!({
         "hierarchy_nodes": prepared.hierarchy_nodes,
         "hierarchy_edges": prepared.hierarchy_edges,
         "flow_nodes": prepared.flow_nodes,
@@ -16,5 +17,32 @@ pub fn render(graph: &Graph, render_config: &RenderConfig) -> Result<String, Box
         "layers": prepared.layer_map,
         "hierarchy_tree_edges": prepared.hierarchy_tree_edges,
     });
+    if render_config.timestamp_format == TimestampFormat::EpochMillis {
+        timestamps_to_epoch_millis(&mut res, false);
+    }
     Ok(serde_json::to_string_pretty(&res)?)
 }
+
+/// Rewrite RFC3339 strings inside `attributes` objects as epoch milliseconds.
+/// Only attribute values are touched, so a label that happens to look like a
+/// timestamp is left alone.
+fn timestamps_to_epoch_millis(value: &mut Value, in_attributes: bool) {
+    match value {
+        Value::String(text) if in_attributes => {
+            if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(text) {
+                *value = Value::from(parsed.timestamp_millis());
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                timestamps_to_epoch_millis(item, in_attributes);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                timestamps_to_epoch_millis(item, in_attributes || key == "attributes");
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,41 @@
+use crate::graph::{Graph, TreeNode};
+use crate::plan::RenderConfig;
+use serde::Serialize;
+use std::error::Error;
+
+/// Nested node shape consumed by D3 `hierarchy()` (tree, treemap, pack).
+#[derive(Serialize)]
+struct HierarchyNode<'a> {
+    id: &'a str,
+    label: &'a str,
+    weight: i32,
+    children: Vec<HierarchyNode<'a>>,
+}
+
+fn to_hierarchy_node(node: &TreeNode, use_node_weight: bool) -> HierarchyNode<'_> {
+    HierarchyNode {
+        id: &node.id,
+        label: &node.label,
+        weight: if use_node_weight { node.weight } else { 1 },
+        children: node
+            .children
+            .iter()
+            .map(|child| to_hierarchy_node(child, use_node_weight))
+            .collect(),
+    }
+}
+
+/// Render the `belongs_to` hierarchy as an array of nested root documents.
+pub fn render(graph: &Graph, render_config: &RenderConfig) -> Result<String, Box<dyn Error>> {
+    let tree = graph.build_tree();
+    let roots: Vec<HierarchyNode> = tree
+        .iter()
+        .map(|node| to_hierarchy_node(node, render_config.use_node_weight))
+        .collect();
+
+    if render_config.prettify {
+        Ok(serde_json::to_string_pretty(&roots)?)
+    } else {
+        Ok(serde_json::to_string(&roots)?)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::graph::Graph;
+use crate::plan::RenderConfig;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::error::Error;
+
+/// Render the graph as JSON Lines: a `meta` record, then one record per
+/// layer (when layers are applied), node and edge, each on its own line and
+/// tagged with a `type` field so streams can be split with `jq`.
+pub fn render(graph: &Graph, render_config: &RenderConfig) -> Result<String, Box<dyn Error>> {
+    let mut nodes = graph.nodes.clone();
+    let mut edges = graph.edges.clone();
+    crate::export::renderer::apply_weight_config(&mut nodes, &mut edges, render_config);
+    let layers = if render_config.apply_layers {
+        crate::export::renderer::prepare_graph_data(graph, render_config).layers
+    } else {
+        Vec::new()
+    };
+
+    let mut out = String::new();
+    push_line(
+        &mut out,
+        json!({
+            "type": "meta",
+            "graph": graph.name,
+            "counts": {
+                "layers": layers.len(),
+                "nodes": nodes.len(),
+                "edges": edges.len(),
+            },
+        }),
+    )?;
+    for layer in &layers {
+        push_record(&mut out, "layer", layer)?;
+    }
+    for node in &nodes {
+        push_record(&mut out, "node", node)?;
+    }
+    for edge in &edges {
+        push_record(&mut out, "edge", edge)?;
+    }
+    Ok(out)
+}
+
+fn push_record<T: Serialize>(
+    out: &mut String,
+    record_type: &str,
+    item: &T,
+) -> Result<(), Box<dyn Error>> {
+    let mut record = json!({ "type": record_type });
+    if let (Some(map), Value::Object(fields)) =
+        (record.as_object_mut(), serde_json::to_value(item)?)
+    {
+        map.extend(fields);
+    }
+    push_line(out, record)
+}
+
+/// Every record, including the last, ends with a single newline.
+fn push_line(out: &mut String, record: Value) -> Result<(), Box<dyn Error>> {
+    out.push_str(&serde_json::to_string(&record)?);
+    out.push('\n');
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use crate::export::csv_common::{attribute_columns, attribute_header};
+use crate::graph::{Edge, Graph, Node};
+use crate::plan::RenderConfig;
+use arrow_array::{
+    ArrayRef, BooleanArray, Float64Array, Int32Array, Int64Array, RecordBatch, StringArray,
+};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+use serde_json::Value;
+use std::error::Error;
+use std::sync::Arc;
+
+/// Export graph nodes as a Parquet table
+///
+/// Columns are `id`, `label`, `layer`, `weight`, then one typed `attr_<key>`
+/// column per attribute key in sorted order. Rows are sorted by ID.
+pub fn render_nodes(
+    graph: &Graph,
+    _render_config: &RenderConfig,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut nodes: Vec<&Node> = graph.nodes.iter().collect();
+    nodes.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let columns = vec![
+        text_column("id", nodes.iter().map(|node| node.id.as_str())),
+        text_column("label", nodes.iter().map(|node| node.label.as_str())),
+        text_column("layer", nodes.iter().map(|node| node.layer.as_str())),
+        weight_column(nodes.iter().map(|node| node.weight)),
+    ];
+    let attributes = nodes.iter().map(|node| node.attributes.as_ref()).collect();
+
+    write_table(columns, attributes)
+}
+
+/// Export graph edges as a Parquet table
+///
+/// Columns are `id`, `source`, `target`, `label`, `layer`, `weight`, then one
+/// typed `attr_<key>` column per attribute key in sorted order. Rows are
+/// sorted by ID.
+pub fn render_edges(
+    graph: &Graph,
+    _render_config: &RenderConfig,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut edges: Vec<&Edge> = graph.edges.iter().collect();
+    edges.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let columns = vec![
+        text_column("id", edges.iter().map(|edge| edge.id.as_str())),
+        text_column("source", edges.iter().map(|edge| edge.source.as_str())),
+        text_column("target", edges.iter().map(|edge| edge.target.as_str())),
+        text_column("label", edges.iter().map(|edge| edge.label.as_str())),
+        text_column("layer", edges.iter().map(|edge| edge.layer.as_str())),
+        weight_column(edges.iter().map(|edge| edge.weight)),
+    ];
+    let attributes = edges.iter().map(|edge| edge.attributes.as_ref()).collect();
+
+    write_table(columns, attributes)
+}
+
+fn text_column<'a>(name: &str, values: impl Iterator<Item = &'a str>) -> (Field, ArrayRef) {
+    (
+        Field::new(name, DataType::Utf8, false),
+        Arc::new(StringArray::from_iter_values(values)),
+    )
+}
+
+fn weight_column(values: impl Iterator<Item = i32>) -> (Field, ArrayRef) {
+    (
+        Field::new("weight", DataType::Int32, false),
+        Arc::new(Int32Array::from_iter_values(values)),
+    )
+}
+
+/// Column type inferred from the non-null values of one attribute key.
+#[derive(Clone, Copy, PartialEq)]
+enum AttributeType {
+    Boolean,
+    Integer,
+    Float,
+    Text,
+}
+
+impl AttributeType {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Bool(_) => AttributeType::Boolean,
+            Value::Number(number) if number.is_i64() => AttributeType::Integer,
+            Value::Number(_) => AttributeType::Float,
+            _ => AttributeType::Text,
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (AttributeType::Integer, AttributeType::Float)
+            | (AttributeType::Float, AttributeType::Integer) => AttributeType::Float,
+            _ => AttributeType::Text,
+        }
+    }
+}
+
+/// Append one column per attribute key, typed from the data: booleans,
+/// integers and floats stay numeric (integers widen to float when mixed), and
+/// anything else becomes text, with non-string values written as JSON. Rows
+/// without the key are null. Columns are named like the CSV exports'
+/// attribute headers, so they never clash with a fixed column.
+fn write_table(
+    mut columns: Vec<(Field, ArrayRef)>,
+    attributes: Vec<Option<&Value>>,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    for key in attribute_columns(attributes.iter().copied()) {
+        let values: Vec<Option<&Value>> = attributes
+            .iter()
+            .map(|attrs| {
+                attrs
+                    .and_then(|attrs| attrs.get(&key))
+                    .filter(|v| !v.is_null())
+            })
+            .collect();
+        let column_type = values
+            .iter()
+            .flatten()
+            .map(|value| AttributeType::of(value))
+            .reduce(AttributeType::merge)
+            .unwrap_or(AttributeType::Text);
+
+        let (data_type, array): (DataType, ArrayRef) = match column_type {
+            AttributeType::Boolean => (
+                DataType::Boolean,
+                Arc::new(BooleanArray::from(
+                    values
+                        .iter()
+                        .map(|v| v.and_then(Value::as_bool))
+                        .collect::<Vec<_>>(),
+                )),
+            ),
+            AttributeType::Integer => (
+                DataType::Int64,
+                Arc::new(Int64Array::from(
+                    values
+                        .iter()
+                        .map(|v| v.and_then(Value::as_i64))
+                        .collect::<Vec<_>>(),
+                )),
+            ),
+            AttributeType::Float => (
+                DataType::Float64,
+                Arc::new(Float64Array::from(
+                    values
+                        .iter()
+                        .map(|v| v.and_then(Value::as_f64))
+                        .collect::<Vec<_>>(),
+                )),
+            ),
+            AttributeType::Text => (
+                DataType::Utf8,
+                Arc::new(StringArray::from(
+                    values
+                        .iter()
+                        .map(|v| {
+                            v.map(|value| match value {
+                                Value::String(text) => text.clone(),
+                                other => other.to_string(),
+                            })
+                        })
+                        .collect::<Vec<_>>(),
+                )),
+            ),
+        };
+
+        columns.push((Field::new(attribute_header(&key), data_type, true), array));
+    }
+
+    let (fields, arrays): (Vec<Field>, Vec<ArrayRef>) = columns.into_iter().unzip();
+    let schema = Arc::new(Schema::new(fields));
+    let batch = RecordBatch::try_new(schema.clone(), arrays)?;
+
+    let mut buffer = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut buffer, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(buffer)
+}
@@ -0,0 +1,210 @@
+use crate::export::renderer::prepare_graph_data;
+use crate::graph::{Graph, Layer, TreeNode};
+use crate::plan::{RenderConfig, SvgRenderOptions};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Write;
+
+const MARGIN: f64 = 20.0;
+const NODE_HEIGHT: f64 = 32.0;
+const NODE_PADDING: f64 = 10.0;
+const H_GAP: f64 = 24.0;
+const V_GAP: f64 = 48.0;
+const FONT_SIZE: f64 = 12.0;
+/// Rough advance of one character at `FONT_SIZE`; there is no font metrics
+/// source here, so widths are estimates.
+const CHAR_WIDTH: f64 = 7.0;
+
+struct PlacedNode {
+    id: String,
+    label: String,
+    layer: String,
+    x: f64,
+    y: f64,
+    width: f64,
+}
+
+/// Truncate `label` so it fits in `max_width` pixels, ending in an ellipsis
+/// when anything was cut.
+fn fit_label(label: &str, max_width: u32) -> String {
+    let max_chars = ((max_width as f64 / CHAR_WIDTH).floor() as usize).max(1);
+    if label.chars().count() <= max_chars {
+        return label.to_string();
+    }
+    let mut truncated: String = label.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn colour(value: &str) -> String {
+    format!("#{}", value.trim_start_matches('#'))
+}
+
+/// Lay the `belongs_to` tree out in rows by depth: leaves take the next free
+/// slot left to right and parents are centred over their children. Returns
+/// the right edge of the placed subtree.
+fn place(
+    node: &TreeNode,
+    depth: usize,
+    left: f64,
+    options: &SvgRenderOptions,
+    placed: &mut Vec<PlacedNode>,
+    links: &mut Vec<(usize, usize)>,
+) -> f64 {
+    let label = fit_label(&node.label, options.label_max_width);
+    let width = label.chars().count() as f64 * CHAR_WIDTH + 2.0 * NODE_PADDING;
+    let idx = placed.len();
+    placed.push(PlacedNode {
+        id: node.id.clone(),
+        label,
+        layer: node.layer.clone(),
+        x: left,
+        y: MARGIN + depth as f64 * (NODE_HEIGHT + V_GAP),
+        width,
+    });
+
+    if node.children.is_empty() {
+        return left + width;
+    }
+
+    let mut right = left;
+    let mut cursor = left;
+    for child in &node.children {
+        links.push((idx, placed.len()));
+        right = place(child, depth + 1, cursor, options, placed, links);
+        cursor = right + H_GAP;
+    }
+
+    let span = right - left;
+    if width > span {
+        // Parent is wider than its children: centre the children under it.
+        let shift = (width - span) / 2.0;
+        for child in placed.iter_mut().skip(idx + 1) {
+            child.x += shift;
+        }
+        left + width
+    } else {
+        placed[idx].x = left + (span - width) / 2.0;
+        right
+    }
+}
+
+/// Render a self-contained SVG preview: the hierarchy laid out in rows, flow
+/// edges as straight lines, and nodes styled with their layer colours.
+pub fn render(graph: &Graph, render_config: &RenderConfig) -> Result<String, Box<dyn Error>> {
+    let options = render_config.target_options.svg.clone().unwrap_or_default();
+    let data = prepare_graph_data(graph, render_config);
+
+    let mut placed = Vec::new();
+    let mut links = Vec::new();
+    let mut left = MARGIN;
+    for root in graph.build_tree() {
+        left = place(&root, 0, left, &options, &mut placed, &mut links) + H_GAP;
+    }
+
+    let width = placed
+        .iter()
+        .map(|node| node.x + node.width)
+        .fold(0.0, f64::max)
+        + MARGIN;
+    let height = placed
+        .iter()
+        .map(|node| node.y + NODE_HEIGHT)
+        .fold(0.0, f64::max)
+        + MARGIN;
+    let position: HashMap<&str, &PlacedNode> =
+        placed.iter().map(|node| (node.id.as_str(), node)).collect();
+    let style = |layer_id: &str| -> Option<&Layer> {
+        if render_config.apply_layers {
+            data.layer_map.get(layer_id)
+        } else {
+            None
+        }
+    };
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="{FONT_SIZE}">"#,
+        w = width.ceil(),
+        h = height.ceil(),
+    )?;
+    writeln!(svg, "  <title>{}</title>", escape_xml(&graph.name))?;
+
+    for (parent, child) in &links {
+        let (parent, child) = (&placed[*parent], &placed[*child]);
+        writeln!(
+            svg,
+            r##"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#9ca3af" stroke-dasharray="4 3"/>"##,
+            parent.x + parent.width / 2.0,
+            parent.y + NODE_HEIGHT,
+            child.x + child.width / 2.0,
+            child.y,
+        )?;
+    }
+
+    for edge in &data.flow_edges {
+        let (Some(source), Some(target)) = (
+            position.get(edge.source.as_str()),
+            position.get(edge.target.as_str()),
+        ) else {
+            continue;
+        };
+        let stroke = style(&edge.layer)
+            .map(|layer| colour(&layer.border_color))
+            .unwrap_or_else(|| "#4b5563".to_string());
+        writeln!(
+            svg,
+            r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}"/>"#,
+            source.x + source.width / 2.0,
+            source.y + NODE_HEIGHT / 2.0,
+            target.x + target.width / 2.0,
+            target.y + NODE_HEIGHT / 2.0,
+            stroke,
+        )?;
+    }
+
+    for node in &placed {
+        let (fill, text, border) = match style(&node.layer) {
+            Some(layer) => (
+                colour(&layer.background_color),
+                colour(&layer.text_color),
+                colour(&layer.border_color),
+            ),
+            None => (
+                "#ffffff".to_string(),
+                "#000000".to_string(),
+                "#000000".to_string(),
+            ),
+        };
+        let fill = data
+            .node_colors
+            .get(&node.id)
+            .map(|color| colour(color))
+            .unwrap_or(fill);
+        writeln!(
+            svg,
+            r#"  <rect x="{}" y="{}" width="{}" height="{NODE_HEIGHT}" rx="4" fill="{}" stroke="{}"/>"#,
+            node.x, node.y, node.width, fill, border,
+        )?;
+        writeln!(
+            svg,
+            r#"  <text x="{}" y="{}" text-anchor="middle" dominant-baseline="central" fill="{}">{}</text>"#,
+            node.x + node.width / 2.0,
+            node.y + NODE_HEIGHT / 2.0,
+            text,
+            escape_xml(&node.label),
+        )?;
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
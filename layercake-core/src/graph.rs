@@ -1,10 +1,12 @@
 use csv::StringRecord;
 use indexmap::{IndexMap, IndexSet};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
 use tracing::{debug, error, warn};
 
 use crate::data_loader::{DfEdgeLoadProfile, DfNodeLoadProfile};
+use crate::services::graph_analysis_service::union_find_root;
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Graph {
@@ -26,6 +28,154 @@ pub struct PartitionWidthAggregation {
     pub retained_count: usize,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct CommunityDetectionSummary {
+    /// Detected communities (node ids), ordered by first appearance in the node list.
+    pub communities: Vec<Vec<String>>,
+    /// Nodes folded into the "unclustered" bucket because their community was too small.
+    pub unclustered_nodes: usize,
+    pub layers_added: usize,
+    pub nodes_modified: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ShortestPathTreeSummary {
+    /// Nodes reachable from the root, including the root itself.
+    pub reachable_nodes: usize,
+    pub tree_edges: usize,
+    pub removed_edges: usize,
+    pub removed_nodes: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NeighborhoodSummary {
+    /// Nodes within the radius, including the seed itself.
+    pub neighborhood_nodes: usize,
+    /// Edges kept with one endpoint outside the neighborhood, tagged `boundary`.
+    pub boundary_edges: usize,
+    pub removed_edges: usize,
+    pub removed_nodes: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpanningTreeSummary {
+    /// Connected components of the flow nodes; the result has one tree per component.
+    pub components: usize,
+    pub tree_edges: usize,
+    pub removed_edges: usize,
+    pub total_weight: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReciprocalEdgeSummary {
+    /// Pairs of opposing edges found (a->b matched with b->a).
+    pub pairs: usize,
+    /// Edges removed by folding each pair into one; zero when kept separate.
+    pub removed_edges: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HierarchyDepthSummary {
+    pub max_depth: usize,
+    /// Nodes promoted to roots to break `belongs_to` cycles, one per cycle.
+    pub broken_cycles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DegreeFilterSummary {
+    pub removed_nodes: usize,
+    pub removed_edges: usize,
+    /// Partitions whose children were all removed; they are kept and marked `empty`.
+    pub emptied_partitions: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PageRankSummary {
+    /// Power iterations run before converging or reaching the limit.
+    pub iterations: usize,
+    /// True when the L1 change between iterations fell below the threshold.
+    pub converged: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WeightNormalizationSummary {
+    /// Edges whose weight was rescaled; zero weights kept by `preserve_zero` are not counted.
+    pub normalized_edges: usize,
+    /// True when the rescaled weights had no spread and all went to the range midpoint.
+    pub uniform: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CutStructureSummary {
+    /// Flow nodes whose removal disconnects their component, in node order.
+    pub articulation_points: Vec<String>,
+    /// Ids of edges whose removal disconnects their component, in edge order.
+    pub bridges: Vec<String>,
+}
+
+/// Id scheme applied by [`Graph::normalize_node_ids`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NodeIdStrategy {
+    /// Lowercase `[a-z0-9_]` slug of the old id; collisions get `_2`, `_3`, ...
+    Slugify,
+    /// `n1`, `n2`, ... in node order.
+    Sequential,
+    /// The old id behind a fixed prefix.
+    Prefixed,
+}
+
+/// Edges counted towards a node's degree by [`Graph::filter_by_degree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DegreeDirection {
+    /// Edges targeting the node.
+    In,
+    /// Edges leaving the node.
+    Out,
+    /// Both; a self-loop counts twice.
+    Total,
+}
+
+/// Scaling applied by [`Graph::normalize_edge_weights`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeightNormalization {
+    /// Linear map of the observed min..max onto the target range.
+    MinMax,
+    /// `(w - mean) / std`, times [`STANDARDIZED_WEIGHT_SCALE`].
+    ZScore,
+    /// `(w - median) / IQR`, times [`STANDARDIZED_WEIGHT_SCALE`]; robust to outliers.
+    Robust,
+}
+
+/// Edge weights are integers, so z-scores and robust scores are multiplied by
+/// this factor before rounding: a weight of 100 sits one standard deviation
+/// (or one interquartile range) above the centre.
+pub const STANDARDIZED_WEIGHT_SCALE: f64 = 100.0;
+
+/// Search used by [`Graph::shortest_path`] when edge weights are honoured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PathAlgorithm {
+    Dijkstra,
+    /// Dijkstra guided by a hop-count lower bound towards the target.
+    AStar,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPath {
+    /// Node ids from source to target, inclusive.
+    pub path: Vec<String>,
+    pub cost: i64,
+}
+
+/// How two layers of a graph connect, from [`Graph::layer_connectivity`].
+#[derive(Debug, Clone)]
+pub struct LayerConnectivity {
+    /// Edges with one endpoint in each layer, in either direction.
+    pub direct_edges: Vec<Edge>,
+    /// Fewest-hop node path from a node in the first layer to a node in the
+    /// second, inclusive; `None` when the layers are not connected.
+    pub path: Option<Vec<String>>,
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct LayerAggregationSummary {
@@ -490,13 +640,20 @@ impl Graph {
         nodes
     }
 
+    /// Build the `belongs_to` hierarchy as nested tree nodes, rooted at nodes
+    /// without a parent.
+    ///
+    /// Nodes whose `belongs_to` chain loops back on itself never reach a root;
+    /// each such cycle is broken by promoting its lowest-id node to a root,
+    /// with a warning, so the nodes are neither dropped nor revisited forever.
     pub fn build_tree(&self) -> Vec<TreeNode> {
         fn build_tree(
             node: &Node,
             depth: i32,
             graph: &Graph,
-            tree: &mut Vec<TreeNode>,
+            visited: &mut HashSet<String>,
         ) -> TreeNode {
+            visited.insert(node.id.clone());
             let mut tree_node = TreeNode::from_node(node);
             tree_node.depth = depth;
 
@@ -509,21 +666,175 @@ impl Graph {
 
             let children = graph.get_children(node);
             for child in children {
-                let child_node = build_tree(child, depth + 1, graph, tree);
+                // The edge closing a cycle back to an ancestor is dropped.
+                if visited.contains(&child.id) {
+                    continue;
+                }
+                let child_node = build_tree(child, depth + 1, graph, visited);
                 tree_node.children.push(child_node);
             }
             tree_node
         }
 
         let root_nodes = self.get_root_nodes();
+        let mut visited = HashSet::new();
         let mut tree = Vec::new();
         for root_node in root_nodes {
-            let node = build_tree(root_node, 0, self, &mut tree);
+            let node = build_tree(root_node, 0, self, &mut visited);
             tree.push(node);
         }
+
+        let mut stranded: Vec<&Node> = self
+            .nodes
+            .iter()
+            .filter(|n| !visited.contains(&n.id))
+            .collect();
+        stranded.sort_by(|a, b| a.id.cmp(&b.id));
+        for node in stranded {
+            if visited.contains(&node.id) || !self.is_in_belongs_to_cycle(node) {
+                continue;
+            }
+            warn!(
+                "Node {} is part of a belongs_to cycle; treating it as a root",
+                node.id
+            );
+            let root = build_tree(node, 0, self, &mut visited);
+            tree.push(root);
+        }
         tree
     }
 
+    /// Whether following `belongs_to` from `node` leads back to `node`.
+    fn is_in_belongs_to_cycle(&self, node: &Node) -> bool {
+        let mut current = node;
+        for _ in 0..self.nodes.len() {
+            match current
+                .belongs_to
+                .as_deref()
+                .filter(|parent| !parent.is_empty())
+                .and_then(|parent| self.get_node_by_id(parent))
+            {
+                Some(parent) if parent.id == node.id => return true,
+                Some(parent) => current = parent,
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Pair each edge a->b with an unmatched b->a edge, in edge order, and
+    /// mark both `bidirectional`. Unless `keep_separate` is set, each pair is
+    /// folded into its first edge, which takes the combined weight. Self-loops
+    /// are never paired.
+    pub fn merge_reciprocal_edges(&mut self, keep_separate: bool) -> ReciprocalEdgeSummary {
+        let mut open: HashMap<(String, String), Vec<usize>> = HashMap::new();
+        let mut pairs: Vec<(usize, usize)> = Vec::new();
+        for (idx, edge) in self.edges.iter().enumerate() {
+            if edge.source == edge.target {
+                continue;
+            }
+            let reverse = (edge.target.clone(), edge.source.clone());
+            match open.get_mut(&reverse).filter(|waiting| !waiting.is_empty()) {
+                Some(waiting) => pairs.push((waiting.remove(0), idx)),
+                None => open
+                    .entry((edge.source.clone(), edge.target.clone()))
+                    .or_default()
+                    .push(idx),
+            }
+        }
+
+        let mut removed: HashSet<usize> = HashSet::new();
+        for &(first, second) in &pairs {
+            self.edges[first].set_attribute("bidirectional", serde_json::Value::Bool(true));
+            if keep_separate {
+                self.edges[second].set_attribute("bidirectional", serde_json::Value::Bool(true));
+            } else {
+                self.edges[first].weight += self.edges[second].weight;
+                removed.insert(second);
+            }
+        }
+
+        let mut idx = 0;
+        self.edges.retain(|_| {
+            idx += 1;
+            !removed.contains(&(idx - 1))
+        });
+
+        ReciprocalEdgeSummary {
+            pairs: pairs.len(),
+            removed_edges: removed.len(),
+        }
+    }
+
+    /// Store each node's distance from its `belongs_to` root as a `depth`
+    /// attribute, with roots (including nodes whose parent is missing) at 0.
+    ///
+    /// Each `belongs_to` cycle is broken first by clearing the parent of its
+    /// lowest-id node, with a warning, in the same way [`Graph::build_tree`]
+    /// promotes that node to a root.
+    pub fn compute_hierarchy_depth(&mut self) -> HierarchyDepthSummary {
+        let mut order: Vec<usize> = (0..self.nodes.len()).collect();
+        order.sort_by(|a, b| self.nodes[*a].id.cmp(&self.nodes[*b].id));
+        let mut broken_cycles = Vec::new();
+        for idx in order {
+            if self.is_in_belongs_to_cycle(&self.nodes[idx]) {
+                warn!(
+                    "Node {} is part of a belongs_to cycle; treating it as a root",
+                    self.nodes[idx].id
+                );
+                self.nodes[idx].belongs_to = None;
+                broken_cycles.push(self.nodes[idx].id.clone());
+            }
+        }
+
+        let parent: HashMap<&str, &str> = self
+            .nodes
+            .iter()
+            .filter_map(|node| {
+                let parent = node.belongs_to.as_deref().filter(|p| !p.is_empty())?;
+                Some((node.id.as_str(), parent))
+            })
+            .collect();
+        let known: HashSet<&str> = self.nodes.iter().map(|node| node.id.as_str()).collect();
+        let mut depth: HashMap<&str, usize> = HashMap::new();
+        for node in &self.nodes {
+            // Climb until a node of known depth or a root, then unwind.
+            let mut path = Vec::new();
+            let mut current = node.id.as_str();
+            let base = loop {
+                if let Some(&value) = depth.get(current) {
+                    break value;
+                }
+                match parent.get(current).filter(|p| known.contains(**p)) {
+                    Some(&next) => {
+                        path.push(current);
+                        current = next;
+                    }
+                    None => {
+                        depth.insert(current, 0);
+                        break 0;
+                    }
+                }
+            };
+            for (offset, id) in path.into_iter().rev().enumerate() {
+                depth.insert(id, base + offset + 1);
+            }
+        }
+
+        let depth: HashMap<String, usize> = depth
+            .into_iter()
+            .map(|(id, value)| (id.to_string(), value))
+            .collect();
+        for node in self.nodes.iter_mut() {
+            node.set_attribute("depth", serde_json::Value::from(depth[&node.id]));
+        }
+
+        HierarchyDepthSummary {
+            max_depth: depth.values().copied().max().unwrap_or(0),
+            broken_cycles,
+        }
+    }
+
     /// Build a hierarchy tree based on edges rather than `belongs_to` metadata.
     /// This is useful after GenerateHierarchy rewires structure into edges.
     pub fn build_tree_from_edges(&self) -> Vec<TreeNode> {
@@ -1179,54 +1490,1349 @@ impl Graph {
                     continue;
                 }
 
-                let aggregate_ids: Vec<String> = members.into_iter().collect();
-                let aggregate_label = format!("{} agg({})", layer_id, aggregate_ids.len());
-
-                let (aggregate_node, aggregated_pairs) = match self.replace_with_aggregate_node(
-                    &aggregate_ids,
-                    aggregate_label,
-                    layer_id.clone(),
-                    belongs_to.clone(),
-                    None,
-                ) {
-                    Some(value) => value,
-                    None => continue,
-                };
-
-                let anchor_label = node_lookup.get(&anchor_id).map(|node| node.label.clone());
+                let aggregate_ids: Vec<String> = members.into_iter().collect();
+                let aggregate_label = format!("{} agg({})", layer_id, aggregate_ids.len());
+
+                let (aggregate_node, aggregated_pairs) = match self.replace_with_aggregate_node(
+                    &aggregate_ids,
+                    aggregate_label,
+                    layer_id.clone(),
+                    belongs_to.clone(),
+                    None,
+                ) {
+                    Some(value) => value,
+                    None => continue,
+                };
+
+                let anchor_label = node_lookup.get(&anchor_id).map(|node| node.label.clone());
+
+                return Ok(Some(LayerAggregationSummary {
+                    layer_id,
+                    belongs_to,
+                    anchor_node_id: anchor_id,
+                    anchor_node_label: anchor_label,
+                    aggregate_node_id: aggregate_node.id,
+                    aggregate_node_label: aggregate_node.label,
+                    aggregated_nodes: aggregated_pairs,
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Aggregate duplicate edges
+    pub fn aggregate_edges(&mut self) {
+        let mut edge_map: HashMap<String, Edge> = HashMap::new();
+        for edge in &self.edges {
+            let key = format!("{}_{}", edge.source, edge.target);
+            if let Some(existing_edge) = edge_map.get_mut(&key) {
+                existing_edge.weight += edge.weight;
+            } else {
+                edge_map.insert(key, edge.clone());
+            }
+        }
+        debug!(
+            "Aggregated {} edges to {}",
+            self.edges.len(),
+            edge_map.len()
+        );
+        self.edges = edge_map.values().cloned().collect();
+    }
+
+    /// Partition the flow nodes into communities using Louvain modularity maximisation.
+    ///
+    /// Edges are treated as undirected and weighted by `max(1, weight)`. Partition nodes
+    /// and edges touching them are ignored. The result is deterministic for a given graph.
+    pub fn louvain_communities(&self, resolution: f64) -> Vec<Vec<String>> {
+        let node_ids: Vec<String> = self
+            .nodes
+            .iter()
+            .filter(|n| !n.is_partition)
+            .map(|n| n.id.clone())
+            .collect();
+        let index: HashMap<&str, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(idx, id)| (id.as_str(), idx))
+            .collect();
+
+        // Row sums of `adjacency` are node strengths; self-loops are stored twice so the
+        // sum of all strengths stays equal to 2m after aggregation.
+        let mut adjacency: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); node_ids.len()];
+        for edge in &self.edges {
+            let (Some(&s), Some(&t)) = (
+                index.get(edge.source.as_str()),
+                index.get(edge.target.as_str()),
+            ) else {
+                continue;
+            };
+            let weight = std::cmp::max(1, edge.weight) as f64;
+            if s == t {
+                *adjacency[s].entry(s).or_default() += 2.0 * weight;
+            } else {
+                *adjacency[s].entry(t).or_default() += weight;
+                *adjacency[t].entry(s).or_default() += weight;
+            }
+        }
+
+        let total_weight: f64 = adjacency.iter().flat_map(|row| row.values()).sum();
+        let mut membership: Vec<usize> = (0..node_ids.len()).collect();
+
+        if total_weight > 0.0 {
+            loop {
+                let level_size = adjacency.len();
+                let strength: Vec<f64> = adjacency.iter().map(|row| row.values().sum()).collect();
+                let mut community: Vec<usize> = (0..level_size).collect();
+                let mut community_total = strength.clone();
+                let mut moved_any = false;
+
+                loop {
+                    let mut moved = false;
+                    for node in 0..level_size {
+                        let current = community[node];
+                        let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+                        for (&neighbour, &weight) in &adjacency[node] {
+                            if neighbour != node {
+                                *links.entry(community[neighbour]).or_default() += weight;
+                            }
+                        }
+
+                        community_total[current] -= strength[node];
+                        let gain = |target: usize, links_to: f64| {
+                            links_to
+                                - resolution * community_total[target] * strength[node]
+                                    / total_weight
+                        };
+
+                        let mut best = current;
+                        let mut best_gain =
+                            gain(current, links.get(&current).copied().unwrap_or(0.0));
+                        for (&candidate, &links_to) in &links {
+                            let candidate_gain = gain(candidate, links_to);
+                            if candidate_gain > best_gain + 1e-12 {
+                                best = candidate;
+                                best_gain = candidate_gain;
+                            }
+                        }
+
+                        community_total[best] += strength[node];
+                        if best != current {
+                            community[node] = best;
+                            moved = true;
+                            moved_any = true;
+                        }
+                    }
+                    if !moved {
+                        break;
+                    }
+                }
+
+                if !moved_any {
+                    break;
+                }
+
+                let mut renumber: HashMap<usize, usize> = HashMap::new();
+                for &c in &community {
+                    let next = renumber.len();
+                    renumber.entry(c).or_insert(next);
+                }
+                for member in membership.iter_mut() {
+                    *member = renumber[&community[*member]];
+                }
+
+                let mut aggregated: Vec<BTreeMap<usize, f64>> =
+                    vec![BTreeMap::new(); renumber.len()];
+                for (node, row) in adjacency.iter().enumerate() {
+                    let from = renumber[&community[node]];
+                    for (&neighbour, &weight) in row {
+                        let to = renumber[&community[neighbour]];
+                        *aggregated[from].entry(to).or_default() += weight;
+                    }
+                }
+
+                let converged = aggregated.len() == adjacency.len();
+                adjacency = aggregated;
+                if converged {
+                    break;
+                }
+            }
+        }
+
+        let mut order: Vec<usize> = Vec::new();
+        let mut grouped: HashMap<usize, Vec<String>> = HashMap::new();
+        for (idx, &c) in membership.iter().enumerate() {
+            grouped
+                .entry(c)
+                .or_insert_with(|| {
+                    order.push(c);
+                    Vec::new()
+                })
+                .push(node_ids[idx].clone());
+        }
+
+        order
+            .into_iter()
+            .filter_map(|c| grouped.remove(&c))
+            .collect()
+    }
+
+    /// Detect Louvain communities and record them on the graph.
+    ///
+    /// Communities smaller than `min_community_size` are merged into an "unclustered" group.
+    /// When `create_community_layers` is set, one layer is added per community and each node
+    /// is moved into its community's layer; otherwise the community id is written to the
+    /// node's `community` attribute.
+    pub fn detect_communities(
+        &mut self,
+        resolution: f64,
+        min_community_size: usize,
+        create_community_layers: bool,
+    ) -> Result<CommunityDetectionSummary, String> {
+        if !resolution.is_finite() || resolution <= 0.0 {
+            return Err("community resolution must be a positive number".to_string());
+        }
+
+        const COMMUNITY_COLOURS: [(&str, &str, &str); 6] = [
+            ("1e3a8a", "eff6ff", "1e40af"),
+            ("065f46", "ecfdf5", "064e3b"),
+            ("5b21b6", "f5f3ff", "4c1d95"),
+            ("7c2d12", "fff7ed", "431407"),
+            ("a16207", "fefce8", "854d0e"),
+            ("0e7490", "ecfeff", "155e75"),
+        ];
+
+        let communities = self.louvain_communities(resolution);
+        let mut assignment: HashMap<String, String> = HashMap::new();
+        let mut summary = CommunityDetectionSummary::default();
+        let mut community_layers: Vec<Layer> = Vec::new();
+
+        for members in &communities {
+            if members.len() < min_community_size {
+                summary.unclustered_nodes += members.len();
+                for id in members {
+                    assignment.insert(id.clone(), "unclustered".to_string());
+                }
+                continue;
+            }
+            let number = community_layers.len() + 1;
+            let (background, text, border) =
+                COMMUNITY_COLOURS[(number - 1) % COMMUNITY_COLOURS.len()];
+            let layer_id = format!("community_{}", number);
+            community_layers.push(Layer::new(
+                &layer_id,
+                &format!("Community {}", number),
+                background,
+                text,
+                border,
+            ));
+            for id in members {
+                assignment.insert(id.clone(), layer_id.clone());
+            }
+        }
+        if summary.unclustered_nodes > 0 {
+            community_layers.push(Layer::new(
+                "unclustered",
+                "Unclustered",
+                "e5e7eb",
+                "111827",
+                "9ca3af",
+            ));
+        }
+
+        if create_community_layers {
+            for layer in community_layers {
+                if !self.layer_exists(&layer.id) {
+                    self.layers.push(layer);
+                    summary.layers_added += 1;
+                }
+            }
+        }
+
+        for node in self.nodes.iter_mut() {
+            let Some(community_id) = assignment.get(&node.id) else {
+                continue;
+            };
+            if create_community_layers {
+                if node.layer != *community_id {
+                    node.layer = community_id.clone();
+                    summary.nodes_modified += 1;
+                }
+            } else if node
+                .set_attribute("community", serde_json::Value::String(community_id.clone()))
+            {
+                summary.nodes_modified += 1;
+            }
+        }
+
+        summary.communities = communities;
+        Ok(summary)
+    }
+
+    /// Undirected simple adjacency over flow nodes, ignoring self-loops and
+    /// edges that touch partition nodes. Neighbour lists follow node order.
+    fn flow_adjacency(&self) -> IndexMap<String, IndexSet<String>> {
+        let mut adjacency: IndexMap<String, IndexSet<String>> = self
+            .nodes
+            .iter()
+            .filter(|n| !n.is_partition)
+            .map(|n| (n.id.clone(), IndexSet::new()))
+            .collect();
+        for edge in &self.edges {
+            if edge.source == edge.target
+                || !adjacency.contains_key(&edge.source)
+                || !adjacency.contains_key(&edge.target)
+            {
+                continue;
+            }
+            adjacency[&edge.source].insert(edge.target.clone());
+            adjacency[&edge.target].insert(edge.source.clone());
+        }
+        adjacency
+    }
+
+    /// Compute the coreness of every flow node (the largest k for which the node
+    /// belongs to the k-core), by repeatedly peeling the lowest-degree node.
+    pub fn core_numbers(&self) -> IndexMap<String, usize> {
+        let adjacency = self.flow_adjacency();
+        let mut degree: Vec<usize> = adjacency.values().map(IndexSet::len).collect();
+        let mut queue: BTreeSet<(usize, usize)> = degree
+            .iter()
+            .enumerate()
+            .map(|(idx, d)| (*d, idx))
+            .collect();
+        let mut coreness = vec![0; adjacency.len()];
+        let mut current_core = 0;
+
+        while let Some((node_degree, idx)) = queue.pop_first() {
+            current_core = current_core.max(node_degree);
+            coreness[idx] = current_core;
+            for neighbour in &adjacency[idx] {
+                let n_idx = adjacency
+                    .get_index_of(neighbour)
+                    .expect("neighbour is a flow node");
+                if queue.remove(&(degree[n_idx], n_idx)) {
+                    degree[n_idx] -= 1;
+                    queue.insert((degree[n_idx], n_idx));
+                }
+            }
+        }
+
+        adjacency.keys().cloned().zip(coreness).collect()
+    }
+
+    /// Betweenness centrality of every flow node using Brandes' algorithm with
+    /// a priority queue, so shortest paths may be weighted.
+    ///
+    /// With `weighted`, edge weights are traversal costs and must be positive:
+    /// a zero-cost edge can tie a node that is already settled, which would
+    /// drop shortest paths from the count. Otherwise every hop costs 1. Parallel edges collapse to the
+    /// cheapest one, and self-loops and edges touching partition nodes are
+    /// ignored. Undirected scores count each unordered pair once. With
+    /// `normalized`, scores are divided by `(n-1)(n-2)` when directed and by
+    /// `(n-1)(n-2)/2` when undirected, so both fall in `[0, 1]`.
+    pub fn betweenness_centrality(
+        &self,
+        weighted: bool,
+        directed: bool,
+        normalized: bool,
+    ) -> Result<IndexMap<String, f64>, String> {
+        let index: IndexSet<&str> = self
+            .nodes
+            .iter()
+            .filter(|n| !n.is_partition)
+            .map(|n| n.id.as_str())
+            .collect();
+        let count = index.len();
+
+        let mut costs: Vec<HashMap<usize, i64>> = vec![HashMap::new(); count];
+        for edge in &self.edges {
+            let (Some(source), Some(target)) = (
+                index.get_index_of(edge.source.as_str()),
+                index.get_index_of(edge.target.as_str()),
+            ) else {
+                continue;
+            };
+            if source == target {
+                continue;
+            }
+            let cost = if weighted {
+                if edge.weight <= 0 {
+                    return Err(format!(
+                        "Edge '{}' has non-positive weight {}",
+                        edge.id, edge.weight
+                    ));
+                }
+                edge.weight as i64
+            } else {
+                1
+            };
+            let mut link = |from: usize, to: usize| {
+                let known = costs[from].entry(to).or_insert(cost);
+                *known = (*known).min(cost);
+            };
+            link(source, target);
+            if !directed {
+                link(target, source);
+            }
+        }
+        let successors: Vec<Vec<(usize, i64)>> = costs
+            .into_iter()
+            .map(|targets| {
+                let mut targets: Vec<(usize, i64)> = targets.into_iter().collect();
+                targets.sort_unstable();
+                targets
+            })
+            .collect();
+
+        let mut scores = vec![0.0; count];
+        for source in 0..count {
+            let mut distance: Vec<Option<i64>> = vec![None; count];
+            let mut paths = vec![0.0; count];
+            let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); count];
+            let mut settled = vec![false; count];
+            let mut order = Vec::with_capacity(count);
+            distance[source] = Some(0);
+            paths[source] = 1.0;
+
+            let mut heap = BinaryHeap::from([Reverse((0i64, source))]);
+            while let Some(Reverse((cost, current))) = heap.pop() {
+                if settled[current] {
+                    continue;
+                }
+                settled[current] = true;
+                order.push(current);
+                for &(next, weight) in &successors[current] {
+                    if settled[next] {
+                        continue;
+                    }
+                    let candidate = cost + weight;
+                    match distance[next] {
+                        Some(known) if known < candidate => {}
+                        Some(known) if known == candidate => {
+                            paths[next] += paths[current];
+                            predecessors[next].push(current);
+                        }
+                        _ => {
+                            distance[next] = Some(candidate);
+                            paths[next] = paths[current];
+                            predecessors[next] = vec![current];
+                            heap.push(Reverse((candidate, next)));
+                        }
+                    }
+                }
+            }
+
+            let mut dependency = vec![0.0; count];
+            while let Some(node) = order.pop() {
+                for &previous in &predecessors[node] {
+                    dependency[previous] +=
+                        paths[previous] / paths[node] * (1.0 + dependency[node]);
+                }
+                if node != source {
+                    scores[node] += dependency[node];
+                }
+            }
+        }
+
+        // Undirected searches run from both ends of every pair.
+        let scale = if normalized {
+            if count < 3 {
+                0.0
+            } else {
+                1.0 / ((count - 1) * (count - 2)) as f64
+            }
+        } else if directed {
+            1.0
+        } else {
+            0.5
+        };
+
+        Ok(index
+            .iter()
+            .zip(scores)
+            .map(|(id, score)| (id.to_string(), score * scale))
+            .collect())
+    }
+
+    /// Compute weighted PageRank over the flow graph by power iteration and
+    /// store each flow node's score in its `pagerank` attribute. Links are
+    /// followed in proportion to edge weight (non-positive weights carry no
+    /// rank); nodes without outgoing weight spread their rank uniformly.
+    /// Iteration stops early once the L1 change drops below 1e-10.
+    pub fn annotate_pagerank(
+        &mut self,
+        damping: f64,
+        max_iterations: usize,
+    ) -> Result<PageRankSummary, String> {
+        const TOLERANCE: f64 = 1e-10;
+        if !(0.0..1.0).contains(&damping) {
+            return Err(format!("damping must be in [0, 1), got {}", damping));
+        }
+        if max_iterations == 0 {
+            return Err("iterations must be at least 1".to_string());
+        }
+
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for node in self.nodes.iter().filter(|n| !n.is_partition) {
+            let next = index.len();
+            index.entry(node.id.as_str()).or_insert(next);
+        }
+        let count = index.len();
+        if count == 0 {
+            return Ok(PageRankSummary {
+                iterations: 0,
+                converged: true,
+            });
+        }
+
+        let mut links: Vec<(usize, usize, f64)> = Vec::new();
+        let mut out_weight = vec![0.0; count];
+        for edge in &self.edges {
+            if let (Some(&source), Some(&target)) = (
+                index.get(edge.source.as_str()),
+                index.get(edge.target.as_str()),
+            ) {
+                let weight = f64::from(edge.weight.max(0));
+                if weight > 0.0 {
+                    links.push((source, target, weight));
+                    out_weight[source] += weight;
+                }
+            }
+        }
+
+        let n = count as f64;
+        let mut rank = vec![1.0 / n; count];
+        let mut summary = PageRankSummary::default();
+        while summary.iterations < max_iterations {
+            summary.iterations += 1;
+            let dangling: f64 = (0..count)
+                .filter(|&idx| out_weight[idx] == 0.0)
+                .map(|idx| rank[idx])
+                .sum();
+            let base = (1.0 - damping) / n + damping * dangling / n;
+            let mut next = vec![base; count];
+            for &(source, target, weight) in &links {
+                next[target] += damping * rank[source] * weight / out_weight[source];
+            }
+            let delta: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+            rank = next;
+            if delta < TOLERANCE {
+                summary.converged = true;
+                break;
+            }
+        }
+
+        let scores: HashMap<String, f64> = index
+            .into_iter()
+            .map(|(id, idx)| (id.to_string(), rank[idx]))
+            .collect();
+        for node in &mut self.nodes {
+            if let Some(score) = scores.get(&node.id) {
+                node.set_attribute("pagerank", (*score).into());
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Rescale edge weights with `method`. MinMax maps onto `range`; ZScore
+    /// and Robust produce scaled standard scores and ignore `range`, except
+    /// that weights with no spread (all equal) always go to its midpoint. With
+    /// `preserve_zero`, zero-weight edges stay zero and are left out of the
+    /// statistics.
+    pub fn normalize_edge_weights(
+        &mut self,
+        method: WeightNormalization,
+        range: (i32, i32),
+        preserve_zero: bool,
+    ) -> Result<WeightNormalizationSummary, String> {
+        let (low, high) = range;
+        if low > high {
+            return Err(format!(
+                "Weight range minimum {} is greater than maximum {}",
+                low, high
+            ));
+        }
+
+        let selected: Vec<usize> = self
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, edge)| !(preserve_zero && edge.weight == 0))
+            .map(|(idx, _)| idx)
+            .collect();
+        if selected.is_empty() {
+            return Ok(WeightNormalizationSummary::default());
+        }
+
+        let mut weights: Vec<f64> = selected
+            .iter()
+            .map(|&idx| self.edges[idx].weight as f64)
+            .collect();
+        weights.sort_by(f64::total_cmp);
+        let quantile = |q: f64| {
+            let pos = q * (weights.len() - 1) as f64;
+            let (below, above) = (pos.floor() as usize, pos.ceil() as usize);
+            weights[below] + (weights[above] - weights[below]) * (pos - below as f64)
+        };
+        let (centre, spread) = match method {
+            WeightNormalization::MinMax => (weights[0], weights[weights.len() - 1] - weights[0]),
+            WeightNormalization::ZScore => {
+                let mean = weights.iter().sum::<f64>() / weights.len() as f64;
+                let variance =
+                    weights.iter().map(|w| (w - mean).powi(2)).sum::<f64>() / weights.len() as f64;
+                (mean, variance.sqrt())
+            }
+            WeightNormalization::Robust => (quantile(0.5), quantile(0.75) - quantile(0.25)),
+        };
+
+        let (low, high) = (low as f64, high as f64);
+        for &idx in &selected {
+            let weight = self.edges[idx].weight as f64;
+            let scaled = if spread == 0.0 {
+                (low + high) / 2.0
+            } else if method == WeightNormalization::MinMax {
+                low + (weight - centre) / spread * (high - low)
+            } else {
+                (weight - centre) / spread * STANDARDIZED_WEIGHT_SCALE
+            };
+            self.edges[idx].weight = scaled.round() as i32;
+        }
+
+        Ok(WeightNormalizationSummary {
+            normalized_edges: selected.len(),
+            uniform: spread == 0.0,
+        })
+    }
+
+    /// Find articulation points (cut vertices) and bridges on the undirected
+    /// projection of the flow graph using Tarjan's low-link DFS, and record
+    /// them as boolean `is_articulation` node and `is_bridge` edge attributes.
+    /// Parallel edges between the same pair are never bridges; edges touching
+    /// partition nodes and self-loops are tagged `false`.
+    pub fn tag_cut_structure(&mut self) -> CutStructureSummary {
+        let adjacency = self.flow_adjacency();
+        let neighbours: Vec<Vec<usize>> = adjacency
+            .values()
+            .map(|set| {
+                set.iter()
+                    .map(|id| {
+                        adjacency
+                            .get_index_of(id)
+                            .expect("neighbour is a flow node")
+                    })
+                    .collect()
+            })
+            .collect();
+        let pair_key = |a: usize, b: usize| (a.min(b), a.max(b));
+        let mut multiplicity: HashMap<(usize, usize), usize> = HashMap::new();
+        for edge in &self.edges {
+            if let (Some(a), Some(b)) = (
+                adjacency.get_index_of(&edge.source),
+                adjacency.get_index_of(&edge.target),
+            ) {
+                if a != b {
+                    *multiplicity.entry(pair_key(a, b)).or_default() += 1;
+                }
+            }
+        }
+
+        let count = adjacency.len();
+        let mut discovery = vec![usize::MAX; count];
+        let mut low = vec![0; count];
+        let mut parent: Vec<Option<usize>> = vec![None; count];
+        let mut is_articulation = vec![false; count];
+        let mut bridge_pairs: HashSet<(usize, usize)> = HashSet::new();
+        let mut timer = 0;
+
+        // Iterative DFS so deep chains cannot overflow the stack.
+        for root in 0..count {
+            if discovery[root] != usize::MAX {
+                continue;
+            }
+            discovery[root] = timer;
+            low[root] = timer;
+            timer += 1;
+            let mut root_children = 0;
+            let mut stack = vec![(root, 0)];
+            while let Some((node, next)) = stack.last_mut() {
+                let node = *node;
+                if let Some(&neighbour) = neighbours[node].get(*next) {
+                    *next += 1;
+                    if discovery[neighbour] == usize::MAX {
+                        parent[neighbour] = Some(node);
+                        discovery[neighbour] = timer;
+                        low[neighbour] = timer;
+                        timer += 1;
+                        if node == root {
+                            root_children += 1;
+                        }
+                        stack.push((neighbour, 0));
+                    } else if parent[node] != Some(neighbour) {
+                        low[node] = low[node].min(discovery[neighbour]);
+                    }
+                    continue;
+                }
+                stack.pop();
+                if let Some(up) = parent[node] {
+                    low[up] = low[up].min(low[node]);
+                    if low[node] > discovery[up] && multiplicity[&pair_key(up, node)] == 1 {
+                        bridge_pairs.insert(pair_key(up, node));
+                    }
+                    if up != root && low[node] >= discovery[up] {
+                        is_articulation[up] = true;
+                    }
+                }
+            }
+            if root_children > 1 {
+                is_articulation[root] = true;
+            }
+        }
+
+        let mut summary = CutStructureSummary::default();
+        for node in self.nodes.iter_mut() {
+            if let Some(idx) = adjacency.get_index_of(&node.id) {
+                node.set_attribute("is_articulation", is_articulation[idx].into());
+                if is_articulation[idx] {
+                    summary.articulation_points.push(node.id.clone());
+                }
+            }
+        }
+        for edge in self.edges.iter_mut() {
+            let is_bridge = match (
+                adjacency.get_index_of(&edge.source),
+                adjacency.get_index_of(&edge.target),
+            ) {
+                (Some(a), Some(b)) => bridge_pairs.contains(&pair_key(a, b)),
+                _ => false,
+            };
+            edge.set_attribute("is_bridge", is_bridge.into());
+            if is_bridge {
+                summary.bridges.push(edge.id.clone());
+            }
+        }
+        summary
+    }
+
+    /// Annotate each flow node with its `coreness` attribute and, when
+    /// `keep_only_core` is set, drop flow nodes outside the k-core.
+    /// Returns the ids of nodes in the k-core.
+    pub fn apply_k_core(&mut self, k: usize, keep_only_core: bool) -> Vec<String> {
+        let coreness = self.core_numbers();
+
+        for node in self.nodes.iter_mut() {
+            if let Some(value) = coreness.get(&node.id) {
+                node.set_attribute("coreness", serde_json::Value::from(*value));
+            }
+        }
+
+        let core: Vec<String> = coreness
+            .iter()
+            .filter(|(_, value)| **value >= k)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        if keep_only_core {
+            let core_set: HashSet<&str> = core.iter().map(String::as_str).collect();
+            self.nodes
+                .retain(|node| node.is_partition || core_set.contains(node.id.as_str()));
+            self.remove_dangling_edges();
+        }
+
+        core
+    }
+
+    /// Reduce the graph to the breadth-first shortest-path tree from `root`,
+    /// following edges from source to target. Each reachable node keeps the
+    /// first edge that discovered it; all other edges are removed, as are
+    /// unreachable flow nodes (partition nodes are kept for the hierarchy).
+    /// Reachable nodes get a `distance` attribute with their hop count.
+    pub fn shortest_path_tree(&mut self, root: &str) -> Result<ShortestPathTreeSummary, String> {
+        if !self.nodes.iter().any(|node| node.id == root) {
+            return Err(format!("Root node '{}' not found in graph", root));
+        }
+
+        let mut outgoing: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (idx, edge) in self.edges.iter().enumerate() {
+            if edge.source != edge.target {
+                outgoing.entry(edge.source.as_str()).or_default().push(idx);
+            }
+        }
+
+        let mut distance: HashMap<String, usize> = HashMap::from([(root.to_string(), 0)]);
+        let mut tree_edges: HashSet<usize> = HashSet::new();
+        let mut queue = VecDeque::from([root.to_string()]);
+        while let Some(current) = queue.pop_front() {
+            let next_distance = distance[&current] + 1;
+            for &idx in outgoing.get(current.as_str()).into_iter().flatten() {
+                let target = &self.edges[idx].target;
+                if !distance.contains_key(target) {
+                    distance.insert(target.clone(), next_distance);
+                    tree_edges.insert(idx);
+                    queue.push_back(target.clone());
+                }
+            }
+        }
+
+        let edges_before = self.edges.len();
+        let nodes_before = self.nodes.len();
+        let mut idx = 0;
+        self.edges.retain(|_| {
+            idx += 1;
+            tree_edges.contains(&(idx - 1))
+        });
+        self.nodes
+            .retain(|node| node.is_partition || distance.contains_key(&node.id));
+        for node in self.nodes.iter_mut() {
+            if let Some(hops) = distance.get(&node.id) {
+                node.set_attribute("distance", serde_json::Value::from(*hops));
+            }
+        }
+
+        Ok(ShortestPathTreeSummary {
+            reachable_nodes: distance.len(),
+            tree_edges: self.edges.len(),
+            removed_edges: edges_before - self.edges.len(),
+            removed_nodes: nodes_before - self.nodes.len(),
+        })
+    }
+
+    /// Reduce the graph to the nodes within `radius` hops of `seed`, walking
+    /// edges in either direction, and the edges induced between them. Kept
+    /// nodes get a `distance` attribute with their hop count. With
+    /// `preserve_structure` the `belongs_to` ancestors of kept nodes stay so
+    /// the hierarchy still renders; otherwise dropped parents are unlinked.
+    /// With `include_boundary_edges`, edges from a kept node to a dropped one
+    /// are kept as dangling half-edges with a `boundary` attribute.
+    pub fn extract_neighborhood(
+        &mut self,
+        seed: &str,
+        radius: usize,
+        preserve_structure: bool,
+        include_boundary_edges: bool,
+    ) -> Result<NeighborhoodSummary, String> {
+        if !self.nodes.iter().any(|node| node.id == seed) {
+            return Err(format!("Seed node '{}' not found in graph", seed));
+        }
+
+        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            if edge.source != edge.target {
+                adjacency
+                    .entry(edge.source.as_str())
+                    .or_default()
+                    .push(edge.target.as_str());
+                adjacency
+                    .entry(edge.target.as_str())
+                    .or_default()
+                    .push(edge.source.as_str());
+            }
+        }
+
+        let mut distance: HashMap<String, usize> = HashMap::from([(seed.to_string(), 0)]);
+        let mut queue = VecDeque::from([seed.to_string()]);
+        while let Some(current) = queue.pop_front() {
+            let next_distance = distance[&current] + 1;
+            if next_distance > radius {
+                continue;
+            }
+            for &neighbor in adjacency.get(current.as_str()).into_iter().flatten() {
+                if !distance.contains_key(neighbor) {
+                    distance.insert(neighbor.to_string(), next_distance);
+                    queue.push_back(neighbor.to_string());
+                }
+            }
+        }
+
+        let mut kept: HashSet<String> = distance.keys().cloned().collect();
+        if preserve_structure {
+            let parent_of: HashMap<&str, &str> = self
+                .nodes
+                .iter()
+                .filter_map(|node| Some((node.id.as_str(), node.belongs_to.as_deref()?)))
+                .collect();
+            for id in distance.keys() {
+                let mut current = id.as_str();
+                while let Some(&parent) = parent_of.get(current) {
+                    if !kept.insert(parent.to_string()) {
+                        break;
+                    }
+                    current = parent;
+                }
+            }
+        }
+
+        let edges_before = self.edges.len();
+        let nodes_before = self.nodes.len();
+        let mut boundary_edges = 0;
+        self.edges.retain_mut(|edge| {
+            match (kept.contains(&edge.source), kept.contains(&edge.target)) {
+                (true, true) => true,
+                (true, false) | (false, true) if include_boundary_edges => {
+                    edge.set_attribute("boundary", serde_json::Value::Bool(true));
+                    boundary_edges += 1;
+                    true
+                }
+                _ => false,
+            }
+        });
+        self.nodes.retain(|node| kept.contains(&node.id));
+        for node in self.nodes.iter_mut() {
+            if node
+                .belongs_to
+                .as_ref()
+                .is_some_and(|parent| !kept.contains(parent))
+            {
+                node.belongs_to = None;
+            }
+            if let Some(hops) = distance.get(&node.id) {
+                node.set_attribute("distance", serde_json::Value::from(*hops));
+            }
+        }
+
+        Ok(NeighborhoodSummary {
+            neighborhood_nodes: distance.len(),
+            boundary_edges,
+            removed_edges: edges_before - self.edges.len(),
+            removed_nodes: nodes_before - self.nodes.len(),
+        })
+    }
+
+    /// Remove flow nodes whose degree in `direction` falls outside
+    /// `min_degree..=max_degree`, along with the edges left dangling.
+    /// Partition nodes are always kept; those that lose all of their children
+    /// get an `empty` attribute.
+    pub fn filter_by_degree(
+        &mut self,
+        min_degree: usize,
+        max_degree: Option<usize>,
+        direction: DegreeDirection,
+    ) -> DegreeFilterSummary {
+        let mut degree: HashMap<&str, usize> = HashMap::new();
+        for edge in &self.edges {
+            if direction != DegreeDirection::In {
+                *degree.entry(edge.source.as_str()).or_default() += 1;
+            }
+            if direction != DegreeDirection::Out {
+                *degree.entry(edge.target.as_str()).or_default() += 1;
+            }
+        }
+
+        let removed: HashSet<String> = self
+            .nodes
+            .iter()
+            .filter(|node| !node.is_partition)
+            .filter(|node| {
+                let value = degree.get(node.id.as_str()).copied().unwrap_or(0);
+                value < min_degree || max_degree.is_some_and(|max| value > max)
+            })
+            .map(|node| node.id.clone())
+            .collect();
+        if removed.is_empty() {
+            return DegreeFilterSummary::default();
+        }
+
+        let parents_of = |nodes: &[Node]| -> HashSet<String> {
+            nodes
+                .iter()
+                .filter_map(|node| node.belongs_to.clone())
+                .collect()
+        };
+        let parents_before = parents_of(&self.nodes);
+        let edges_before = self.edges.len();
+        self.nodes.retain(|node| !removed.contains(&node.id));
+        self.remove_dangling_edges();
+        let parents_after = parents_of(&self.nodes);
+
+        let mut emptied_partitions = 0;
+        for node in self.nodes.iter_mut() {
+            if node.is_partition
+                && parents_before.contains(&node.id)
+                && !parents_after.contains(&node.id)
+            {
+                node.set_attribute("empty", serde_json::Value::Bool(true));
+                emptied_partitions += 1;
+            }
+        }
+
+        DegreeFilterSummary {
+            removed_nodes: removed.len(),
+            removed_edges: edges_before - self.edges.len(),
+            emptied_partitions,
+        }
+    }
+
+    /// Reduce the edges to a minimum (or, with `maximize`, maximum) spanning
+    /// forest of the flow nodes using Kruskal over the undirected projection.
+    /// Edges are weighted by the numeric attribute `weight_attr`, or by their
+    /// `weight` when none is given. Self-loops and edges touching partition
+    /// nodes are removed; all nodes are kept.
+    pub fn minimum_spanning_tree(
+        &mut self,
+        weight_attr: Option<&str>,
+        maximize: bool,
+    ) -> Result<SpanningTreeSummary, String> {
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for node in self.nodes.iter().filter(|n| !n.is_partition) {
+            let next = index.len();
+            index.entry(node.id.as_str()).or_insert(next);
+        }
+
+        let mut candidates: Vec<(usize, usize, usize, f64)> = Vec::new();
+        for (idx, edge) in self.edges.iter().enumerate() {
+            let (Some(&a), Some(&b)) = (
+                index.get(edge.source.as_str()),
+                index.get(edge.target.as_str()),
+            ) else {
+                continue;
+            };
+            if a == b {
+                continue;
+            }
+            let weight = match weight_attr {
+                None => edge.weight as f64,
+                Some(key) => edge
+                    .attributes
+                    .as_ref()
+                    .and_then(|attrs| attrs.get(key))
+                    .and_then(|value| match value {
+                        serde_json::Value::Number(n) => n.as_f64(),
+                        serde_json::Value::String(s) => s.trim().parse().ok(),
+                        _ => None,
+                    })
+                    .ok_or_else(|| {
+                        format!("Edge '{}' has no numeric '{}' attribute", edge.id, key)
+                    })?,
+            };
+            candidates.push((idx, a, b, weight));
+        }
+        // Stable sort keeps edge order as the tie-break between equal weights
+        candidates.sort_by(|x, y| {
+            let order = x.3.total_cmp(&y.3);
+            if maximize {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+
+        let mut parent: Vec<usize> = (0..index.len()).collect();
+        let mut components = index.len();
+        let mut tree_edges: HashSet<usize> = HashSet::new();
+        let mut total_weight = 0.0;
+        for (idx, a, b, weight) in candidates {
+            let (root_a, root_b) = (
+                union_find_root(&mut parent, a),
+                union_find_root(&mut parent, b),
+            );
+            if root_a != root_b {
+                parent[root_a] = root_b;
+                components -= 1;
+                tree_edges.insert(idx);
+                total_weight += weight;
+            }
+        }
+
+        let edges_before = self.edges.len();
+        let mut idx = 0;
+        self.edges.retain(|_| {
+            idx += 1;
+            tree_edges.contains(&(idx - 1))
+        });
+
+        Ok(SpanningTreeSummary {
+            components,
+            tree_edges: self.edges.len(),
+            removed_edges: edges_before - self.edges.len(),
+            total_weight,
+        })
+    }
+
+    /// Rewrite every node id under `strategy`, updating edge endpoints and
+    /// `belongs_to` references to match. `prefix` is required for
+    /// `NodeIdStrategy::Prefixed` and ignored otherwise. Returns the old -> new
+    /// mapping in node order.
+    pub fn normalize_node_ids(
+        &mut self,
+        strategy: NodeIdStrategy,
+        prefix: Option<&str>,
+    ) -> Result<Vec<(String, String)>, String> {
+        fn slugify(id: &str) -> String {
+            let mut slug = String::with_capacity(id.len());
+            for ch in id.chars() {
+                if ch.is_ascii_alphanumeric() {
+                    slug.push(ch.to_ascii_lowercase());
+                } else if !slug.ends_with('_') {
+                    slug.push('_');
+                }
+            }
+            let slug = slug.trim_matches('_');
+            if slug.is_empty() {
+                "node".to_string()
+            } else {
+                slug.to_string()
+            }
+        }
+
+        let prefix = match strategy {
+            NodeIdStrategy::Prefixed => match prefix.filter(|p| !p.is_empty()) {
+                Some(prefix) => prefix,
+                None => return Err("Prefixed id normalisation requires a prefix".to_string()),
+            },
+            _ => "",
+        };
+
+        let mut taken: HashSet<String> = HashSet::new();
+        let mut renames: HashMap<String, String> = HashMap::new();
+        let mut mapping = Vec::with_capacity(self.nodes.len());
+        for (idx, node) in self.nodes.iter().enumerate() {
+            if let Some(new_id) = renames.get(&node.id) {
+                // Duplicate ids stay merged under a single new id.
+                mapping.push((node.id.clone(), new_id.clone()));
+                continue;
+            }
+            let new_id = match strategy {
+                NodeIdStrategy::Slugify => {
+                    let base = slugify(&node.id);
+                    let mut candidate = base.clone();
+                    let mut suffix = 2;
+                    while taken.contains(&candidate) {
+                        candidate = format!("{}_{}", base, suffix);
+                        suffix += 1;
+                    }
+                    candidate
+                }
+                NodeIdStrategy::Sequential => format!("n{}", idx + 1),
+                NodeIdStrategy::Prefixed => format!("{}{}", prefix, node.id),
+            };
+            taken.insert(new_id.clone());
+            renames.insert(node.id.clone(), new_id.clone());
+            mapping.push((node.id.clone(), new_id));
+        }
+
+        for node in self.nodes.iter_mut() {
+            if let Some(new_id) = renames.get(&node.id) {
+                node.id = new_id.clone();
+            }
+            if let Some(parent) = node.belongs_to.as_mut() {
+                if let Some(new_id) = renames.get(parent.as_str()) {
+                    *parent = new_id.clone();
+                }
+            }
+        }
+        for edge in self.edges.iter_mut() {
+            if let Some(new_id) = renames.get(&edge.source) {
+                edge.source = new_id.clone();
+            }
+            if let Some(new_id) = renames.get(&edge.target) {
+                edge.target = new_id.clone();
+            }
+        }
+
+        Ok(mapping)
+    }
+
+    /// Cheapest directed path from `source` to `target`, or `None` when the
+    /// target is unreachable. Unweighted searches treat every edge as cost 1
+    /// and use BFS; weighted searches use the edge `weight`, which must not be
+    /// negative.
+    pub fn shortest_path(
+        &self,
+        source: &str,
+        target: &str,
+        algorithm: PathAlgorithm,
+        weighted: bool,
+    ) -> Result<Option<ShortestPath>, String> {
+        for endpoint in [source, target] {
+            if self.get_node_by_id(endpoint).is_none() {
+                return Err(format!("Node '{}' not found in graph", endpoint));
+            }
+        }
+        if weighted {
+            if let Some(edge) = self.edges.iter().find(|edge| edge.weight < 0) {
+                return Err(format!(
+                    "Edge '{}' has negative weight {}",
+                    edge.id, edge.weight
+                ));
+            }
+        }
+
+        let mut outgoing: HashMap<&str, Vec<(&str, i64)>> = HashMap::new();
+        for edge in &self.edges {
+            let cost = if weighted { edge.weight as i64 } else { 1 };
+            outgoing
+                .entry(edge.source.as_str())
+                .or_default()
+                .push((edge.target.as_str(), cost));
+        }
+
+        let mut previous: HashMap<&str, &str> = HashMap::new();
+        let cost = if !weighted {
+            let mut hops: HashMap<&str, i64> = HashMap::from([(source, 0)]);
+            let mut queue = VecDeque::from([source]);
+            while let Some(current) = queue.pop_front() {
+                if current == target {
+                    break;
+                }
+                for &(next, _) in outgoing.get(current).into_iter().flatten() {
+                    if !hops.contains_key(next) {
+                        hops.insert(next, hops[current] + 1);
+                        previous.insert(next, current);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            hops.get(target).copied()
+        } else {
+            // Every remaining hop costs at least the lightest edge, so hop counts
+            // to the target scaled by it never overestimate.
+            let heuristic: HashMap<&str, i64> = match algorithm {
+                PathAlgorithm::Dijkstra => HashMap::new(),
+                PathAlgorithm::AStar => {
+                    let min_weight = self.edges.iter().map(|e| e.weight as i64).min();
+                    let mut incoming: HashMap<&str, Vec<&str>> = HashMap::new();
+                    for edge in &self.edges {
+                        incoming
+                            .entry(edge.target.as_str())
+                            .or_default()
+                            .push(edge.source.as_str());
+                    }
+                    let mut hops: HashMap<&str, i64> = HashMap::from([(target, 0)]);
+                    let mut queue = VecDeque::from([target]);
+                    while let Some(current) = queue.pop_front() {
+                        for &prev in incoming.get(current).into_iter().flatten() {
+                            if !hops.contains_key(prev) {
+                                hops.insert(prev, hops[current] + 1);
+                                queue.push_back(prev);
+                            }
+                        }
+                    }
+                    hops.into_iter()
+                        .map(|(id, h)| (id, h * min_weight.unwrap_or(0)))
+                        .collect()
+                }
+            };
+            let estimate = |id: &str| heuristic.get(id).copied().unwrap_or(0);
+
+            let mut best: HashMap<&str, i64> = HashMap::from([(source, 0)]);
+            let mut settled: HashSet<&str> = HashSet::new();
+            let mut heap = BinaryHeap::from([Reverse((estimate(source), 0i64, source))]);
+            while let Some(Reverse((_, cost, current))) = heap.pop() {
+                if current == target {
+                    break;
+                }
+                if !settled.insert(current) {
+                    continue;
+                }
+                for &(next, weight) in outgoing.get(current).into_iter().flatten() {
+                    let candidate = cost + weight;
+                    if best.get(next).is_none_or(|&known| candidate < known) {
+                        best.insert(next, candidate);
+                        previous.insert(next, current);
+                        heap.push(Reverse((candidate + estimate(next), candidate, next)));
+                    }
+                }
+            }
+            best.get(target).copied()
+        };
+
+        Ok(cost.map(|cost| {
+            let mut path = vec![target.to_string()];
+            let mut current = target;
+            while current != source {
+                current = previous[current];
+                path.push(current.to_string());
+            }
+            path.reverse();
+            ShortestPath { path, cost }
+        }))
+    }
 
-                return Ok(Some(LayerAggregationSummary {
-                    layer_id,
-                    belongs_to,
-                    anchor_node_id: anchor_id,
-                    anchor_node_label: anchor_label,
-                    aggregate_node_id: aggregate_node.id,
-                    aggregate_node_label: aggregate_node.label,
-                    aggregated_nodes: aggregated_pairs,
-                }));
+    /// Edges running directly between `layer_a` and `layer_b`, plus the
+    /// fewest-hop path from any `layer_a` node to any `layer_b` node. The path
+    /// ignores edge direction and may pass through other layers. Both layers
+    /// must differ and contain at least one node.
+    pub fn layer_connectivity(
+        &self,
+        layer_a: &str,
+        layer_b: &str,
+    ) -> Result<LayerConnectivity, String> {
+        if layer_a == layer_b {
+            return Err(format!("Layers to connect must differ, got '{}'", layer_a));
+        }
+        for layer in [layer_a, layer_b] {
+            if !self.nodes.iter().any(|node| node.layer == layer) {
+                return Err(format!("Layer '{}' has no nodes in graph", layer));
             }
         }
 
-        Ok(None)
-    }
+        let layer_of: HashMap<&str, &str> = self
+            .nodes
+            .iter()
+            .map(|node| (node.id.as_str(), node.layer.as_str()))
+            .collect();
+        let direct_edges = self
+            .edges
+            .iter()
+            .filter(|edge| {
+                let source = layer_of.get(edge.source.as_str()).copied();
+                let target = layer_of.get(edge.target.as_str()).copied();
+                matches!(
+                    (source, target),
+                    (Some(s), Some(t)) if (s == layer_a && t == layer_b) || (s == layer_b && t == layer_a)
+                )
+            })
+            .cloned()
+            .collect();
 
-    // Aggregate duplicate edges
-    pub fn aggregate_edges(&mut self) {
-        let mut edge_map: HashMap<String, Edge> = HashMap::new();
+        let mut neighbours: HashMap<&str, Vec<&str>> = HashMap::new();
         for edge in &self.edges {
-            let key = format!("{}_{}", edge.source, edge.target);
-            if let Some(existing_edge) = edge_map.get_mut(&key) {
-                existing_edge.weight += edge.weight;
-            } else {
-                edge_map.insert(key, edge.clone());
+            neighbours
+                .entry(edge.source.as_str())
+                .or_default()
+                .push(edge.target.as_str());
+            neighbours
+                .entry(edge.target.as_str())
+                .or_default()
+                .push(edge.source.as_str());
+        }
+
+        // Multi-source BFS seeded with every `layer_a` node; the first
+        // `layer_b` node dequeued ends the fewest-hop path.
+        let mut queue: VecDeque<&str> = self
+            .nodes
+            .iter()
+            .filter(|node| node.layer == layer_a)
+            .map(|node| node.id.as_str())
+            .collect();
+        let mut visited: HashSet<&str> = queue.iter().copied().collect();
+        let mut previous: HashMap<&str, &str> = HashMap::new();
+        let mut reached = None;
+        while let Some(current) = queue.pop_front() {
+            if layer_of.get(current) == Some(&layer_b) {
+                reached = Some(current);
+                break;
+            }
+            for &next in neighbours.get(current).into_iter().flatten() {
+                if visited.insert(next) {
+                    previous.insert(next, current);
+                    queue.push_back(next);
+                }
             }
         }
-        debug!(
-            "Aggregated {} edges to {}",
-            self.edges.len(),
-            edge_map.len()
-        );
-        self.edges = edge_map.values().cloned().collect();
+
+        let path = reached.map(|end| {
+            let mut path = vec![end.to_string()];
+            let mut current = end;
+            while let Some(&prev) = previous.get(current) {
+                current = prev;
+                path.push(current.to_string());
+            }
+            path.reverse();
+            path
+        });
+
+        Ok(LayerConnectivity { direct_edges, path })
     }
 
     /// Generate hierarchy edges from belongs_to relationships.
@@ -1445,6 +3051,199 @@ impl Graph {
         }
     }
 
+    /// Return a copy of the graph restricted to nodes in the given layers.
+    ///
+    /// Edges survive only when both endpoints survive, and layers left without
+    /// nodes or edges are dropped. A partition outside the filter is kept when it
+    /// has children and all of them survive; surviving nodes whose parent was
+    /// dropped are re-attached to their nearest surviving ancestor.
+    pub fn filter_by_layers(&self, layers: &HashSet<String>) -> Graph {
+        let mut kept: HashSet<&str> = self
+            .nodes
+            .iter()
+            .filter(|node| layers.contains(&node.layer))
+            .map(|node| node.id.as_str())
+            .collect();
+
+        let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+        for node in &self.nodes {
+            if let Some(parent) = node.belongs_to.as_deref().filter(|p| !p.is_empty()) {
+                children.entry(parent).or_default().push(node.id.as_str());
+            }
+        }
+
+        loop {
+            let promoted: Vec<&str> = self
+                .nodes
+                .iter()
+                .filter(|node| node.is_partition && !kept.contains(node.id.as_str()))
+                .filter(|node| {
+                    children
+                        .get(node.id.as_str())
+                        .is_some_and(|ids| ids.iter().all(|id| kept.contains(id)))
+                })
+                .map(|node| node.id.as_str())
+                .collect();
+            if promoted.is_empty() {
+                break;
+            }
+            kept.extend(promoted);
+        }
+
+        let parent_of: HashMap<&str, &str> = self
+            .nodes
+            .iter()
+            .filter_map(|node| {
+                node.belongs_to
+                    .as_deref()
+                    .filter(|p| !p.is_empty())
+                    .map(|p| (node.id.as_str(), p))
+            })
+            .collect();
+        let nearest_kept_ancestor = |id: &str| -> Option<String> {
+            let mut visited = HashSet::new();
+            let mut current = parent_of.get(id).copied();
+            while let Some(parent) = current {
+                if !visited.insert(parent) {
+                    return None;
+                }
+                if kept.contains(parent) {
+                    return Some(parent.to_string());
+                }
+                current = parent_of.get(parent).copied();
+            }
+            None
+        };
+
+        let nodes: Vec<Node> = self
+            .nodes
+            .iter()
+            .filter(|node| kept.contains(node.id.as_str()))
+            .map(|node| {
+                let mut node = node.clone();
+                if node
+                    .belongs_to
+                    .as_deref()
+                    .is_some_and(|p| !p.is_empty() && !kept.contains(p))
+                {
+                    node.belongs_to = nearest_kept_ancestor(&node.id);
+                }
+                node
+            })
+            .collect();
+        let edges: Vec<Edge> = self
+            .edges
+            .iter()
+            .filter(|edge| {
+                kept.contains(edge.source.as_str()) && kept.contains(edge.target.as_str())
+            })
+            .cloned()
+            .collect();
+
+        Graph {
+            name: self.name.clone(),
+            layers: self.layers_used_by(&nodes, &edges),
+            nodes,
+            edges,
+            annotations: self.annotations.clone(),
+        }
+    }
+
+    /// Split into one graph per connected component, treating edges as undirected.
+    /// `belongs_to` links also connect nodes so partitions stay with their children.
+    /// Components are ordered by the first appearance of their nodes.
+    pub fn split_by_component(&self) -> Vec<Graph> {
+        let index: HashMap<&str, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| (node.id.as_str(), idx))
+            .collect();
+
+        let mut neighbours: Vec<Vec<usize>> = vec![Vec::new(); self.nodes.len()];
+        let links = self
+            .edges
+            .iter()
+            .map(|edge| (edge.source.as_str(), edge.target.as_str()))
+            .chain(self.nodes.iter().filter_map(|node| {
+                node.belongs_to
+                    .as_deref()
+                    .map(|parent| (node.id.as_str(), parent))
+            }));
+        for (a, b) in links {
+            if let (Some(&a), Some(&b)) = (index.get(a), index.get(b)) {
+                neighbours[a].push(b);
+                neighbours[b].push(a);
+            }
+        }
+
+        let mut component_of: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut count = 0;
+        for start in 0..self.nodes.len() {
+            if component_of[start].is_some() {
+                continue;
+            }
+            component_of[start] = Some(count);
+            let mut stack = vec![start];
+            while let Some(current) = stack.pop() {
+                for &next in &neighbours[current] {
+                    if component_of[next].is_none() {
+                        component_of[next] = Some(count);
+                        stack.push(next);
+                    }
+                }
+            }
+            count += 1;
+        }
+
+        let mut nodes: Vec<Vec<Node>> = vec![Vec::new(); count];
+        for (node, component) in self.nodes.iter().zip(&component_of) {
+            if let Some(component) = component {
+                nodes[*component].push(node.clone());
+            }
+        }
+        let mut edges: Vec<Vec<Edge>> = vec![Vec::new(); count];
+        for edge in &self.edges {
+            if let (Some(&source), Some(&target)) = (
+                index.get(edge.source.as_str()),
+                index.get(edge.target.as_str()),
+            ) {
+                if component_of[source] == component_of[target] {
+                    if let Some(component) = component_of[source] {
+                        edges[component].push(edge.clone());
+                    }
+                }
+            }
+        }
+
+        nodes
+            .into_iter()
+            .zip(edges)
+            .enumerate()
+            .map(|(idx, (nodes, edges))| Graph {
+                name: format!("{} (component {})", self.name, idx + 1),
+                layers: self.layers_used_by(&nodes, &edges),
+                nodes,
+                edges,
+                annotations: self.annotations.clone(),
+            })
+            .collect()
+    }
+
+    /// This graph's layers that are referenced by `nodes` or `edges`, in order.
+    fn layers_used_by(&self, nodes: &[Node], edges: &[Edge]) -> Vec<Layer> {
+        let used_layers: HashSet<&str> = nodes
+            .iter()
+            .map(|node| node.layer.as_str())
+            .chain(edges.iter().map(|edge| edge.layer.as_str()))
+            .collect();
+        self.layers
+            .iter()
+            .filter(|layer| used_layers.contains(layer.id.as_str()))
+            .cloned()
+            .collect()
+    }
+
     pub fn invert_graph(&mut self) -> Result<Graph, String> {
         /*
          * Invert the graph
@@ -1731,6 +3530,10 @@ impl Graph {
             }
         }
 
+        for warning in self.edge_integrity_warnings() {
+            warn!("{}", warning);
+        }
+
         // Log informative message about partition structure
         if !self.has_partition_structure() {
             tracing::info!("Graph has no partition structure - hierarchy features will be unavailable. Add 'is_partition' nodes and 'belongs_to' relationships to use hierarchy features.");
@@ -1742,6 +3545,70 @@ impl Graph {
             Err(errors)
         }
     }
+
+    /// Non-fatal edge problems that trip up some exporters: self-loops and
+    /// duplicate edges sharing the same (source, target, layer).
+    pub fn edge_integrity_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let mut first_seen: HashMap<(&str, &str, &str), &str> = HashMap::new();
+        for edge in &self.edges {
+            if edge.source == edge.target {
+                warnings.push(format!(
+                    "Edge id:[{}] is a self-loop on node {:?}",
+                    edge.id, edge.source
+                ));
+            }
+            let key = (
+                edge.source.as_str(),
+                edge.target.as_str(),
+                edge.layer.as_str(),
+            );
+            match first_seen.get(&key) {
+                Some(first) => warnings.push(format!(
+                    "Edge id:[{}] duplicates edge id:[{}] ({:?} -> {:?} in layer {:?})",
+                    edge.id, first, edge.source, edge.target, edge.layer
+                )),
+                None => {
+                    first_seen.insert(key, edge.id.as_str());
+                }
+            }
+        }
+        warnings
+    }
+
+    /// A copy of the graph without edges whose source is their own target.
+    pub fn remove_self_loops(&self) -> Graph {
+        let mut graph = self.clone();
+        graph.edges.retain(|edge| edge.source != edge.target);
+        graph
+    }
+
+    /// A copy of the graph with duplicate (source, target, layer) edges merged
+    /// into their first occurrence, summing weights. Edge order is preserved.
+    ///
+    /// Unlike `aggregate_edges` this keeps edges on different layers apart.
+    pub fn dedupe_edges(&self) -> Graph {
+        let mut merged: IndexMap<(&str, &str, &str), Edge> = IndexMap::new();
+        for edge in &self.edges {
+            let key = (
+                edge.source.as_str(),
+                edge.target.as_str(),
+                edge.layer.as_str(),
+            );
+            merged
+                .entry(key)
+                .and_modify(|existing| existing.weight += edge.weight)
+                .or_insert_with(|| edge.clone());
+        }
+
+        Graph {
+            name: self.name.clone(),
+            nodes: self.nodes.clone(),
+            edges: merged.into_values().collect(),
+            layers: self.layers.clone(),
+            annotations: self.annotations.clone(),
+        }
+    }
 }
 
 // Helper function to deserialize booleans that might be strings
@@ -1924,7 +3791,29 @@ fn get_stripped_value(
     Ok(strip_quotes_and_whitespace(value).to_string())
 }
 
+/// Insert `key` into an attribute map, creating the map when absent.
+/// Returns true when the stored value changed.
+fn set_attribute_value(
+    attributes: &mut Option<serde_json::Value>,
+    key: &str,
+    value: serde_json::Value,
+) -> bool {
+    let mut map = match attributes.take() {
+        Some(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    let changed = map.get(key) != Some(&value);
+    map.insert(key.to_string(), value);
+    *attributes = Some(serde_json::Value::Object(map));
+    changed
+}
+
 impl Node {
+    /// Set a single attribute on the node. Returns true when the value changed.
+    pub fn set_attribute(&mut self, key: &str, value: serde_json::Value) -> bool {
+        set_attribute_value(&mut self.attributes, key, value)
+    }
+
     pub fn from_row(
         record: &StringRecord,
         node_profile: &DfNodeLoadProfile,
@@ -1975,6 +3864,11 @@ impl Node {
 }
 
 impl Edge {
+    /// Set a single attribute on the edge. Returns true when the value changed.
+    pub fn set_attribute(&mut self, key: &str, value: serde_json::Value) -> bool {
+        set_attribute_value(&mut self.attributes, key, value)
+    }
+
     pub fn from_row(
         record: &StringRecord,
         edge_profile: &DfEdgeLoadProfile,
@@ -2006,7 +3900,17 @@ impl Edge {
                 }
             },
             dataset: None,
-            attributes: None,
+            attributes: {
+                let attributes: serde_json::Map<String, serde_json::Value> = edge_profile
+                    .attribute_columns
+                    .iter()
+                    .filter_map(|(idx, key)| {
+                        let value = record.get(*idx).filter(|value| !value.is_empty())?;
+                        Some((key.clone(), crate::data_loader::parse_attribute_cell(value)))
+                    })
+                    .collect();
+                (!attributes.is_empty()).then_some(serde_json::Value::Object(attributes))
+            },
         })
     }
 }
@@ -2288,6 +4192,228 @@ mod tests {
         }
     }
 
+    /// Node labelled with its id, weight 1.
+    fn make_node(id: &str, layer: &str) -> Node {
+        Node {
+            id: id.to_string(),
+            label: id.to_string(),
+            layer: layer.to_string(),
+            weight: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Unlabelled edge with weight 1.
+    fn make_edge(id: &str, source: &str, target: &str, layer: &str) -> Edge {
+        Edge {
+            id: id.to_string(),
+            source: source.to_string(),
+            target: target.to_string(),
+            layer: layer.to_string(),
+            weight: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_by_layers() {
+        let mut nodes = vec![
+            make_node("root", "infra"),
+            make_node("group", "infra"),
+            make_node("a", "app"),
+            make_node("b", "app"),
+            make_node("mixed", "infra"),
+            make_node("c", "app"),
+            make_node("d", "db"),
+        ];
+        let parents = [
+            None,
+            Some("root"),
+            Some("group"),
+            Some("group"),
+            Some("root"),
+            Some("mixed"),
+            Some("mixed"),
+        ];
+        // Every infra node is a partition.
+        for (node, parent) in nodes.iter_mut().zip(parents) {
+            node.is_partition = node.layer == "infra";
+            node.belongs_to = parent.map(str::to_string);
+        }
+        let graph = Graph {
+            name: "Filter".to_string(),
+            nodes,
+            edges: vec![
+                make_edge("e1", "a", "b", "app"),
+                make_edge("e2", "b", "c", "app"),
+                make_edge("e3", "c", "d", "db"),
+            ],
+            layers: vec![
+                Layer::new("infra", "Infra", "ffffff", "000000", "000000"),
+                Layer::new("app", "App", "ffffff", "000000", "000000"),
+                Layer::new("db", "DB", "ffffff", "000000", "000000"),
+            ],
+            annotations: None,
+        };
+
+        let filtered = graph.filter_by_layers(&HashSet::from(["app".to_string()]));
+
+        let ids: Vec<&str> = filtered.nodes.iter().map(|n| n.id.as_str()).collect();
+        // `group` has all children retained; `mixed` loses `d`, so it and `root` go.
+        assert_eq!(ids, vec!["group", "a", "b", "c"]);
+        let c = filtered.get_node_by_id("c").unwrap();
+        assert_eq!(c.belongs_to, None);
+        let edge_ids: Vec<&str> = filtered.edges.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(edge_ids, vec!["e1", "e2"]);
+        let layer_ids: Vec<&str> = filtered.layers.iter().map(|l| l.id.as_str()).collect();
+        assert_eq!(layer_ids, vec!["infra", "app"]);
+    }
+
+    #[test]
+    fn test_split_by_component() {
+        let graph = Graph {
+            name: "Islands".to_string(),
+            nodes: vec![
+                make_node("a", "app"),
+                make_node("x", "db"),
+                make_node("b", "app"),
+                make_node("y", "db"),
+                make_node("c", "app"),
+            ],
+            // a - b - c on one island, y -> x on the other
+            edges: vec![
+                make_edge("e1", "a", "b", "app"),
+                make_edge("e2", "y", "x", "app"),
+                make_edge("e3", "c", "b", "app"),
+            ],
+            layers: vec![
+                Layer::new("app", "App", "ffffff", "000000", "000000"),
+                Layer::new("db", "DB", "ffffff", "000000", "000000"),
+            ],
+            annotations: None,
+        };
+
+        let parts = graph.split_by_component();
+        assert_eq!(parts.len(), 2);
+
+        let ids = |g: &Graph| g.nodes.iter().map(|n| n.id.clone()).collect::<Vec<_>>();
+        let edge_ids = |g: &Graph| g.edges.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&parts[0]), vec!["a", "b", "c"]);
+        assert_eq!(edge_ids(&parts[0]), vec!["e1", "e3"]);
+        assert_eq!(ids(&parts[1]), vec!["x", "y"]);
+        assert_eq!(edge_ids(&parts[1]), vec!["e2"]);
+        assert_eq!(parts[0].name, "Islands (component 1)");
+        // Each part keeps only the layers it uses.
+        assert_eq!(parts[0].layers.len(), 1);
+        assert_eq!(parts[1].layers.len(), 2);
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        // a -> d directly is one hop but heavy; a -> b -> c -> d is light.
+        let graph = Graph {
+            name: "Paths".to_string(),
+            nodes: ["a", "b", "c", "d", "lonely"]
+                .map(|id| make_node(id, "app"))
+                .to_vec(),
+            edges: [("a", "d", 10), ("a", "b", 1), ("b", "c", 2), ("c", "d", 3)]
+                .map(|(source, target, weight)| Edge {
+                    weight,
+                    ..make_edge(&format!("{source}_{target}"), source, target, "app")
+                })
+                .to_vec(),
+            layers: vec![Layer::new("app", "App", "ffffff", "000000", "000000")],
+            annotations: None,
+        };
+
+        let hops = graph
+            .shortest_path("a", "d", PathAlgorithm::Dijkstra, false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(hops.path, vec!["a", "d"]);
+        assert_eq!(hops.cost, 1);
+
+        for algorithm in [PathAlgorithm::Dijkstra, PathAlgorithm::AStar] {
+            let weighted = graph
+                .shortest_path("a", "d", algorithm, true)
+                .unwrap()
+                .unwrap();
+            assert_eq!(weighted.path, vec!["a", "b", "c", "d"]);
+            assert_eq!(weighted.cost, 6);
+        }
+
+        let same = graph
+            .shortest_path("b", "b", PathAlgorithm::AStar, true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(same.path, vec!["b"]);
+        assert_eq!(same.cost, 0);
+
+        // Edges are directed, so nothing leads back to `a`.
+        assert!(graph
+            .shortest_path("d", "a", PathAlgorithm::Dijkstra, true)
+            .unwrap()
+            .is_none());
+        assert!(graph
+            .shortest_path("a", "lonely", PathAlgorithm::Dijkstra, false)
+            .unwrap()
+            .is_none());
+        assert!(graph
+            .shortest_path("a", "missing", PathAlgorithm::Dijkstra, false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_layer_connectivity() {
+        // ui talks to api directly; ui only reaches db through api.
+        let graph = Graph {
+            name: "Tiers".to_string(),
+            nodes: vec![
+                make_node("web", "ui"),
+                make_node("mobile", "ui"),
+                make_node("gateway", "api"),
+                make_node("orders", "api"),
+                make_node("store", "db"),
+                make_node("archive", "cold"),
+            ],
+            edges: vec![
+                make_edge("web_gateway", "web", "gateway", "flow"),
+                make_edge("mobile_gateway", "mobile", "gateway", "flow"),
+                make_edge("gateway_orders", "gateway", "orders", "flow"),
+                make_edge("store_orders", "store", "orders", "flow"),
+            ],
+            layers: Vec::new(),
+            annotations: None,
+        };
+
+        let direct = graph.layer_connectivity("ui", "api").unwrap();
+        let ids: Vec<_> = direct.direct_edges.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["web_gateway", "mobile_gateway"]);
+        assert_eq!(
+            direct.path,
+            Some(vec!["web".to_string(), "gateway".to_string()])
+        );
+
+        // No ui-db edge, but a three-hop path against the store -> orders edge.
+        let indirect = graph.layer_connectivity("ui", "db").unwrap();
+        assert!(indirect.direct_edges.is_empty());
+        assert_eq!(
+            indirect.path,
+            Some(
+                ["web", "gateway", "orders", "store"]
+                    .map(String::from)
+                    .to_vec()
+            )
+        );
+
+        let isolated = graph.layer_connectivity("ui", "cold").unwrap();
+        assert!(isolated.direct_edges.is_empty());
+        assert_eq!(isolated.path, None);
+
+        assert!(graph.layer_connectivity("ui", "ui").is_err());
+        assert!(graph.layer_connectivity("ui", "missing").is_err());
+    }
+
     #[test]
     fn test_get_root_nodes() {
         let graph = create_test_graph();
@@ -3137,6 +5263,33 @@ mod tests {
         assert_eq!(graph.edges[0].weight, 6); // Sum of all weights (1+2+3)
     }
 
+    #[test]
+    fn test_self_loops_and_duplicate_edges_are_warned_and_fixable() {
+        let mut graph = create_test_graph();
+        graph.edges.push(Edge {
+            weight: 4,
+            ..make_edge("dup", "2", "3", "Layer1")
+        });
+        graph.edges.push(make_edge("loop", "3", "3", "Layer1"));
+
+        // Warnings only: the graph still verifies.
+        assert!(graph.verify_graph_integrity().is_ok());
+        let warnings = graph.edge_integrity_warnings();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("id:[dup] duplicates edge id:[e2]"));
+        assert!(warnings[1].contains("id:[loop] is a self-loop"));
+
+        let cleaned = graph.remove_self_loops().dedupe_edges();
+        assert!(cleaned.edge_integrity_warnings().is_empty());
+        assert_eq!(cleaned.edges.len(), 1);
+        assert_eq!(cleaned.edges[0].id, "e2");
+        assert_eq!(cleaned.edges[0].weight, 5);
+
+        // Same endpoints on another layer are not duplicates.
+        graph.edges.push(make_edge("e3", "2", "3", "Layer2"));
+        assert_eq!(graph.dedupe_edges().edges.len(), 3);
+    }
+
     #[test]
     fn test_verify_graph_integrity() {
         // Create a valid graph
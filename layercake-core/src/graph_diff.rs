@@ -2,18 +2,41 @@
 //!
 //! Answers "what did the merge/transform do?" — the added/removed/changed nodes
 //! and edges between a `from` and a `to` graph, keyed by id. Change detection
-//! compares the full serialised item, so any field difference (label, layer,
-//! weight, attrs…) counts as a change.
+//! compares the full serialised item by default, so any field difference
+//! (label, layer, weight, attrs…) counts as a change; [`NodeComparison`] can
+//! narrow that for nodes. Everything is matched through hash maps, so the diff
+//! is linear in the graph size and ignores item order.
 
 use crate::graph::{Edge, Graph, Node};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct GraphDiff {
     pub nodes: ItemDiff,
     pub edges: ItemDiff,
+    /// Source/target pairs connected in `to` but not in `from`.
+    pub added_edge_pairs: Vec<EdgePair>,
+    /// Source/target pairs connected in `from` but not in `to`.
+    pub removed_edge_pairs: Vec<EdgePair>,
+    /// Nodes present in both graphs whose layer was reassigned.
+    pub layer_changes: Vec<LayerChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgePair {
+    pub source: String,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayerChange {
+    pub node_id: String,
+    pub from: String,
+    pub to: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -37,9 +60,22 @@ impl GraphDiff {
             && self.edges.added.is_empty()
             && self.edges.removed.is_empty()
             && self.edges.changed.is_empty()
+            && self.added_edge_pairs.is_empty()
+            && self.removed_edge_pairs.is_empty()
+            && self.layer_changes.is_empty()
     }
 }
 
+/// Which node fields decide whether a node present in both graphs changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NodeComparison {
+    /// Every serialised field, including weight, belongs_to and comment.
+    #[default]
+    Full,
+    /// Only label, layer and attributes.
+    Content,
+}
+
 /// Diff two graphs parsed from their JSON representations.
 pub fn diff_graph_json(from_json: &str, to_json: &str) -> Result<GraphDiff, serde_json::Error> {
     let from: Graph = serde_json::from_str(from_json)?;
@@ -48,9 +84,22 @@ pub fn diff_graph_json(from_json: &str, to_json: &str) -> Result<GraphDiff, serd
 }
 
 pub fn diff_graphs(from: &Graph, to: &Graph) -> GraphDiff {
+    diff_graphs_with(from, to, NodeComparison::Full)
+}
+
+/// Like [`diff_graphs`], comparing nodes by the fields `comparison` selects.
+pub fn diff_graphs_with(from: &Graph, to: &Graph, comparison: NodeComparison) -> GraphDiff {
+    let (added_edge_pairs, removed_edge_pairs) = diff_edge_pairs(&from.edges, &to.edges);
+    let nodes = match comparison {
+        NodeComparison::Full => diff_items(&from.nodes, &to.nodes, node_id, serialised),
+        NodeComparison::Content => diff_items(&from.nodes, &to.nodes, node_id, node_content),
+    };
     GraphDiff {
-        nodes: diff_items(&from.nodes, &to.nodes, node_id),
-        edges: diff_items(&from.edges, &to.edges, edge_id),
+        nodes,
+        edges: diff_items(&from.edges, &to.edges, edge_id, serialised),
+        added_edge_pairs,
+        removed_edge_pairs,
+        layer_changes: diff_layers(&from.nodes, &to.nodes),
     }
 }
 
@@ -58,6 +107,19 @@ fn node_id(n: &Node) -> String {
     n.id.clone()
 }
 
+fn serialised<T: Serialize>(item: &T) -> serde_json::Value {
+    serde_json::to_value(item).unwrap_or_default()
+}
+
+/// The parts of a node compared under [`NodeComparison::Content`].
+fn node_content(n: &Node) -> serde_json::Value {
+    serde_json::json!({
+        "label": n.label,
+        "layer": n.layer,
+        "attributes": n.attributes,
+    })
+}
+
 fn edge_id(e: &Edge) -> String {
     if e.id.is_empty() {
         format!("{}:{}", e.source, e.target)
@@ -66,14 +128,18 @@ fn edge_id(e: &Edge) -> String {
     }
 }
 
-fn diff_items<T: Serialize, F: Fn(&T) -> String>(from: &[T], to: &[T], id_of: F) -> ItemDiff {
+fn diff_items<T, F, C>(from: &[T], to: &[T], id_of: F, content_of: C) -> ItemDiff
+where
+    F: Fn(&T) -> String,
+    C: Fn(&T) -> serde_json::Value,
+{
     let from_map: HashMap<String, serde_json::Value> = from
         .iter()
-        .map(|item| (id_of(item), serde_json::to_value(item).unwrap_or_default()))
+        .map(|item| (id_of(item), content_of(item)))
         .collect();
     let to_map: HashMap<String, serde_json::Value> = to
         .iter()
-        .map(|item| (id_of(item), serde_json::to_value(item).unwrap_or_default()))
+        .map(|item| (id_of(item), content_of(item)))
         .collect();
 
     let mut diff = ItemDiff::default();
@@ -95,6 +161,46 @@ fn diff_items<T: Serialize, F: Fn(&T) -> String>(from: &[T], to: &[T], id_of: F)
     diff
 }
 
+fn diff_edge_pairs(from: &[Edge], to: &[Edge]) -> (Vec<EdgePair>, Vec<EdgePair>) {
+    let pairs = |edges: &[Edge]| -> HashSet<EdgePair> {
+        edges
+            .iter()
+            .map(|e| EdgePair {
+                source: e.source.clone(),
+                target: e.target.clone(),
+            })
+            .collect()
+    };
+    let from_pairs = pairs(from);
+    let to_pairs = pairs(to);
+
+    let mut added: Vec<EdgePair> = to_pairs.difference(&from_pairs).cloned().collect();
+    let mut removed: Vec<EdgePair> = from_pairs.difference(&to_pairs).cloned().collect();
+    added.sort();
+    removed.sort();
+    (added, removed)
+}
+
+fn diff_layers(from: &[Node], to: &[Node]) -> Vec<LayerChange> {
+    let from_layers: HashMap<&str, &str> = from
+        .iter()
+        .map(|n| (n.id.as_str(), n.layer.as_str()))
+        .collect();
+    let mut changes: Vec<LayerChange> = to
+        .iter()
+        .filter_map(|n| {
+            let previous = *from_layers.get(n.id.as_str())?;
+            (previous != n.layer).then(|| LayerChange {
+                node_id: n.id.clone(),
+                from: previous.to_string(),
+                to: n.layer.clone(),
+            })
+        })
+        .collect();
+    changes.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+    changes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,4 +240,55 @@ mod tests {
         assert!(d.is_empty());
         assert_eq!(d.nodes.unchanged, 1);
     }
+
+    #[test]
+    fn reports_edge_pairs_and_layer_changes_regardless_of_order() {
+        let from = g(r#"{"nodes":[
+            {"id":"a","label":"A","layer":"l","weight":1},
+            {"id":"b","label":"B","layer":"l","weight":1},
+            {"id":"c","label":"C","layer":"l","weight":1}
+        ],"edges":[
+            {"id":"e1","source":"a","target":"b","label":"","layer":"l","weight":1},
+            {"id":"e2","source":"b","target":"c","label":"","layer":"l","weight":1}
+        ],"layers":[]}"#);
+        // Same graph stored in a different order, with c moved to layer m, a
+        // reweighted and b -> c rewired to c -> a.
+        let to = g(r#"{"nodes":[
+            {"id":"c","label":"C","layer":"m","weight":1},
+            {"id":"b","label":"B","layer":"l","weight":1},
+            {"id":"a","label":"A","layer":"l","weight":5}
+        ],"edges":[
+            {"id":"e3","source":"c","target":"a","label":"","layer":"l","weight":1},
+            {"id":"e1","source":"a","target":"b","label":"","layer":"l","weight":1}
+        ],"layers":[]}"#);
+
+        let full = diff_graph_json(&from, &to).unwrap();
+        assert_eq!(full.nodes.changed, vec!["a", "c"]);
+
+        let from: Graph = serde_json::from_str(&from).unwrap();
+        let to: Graph = serde_json::from_str(&to).unwrap();
+        let d = diff_graphs_with(&from, &to, NodeComparison::Content);
+        // Comparing content only, a's new weight is not a modification
+        assert_eq!(d.nodes.changed, vec!["c"]);
+        assert_eq!(d.nodes.unchanged, 2);
+        assert_eq!(
+            d.layer_changes,
+            vec![LayerChange {
+                node_id: "c".to_string(),
+                from: "l".to_string(),
+                to: "m".to_string(),
+            }]
+        );
+        let pair = |s: &str, t: &str| EdgePair {
+            source: s.to_string(),
+            target: t.to_string(),
+        };
+        assert_eq!(d.added_edge_pairs, vec![pair("c", "a")]);
+        assert_eq!(d.removed_edge_pairs, vec![pair("b", "c")]);
+
+        let mut reordered = to.clone();
+        reordered.nodes.reverse();
+        reordered.edges.reverse();
+        assert!(diff_graphs(&to, &reordered).is_empty());
+    }
 }
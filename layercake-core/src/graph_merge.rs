@@ -0,0 +1,357 @@
+//! Merge several dataset graphs into one, joining on node id.
+//!
+//! The strategy decides which node ids survive (union, intersection, or the
+//! ids of the first/last graph); colliding nodes and edges are combined field
+//! by field, with attributes unioned key by key. A collision only counts as a
+//! conflict when the two sides disagree on a value, and the conflict policy
+//! decides whether the first or last value wins or the merge is rejected.
+
+use crate::graph::{Edge, Graph, Layer, Node};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MergeStrategy {
+    /// Keep every node id seen in any graph.
+    Union,
+    /// Keep only node ids present in every graph.
+    Intersection,
+    /// Keep the node ids of the first graph.
+    Left,
+    /// Keep the node ids of the last graph.
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConflictResolution {
+    /// The value from the earliest graph wins.
+    PreferFirst,
+    /// The value from the latest graph wins.
+    PreferLast,
+    /// Reject the merge and report the conflicting ids.
+    Error,
+}
+
+/// Ids whose values disagreed between graphs under `ConflictResolution::Error`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphMergeConflict {
+    pub node_ids: Vec<String>,
+    pub edge_ids: Vec<String>,
+}
+
+impl std::fmt::Display for GraphMergeConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Merge conflicts on")?;
+        if !self.node_ids.is_empty() {
+            write!(f, " nodes [{}]", self.node_ids.join(", "))?;
+        }
+        if !self.edge_ids.is_empty() {
+            if !self.node_ids.is_empty() {
+                write!(f, " and")?;
+            }
+            write!(f, " edges [{}]", self.edge_ids.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GraphMergeConflict {}
+
+/// Parse a dataset's `graph_json`, tolerating the `{}` stored for empty datasets.
+pub fn graph_from_data_set_json(graph_json: &str) -> Result<Graph, serde_json::Error> {
+    let mut value: serde_json::Value = serde_json::from_str(graph_json)?;
+    if let Some(obj) = value.as_object_mut() {
+        for key in ["nodes", "edges", "layers"] {
+            obj.entry(key)
+                .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        }
+    }
+    serde_json::from_value(value)
+}
+
+/// Merge `graphs` in order. Edges are kept when both endpoints survive the join.
+pub fn merge_graphs(
+    name: &str,
+    graphs: &[Graph],
+    strategy: MergeStrategy,
+    resolution: ConflictResolution,
+) -> Result<Graph, GraphMergeConflict> {
+    let mut conflicts = GraphMergeConflict::default();
+
+    let mut nodes: IndexMap<String, Node> = IndexMap::new();
+    let mut edges: IndexMap<String, Edge> = IndexMap::new();
+    let mut layers: IndexMap<String, Layer> = IndexMap::new();
+    let mut annotations = Vec::new();
+
+    for graph in graphs {
+        for node in &graph.nodes {
+            match nodes.get_mut(&node.id) {
+                Some(existing) => {
+                    if merge_node(existing, node, resolution) {
+                        conflicts.node_ids.push(node.id.clone());
+                    }
+                }
+                None => {
+                    nodes.insert(node.id.clone(), node.clone());
+                }
+            }
+        }
+        for edge in &graph.edges {
+            match edges.get_mut(&edge.id) {
+                Some(existing) => {
+                    if merge_edge(existing, edge, resolution) {
+                        conflicts.edge_ids.push(edge.id.clone());
+                    }
+                }
+                None => {
+                    edges.insert(edge.id.clone(), edge.clone());
+                }
+            }
+        }
+        for layer in &graph.layers {
+            layers
+                .entry(layer.id.clone())
+                .or_insert_with(|| layer.clone());
+        }
+        if let Some(text) = graph.annotations.as_ref().filter(|t| !t.trim().is_empty()) {
+            annotations.push(text.clone());
+        }
+    }
+
+    let keep: HashSet<String> = match strategy {
+        MergeStrategy::Union => nodes.keys().cloned().collect(),
+        MergeStrategy::Intersection => nodes
+            .keys()
+            .filter(|id| graphs.iter().all(|g| g.nodes.iter().any(|n| &n.id == *id)))
+            .cloned()
+            .collect(),
+        MergeStrategy::Left => graphs
+            .first()
+            .map(|g| g.nodes.iter().map(|n| n.id.clone()).collect())
+            .unwrap_or_default(),
+        MergeStrategy::Right => graphs
+            .last()
+            .map(|g| g.nodes.iter().map(|n| n.id.clone()).collect())
+            .unwrap_or_default(),
+    };
+
+    // Only conflicts on items that survive the join are worth rejecting.
+    if resolution == ConflictResolution::Error {
+        let mut seen = HashSet::new();
+        conflicts
+            .node_ids
+            .retain(|id| keep.contains(id) && seen.insert(format!("n:{id}")));
+        conflicts.edge_ids.retain(|id| {
+            edges
+                .get(id)
+                .is_some_and(|e| keep.contains(&e.source) && keep.contains(&e.target))
+                && seen.insert(format!("e:{id}"))
+        });
+        if !conflicts.node_ids.is_empty() || !conflicts.edge_ids.is_empty() {
+            return Err(conflicts);
+        }
+    }
+
+    let nodes: Vec<Node> = nodes
+        .into_values()
+        .filter(|n| keep.contains(&n.id))
+        .map(|mut n| {
+            if n.belongs_to.as_ref().is_some_and(|p| !keep.contains(p)) {
+                n.belongs_to = None;
+            }
+            n
+        })
+        .collect();
+    let edges: Vec<Edge> = edges
+        .into_values()
+        .filter(|e| keep.contains(&e.source) && keep.contains(&e.target))
+        .collect();
+
+    Ok(Graph {
+        name: name.to_string(),
+        nodes,
+        edges,
+        layers: layers.into_values().collect(),
+        annotations: if annotations.is_empty() {
+            None
+        } else {
+            Some(annotations.join("\n\n"))
+        },
+    })
+}
+
+/// Resolve one field; returns true when the two sides disagreed.
+fn resolve<T: PartialEq + Clone>(
+    existing: &mut T,
+    incoming: &T,
+    resolution: ConflictResolution,
+) -> bool {
+    if existing == incoming {
+        return false;
+    }
+    if resolution == ConflictResolution::PreferLast {
+        *existing = incoming.clone();
+    }
+    true
+}
+
+fn merge_attributes(
+    existing: &mut Option<serde_json::Value>,
+    incoming: &Option<serde_json::Value>,
+    resolution: ConflictResolution,
+) -> bool {
+    let incoming = match incoming {
+        Some(serde_json::Value::Object(map)) => map,
+        Some(other) => return resolve(existing, &Some(other.clone()), resolution),
+        None => return false,
+    };
+    let mut merged = match existing.take() {
+        Some(serde_json::Value::Object(map)) => map,
+        Some(other) => {
+            *existing = Some(other);
+            return resolve(
+                existing,
+                &Some(serde_json::Value::Object(incoming.clone())),
+                resolution,
+            );
+        }
+        None => serde_json::Map::new(),
+    };
+
+    let mut conflicted = false;
+    for (key, value) in incoming {
+        match merged.get_mut(key) {
+            Some(current) => conflicted |= resolve(current, value, resolution),
+            None => {
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    *existing = Some(serde_json::Value::Object(merged));
+    conflicted
+}
+
+fn merge_node(existing: &mut Node, incoming: &Node, resolution: ConflictResolution) -> bool {
+    let mut conflicted = resolve(&mut existing.label, &incoming.label, resolution);
+    conflicted |= resolve(&mut existing.layer, &incoming.layer, resolution);
+    conflicted |= resolve(
+        &mut existing.is_partition,
+        &incoming.is_partition,
+        resolution,
+    );
+    conflicted |= resolve(&mut existing.belongs_to, &incoming.belongs_to, resolution);
+    conflicted |= resolve(&mut existing.weight, &incoming.weight, resolution);
+    conflicted |= resolve(&mut existing.comment, &incoming.comment, resolution);
+    conflicted |= merge_attributes(&mut existing.attributes, &incoming.attributes, resolution);
+    conflicted
+}
+
+fn merge_edge(existing: &mut Edge, incoming: &Edge, resolution: ConflictResolution) -> bool {
+    let mut conflicted = resolve(&mut existing.source, &incoming.source, resolution);
+    conflicted |= resolve(&mut existing.target, &incoming.target, resolution);
+    conflicted |= resolve(&mut existing.label, &incoming.label, resolution);
+    conflicted |= resolve(&mut existing.layer, &incoming.layer, resolution);
+    conflicted |= resolve(&mut existing.weight, &incoming.weight, resolution);
+    conflicted |= resolve(&mut existing.comment, &incoming.comment, resolution);
+    conflicted |= merge_attributes(&mut existing.attributes, &incoming.attributes, resolution);
+    conflicted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn g(json: &str) -> Graph {
+        graph_from_data_set_json(json).unwrap()
+    }
+
+    fn left() -> Graph {
+        g(r#"{"nodes":[
+            {"id":"a","label":"A","layer":"l","weight":1,"is_partition":false,"belongs_to":null,"comment":null,"attributes":{"owner":"ops"}},
+            {"id":"b","label":"B","layer":"l","weight":1,"is_partition":false,"belongs_to":null,"comment":null}
+        ],"edges":[
+            {"id":"e1","source":"a","target":"b","label":"","layer":"l","weight":1,"comment":null}
+        ]}"#)
+    }
+
+    fn right() -> Graph {
+        g(r#"{"nodes":[
+            {"id":"a","label":"A","layer":"l","weight":1,"is_partition":false,"belongs_to":null,"comment":null,"attributes":{"tier":"gold"}},
+            {"id":"c","label":"C","layer":"l","weight":1,"is_partition":false,"belongs_to":null,"comment":null}
+        ],"edges":[
+            {"id":"e2","source":"a","target":"c","label":"","layer":"l","weight":1,"comment":null}
+        ]}"#)
+    }
+
+    fn ids(graph: &Graph) -> Vec<&str> {
+        graph.nodes.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    #[test]
+    fn union_dedupes_shared_nodes_and_unions_attributes() {
+        let merged = merge_graphs(
+            "merged",
+            &[left(), right()],
+            MergeStrategy::Union,
+            ConflictResolution::Error,
+        )
+        .unwrap();
+
+        assert_eq!(ids(&merged), vec!["a", "b", "c"]);
+        assert_eq!(merged.edges.len(), 2);
+        let a = merged.nodes.iter().find(|n| n.id == "a").unwrap();
+        assert_eq!(
+            a.attributes,
+            Some(serde_json::json!({"owner": "ops", "tier": "gold"}))
+        );
+    }
+
+    #[test]
+    fn join_strategies_select_node_ids() {
+        let graphs = [left(), right()];
+        let run = |strategy| {
+            merge_graphs("m", &graphs, strategy, ConflictResolution::PreferFirst).unwrap()
+        };
+
+        let intersection = run(MergeStrategy::Intersection);
+        assert_eq!(ids(&intersection), vec!["a"]);
+        assert!(intersection.edges.is_empty());
+        assert_eq!(ids(&run(MergeStrategy::Left)), vec!["a", "b"]);
+        assert_eq!(ids(&run(MergeStrategy::Right)), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn conflicting_values_follow_resolution_policy() {
+        let mut other = right();
+        other.nodes[0].label = "Renamed".to_string();
+        let graphs = [left(), other];
+
+        let err = merge_graphs(
+            "m",
+            &graphs,
+            MergeStrategy::Union,
+            ConflictResolution::Error,
+        )
+        .unwrap_err();
+        assert_eq!(err.node_ids, vec!["a"]);
+        assert!(err.edge_ids.is_empty());
+
+        let first = merge_graphs(
+            "m",
+            &graphs,
+            MergeStrategy::Union,
+            ConflictResolution::PreferFirst,
+        )
+        .unwrap();
+        assert_eq!(first.nodes[0].label, "A");
+        let last = merge_graphs(
+            "m",
+            &graphs,
+            MergeStrategy::Union,
+            ConflictResolution::PreferLast,
+        )
+        .unwrap();
+        assert_eq!(last.nodes[0].label, "Renamed");
+    }
+}
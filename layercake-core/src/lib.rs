@@ -1,6 +1,7 @@
 pub mod auth;
 pub mod common;
 pub mod data_loader;
+pub mod dataset_schema;
 pub mod errors;
 pub mod export;
 pub mod generate_commands;
@@ -11,6 +12,7 @@ pub mod plan_dag;
 pub mod plan_execution;
 pub mod doctor;
 pub mod graph_diff;
+pub mod graph_merge;
 pub mod palette;
 pub mod sequence_context;
 pub mod sequence_types;
@@ -0,0 +1,150 @@
+//! Cancellation of in-flight plan executions.
+//!
+//! Every `executePlan` run registers a token under its project/plan id. The
+//! DAG executor checks the token between nodes, so a cancel request stops the
+//! run at the next node boundary and leaves already-completed upstream
+//! artefacts untouched for a later re-run to pick up.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::database::entities::graph_data;
+
+pub use tokio_util::sync::CancellationToken;
+
+/// Tokens of the plan executions currently running, keyed by (project, plan).
+#[derive(Default)]
+pub struct PlanExecutionRegistry {
+    running: Mutex<HashMap<(i32, i32), (u64, CancellationToken)>>,
+    next_run: AtomicU64,
+}
+
+impl PlanExecutionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a run of `plan_id`; the registration lasts as long as the
+    /// returned guard. A newer run of the same plan replaces the older entry.
+    pub fn register(self: &Arc<Self>, project_id: i32, plan_id: i32) -> PlanExecutionGuard {
+        let run_id = self.next_run.fetch_add(1, Ordering::Relaxed);
+        let token = CancellationToken::new();
+        self.running
+            .lock()
+            .expect("plan execution registry poisoned")
+            .insert((project_id, plan_id), (run_id, token.clone()));
+        PlanExecutionGuard {
+            registry: Arc::clone(self),
+            key: (project_id, plan_id),
+            run_id,
+            token,
+        }
+    }
+
+    /// Cancel every running execution of the project's plans, returning the
+    /// ids of the plans that were signalled.
+    pub fn cancel_project(&self, project_id: i32) -> Vec<i32> {
+        let running = self
+            .running
+            .lock()
+            .expect("plan execution registry poisoned");
+        let mut plan_ids: Vec<i32> = running
+            .iter()
+            .filter(|((project, _), _)| *project == project_id)
+            .map(|((_, plan_id), (_, token))| {
+                token.cancel();
+                *plan_id
+            })
+            .collect();
+        plan_ids.sort_unstable();
+        plan_ids
+    }
+
+    pub fn is_running(&self, project_id: i32, plan_id: i32) -> bool {
+        self.running
+            .lock()
+            .expect("plan execution registry poisoned")
+            .contains_key(&(project_id, plan_id))
+    }
+}
+
+/// Registration of a single run; dropping it unregisters the run.
+pub struct PlanExecutionGuard {
+    registry: Arc<PlanExecutionRegistry>,
+    key: (i32, i32),
+    run_id: u64,
+    token: CancellationToken,
+}
+
+impl PlanExecutionGuard {
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+}
+
+impl Drop for PlanExecutionGuard {
+    fn drop(&mut self) {
+        if let Ok(mut running) = self.registry.running.lock() {
+            if running
+                .get(&self.key)
+                .is_some_and(|(run_id, _)| *run_id == self.run_id)
+            {
+                running.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// A DAG node that was skipped because its run was cancelled.
+#[derive(Debug, Clone)]
+pub struct CancelledNode {
+    pub node_id: String,
+    pub node_type: String,
+    /// The node's computed graph_data row, now marked `cancelled`, if it had one.
+    pub graph_data: Option<graph_data::Model>,
+}
+
+/// Error returned by `DagExecutor::execute_dag` when its token is cancelled.
+#[derive(Debug, Clone)]
+pub struct PlanExecutionCancelled {
+    pub plan_id: i32,
+    pub cancelled_nodes: Vec<CancelledNode>,
+}
+
+impl std::fmt::Display for PlanExecutionCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Execution of plan {} was cancelled; {} node(s) not run",
+            self.plan_id,
+            self.cancelled_nodes.len()
+        )
+    }
+}
+
+impl std::error::Error for PlanExecutionCancelled {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_signals_only_the_projects_runs() {
+        let registry = Arc::new(PlanExecutionRegistry::new());
+        let first = registry.register(1, 10);
+        let other = registry.register(2, 20);
+
+        assert_eq!(registry.cancel_project(1), vec![10]);
+        assert!(first.token().is_cancelled());
+        assert!(!other.token().is_cancelled());
+
+        // A finished older run must not unregister the newer run of the plan.
+        let rerun = registry.register(1, 10);
+        drop(first);
+        assert!(registry.is_running(1, 10));
+        drop(rerun);
+        assert!(!registry.is_running(1, 10));
+        assert!(registry.cancel_project(1).is_empty());
+    }
+}
@@ -7,7 +7,8 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use tracing::{debug_span, info, warn, Instrument};
 
 use crate::database::entities::graph_data;
-use crate::database::entities::{plan_dag_nodes, projections, sequence_contexts};
+use crate::database::entities::{data_sets, plan_dag_nodes, projections, sequence_contexts};
+use crate::pipeline::cancellation::{CancellationToken, CancelledNode, PlanExecutionCancelled};
 use crate::pipeline::dag_context::DagExecutionContext;
 use crate::pipeline::graph_data_persist_utils::{
     edges_to_graph_data_inputs, nodes_to_graph_data_inputs,
@@ -30,6 +31,10 @@ pub struct DagExecutor {
     warnings: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
     /// Per-node execution records (id, type, duration) accumulated during a run.
     node_records: std::sync::Arc<std::sync::Mutex<Vec<NodeExecutionRecord>>>,
+    /// Checked between nodes by `execute_dag`; see `with_cancellation`.
+    cancellation: Option<CancellationToken>,
+    /// Recompute every node in `execute_dag` even when its source hash matches.
+    force: bool,
 }
 
 /// Which phase a node's work happens in.
@@ -62,8 +67,11 @@ pub struct NodeExecutionRecord {
     pub node_type: String,
     pub duration_ms: u64,
     pub phase: ExecutionPhase,
+    /// The node's output from a previous run was reused; see `execute_dag`.
+    pub cached: bool,
 }
 
+
 /// Options for creating or updating graph_data records originating from DAG nodes
 struct GraphRecordOptions {
     metadata: Option<JsonValue>,
@@ -103,9 +111,30 @@ impl DagExecutor {
             merge_builder,
             warnings: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
             node_records: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
+            cancellation: None,
+            force: false,
         }
     }
 
+    /// With `force`, `execute_dag` recomputes every node instead of reusing
+    /// outputs whose source hash is unchanged.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Stop `execute_dag` at the next node boundary once `token` is cancelled.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
     fn push_warning(&self, warning: impl Into<String>) {
         if let Ok(mut w) = self.warnings.lock() {
             w.push(warning.into());
@@ -1263,6 +1292,7 @@ impl DagExecutor {
         hasher.update(node_id.as_bytes());
         hasher.update(upstream_graph.id.to_le_bytes());
         hasher.update(upstream_graph.updated_at.timestamp_micros().to_le_bytes());
+        hasher.update(upstream_graph.last_edit_sequence.to_le_bytes());
         if let Some(hash) = &upstream_graph.source_hash {
             hasher.update(hash.as_bytes());
         }
@@ -1281,6 +1311,7 @@ impl DagExecutor {
         hasher.update(node_id.as_bytes());
         hasher.update(upstream_graph.id.to_le_bytes());
         hasher.update(upstream_graph.updated_at.timestamp_micros().to_le_bytes());
+        hasher.update(upstream_graph.last_edit_sequence.to_le_bytes());
         if let Some(hash) = &upstream_graph.source_hash {
             hasher.update(hash.as_bytes());
         }
@@ -1377,7 +1408,21 @@ impl DagExecutor {
         let mut context = self.maybe_context();
 
         // Execute nodes in order
-        for node_id in sorted_nodes {
+        for (index, node_id) in sorted_nodes.iter().enumerate() {
+            if self.is_cancelled() {
+                let cancelled_nodes = self.mark_cancelled(&sorted_nodes[index..], nodes).await;
+                info!(
+                    "Plan {} execution cancelled before node {}; {} node(s) not run",
+                    plan_id,
+                    node_id,
+                    cancelled_nodes.len()
+                );
+                return Err(PlanExecutionCancelled {
+                    plan_id,
+                    cancelled_nodes,
+                }
+                .into());
+            }
             let span = debug_span!(
                 "dag_execute_node",
                 project_id,
@@ -1385,33 +1430,171 @@ impl DagExecutor {
                 node_id = node_id.as_str()
             );
             let started = std::time::Instant::now();
-            self.execute_node(
-                project_id,
-                plan_id,
-                &node_id,
-                nodes,
-                edges,
-                context.as_mut(),
-            )
-            .instrument(span)
-            .await?;
-            let node_type = nodes
-                .iter()
-                .find(|n| n.id == node_id)
-                .map(|n| n.node_type.clone())
-                .unwrap_or_default();
+            let node = nodes.iter().find(|n| &n.id == node_id);
+            let cached = match node {
+                Some(node) if !self.force => self.is_up_to_date(node, edges).await?,
+                _ => false,
+            };
+            if cached {
+                info!(
+                    "Node {} is unchanged since the last run; reusing its graph",
+                    node_id
+                );
+            } else {
+                self.execute_node(project_id, plan_id, node_id, nodes, edges, context.as_mut())
+                    .instrument(span)
+                    .await?;
+            }
+
+            let node_type = node.map(|n| n.node_type.clone()).unwrap_or_default();
             let phase = ExecutionPhase::for_node_type(&node_type);
             self.push_node_record(NodeExecutionRecord {
                 node_id: node_id.clone(),
                 node_type,
                 duration_ms: started.elapsed().as_millis() as u64,
                 phase,
+                cached,
             });
         }
 
         Ok(())
     }
 
+    /// Whether the node's graph_data row is active and carries the
+    /// `source_hash` its current inputs would produce, so running it again
+    /// would only rewrite the same graph.
+    async fn is_up_to_date(
+        &self,
+        node: &plan_dag_nodes::Model,
+        edges: &[(String, String)],
+    ) -> Result<bool> {
+        let Some(expected) = self.expected_source_hash(node, edges).await? else {
+            return Ok(false);
+        };
+        let graph_data = self
+            .graph_data_builder
+            .graph_data_service
+            .get_by_dag_node(&node.id)
+            .await?;
+        Ok(graph_data.is_some_and(|gd| {
+            gd.status == graph_data::GraphDataStatus::Active.as_str()
+                && gd.source_hash.as_deref() == Some(expected.as_str())
+        }))
+    }
+
+    /// The `source_hash` the node would persist, for node types whose hash
+    /// depends only on their config and stored inputs. `None` for nodes that
+    /// have to run to know it (merges hash their output) or whose inputs are
+    /// missing or malformed; `execute_node` reports the latter.
+    async fn expected_source_hash(
+        &self,
+        node: &plan_dag_nodes::Model,
+        edges: &[(String, String)],
+    ) -> Result<Option<String>> {
+        let upstream_graph = || async {
+            let upstream_ids = self.get_upstream_nodes(&node.id, edges);
+            match upstream_ids.as_slice() {
+                [upstream_id] => {
+                    self.graph_data_builder
+                        .graph_data_service
+                        .get_by_dag_node(upstream_id)
+                        .await
+                }
+                _ => Ok(None),
+            }
+        };
+
+        match node.node_type.as_str() {
+            "DataSetNode" => {
+                let config: JsonValue = match serde_json::from_str(&node.config_json) {
+                    Ok(config) => config,
+                    Err(_) => return Ok(None),
+                };
+                let Some(data_set_id) = config["dataSetId"].as_i64() else {
+                    return Ok(None);
+                };
+                let data_set = data_sets::Entity::find_by_id(data_set_id as i32)
+                    .one(&self.db)
+                    .await?;
+                Ok(data_set.map(|data_set| {
+                    format!("{:x}", Sha256::digest(data_set.graph_json.as_bytes()))
+                }))
+            }
+            "TransformNode" => {
+                let Ok(config) = serde_json::from_str::<TransformNodeConfig>(&node.config_json)
+                else {
+                    return Ok(None);
+                };
+                match upstream_graph().await? {
+                    Some(upstream) => self
+                        .compute_transform_hash(&node.id, &upstream, &config)
+                        .map(Some),
+                    None => Ok(None),
+                }
+            }
+            "FilterNode" => {
+                let Ok(config) = serde_json::from_str::<FilterNodeConfig>(&node.config_json)
+                else {
+                    return Ok(None);
+                };
+                match upstream_graph().await? {
+                    Some(upstream) => self
+                        .compute_filter_hash(&node.id, &upstream, &config)
+                        .map(Some),
+                    None => Ok(None),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Mark the computed graph_data rows of nodes that will not run as
+    /// `cancelled`. Rows of nodes that already ran are left as they are, so a
+    /// re-run can reuse them. Failures are logged: the run is stopping anyway.
+    async fn mark_cancelled(
+        &self,
+        remaining: &[String],
+        nodes: &[plan_dag_nodes::Model],
+    ) -> Vec<CancelledNode> {
+        let graph_data_service = &self.graph_data_builder.graph_data_service;
+        let mut cancelled = Vec::with_capacity(remaining.len());
+        for node_id in remaining {
+            let node_type = nodes
+                .iter()
+                .find(|n| &n.id == node_id)
+                .map(|n| n.node_type.clone())
+                .unwrap_or_default();
+            let graph_data = match graph_data_service.get_by_dag_node(node_id).await {
+                Ok(Some(gd)) if gd.source_type == "computed" => {
+                    match graph_data_service
+                        .mark_status(gd.id, graph_data::GraphDataStatus::Cancelled, None)
+                        .await
+                    {
+                        Ok(()) => Some(graph_data::Model {
+                            status: graph_data::GraphDataStatus::Cancelled.into(),
+                            ..gd
+                        }),
+                        Err(e) => {
+                            warn!("Failed to mark graph_data {} as cancelled: {}", gd.id, e);
+                            Some(gd)
+                        }
+                    }
+                }
+                Ok(_) => None,
+                Err(e) => {
+                    warn!("Failed to load graph_data for node {}: {}", node_id, e);
+                    None
+                }
+            };
+            cancelled.push(CancelledNode {
+                node_id: node_id.clone(),
+                node_type,
+                graph_data,
+            });
+        }
+        cancelled
+    }
+
     /// Execute nodes affected by a change
     /// This identifies downstream nodes and executes them in order
     pub async fn execute_affected_nodes(
@@ -1827,8 +2010,89 @@ impl DagExecutor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::entities::projects;
+    use crate::services::GraphDataService;
     use chrono::Utc;
 
+    fn graph_node(id: &str) -> plan_dag_nodes::Model {
+        plan_dag_nodes::Model {
+            id: id.to_string(),
+            plan_id: 1,
+            node_type: "GraphNode".to_string(),
+            position_x: 0.0,
+            position_y: 0.0,
+            source_position: None,
+            target_position: None,
+            metadata_json: "{}".to_string(),
+            config_json: "{}".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn cancellation_marks_only_unrun_computed_graphs() {
+        let db = crate::database::test_utils::setup_test_db().await;
+        let mut project = projects::ActiveModel::new();
+        project.name = Set("Cancel".to_string());
+        let project = project.insert(&db).await.unwrap();
+
+        let service = GraphDataService::new(db.clone());
+        let upstream = service
+            .create_computed(project.id, "A".to_string(), "A".to_string())
+            .await
+            .unwrap();
+        service
+            .mark_complete(upstream.id, "hash".to_string())
+            .await
+            .unwrap();
+        let downstream = service
+            .create_computed(project.id, "B".to_string(), "B".to_string())
+            .await
+            .unwrap();
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let executor = DagExecutor::new(db.clone()).with_cancellation(token);
+        let nodes = vec![graph_node("A"), graph_node("B")];
+        let edges = vec![("A".to_string(), "B".to_string())];
+
+        // Cancelled at the boundary after "A": only "B" is left to mark.
+        let cancelled = executor.mark_cancelled(&["B".to_string()], &nodes).await;
+        assert_eq!(cancelled.len(), 1);
+        assert_eq!(
+            cancelled[0].graph_data.as_ref().map(|gd| gd.id),
+            Some(downstream.id)
+        );
+        let status = |id| {
+            let service = &service;
+            async move {
+                service
+                    .get_by_id(id)
+                    .await
+                    .unwrap()
+                    .map(|gd| gd.status)
+                    .unwrap()
+            }
+        };
+        assert_eq!(status(upstream.id).await, "active");
+        assert_eq!(status(downstream.id).await, "cancelled");
+
+        // A run cancelled up front stops before executing any node.
+        let err = executor
+            .execute_dag(project.id, 1, &nodes, &edges)
+            .await
+            .unwrap_err();
+        let cancelled = err.downcast::<PlanExecutionCancelled>().unwrap();
+        let ids: Vec<_> = cancelled
+            .cancelled_nodes
+            .iter()
+            .map(|n| n.node_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["A", "B"]);
+        assert!(executor.take_node_records().is_empty());
+    }
+
     #[test]
     fn test_topological_sort() {
         let executor = DagExecutor::new(DatabaseConnection::default());
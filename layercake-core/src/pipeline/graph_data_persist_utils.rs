@@ -2,25 +2,32 @@ use crate::services::graph_data_service::{GraphDataEdgeInput, GraphDataNodeInput
 use chrono::Utc;
 use serde_json::{json, Value};
 
-/// Helper to build a GraphDataNodeInput from a Graph node.
-/// Converts from the in-memory Graph representation to the graph_data schema.
-pub fn node_to_graph_data_input(node: &crate::graph::Node) -> GraphDataNodeInput {
-    // Build attributes JSON with layer information
+/// Attributes JSON stored for a node or edge: its `layer` and `comment`, plus
+/// its own attributes (e.g. transform outputs such as `pagerank`), which never
+/// override those two keys.
+fn persisted_attributes(
+    layer: &str,
+    comment: Option<&String>,
+    attributes: Option<&Value>,
+) -> Option<Value> {
     let mut attrs = serde_json::Map::new();
-
-    // Store layer information in attributes
-    attrs.insert("layer".to_string(), json!(node.layer.clone()));
-
-    // Add comment to attributes if present
-    if let Some(comment) = &node.comment {
+    attrs.insert("layer".to_string(), json!(layer));
+    if let Some(comment) = comment {
         attrs.insert("comment".to_string(), json!(comment));
     }
+    if let Some(Value::Object(extra)) = attributes {
+        for (key, value) in extra {
+            attrs.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    Some(Value::Object(attrs))
+}
 
-    let attributes = if attrs.is_empty() {
-        None
-    } else {
-        Some(Value::Object(attrs))
-    };
+/// Helper to build a GraphDataNodeInput from a Graph node.
+/// Converts from the in-memory Graph representation to the graph_data schema.
+pub fn node_to_graph_data_input(node: &crate::graph::Node) -> GraphDataNodeInput {
+    let attributes =
+        persisted_attributes(&node.layer, node.comment.as_ref(), node.attributes.as_ref());
 
     GraphDataNodeInput {
         external_id: node.id.clone(),
@@ -39,22 +46,8 @@ pub fn node_to_graph_data_input(node: &crate::graph::Node) -> GraphDataNodeInput
 /// Helper to build a GraphDataEdgeInput from a Graph edge.
 /// Converts from the in-memory Graph representation to the graph_data schema.
 pub fn edge_to_graph_data_input(edge: &crate::graph::Edge) -> GraphDataEdgeInput {
-    // Build attributes JSON with layer information
-    let mut attrs = serde_json::Map::new();
-
-    // Store layer information in attributes
-    attrs.insert("layer".to_string(), json!(edge.layer.clone()));
-
-    // Add comment to attributes if present
-    if let Some(comment) = &edge.comment {
-        attrs.insert("comment".to_string(), json!(comment));
-    }
-
-    let attributes = if attrs.is_empty() {
-        None
-    } else {
-        Some(Value::Object(attrs))
-    };
+    let attributes =
+        persisted_attributes(&edge.layer, edge.comment.as_ref(), edge.attributes.as_ref());
 
     GraphDataEdgeInput {
         external_id: edge.id.clone(),
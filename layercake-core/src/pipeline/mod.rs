@@ -1,13 +1,18 @@
+pub mod cancellation;
 mod dag_context;
 mod dag_executor;
 mod dataset_importer;
 #[allow(dead_code)]
 mod graph_data_builder;
-mod graph_data_persist_utils;
+pub(crate) mod graph_data_persist_utils;
 mod merge_builder;
 #[allow(dead_code)]
 mod types;
 
+pub use cancellation::{
+    CancellationToken, CancelledNode, PlanExecutionCancelled, PlanExecutionGuard,
+    PlanExecutionRegistry,
+};
 pub use dag_executor::{DagExecutor, ExecutionPhase, NodeExecutionRecord};
 pub use dataset_importer::DatasourceImporter;
 pub use graph_data_builder::GraphDataBuilder;
@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use strum::{EnumIter, IntoEnumIterator};
 
 /// ## Structure
 /// This module contains the data structures for the configuration file.
@@ -25,10 +26,14 @@ use std::collections::HashMap;
 ///           │   ├── DOT
 ///           │   ├── DOTHierarchy
 ///           │   ├── JSON
+///           │   ├── JSONHierarchy
+///           │   ├── JSONL
 ///           │   ├── PlantUML
 ///           │   ├── CSVNodes
 ///           │   ├── CSVEdges
+///           │   ├── CSVAdjacency
 ///           │   ├── Mermaid
+///           │   ├── SVG
 ///           │   └── Custom(CustomExportProfile)
 ///           └── graph_config: Option<ExportProfileGraphConfig>
 ///               ├── generate_hierarchy: Option<bool>
@@ -126,7 +131,14 @@ pub struct ExportProfileRenderConfig {
     pub note_position: Option<NotePosition>,
     pub use_node_weight: Option<bool>,
     pub use_edge_weight: Option<bool>,
+    pub normalize_edge_weights: Option<bool>,
+    pub render_partitions_as_nodes: Option<bool>,
+    pub directed: Option<bool>,
+    pub precompute_layout: Option<bool>,
+    pub prettify: Option<bool>,
+    pub timestamp_format: Option<TimestampFormat>,
     pub layer_source_styles: Option<Vec<LayerSourceStyleOverride>>,
+    pub color_by_attribute: Option<ColorByAttribute>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Copy)]
@@ -135,6 +147,16 @@ pub enum RenderConfigOrientation {
     TB,
 }
 
+/// How JSON exports write timestamps found in node, edge and layer attributes.
+#[derive(Serialize, Deserialize, Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum TimestampFormat {
+    #[serde(rename = "rfc3339")]
+    #[default]
+    Rfc3339,
+    #[serde(rename = "epochMillis")]
+    EpochMillis,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Copy, PartialEq, Eq, Default)]
 pub enum NotePosition {
     #[serde(rename = "left")]
@@ -156,6 +178,10 @@ pub enum RenderConfigBuiltInStyle {
     Light,
     #[serde(rename = "dark")]
     Dark,
+    /// White-on-blue drafting style (PlantUML only; other formats render it
+    /// as `light`).
+    #[serde(rename = "blueprint")]
+    Blueprint,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Copy)]
@@ -180,31 +206,272 @@ pub struct LayerSourceStyleOverride {
     pub mode: LayerSourceStyle,
 }
 
+/// Heatmap-style node colouring: each node's background is interpolated
+/// between `min_color` and `max_color` (hex, `#` optional) by where its
+/// numeric `key` attribute falls in the graph's range for that attribute.
 #[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ColorByAttribute {
+    pub key: String,
+    pub min_color: String,
+    pub max_color: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct CustomExportProfile {
     pub template: String,
     pub partials: Option<HashMap<String, String>>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Export formats. Variants are declared in menu order, which
+/// [`ExportFileType::built_in`] follows.
+#[derive(Serialize, Deserialize, Debug, Clone, EnumIter)]
 pub enum ExportFileType {
-    GML,
     DOT,
     DOTHierarchy,
+    GML,
     JSON,
+    JSONHierarchy,
+    JSONL,
     PlantUML,
     PlantUmlMindmap,
     PlantUmlWbs,
-    CSVNodes,
-    CSVEdges,
-    CSVMatrix,
     Mermaid,
     MermaidMindmap,
     MermaidTreemap,
+    CSVNodes,
+    CSVEdges,
+    CSVMatrix,
+    CSVAdjacency,
     JSGraph,
+    SVG,
+    /// Node table as Parquet; needs the `parquet` feature.
+    ParquetNodes,
+    /// Edge table as Parquet; needs the `parquet` feature.
+    ParquetEdges,
     Custom(CustomExportProfile),
 }
 
+/// What a built-in exporter produces, advertised to clients so they can offer
+/// only valid formats and name downloads correctly.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExportFormatInfo {
+    /// Variant name as used in render targets and export profiles.
+    pub id: &'static str,
+    pub name: &'static str,
+    pub extension: &'static str,
+    /// Content type of the rendered output.
+    pub mime_type: &'static str,
+    pub supports_hierarchy: bool,
+    pub supports_layers: bool,
+    pub supports_weights: bool,
+    pub binary: bool,
+}
+
+impl ExportFileType {
+    /// Every built-in exporter, in menu order. `Custom` is left out because it
+    /// needs a user-supplied template.
+    pub fn built_in() -> Vec<ExportFileType> {
+        ExportFileType::iter()
+            .filter(|format| {
+                !matches!(format, ExportFileType::Custom(_))
+                    && (cfg!(feature = "parquet")
+                        || !matches!(
+                            format,
+                            ExportFileType::ParquetNodes | ExportFileType::ParquetEdges
+                        ))
+            })
+            .collect()
+    }
+
+    /// Capabilities of a built-in exporter; `None` for `Custom`.
+    pub fn format_info(&self) -> Option<ExportFormatInfo> {
+        let info = |id, name, extension, mime_type, hierarchy, layers, weights| ExportFormatInfo {
+            id,
+            name,
+            extension,
+            mime_type,
+            supports_hierarchy: hierarchy,
+            supports_layers: layers,
+            supports_weights: weights,
+            binary: false,
+        };
+        Some(match self {
+            ExportFileType::GML => info("GML", "GML", "gml", "text/plain", true, true, true),
+            ExportFileType::DOT => info(
+                "DOT",
+                "Graphviz DOT",
+                "dot",
+                "text/vnd.graphviz",
+                true,
+                true,
+                true,
+            ),
+            ExportFileType::DOTHierarchy => info(
+                "DOTHierarchy",
+                "Graphviz DOT (hierarchy)",
+                "dot",
+                "text/vnd.graphviz",
+                true,
+                true,
+                true,
+            ),
+            ExportFileType::JSON => {
+                info("JSON", "JSON", "json", "application/json", true, true, true)
+            }
+            ExportFileType::JSONHierarchy => info(
+                "JSONHierarchy",
+                "JSON (hierarchy)",
+                "json",
+                "application/json",
+                true,
+                false,
+                true,
+            ),
+            ExportFileType::JSONL => info(
+                "JSONL",
+                "JSON Lines",
+                "jsonl",
+                "application/x-ndjson",
+                false,
+                true,
+                true,
+            ),
+            ExportFileType::PlantUML => info(
+                "PlantUML",
+                "PlantUML",
+                "puml",
+                "text/plain",
+                true,
+                true,
+                false,
+            ),
+            ExportFileType::PlantUmlMindmap => info(
+                "PlantUmlMindmap",
+                "PlantUML mindmap",
+                "puml",
+                "text/plain",
+                true,
+                true,
+                false,
+            ),
+            ExportFileType::PlantUmlWbs => info(
+                "PlantUmlWbs",
+                "PlantUML WBS",
+                "puml",
+                "text/plain",
+                true,
+                true,
+                false,
+            ),
+            ExportFileType::CSVNodes => info(
+                "CSVNodes",
+                "CSV nodes",
+                "csv",
+                "text/csv",
+                true,
+                true,
+                false,
+            ),
+            ExportFileType::CSVEdges => info(
+                "CSVEdges",
+                "CSV edges",
+                "csv",
+                "text/csv",
+                false,
+                true,
+                true,
+            ),
+            ExportFileType::CSVMatrix => info(
+                "CSVMatrix",
+                "CSV matrix",
+                "csv",
+                "text/csv",
+                false,
+                false,
+                true,
+            ),
+            ExportFileType::CSVAdjacency => info(
+                "CSVAdjacency",
+                "CSV adjacency list",
+                "csv",
+                "text/csv",
+                false,
+                false,
+                true,
+            ),
+            ExportFileType::Mermaid => info(
+                "Mermaid",
+                "Mermaid",
+                "mermaid",
+                "text/plain",
+                true,
+                true,
+                false,
+            ),
+            ExportFileType::MermaidMindmap => info(
+                "MermaidMindmap",
+                "Mermaid mindmap",
+                "mmd",
+                "text/plain",
+                true,
+                false,
+                false,
+            ),
+            ExportFileType::MermaidTreemap => info(
+                "MermaidTreemap",
+                "Mermaid treemap",
+                "mmd",
+                "text/plain",
+                true,
+                false,
+                true,
+            ),
+            ExportFileType::JSGraph => info(
+                "JSGraph",
+                "3D force graph (HTML)",
+                "html",
+                "text/html",
+                false,
+                true,
+                true,
+            ),
+            ExportFileType::SVG => info("SVG", "SVG", "svg", "image/svg+xml", true, true, false),
+            ExportFileType::ParquetNodes => ExportFormatInfo {
+                binary: true,
+                ..info(
+                    "ParquetNodes",
+                    "Parquet nodes",
+                    "parquet",
+                    "application/vnd.apache.parquet",
+                    true,
+                    true,
+                    true,
+                )
+            },
+            ExportFileType::ParquetEdges => ExportFormatInfo {
+                binary: true,
+                ..info(
+                    "ParquetEdges",
+                    "Parquet edges",
+                    "parquet",
+                    "application/vnd.apache.parquet",
+                    false,
+                    true,
+                    true,
+                )
+            },
+            ExportFileType::Custom(_) => return None,
+        })
+    }
+
+    /// Look up a built-in exporter by its format id.
+    pub fn from_format_id(id: &str) -> Option<ExportFileType> {
+        Self::built_in()
+            .into_iter()
+            .find(|format| format.format_info().is_some_and(|info| info.id == id))
+    }
+}
+
 impl Default for ExportProfileRenderConfig {
     fn default() -> Self {
         Self {
@@ -219,7 +486,14 @@ impl Default for ExportProfileRenderConfig {
             note_position: Some(NotePosition::Left),
             use_node_weight: Some(true),
             use_edge_weight: Some(true),
+            normalize_edge_weights: Some(false),
+            render_partitions_as_nodes: Some(false),
+            directed: None,
+            precompute_layout: Some(false),
+            prettify: Some(false),
+            timestamp_format: Some(TimestampFormat::Rfc3339),
             layer_source_styles: None,
+            color_by_attribute: None,
         }
     }
 }
@@ -240,8 +514,52 @@ pub struct RenderConfig {
     pub use_node_weight: bool,
     #[serde(default = "default_true")]
     pub use_edge_weight: bool,
+    /// Replace edge weights with their relative 1-6 weight for every exporter.
+    #[serde(default)]
+    pub normalize_edge_weights: bool,
+    /// Render partition nodes as styled group-header nodes linked to their
+    /// children instead of leaving them out of flow output, coloured from the
+    /// `built_in_styles` palette. DOT only (other exports reject it), and only
+    /// when nodes are not already drawn as containers.
+    #[serde(default)]
+    pub render_partitions_as_nodes: bool,
+    /// Treat edges as directed (GML only). When set, GML output declares
+    /// `directed 1`/`directed 0`; when off, endpoints are ordered and edges
+    /// between the same pair are folded into one with their summed weight.
+    /// Unset keeps edges as they are without declaring either.
+    #[serde(default)]
+    pub directed: Option<bool>,
+    /// Write deterministic `x`/`y` positions into exported nodes (JSGraph
+    /// only), grouping nodes under their top-level partition.
+    #[serde(default)]
+    pub precompute_layout: bool,
+    /// Indent JSON hierarchy output for readability. Plain JSON exports are
+    /// always indented.
+    #[serde(default)]
+    pub prettify: bool,
+    #[serde(default)]
+    pub timestamp_format: TimestampFormat,
     #[serde(default)]
     pub layer_source_styles: Vec<LayerSourceStyleOverride>,
+    /// Override node background colours from a numeric node attribute (DOT,
+    /// Mermaid, SVG and JSGraph). Nodes without the attribute keep their
+    /// layer colour.
+    #[serde(default)]
+    pub color_by_attribute: Option<ColorByAttribute>,
+}
+
+impl RenderConfig {
+    /// Reject options that `format` has no rendering for, so they fail
+    /// loudly instead of being dropped from the output.
+    pub fn check_supported_by(&self, format: &ExportFileType) -> Result<(), String> {
+        if self.render_partitions_as_nodes && !matches!(format, ExportFileType::DOT) {
+            return Err(format!(
+                "render_partitions_as_nodes is only supported by DOT exports, not {:?}",
+                format
+            ));
+        }
+        Ok(())
+    }
 }
 
 fn default_true() -> bool {
@@ -252,6 +570,10 @@ fn default_true() -> bool {
 pub struct RenderTargetOptions {
     pub graphviz: Option<GraphvizRenderOptions>,
     pub mermaid: Option<MermaidRenderOptions>,
+    #[serde(default)]
+    pub svg: Option<SvgRenderOptions>,
+    #[serde(default)]
+    pub plantuml: Option<PlantUmlRenderOptions>,
 }
 
 impl Default for RenderTargetOptions {
@@ -259,6 +581,8 @@ impl Default for RenderTargetOptions {
         Self {
             graphviz: Some(GraphvizRenderOptions::default()),
             mermaid: None,
+            svg: None,
+            plantuml: None,
         }
     }
 }
@@ -308,6 +632,33 @@ pub enum GraphvizCommentStyle {
     Tooltip,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SvgRenderOptions {
+    /// Widest node label, in pixels, before it is truncated with an ellipsis.
+    pub label_max_width: u32,
+}
+
+impl Default for SvgRenderOptions {
+    fn default() -> Self {
+        Self {
+            label_max_width: 160,
+        }
+    }
+}
+
+/// Global PlantUML `skinparam` overrides, applied on top of the
+/// `built_in_styles` preset. Layer colours still style individual nodes.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct PlantUmlRenderOptions {
+    /// Hex colour, with or without a leading `#`.
+    pub background_color: Option<String>,
+    pub font_name: Option<String>,
+    /// Hex colour, with or without a leading `#`.
+    pub arrow_color: Option<String>,
+    /// Corner radius for node shapes; 0 draws square corners.
+    pub round_corner: Option<u32>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct MermaidRenderOptions {
     pub look: MermaidLook,
@@ -384,6 +735,71 @@ impl Default for GraphConfig {
     }
 }
 
+impl Plan {
+    /// Expand `${VAR}` and `${VAR:-default}` in import and export filenames
+    /// from the process environment. `$$` yields a literal `$`.
+    pub fn interpolate_paths(&mut self) -> anyhow::Result<()> {
+        self.interpolate_paths_with(|name| std::env::var(name).ok())
+    }
+
+    /// As `interpolate_paths`, resolving variables through `lookup`.
+    pub fn interpolate_paths_with(
+        &mut self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> anyhow::Result<()> {
+        for (index, profile) in self.import.profiles.iter_mut().enumerate() {
+            profile.filename = interpolate_variables(&profile.filename, &lookup).map_err(|e| {
+                anyhow::anyhow!("Plan field import.profiles[{}].filename: {}", index, e)
+            })?;
+        }
+        for (index, profile) in self.export.profiles.iter_mut().enumerate() {
+            profile.filename = interpolate_variables(&profile.filename, &lookup).map_err(|e| {
+                anyhow::anyhow!("Plan field export.profiles[{}].filename: {}", index, e)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Shell-style substitution: `:-` falls back to the default when the variable
+/// is unset or empty, and a `$` not followed by `{` or `$` is kept as is.
+fn interpolate_variables(
+    value: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut output = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(position) = rest.find('$') {
+        output.push_str(&rest[..position]);
+        rest = &rest[position + 1..];
+
+        if let Some(after) = rest.strip_prefix('$') {
+            output.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('{') {
+            let end = after
+                .find('}')
+                .ok_or_else(|| format!("unterminated variable reference in {:?}", value))?;
+            let (name, default) = match after[..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&after[..end], None),
+            };
+            let resolved = match (lookup(name), default) {
+                (Some(current), Some(default)) if current.is_empty() => default.to_string(),
+                (Some(current), _) => current,
+                (None, Some(default)) => default.to_string(),
+                (None, None) => return Err(format!("environment variable {} is not set", name)),
+            };
+            output.push_str(&resolved);
+            rest = &after[end + 1..];
+        } else {
+            output.push('$');
+        }
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
 impl ExportProfileItem {
     pub fn get_graph_config(&self) -> GraphConfig {
         let graph_config = self.graph_config.unwrap_or_default();
@@ -448,7 +864,14 @@ impl ExportProfileItem {
         let note_position = render_config.note_position.unwrap_or(NotePosition::Left);
         let use_node_weight = render_config.use_node_weight.unwrap_or(true);
         let use_edge_weight = render_config.use_edge_weight.unwrap_or(true);
+        let normalize_edge_weights = render_config.normalize_edge_weights.unwrap_or(false);
+        let render_partitions_as_nodes = render_config.render_partitions_as_nodes.unwrap_or(false);
+        let directed = render_config.directed;
+        let precompute_layout = render_config.precompute_layout.unwrap_or(false);
+        let prettify = render_config.prettify.unwrap_or(false);
+        let timestamp_format = render_config.timestamp_format.unwrap_or_default();
         let layer_source_styles = render_config.layer_source_styles.unwrap_or_default();
+        let color_by_attribute = render_config.color_by_attribute;
 
         RenderConfig {
             contain_nodes,
@@ -460,7 +883,14 @@ impl ExportProfileItem {
             note_position,
             use_node_weight,
             use_edge_weight,
+            normalize_edge_weights,
+            render_partitions_as_nodes,
+            directed,
+            precompute_layout,
+            prettify,
+            timestamp_format,
             layer_source_styles,
+            color_by_attribute,
         }
     }
 }
@@ -482,6 +912,37 @@ mod tests {
         println!("{}", yaml_str);
         assert!(yaml_str.contains("profiles"));
     }
+
+    #[test]
+    fn test_interpolate_paths_resolves_defaults_and_reports_missing_variables() {
+        let yaml_str = r#"
+import:
+  profiles:
+    - filename: ${DATA_DIR}/nodes.csv
+      filetype: Nodes
+    - filename: ${EDGES_DIR:-fixtures}/edges.csv
+      filetype: Edges
+export:
+  profiles:
+    - filename: ${OUT_DIR:-out}/cost$$.gml
+      exporter: GML
+"#;
+        let lookup = |name: &str| (name == "DATA_DIR").then(|| "/srv/data".to_string());
+
+        let mut plan: Plan = serde_yaml::from_str(yaml_str).unwrap();
+        plan.interpolate_paths_with(lookup).unwrap();
+        assert_eq!(plan.import.profiles[0].filename, "/srv/data/nodes.csv");
+        assert_eq!(plan.import.profiles[1].filename, "fixtures/edges.csv");
+        assert_eq!(plan.export.profiles[0].filename, "out/cost$.gml");
+
+        let mut plan: Plan = serde_yaml::from_str(yaml_str).unwrap();
+        let error = plan.interpolate_paths_with(|_| None).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Plan field import.profiles[0].filename: environment variable DATA_DIR is not set"
+        );
+    }
+
     #[test]
     fn test_deserialization() {
         let yaml_str = r#"
@@ -515,4 +976,22 @@ export:
 
         let _config: Plan = serde_yaml::from_str(yaml_str).unwrap();
     }
+
+    #[test]
+    fn test_built_in_format_ids_match_exporter_names() {
+        for format in ExportFileType::built_in() {
+            let info = format.format_info().unwrap();
+            assert_eq!(serde_json::to_value(&format).unwrap(), info.id);
+            assert!(matches!(
+                ExportFileType::from_format_id(info.id),
+                Some(found) if found.format_info() == Some(info)
+            ));
+        }
+        assert!(ExportFileType::from_format_id("Custom").is_none());
+
+        let formats = ExportFileType::built_in();
+        let parquet = if cfg!(feature = "parquet") { 2 } else { 0 };
+        assert_eq!(formats.len(), ExportFileType::iter().count() - 3 + parquet);
+        assert_eq!(formats[0].format_info().unwrap().id, "DOT");
+    }
 }
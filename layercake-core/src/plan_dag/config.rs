@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::plan::RenderConfigBuiltInStyle;
+
 // Story Node Configuration
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -34,4 +36,17 @@ pub enum RenderBuiltinStyle {
     None,
     Light,
     Dark,
+    /// PlantUML only; other formats render it as `Light`.
+    Blueprint,
+}
+
+impl From<RenderBuiltinStyle> for RenderConfigBuiltInStyle {
+    fn from(style: RenderBuiltinStyle) -> Self {
+        match style {
+            RenderBuiltinStyle::None => RenderConfigBuiltInStyle::None,
+            RenderBuiltinStyle::Light => RenderConfigBuiltInStyle::Light,
+            RenderBuiltinStyle::Dark => RenderConfigBuiltInStyle::Dark,
+            RenderBuiltinStyle::Blueprint => RenderConfigBuiltInStyle::Blueprint,
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use anyhow::{anyhow, Result as AnyResult};
 use serde::{Deserialize, Serialize};
 
-use crate::graph::Graph;
+use crate::graph::{DegreeDirection, Graph, NodeIdStrategy, WeightNormalization};
 
 // Transform Node Configuration
 #[derive(Clone, Debug, Serialize)]
@@ -362,6 +362,214 @@ impl GraphTransform {
                 };
                 Some(annotation)
             }
+            GraphTransformKind::CommunityDetection => {
+                let resolution = self.params.community_resolution.unwrap_or(1.0);
+                let min_size = self.params.min_community_size.unwrap_or(1);
+                let create_layers = self.params.create_community_layers.unwrap_or(false);
+                let summary = graph
+                    .detect_communities(resolution, min_size, create_layers)
+                    .map_err(|e| anyhow!(e))?;
+                let clustered = summary
+                    .communities
+                    .iter()
+                    .filter(|members| members.len() >= min_size)
+                    .count();
+                Some(format!(
+                    "### Transform: Community Detection (Louvain)\n- Resolution: {}\n- Minimum community size: {}\n- Communities: {}\n- Unclustered nodes: {}\n- Layers added: {}\n- Nodes modified: {}",
+                    resolution,
+                    min_size,
+                    clustered,
+                    summary.unclustered_nodes,
+                    summary.layers_added,
+                    summary.nodes_modified
+                ))
+            }
+            GraphTransformKind::KCore => {
+                let k = self
+                    .params
+                    .k_core
+                    .ok_or_else(|| anyhow!("KCore transform requires k_core"))?;
+                let keep_only_core = self.params.keep_only_core.unwrap_or(false);
+                let core = graph.apply_k_core(k, keep_only_core);
+                Some(format!(
+                    "### Transform: K-Core\n- k: {}\n- Nodes in k-core: {}\n- Keep only core: {}\n- Nodes after: {}\n- Edges after: {}",
+                    k,
+                    core.len(),
+                    keep_only_core,
+                    graph.nodes.len(),
+                    graph.edges.len()
+                ))
+            }
+            GraphTransformKind::ShortestPathTree => {
+                let root = self.params.shortest_path_root.as_deref().ok_or_else(|| {
+                    anyhow!("ShortestPathTree transform requires shortest_path_root")
+                })?;
+                let summary = graph.shortest_path_tree(root).map_err(|e| anyhow!(e))?;
+                Some(format!(
+                    "### Transform: Shortest Path Tree\n- Root: {}\n- Reachable nodes: {}\n- Tree edges: {}\n- Edges removed: {}\n- Nodes removed: {}",
+                    root,
+                    summary.reachable_nodes,
+                    summary.tree_edges,
+                    summary.removed_edges,
+                    summary.removed_nodes
+                ))
+            }
+            GraphTransformKind::NeighborhoodSubgraph => {
+                let seed = self.params.neighborhood_seed.as_deref().ok_or_else(|| {
+                    anyhow!("NeighborhoodSubgraph transform requires neighborhood_seed")
+                })?;
+                let radius = self.params.neighborhood_radius.unwrap_or(1);
+                let summary = graph
+                    .extract_neighborhood(
+                        seed,
+                        radius,
+                        self.params.preserve_structure.unwrap_or(true),
+                        self.params.include_boundary_edges.unwrap_or(false),
+                    )
+                    .map_err(|e| anyhow!(e))?;
+                Some(format!(
+                    "### Transform: Neighborhood Subgraph\n- Seed: {}\n- Radius: {}\n- Nodes in neighborhood: {}\n- Boundary edges: {}\n- Nodes removed: {}\n- Edges removed: {}",
+                    seed,
+                    radius,
+                    summary.neighborhood_nodes,
+                    summary.boundary_edges,
+                    summary.removed_nodes,
+                    summary.removed_edges
+                ))
+            }
+            GraphTransformKind::NormalizeNodeIds => {
+                let strategy = self
+                    .params
+                    .node_id_strategy
+                    .unwrap_or(NodeIdStrategy::Slugify);
+                let mapping = graph
+                    .normalize_node_ids(strategy, self.params.node_id_prefix.as_deref())
+                    .map_err(|e| anyhow!(e))?;
+                let changed: Vec<_> = mapping.iter().filter(|(old, new)| old != new).collect();
+                let mut annotation = format!(
+                    "### Transform: Normalize Node Ids\n- Strategy: {:?}\n- Ids changed: {}",
+                    strategy,
+                    changed.len()
+                );
+                if !changed.is_empty() {
+                    annotation.push_str("\n\n| Old id | New id |\n| --- | --- |\n");
+                    for (old, new) in changed {
+                        annotation.push_str(&format!("| {} | {} |\n", old, new));
+                    }
+                }
+                Some(annotation)
+            }
+            GraphTransformKind::CutStructure => {
+                let summary = graph.tag_cut_structure();
+                let list = |ids: &[String]| {
+                    if ids.is_empty() {
+                        "none".to_string()
+                    } else {
+                        ids.join(", ")
+                    }
+                };
+                Some(format!(
+                    "### Transform: Articulation Points & Bridges\n- Articulation points ({}): {}\n- Bridges ({}): {}",
+                    summary.articulation_points.len(),
+                    list(&summary.articulation_points),
+                    summary.bridges.len(),
+                    list(&summary.bridges)
+                ))
+            }
+            GraphTransformKind::NodeDegreeFilter => {
+                let min_degree = self.params.min_degree.unwrap_or(0);
+                let max_degree = self.params.max_degree;
+                let direction = self
+                    .params
+                    .degree_direction
+                    .unwrap_or(DegreeDirection::Total);
+                let summary = graph.filter_by_degree(min_degree, max_degree, direction);
+                Some(format!(
+                    "### Transform: Node Degree Filter\n- Direction: {:?}\n- Degree range: {}..={}\n- Nodes removed: {}\n- Edges removed: {}\n- Partitions emptied: {}",
+                    direction,
+                    min_degree,
+                    max_degree.map_or_else(|| "any".to_string(), |max| max.to_string()),
+                    summary.removed_nodes,
+                    summary.removed_edges,
+                    summary.emptied_partitions
+                ))
+            }
+            GraphTransformKind::ComputeHierarchyDepth => {
+                let summary = graph.compute_hierarchy_depth();
+                let mut annotation = format!(
+                    "### Transform: Hierarchy Depth\n- Max depth: {}\n- Cycles broken: {}",
+                    summary.max_depth,
+                    summary.broken_cycles.len()
+                );
+                if !summary.broken_cycles.is_empty() {
+                    annotation.push_str(&format!(
+                        "\n- Promoted to root: {}",
+                        summary.broken_cycles.join(", ")
+                    ));
+                }
+                Some(annotation)
+            }
+            GraphTransformKind::MergeReciprocalEdges => {
+                let keep_separate = self.params.keep_reciprocal_edges_separate.unwrap_or(false);
+                let summary = graph.merge_reciprocal_edges(keep_separate);
+                Some(format!(
+                    "### Transform: Merge Reciprocal Edges\n- Reciprocal pairs: {}\n- Keep separate: {}\n- Edges removed: {}\n- Edges after: {}",
+                    summary.pairs,
+                    keep_separate,
+                    summary.removed_edges,
+                    graph.edges.len()
+                ))
+            }
+            GraphTransformKind::PageRank => {
+                let damping = self.params.pagerank_damping.unwrap_or(0.85);
+                let iterations = self.params.pagerank_iterations.unwrap_or(100);
+                let summary = graph
+                    .annotate_pagerank(damping, iterations)
+                    .map_err(|e| anyhow!(e))?;
+                Some(format!(
+                    "### Transform: PageRank\n- Damping: {}\n- Iterations: {}\n- Converged: {}",
+                    damping, summary.iterations, summary.converged
+                ))
+            }
+            GraphTransformKind::NormalizeEdgeWeights => {
+                let method = self
+                    .params
+                    .weight_normalization
+                    .unwrap_or(WeightNormalization::MinMax);
+                let range = (
+                    self.params.weight_range_min.unwrap_or(1),
+                    self.params.weight_range_max.unwrap_or(100),
+                );
+                let preserve_zero = self.params.preserve_zero_weights.unwrap_or(false);
+                let summary = graph
+                    .normalize_edge_weights(method, range, preserve_zero)
+                    .map_err(|e| anyhow!(e))?;
+                Some(format!(
+                    "### Transform: Normalize Edge Weights\n- Method: {:?}\n- Range: {}..{}\n- Preserve zero: {}\n- Edges normalized: {}\n- Uniform weights: {}",
+                    method,
+                    range.0,
+                    range.1,
+                    preserve_zero,
+                    summary.normalized_edges,
+                    summary.uniform
+                ))
+            }
+            GraphTransformKind::MinimumSpanningTree => {
+                let weight_attr = self.params.spanning_tree_weight_attribute.as_deref();
+                let maximize = self.params.spanning_tree_maximize.unwrap_or(false);
+                let summary = graph
+                    .minimum_spanning_tree(weight_attr, maximize)
+                    .map_err(|e| anyhow!(e))?;
+                Some(format!(
+                    "### Transform: {} Spanning Tree\n- Weight: {}\n- Components: {}\n- Tree edges: {}\n- Edges removed: {}\n- Total weight: {}",
+                    if maximize { "Maximum" } else { "Minimum" },
+                    weight_attr.unwrap_or("edge weight"),
+                    summary.components,
+                    summary.tree_edges,
+                    summary.removed_edges,
+                    summary.total_weight
+                ))
+            }
             GraphTransformKind::AggregateEdges => {
                 unreachable!("AggregateEdges should have been handled earlier")
             }
@@ -384,6 +592,18 @@ pub enum GraphTransformKind {
     GenerateHierarchy,
     AggregateLayerNodes,
     AggregateEdges,
+    CommunityDetection,
+    KCore,
+    ShortestPathTree,
+    NeighborhoodSubgraph,
+    NormalizeNodeIds,
+    CutStructure,
+    MinimumSpanningTree,
+    NodeDegreeFilter,
+    ComputeHierarchyDepth,
+    MergeReciprocalEdges,
+    PageRank,
+    NormalizeEdgeWeights,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -408,6 +628,54 @@ pub struct GraphTransformParams {
     pub exclude_partition_nodes: Option<bool>,
     #[serde(alias = "keepFlowEdges")]
     pub keep_flow_edges: Option<bool>,
+    #[serde(alias = "community_resolution")]
+    pub community_resolution: Option<f64>,
+    #[serde(alias = "min_community_size")]
+    pub min_community_size: Option<usize>,
+    #[serde(alias = "create_community_layers")]
+    pub create_community_layers: Option<bool>,
+    #[serde(alias = "k_core")]
+    pub k_core: Option<usize>,
+    #[serde(alias = "keep_only_core")]
+    pub keep_only_core: Option<bool>,
+    #[serde(alias = "shortest_path_root")]
+    pub shortest_path_root: Option<String>,
+    #[serde(alias = "neighborhood_seed")]
+    pub neighborhood_seed: Option<String>,
+    #[serde(alias = "neighborhood_radius")]
+    pub neighborhood_radius: Option<usize>,
+    #[serde(alias = "preserve_structure")]
+    pub preserve_structure: Option<bool>,
+    #[serde(alias = "include_boundary_edges")]
+    pub include_boundary_edges: Option<bool>,
+    #[serde(alias = "node_id_strategy")]
+    pub node_id_strategy: Option<NodeIdStrategy>,
+    #[serde(alias = "node_id_prefix")]
+    pub node_id_prefix: Option<String>,
+    #[serde(alias = "spanning_tree_weight_attribute")]
+    pub spanning_tree_weight_attribute: Option<String>,
+    #[serde(alias = "spanning_tree_maximize")]
+    pub spanning_tree_maximize: Option<bool>,
+    #[serde(alias = "min_degree")]
+    pub min_degree: Option<usize>,
+    #[serde(alias = "max_degree")]
+    pub max_degree: Option<usize>,
+    #[serde(alias = "degree_direction")]
+    pub degree_direction: Option<DegreeDirection>,
+    #[serde(alias = "keep_reciprocal_edges_separate")]
+    pub keep_reciprocal_edges_separate: Option<bool>,
+    #[serde(alias = "pagerank_damping")]
+    pub pagerank_damping: Option<f64>,
+    #[serde(alias = "pagerank_iterations")]
+    pub pagerank_iterations: Option<usize>,
+    #[serde(alias = "weight_normalization")]
+    pub weight_normalization: Option<WeightNormalization>,
+    #[serde(alias = "weight_range_min")]
+    pub weight_range_min: Option<i32>,
+    #[serde(alias = "weight_range_max")]
+    pub weight_range_max: Option<i32>,
+    #[serde(alias = "preserve_zero_weights")]
+    pub preserve_zero_weights: Option<bool>,
 }
 
 /// Wire format for deserializing TransformNodeConfig supporting both v1 and v2 schemas.
@@ -590,7 +858,19 @@ impl TransformNodeConfig {
                 GraphTransformKind::GenerateHierarchy => {
                     config.generate_hierarchy = true;
                 }
-                GraphTransformKind::AggregateLayerNodes => {}
+                GraphTransformKind::AggregateLayerNodes
+                | GraphTransformKind::CommunityDetection
+                | GraphTransformKind::KCore
+                | GraphTransformKind::ShortestPathTree
+                | GraphTransformKind::NeighborhoodSubgraph
+                | GraphTransformKind::NormalizeNodeIds
+                | GraphTransformKind::CutStructure
+                | GraphTransformKind::MinimumSpanningTree
+                | GraphTransformKind::NodeDegreeFilter
+                | GraphTransformKind::ComputeHierarchyDepth
+                | GraphTransformKind::MergeReciprocalEdges
+                | GraphTransformKind::PageRank
+                | GraphTransformKind::NormalizeEdgeWeights => {}
                 GraphTransformKind::AggregateEdges => {
                     config.aggregate_edges = transform.params.enabled.unwrap_or(true);
                 }
@@ -605,7 +885,8 @@ impl TransformNodeConfig {
 mod tests {
     use super::*;
     use crate::graph::{Edge, Layer, Node};
-    use std::collections::HashSet;
+    use crate::services::graph_analysis_service::union_find_root;
+    use std::collections::{HashMap, HashSet};
 
     fn sample_graph() -> Graph {
         Graph {
@@ -872,6 +1153,716 @@ mod tests {
         }
     }
 
+    /// Node on `layer1`, labelled with its id.
+    fn make_node(id: &str) -> Node {
+        Node {
+            id: id.to_string(),
+            label: id.to_string(),
+            layer: "layer1".to_string(),
+            weight: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Edge on `layer1` with the id `{source}_{target}`.
+    fn make_edge(source: &str, target: &str, weight: i32) -> Edge {
+        Edge {
+            id: format!("{}_{}", source, target),
+            source: source.to_string(),
+            target: target.to_string(),
+            layer: "layer1".to_string(),
+            weight,
+            ..Default::default()
+        }
+    }
+
+    fn barbell_graph() -> Graph {
+        let left = ["a1", "a2", "a3", "a4"];
+        let right = ["b1", "b2", "b3", "b4"];
+        let mut edges = Vec::new();
+        for clique in [&left, &right] {
+            for (i, source) in clique.iter().enumerate() {
+                for target in &clique[i + 1..] {
+                    edges.push(make_edge(source, target, 1));
+                }
+            }
+        }
+        edges.push(make_edge("a1", "b1", 1));
+
+        Graph {
+            name: "Barbell".to_string(),
+            nodes: left
+                .iter()
+                .chain(right.iter())
+                .map(|id| make_node(id))
+                .collect(),
+            edges,
+            layers: vec![Layer::new(
+                "layer1", "Layer 1", "ffffff", "000000", "000000",
+            )],
+            annotations: None,
+        }
+    }
+
+    #[test]
+    fn community_detection_splits_barbell_into_two_layers() {
+        let mut graph = barbell_graph();
+        let transform = GraphTransform {
+            kind: GraphTransformKind::CommunityDetection,
+            params: GraphTransformParams {
+                create_community_layers: Some(true),
+                ..Default::default()
+            },
+        };
+
+        transform
+            .apply_to(&mut graph)
+            .expect("community detection should succeed");
+
+        let layer_of = |id: &str| {
+            graph
+                .nodes
+                .iter()
+                .find(|n| n.id == id)
+                .map(|n| n.layer.clone())
+                .unwrap()
+        };
+        let communities: HashSet<String> = graph.nodes.iter().map(|n| n.layer.clone()).collect();
+        assert_eq!(communities.len(), 2, "barbell should yield two communities");
+        for id in ["a2", "a3", "a4"] {
+            assert_eq!(layer_of(id), layer_of("a1"));
+        }
+        for id in ["b2", "b3", "b4"] {
+            assert_eq!(layer_of(id), layer_of("b1"));
+        }
+        assert_ne!(layer_of("a1"), layer_of("b1"));
+        assert!(graph.layers.iter().any(|l| l.id == "community_1"));
+        assert!(graph.layers.iter().any(|l| l.id == "community_2"));
+        assert!(!graph.layers.iter().any(|l| l.id == "unclustered"));
+
+        let summary = barbell_graph()
+            .detect_communities(1.0, 1, true)
+            .expect("community detection should succeed");
+        assert_eq!(summary.layers_added, 2);
+        assert_eq!(summary.nodes_modified, 8);
+    }
+
+    #[test]
+    fn community_detection_merges_small_communities_into_unclustered() {
+        let mut graph = barbell_graph();
+        graph.nodes.push(make_node("loner"));
+
+        let summary = graph
+            .detect_communities(1.0, 2, true)
+            .expect("community detection should succeed");
+
+        assert_eq!(summary.unclustered_nodes, 1);
+        assert_eq!(summary.layers_added, 3);
+        let loner = graph.nodes.iter().find(|n| n.id == "loner").unwrap();
+        assert_eq!(loner.layer, "unclustered");
+    }
+
+    /// A 4-clique (a1..a4) with pendants p1 (on a1) and p2 (on p1), plus an isolated node.
+    fn clique_with_pendants() -> Graph {
+        let mut graph = barbell_graph();
+        graph.nodes.retain(|n| n.id.starts_with('a'));
+        graph.remove_dangling_edges();
+        for id in ["p1", "p2", "isolated"] {
+            graph.nodes.push(make_node(id));
+        }
+        for (source, target) in [("a1", "p1"), ("p1", "p2")] {
+            graph.edges.push(make_edge(source, target, 1));
+        }
+        graph
+    }
+
+    fn apply_k_core(graph: &mut Graph, k: usize) {
+        GraphTransform {
+            kind: GraphTransformKind::KCore,
+            params: GraphTransformParams {
+                k_core: Some(k),
+                keep_only_core: Some(true),
+                ..Default::default()
+            },
+        }
+        .apply_to(graph)
+        .expect("k-core transform should succeed");
+    }
+
+    /// Every edge joins two different trees of a union-find, so no cycles.
+    fn assert_acyclic(graph: &Graph) {
+        let index: HashMap<&str, usize> = graph
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| (node.id.as_str(), idx))
+            .collect();
+        let mut parent: Vec<usize> = (0..index.len()).collect();
+        for edge in &graph.edges {
+            let (a, b) = (
+                union_find_root(&mut parent, index[edge.source.as_str()]),
+                union_find_root(&mut parent, index[edge.target.as_str()]),
+            );
+            assert_ne!(a, b, "edge {} closes a cycle", edge.id);
+            parent[a] = b;
+        }
+    }
+
+    #[test]
+    fn k_core_keeps_clique_and_drops_low_coreness_nodes() {
+        let coreness = clique_with_pendants().core_numbers();
+        assert_eq!(coreness["a1"], 3);
+        assert_eq!(coreness["a4"], 3);
+        assert_eq!(coreness["p1"], 1);
+        assert_eq!(coreness["p2"], 1);
+        assert_eq!(coreness["isolated"], 0);
+
+        let ids = |graph: &Graph| -> HashSet<String> {
+            graph.nodes.iter().map(|n| n.id.clone()).collect()
+        };
+
+        let mut one_core = clique_with_pendants();
+        apply_k_core(&mut one_core, 1);
+        assert!(!ids(&one_core).contains("isolated"));
+        assert!(ids(&one_core).contains("p2"));
+
+        let mut two_core = clique_with_pendants();
+        apply_k_core(&mut two_core, 2);
+        assert!(!ids(&two_core).contains("p1"));
+        assert!(!ids(&two_core).contains("p2"));
+
+        let mut three_core = clique_with_pendants();
+        apply_k_core(&mut three_core, 3);
+        let expected: HashSet<String> = ["a1", "a2", "a3", "a4"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(ids(&three_core), expected);
+        assert_eq!(three_core.edges.len(), 6);
+        let attrs = three_core.nodes[0].attributes.as_ref().unwrap();
+        assert_eq!(attrs["coreness"], 3);
+
+        let mut four_core = clique_with_pendants();
+        apply_k_core(&mut four_core, 4);
+        assert!(four_core.nodes.is_empty());
+    }
+
+    #[test]
+    fn cut_structure_flags_bridges_and_articulation_points() {
+        let mut graph = barbell_graph();
+        // A pendant behind a bridge, and a pendant behind a parallel pair
+        // (which is not a bridge, though its anchor is still a cut vertex).
+        for id in ["c1", "c2"] {
+            graph.nodes.push(make_node(id));
+        }
+        for (source, target) in [("b4", "c1"), ("a2", "c2"), ("c2", "a2")] {
+            graph.edges.push(make_edge(source, target, 1));
+        }
+
+        let annotation = GraphTransform {
+            kind: GraphTransformKind::CutStructure,
+            params: GraphTransformParams::default(),
+        }
+        .apply_to(&mut graph)
+        .expect("cut structure transform should succeed")
+        .expect("annotation");
+        assert!(
+            annotation.contains("Bridges (2): a1_b1, b4_c1"),
+            "{annotation}"
+        );
+
+        let flag = |attributes: &Option<serde_json::Value>, key: &str| {
+            attributes.as_ref().unwrap()[key].as_bool().unwrap()
+        };
+        let articulation: HashSet<&str> = graph
+            .nodes
+            .iter()
+            .filter(|n| flag(&n.attributes, "is_articulation"))
+            .map(|n| n.id.as_str())
+            .collect();
+        assert_eq!(articulation, HashSet::from(["a1", "a2", "b1", "b4"]));
+        let bridges: Vec<&str> = graph
+            .edges
+            .iter()
+            .filter(|e| flag(&e.attributes, "is_bridge"))
+            .map(|e| e.id.as_str())
+            .collect();
+        assert_eq!(bridges, vec!["a1_b1", "b4_c1"]);
+    }
+
+    #[test]
+    fn neighborhood_subgraph_keeps_nodes_within_radius() {
+        let path = || {
+            let ids = ["A", "B", "C", "D", "E"];
+            Graph {
+                name: "Path".to_string(),
+                nodes: ids.map(make_node).to_vec(),
+                edges: ids
+                    .windows(2)
+                    .map(|pair| make_edge(pair[0], pair[1], 1))
+                    .collect(),
+                ..Default::default()
+            }
+        };
+        let transform = |include_boundary_edges: bool| GraphTransform {
+            kind: GraphTransformKind::NeighborhoodSubgraph,
+            params: GraphTransformParams {
+                neighborhood_seed: Some("C".to_string()),
+                neighborhood_radius: Some(1),
+                include_boundary_edges: Some(include_boundary_edges),
+                ..Default::default()
+            },
+        };
+
+        let mut graph = path();
+        transform(false)
+            .apply_to(&mut graph)
+            .expect("neighborhood transform should succeed");
+        let node_ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(node_ids, vec!["B", "C", "D"]);
+        let edge_ids: Vec<&str> = graph.edges.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(edge_ids, vec!["B_C", "C_D"]);
+
+        let mut graph = path();
+        transform(true)
+            .apply_to(&mut graph)
+            .expect("neighborhood transform should succeed");
+        let boundary: Vec<&str> = graph
+            .edges
+            .iter()
+            .filter(|e| e.attributes.as_ref().is_some_and(|a| a["boundary"] == true))
+            .map(|e| e.id.as_str())
+            .collect();
+        assert_eq!(boundary, vec!["A_B", "D_E"]);
+        assert_eq!(graph.edges.len(), 4);
+
+        let mut missing = transform(false);
+        missing.params.neighborhood_seed = Some("Z".to_string());
+        assert!(missing.apply_to(&mut path()).is_err());
+    }
+
+    #[test]
+    fn shortest_path_tree_keeps_one_acyclic_path_per_reachable_node() {
+        let mut graph = clique_with_pendants();
+        // Back edge and a parallel route so the tree has something to drop.
+        for (source, target) in [("p2", "a1"), ("a4", "p1")] {
+            graph.edges.push(make_edge(source, target, 1));
+        }
+
+        let annotation = GraphTransform {
+            kind: GraphTransformKind::ShortestPathTree,
+            params: GraphTransformParams {
+                shortest_path_root: Some("a1".to_string()),
+                ..Default::default()
+            },
+        }
+        .apply_to(&mut graph)
+        .expect("shortest path tree transform should succeed")
+        .expect("annotation");
+        assert!(annotation.contains("Root: a1"));
+
+        let reachable = graph.nodes.iter().filter(|n| !n.is_partition).count();
+        assert!(!graph.nodes.iter().any(|n| n.id == "isolated"));
+        assert_eq!(graph.edges.len(), reachable - 1);
+
+        assert_acyclic(&graph);
+
+        let p2 = graph.nodes.iter().find(|n| n.id == "p2").unwrap();
+        assert_eq!(p2.attributes.as_ref().unwrap()["distance"], 2);
+    }
+
+    #[test]
+    fn minimum_spanning_tree_leaves_one_acyclic_tree_per_component() {
+        let mut graph = clique_with_pendants();
+        for (idx, edge) in graph.edges.iter_mut().enumerate() {
+            edge.set_attribute("cost", serde_json::Value::from(idx));
+        }
+        let components = 2; // the clique with its pendants, and `isolated`
+        let flow_nodes = graph.nodes.iter().filter(|n| !n.is_partition).count();
+
+        let annotation = GraphTransform {
+            kind: GraphTransformKind::MinimumSpanningTree,
+            params: GraphTransformParams {
+                spanning_tree_weight_attribute: Some("cost".to_string()),
+                ..Default::default()
+            },
+        }
+        .apply_to(&mut graph)
+        .expect("spanning tree transform should succeed")
+        .expect("annotation");
+        assert!(annotation.contains("Components: 2"), "{annotation}");
+        assert_eq!(graph.nodes.len(), flow_nodes);
+        assert_eq!(graph.edges.len(), flow_nodes - components);
+
+        assert_acyclic(&graph);
+
+        // The cheapest clique edges form a star around a1; maximising picks
+        // the most expensive ones instead.
+        let ids: Vec<&str> = graph.edges.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["a1_a2", "a1_a3", "a1_a4", "a1_p1", "p1_p2"]);
+
+        let mut graph = clique_with_pendants();
+        for (idx, edge) in graph.edges.iter_mut().enumerate() {
+            edge.set_attribute("cost", serde_json::Value::from(idx));
+        }
+        GraphTransform {
+            kind: GraphTransformKind::MinimumSpanningTree,
+            params: GraphTransformParams {
+                spanning_tree_weight_attribute: Some("cost".to_string()),
+                spanning_tree_maximize: Some(true),
+                ..Default::default()
+            },
+        }
+        .apply_to(&mut graph)
+        .expect("spanning tree transform should succeed");
+        let ids: Vec<&str> = graph.edges.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["a1_a4", "a2_a4", "a3_a4", "a1_p1", "p1_p2"]);
+    }
+
+    #[test]
+    fn node_degree_filter_keeps_only_the_star_hub() {
+        let mut graph = Graph {
+            name: "Star".to_string(),
+            nodes: vec![
+                Node {
+                    is_partition: true,
+                    ..make_node("root")
+                },
+                Node {
+                    is_partition: true,
+                    belongs_to: Some("root".to_string()),
+                    ..make_node("leaves")
+                },
+                Node {
+                    belongs_to: Some("root".to_string()),
+                    ..make_node("hub")
+                },
+            ],
+            ..Default::default()
+        };
+        for leaf in ["l1", "l2", "l3", "l4"] {
+            graph.nodes.push(Node {
+                belongs_to: Some("leaves".to_string()),
+                ..make_node(leaf)
+            });
+            graph.edges.push(make_edge("hub", leaf, 1));
+        }
+
+        let annotation = GraphTransform {
+            kind: GraphTransformKind::NodeDegreeFilter,
+            params: GraphTransformParams {
+                min_degree: Some(2),
+                ..Default::default()
+            },
+        }
+        .apply_to(&mut graph)
+        .expect("degree filter should succeed")
+        .expect("annotation");
+        assert!(annotation.contains("Nodes removed: 4"), "{annotation}");
+        assert!(annotation.contains("Edges removed: 4"), "{annotation}");
+
+        let flow: Vec<&str> = graph
+            .nodes
+            .iter()
+            .filter(|n| !n.is_partition)
+            .map(|n| n.id.as_str())
+            .collect();
+        assert_eq!(flow, vec!["hub"]);
+        assert!(graph.edges.is_empty());
+
+        let leaves = graph.nodes.iter().find(|n| n.id == "leaves").unwrap();
+        assert_eq!(leaves.attributes.as_ref().unwrap()["empty"], true);
+        let root = graph.nodes.iter().find(|n| n.id == "root").unwrap();
+        assert!(root.attributes.is_none());
+    }
+
+    #[test]
+    fn compute_hierarchy_depth_numbers_levels_and_breaks_cycles() {
+        let mut graph = Graph {
+            name: "Hierarchy".to_string(),
+            nodes: [
+                ("leaf", Some("p_mid")),
+                ("p_mid", Some("root")),
+                ("root", None),
+                ("sibling", Some("root")),
+                // A two-node belongs_to loop with no way back to a root.
+                ("x", Some("y")),
+                ("y", Some("x")),
+            ]
+            .map(|(id, parent)| Node {
+                is_partition: parent.is_none() || id.starts_with('p'),
+                belongs_to: parent.map(str::to_string),
+                ..make_node(id)
+            })
+            .to_vec(),
+            ..Default::default()
+        };
+
+        let annotation = GraphTransform {
+            kind: GraphTransformKind::ComputeHierarchyDepth,
+            params: GraphTransformParams::default(),
+        }
+        .apply_to(&mut graph)
+        .expect("hierarchy depth transform should succeed")
+        .expect("annotation");
+        assert!(annotation.contains("Max depth: 2"), "{annotation}");
+        assert!(annotation.contains("Promoted to root: x"), "{annotation}");
+
+        let depth = |id: &str| {
+            let node = graph.nodes.iter().find(|n| n.id == id).unwrap();
+            node.attributes.as_ref().unwrap()["depth"].as_u64().unwrap()
+        };
+        assert_eq!(depth("root"), 0);
+        assert_eq!(depth("p_mid"), 1);
+        assert_eq!(depth("sibling"), 1);
+        assert_eq!(depth("leaf"), 2);
+        assert_eq!(depth("x"), 0);
+        assert_eq!(depth("y"), 1);
+    }
+
+    #[test]
+    fn merge_reciprocal_edges_folds_pairs_into_one_bidirectional_edge() {
+        let reciprocal_graph = || Graph {
+            name: "Reciprocal".to_string(),
+            edges: vec![
+                make_edge("a", "b", 2),
+                make_edge("b", "c", 1),
+                make_edge("b", "a", 3),
+            ],
+            ..Default::default()
+        };
+        let transform = |keep_separate: Option<bool>| GraphTransform {
+            kind: GraphTransformKind::MergeReciprocalEdges,
+            params: GraphTransformParams {
+                keep_reciprocal_edges_separate: keep_separate,
+                ..Default::default()
+            },
+        };
+        let bidirectional = |edge: &Edge| {
+            edge.attributes
+                .as_ref()
+                .is_some_and(|attrs| attrs["bidirectional"] == true)
+        };
+
+        let mut graph = reciprocal_graph();
+        let annotation = transform(None)
+            .apply_to(&mut graph)
+            .expect("merge transform should succeed")
+            .expect("annotation");
+        assert!(annotation.contains("Reciprocal pairs: 1"), "{annotation}");
+        let ids: Vec<&str> = graph.edges.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["a_b", "b_c"]);
+        assert_eq!(graph.edges[0].weight, 5);
+        assert!(bidirectional(&graph.edges[0]));
+        assert!(!bidirectional(&graph.edges[1]));
+
+        let mut graph = reciprocal_graph();
+        transform(Some(true))
+            .apply_to(&mut graph)
+            .expect("merge transform should succeed");
+        assert_eq!(graph.edges.len(), 3);
+        let flagged: Vec<&str> = graph
+            .edges
+            .iter()
+            .filter(|e| bidirectional(e))
+            .map(|e| e.id.as_str())
+            .collect();
+        assert_eq!(flagged, vec!["a_b", "b_a"]);
+        assert_eq!(graph.edges[0].weight, 2);
+    }
+
+    #[test]
+    fn pagerank_matches_hand_computed_scores() {
+        // a splits its rank 3:1 between b and d; d is dangling.
+        let mut graph = Graph {
+            name: "PageRank".to_string(),
+            nodes: ["a", "b", "c", "d"].map(make_node).to_vec(),
+            edges: vec![
+                make_edge("a", "b", 3),
+                make_edge("a", "d", 1),
+                make_edge("b", "c", 1),
+                make_edge("c", "a", 1),
+            ],
+            ..Default::default()
+        };
+
+        let annotation = GraphTransform {
+            kind: GraphTransformKind::PageRank,
+            params: GraphTransformParams {
+                pagerank_damping: Some(0.85),
+                pagerank_iterations: Some(500),
+                ..Default::default()
+            },
+        }
+        .apply_to(&mut graph)
+        .expect("pagerank transform should succeed")
+        .expect("annotation");
+        assert!(annotation.contains("Converged: true"), "{annotation}");
+
+        // With k = 0.15/4 + 0.85 * d / 4 shared by every node:
+        //   a = k + 0.85c, b = k + 0.85 * 0.75a, c = k + 0.85b, d = k + 0.85 * 0.25a
+        // which, with a + b + c + d = 1, solves to the values below.
+        let score = |id: &str| {
+            let node = graph.nodes.iter().find(|n| n.id == id).unwrap();
+            node.attributes.as_ref().unwrap()["pagerank"]
+                .as_f64()
+                .unwrap()
+        };
+        for (id, expected) in [
+            ("a", 0.312583063),
+            ("b", 0.264814660),
+            ("c", 0.290635418),
+            ("d", 0.131966858),
+        ] {
+            assert!((score(id) - expected).abs() < 1e-6, "{id}: {}", score(id));
+        }
+        let total: f64 = ["a", "b", "c", "d"].map(score).iter().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn normalize_edge_weights_per_method() {
+        let graph_with = |weights: &[i32]| Graph {
+            name: "Weights".to_string(),
+            edges: weights
+                .iter()
+                .enumerate()
+                .map(|(idx, weight)| Edge {
+                    id: format!("e{idx}"),
+                    ..make_edge("a", "b", *weight)
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let normalize = |weights: &[i32],
+                         method: WeightNormalization,
+                         range: (i32, i32),
+                         preserve_zero: bool| {
+            let mut graph = graph_with(weights);
+            GraphTransform {
+                kind: GraphTransformKind::NormalizeEdgeWeights,
+                params: GraphTransformParams {
+                    weight_normalization: Some(method),
+                    weight_range_min: Some(range.0),
+                    weight_range_max: Some(range.1),
+                    preserve_zero_weights: Some(preserve_zero),
+                    ..Default::default()
+                },
+            }
+            .apply_to(&mut graph)
+            .expect("normalize transform should succeed");
+            graph.edges.iter().map(|e| e.weight).collect::<Vec<_>>()
+        };
+
+        // MinMax: 10..50 onto 0..100
+        assert_eq!(
+            normalize(
+                &[10, 20, 30, 50],
+                WeightNormalization::MinMax,
+                (0, 100),
+                false
+            ),
+            vec![0, 25, 50, 100]
+        );
+        // Zero weights stay zero and do not stretch the observed range
+        assert_eq!(
+            normalize(&[0, 10, 20], WeightNormalization::MinMax, (1, 11), true),
+            vec![0, 1, 11]
+        );
+        // ZScore: mean 5, population std 2, scores scaled by 100
+        assert_eq!(
+            normalize(
+                &[2, 4, 4, 4, 5, 5, 7, 9],
+                WeightNormalization::ZScore,
+                (0, 100),
+                false
+            ),
+            vec![-150, -50, -50, -50, 0, 0, 100, 200]
+        );
+        // Robust: median 3, IQR 2 (quartiles 2 and 4); the outlier does not shift the scale
+        assert_eq!(
+            normalize(
+                &[1, 2, 3, 4, 100],
+                WeightNormalization::Robust,
+                (0, 100),
+                false
+            ),
+            vec![-100, -50, 0, 50, 4850]
+        );
+        // Uniform weights go to the range midpoint for every method
+        for method in [
+            WeightNormalization::MinMax,
+            WeightNormalization::ZScore,
+            WeightNormalization::Robust,
+        ] {
+            assert_eq!(normalize(&[7, 7, 7], method, (0, 10), false), vec![5, 5, 5]);
+        }
+
+        let mut graph = graph_with(&[1, 2]);
+        assert!(graph
+            .normalize_edge_weights(WeightNormalization::MinMax, (10, 1), false)
+            .is_err());
+    }
+
+    #[test]
+    fn normalize_node_ids_rewires_edges_and_belongs_to() {
+        let mut graph = Graph {
+            name: "Merged".to_string(),
+            nodes: [
+                ("Team A", None),
+                ("team-a", Some("Team A")),
+                ("Svc.1", Some("Team A")),
+            ]
+            .map(|(id, parent)| Node {
+                is_partition: parent.is_none(),
+                belongs_to: parent.map(str::to_string),
+                ..make_node(id)
+            })
+            .to_vec(),
+            edges: vec![Edge {
+                id: "e1".to_string(),
+                ..make_edge("team-a", "Svc.1", 1)
+            }],
+            layers: vec![],
+            annotations: None,
+        };
+
+        let annotation = GraphTransform {
+            kind: GraphTransformKind::NormalizeNodeIds,
+            params: GraphTransformParams {
+                node_id_strategy: Some(NodeIdStrategy::Slugify),
+                ..Default::default()
+            },
+        }
+        .apply_to(&mut graph)
+        .expect("normalize transform should succeed")
+        .expect("annotation");
+        assert!(annotation.contains("| team-a | team_a_2 |"), "{annotation}");
+
+        let ids: Vec<_> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["team_a", "team_a_2", "svc_1"]);
+        assert_eq!(graph.edges[0].source, "team_a_2");
+        assert_eq!(graph.edges[0].target, "svc_1");
+        assert!(graph.nodes[1..]
+            .iter()
+            .all(|n| n.belongs_to.as_deref() == Some("team_a")));
+
+        let mapping = graph
+            .normalize_node_ids(NodeIdStrategy::Sequential, None)
+            .unwrap();
+        assert_eq!(mapping[2], ("svc_1".to_string(), "n3".to_string()));
+        assert_eq!(graph.edges[0].source, "n2");
+        assert_eq!(graph.nodes[2].belongs_to.as_deref(), Some("n1"));
+
+        assert!(graph
+            .normalize_node_ids(NodeIdStrategy::Prefixed, None)
+            .is_err());
+        graph
+            .normalize_node_ids(NodeIdStrategy::Prefixed, Some("org_"))
+            .unwrap();
+        assert_eq!(graph.edges[0].target, "org_n3");
+    }
+
     #[test]
     fn apply_transforms_runs_aggregate_when_present() {
         let mut graph = sample_graph();
@@ -2,8 +2,10 @@ use crate::data_loader;
 use crate::graph::{Edge, Graph, Layer, Node};
 use crate::plan::{ExportFileType, ExportProfileItem, ImportFileType, Plan};
 use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::Path;
-use std::sync::mpsc::channel;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
 use anyhow::{anyhow, Result};
@@ -66,49 +68,7 @@ fn load_data_into_graph(graph: &mut Graph, plan: &Plan, plan_file_path: &Path) -
             profile.filetype
         );
 
-        let file_path_str = import_file_path.to_str().ok_or_else(|| {
-            anyhow!(
-                "Import file path contains invalid UTF-8: {}",
-                import_file_path.display()
-            )
-        })?;
-        let (headers, records) = load_file(file_path_str)?;
-
-        match profile.filetype {
-            ImportFileType::Nodes => {
-                let node_profile = data_loader::create_df_node_load_profile(&headers);
-                info!("{}", node_profile);
-                data_loader::verify_nodes_headers(&headers)?;
-                data_loader::verify_id_column(&records, node_profile.id_column)?;
-
-                for record in &records {
-                    match Node::from_row(record, &node_profile) {
-                        Ok(node) => graph.nodes.push(node),
-                        Err(e) => return Err(anyhow::anyhow!("Error creating node: {}", e)),
-                    };
-                }
-            }
-            ImportFileType::Edges => {
-                // TODO Add verification for edges
-                let edge_profile = data_loader::create_df_edge_load_profile(&headers);
-                info!("{}", edge_profile);
-                for record in &records {
-                    match Edge::from_row(record, &edge_profile) {
-                        Ok(edge) => graph.edges.push(edge),
-                        Err(e) => return Err(anyhow::anyhow!("Error creating edge: {}", e)),
-                    };
-                }
-            }
-            ImportFileType::Layers => {
-                // TODO Add verification for layers
-                for record in &records {
-                    match Layer::from_row(record) {
-                        Ok(layer) => graph.layers.push(layer.clone()),
-                        Err(e) => return Err(anyhow::anyhow!("Error creating layer: {}", e)),
-                    };
-                }
-            }
-        }
+        import_file(graph, &import_file_path, &profile.filetype)?;
     }
 
     info!(
@@ -121,6 +81,115 @@ fn load_data_into_graph(graph: &mut Graph, plan: &Plan, plan_file_path: &Path) -
     Ok(())
 }
 
+/// Loads one nodes, edges or layers file into the graph
+fn import_file(
+    graph: &mut Graph,
+    import_file_path: &Path,
+    filetype: &ImportFileType,
+) -> Result<()> {
+    let file_path_str = import_file_path.to_str().ok_or_else(|| {
+        anyhow!(
+            "Import file path contains invalid UTF-8: {}",
+            import_file_path.display()
+        )
+    })?;
+    let (headers, records) = load_file(file_path_str)?;
+
+    match filetype {
+        ImportFileType::Nodes => {
+            let node_profile = data_loader::create_df_node_load_profile(&headers);
+            info!("{}", node_profile);
+            data_loader::verify_nodes_headers(&headers)?;
+            data_loader::verify_id_column(&records, node_profile.id_column)?;
+
+            for record in &records {
+                match Node::from_row(record, &node_profile) {
+                    Ok(node) => graph.nodes.push(node),
+                    Err(e) => return Err(anyhow::anyhow!("Error creating node: {}", e)),
+                };
+            }
+        }
+        ImportFileType::Edges => {
+            // TODO Add verification for edges
+            let edge_profile = data_loader::create_df_edge_load_profile(&headers);
+            info!("{}", edge_profile);
+            for record in &records {
+                match Edge::from_row(record, &edge_profile) {
+                    Ok(edge) => graph.edges.push(edge),
+                    Err(e) => return Err(anyhow::anyhow!("Error creating edge: {}", e)),
+                };
+            }
+        }
+        ImportFileType::Layers => {
+            // TODO Add verification for layers
+            for record in &records {
+                match Layer::from_row(record) {
+                    Ok(layer) => graph.layers.push(layer.clone()),
+                    Err(e) => return Err(anyhow::anyhow!("Error creating layer: {}", e)),
+                };
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Load a graph for a one-off export outside a plan
+///
+/// `input` is either a graph JSON file (the shape data sets store, e.g. a
+/// `.layercake` or `.json` file) or a directory holding `nodes`, `edges` (or
+/// `links`) and optional `layers` files as CSV or TSV.
+pub fn load_graph(input: &Path) -> Result<Graph> {
+    if input.is_dir() {
+        return load_graph_from_dir(input);
+    }
+
+    let content = std::fs::read_to_string(input)
+        .map_err(|e| anyhow!("Failed to read {}: {}", input.display(), e))?;
+    let mut graph = graph_from_json(&content)?;
+    if graph.name.is_empty() {
+        if let Some(stem) = input.file_stem().and_then(|stem| stem.to_str()) {
+            graph.name = stem.to_string();
+        }
+    }
+    Ok(graph)
+}
+
+/// Parse graph JSON, treating missing `nodes`, `edges` or `layers` as empty
+pub fn graph_from_json(content: &str) -> Result<Graph> {
+    crate::graph_merge::graph_from_data_set_json(content)
+        .map_err(|e| anyhow!("Failed to parse graph JSON: {}", e))
+}
+
+fn load_graph_from_dir(dir: &Path) -> Result<Graph> {
+    let find = |stems: &[&str]| {
+        stems
+            .iter()
+            .flat_map(|stem| ["csv", "tsv"].map(|ext| dir.join(format!("{}.{}", stem, ext))))
+            .find(|path| path.is_file())
+    };
+
+    let mut graph = Graph {
+        name: dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("Unnamed Graph")
+            .to_string(),
+        ..Default::default()
+    };
+    let nodes = find(&["nodes"])
+        .ok_or_else(|| anyhow!("No nodes.csv or nodes.tsv in {}", dir.display()))?;
+    import_file(&mut graph, &nodes, &ImportFileType::Nodes)?;
+    if let Some(edges) = find(&["edges", "links"]) {
+        import_file(&mut graph, &edges, &ImportFileType::Edges)?;
+    }
+    if let Some(layers) = find(&["layers"]) {
+        import_file(&mut graph, &layers, &ImportFileType::Layers)?;
+    }
+
+    Ok(graph)
+}
+
 /// Applies transformations to the graph based on the profile configuration
 fn apply_graph_transformations(
     graph: &mut Graph,
@@ -212,16 +281,58 @@ fn export_graph(graph: &Graph, profile: &ExportProfileItem) -> Result<()> {
 
     let render_config = profile.get_render_config();
 
-    let result = match &profile.exporter {
+    match render_export(graph, &profile.exporter, &render_config) {
+        Ok(output) => {
+            if let Err(e) = crate::common::write_bytes_to_file(&profile.filename, &output) {
+                error!("Failed to write to file {}: {}", profile.filename, e);
+            }
+        }
+        Err(e) => {
+            error!("Failed to export file {}: {}", profile.filename, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Render `graph` with a single exporter using the render defaults of an
+/// export profile without `render_config`, for one-off exports outside a plan
+pub fn render_adhoc_export(graph: &Graph, exporter: &ExportFileType) -> Result<Vec<u8>> {
+    let profile = ExportProfileItem {
+        filename: String::new(),
+        exporter: exporter.clone(),
+        render_config: None,
+        graph_config: None,
+    };
+    render_export(graph, exporter, &profile.get_render_config())
+        .map_err(|e| anyhow!("{:?} export failed: {}", exporter, e))
+}
+
+/// Runs the renderer for `exporter`, returning the file contents
+fn render_export(
+    graph: &Graph,
+    exporter: &ExportFileType,
+    render_config: &crate::plan::RenderConfig,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    render_config.check_supported_by(exporter)?;
+    let render_config = render_config.clone();
+    let result = match exporter {
         ExportFileType::GML => crate::export::to_gml::render(graph, &render_config),
         ExportFileType::DOT => crate::export::to_dot::render(graph, &render_config),
         ExportFileType::DOTHierarchy => {
             crate::export::to_dot_hierarchy::render(graph, &render_config)
         }
         ExportFileType::JSON => crate::export::to_json::render(graph, &render_config),
+        ExportFileType::JSONHierarchy => {
+            crate::export::to_json_hierarchy::render(graph, &render_config)
+        }
+        ExportFileType::JSONL => crate::export::to_jsonl::render(graph, &render_config),
         ExportFileType::CSVNodes => crate::export::to_csv_nodes::render(graph, &render_config),
         ExportFileType::CSVEdges => crate::export::to_csv_edges::render(graph, &render_config),
         ExportFileType::CSVMatrix => crate::export::to_csv_matrix::render(graph, &render_config),
+        ExportFileType::CSVAdjacency => {
+            crate::export::to_csv_adjacency::render(graph, &render_config)
+        }
         ExportFileType::PlantUML => crate::export::to_plantuml::render(graph, &render_config),
         ExportFileType::PlantUmlMindmap => {
             crate::export::to_plantuml_mindmap::render(graph, &render_config)
@@ -237,23 +348,16 @@ fn export_graph(graph: &Graph, profile: &ExportProfileItem) -> Result<()> {
             crate::export::to_mermaid_treemap::render(graph, &render_config)
         }
         ExportFileType::JSGraph => crate::export::to_jsgraph::render(graph, &render_config),
+        ExportFileType::SVG => crate::export::to_svg::render(graph, &render_config),
         ExportFileType::Custom(template_config) => {
             crate::export::to_custom::render(graph, &render_config, template_config)
         }
-    };
-
-    match result {
-        Ok(output) => {
-            if let Err(e) = crate::common::write_string_to_file(&profile.filename, &output) {
-                error!("Failed to write to file {}: {}", profile.filename, e);
-            }
-        }
-        Err(e) => {
-            error!("Failed to export file {}: {}", profile.filename, e);
+        ExportFileType::ParquetNodes | ExportFileType::ParquetEdges => {
+            return crate::export::render_bytes(graph, exporter, &render_config);
         }
-    }
+    };
 
-    Ok(())
+    result.map(String::into_bytes)
 }
 
 /// Executes a single export plan
@@ -304,57 +408,191 @@ fn run_plan(plan: Plan, plan_file_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Default window in which filesystem events are coalesced into one rebuild
+pub const DEFAULT_WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
 /// Main function to execute a plan, with optional file watching
 pub fn execute_plan(plan: String, watch: bool) -> Result<()> {
+    execute_plan_with_debounce(plan, watch, DEFAULT_WATCH_DEBOUNCE)
+}
+
+/// Execute a plan; in watch mode, changes within `debounce` of each other
+/// trigger a single re-execution.
+pub fn execute_plan_with_debounce(plan: String, watch: bool, debounce: Duration) -> Result<()> {
     info!("Executing plan {}", plan);
 
     let plan_file_path = std::path::Path::new(&plan);
-    let path_content = std::fs::read_to_string(plan_file_path)?;
-    let plan: Plan = serde_yaml::from_str(&path_content)?;
+    let plan = load_plan(plan_file_path)?;
 
     debug!("Executing plan: {:?}", plan);
     run_plan(plan.clone(), plan_file_path)?;
 
     if watch {
-        watch_for_changes(plan, plan_file_path)?;
+        watch_for_changes(plan, plan_file_path, debounce)?;
     }
 
     Ok(())
 }
 
-/// Sets up file watching for input files to re-run the plan on changes
-fn watch_for_changes(plan: Plan, plan_file_path: &Path) -> Result<()> {
-    info!("Watching for changes");
-    let files: Vec<String> = plan
+/// Read and parse a plan file, expanding environment variables in its paths
+fn load_plan(plan_file_path: &Path) -> Result<Plan> {
+    let content = std::fs::read_to_string(plan_file_path)?;
+    let mut plan: Plan = serde_yaml::from_str(&content)?;
+    plan.interpolate_paths()?;
+    Ok(plan)
+}
+
+/// The plan file plus every imported data file, resolved against the plan's directory
+fn watched_files(plan: &Plan, plan_file_path: &Path) -> Result<HashSet<PathBuf>> {
+    let parent_dir = plan_file_path
+        .parent()
+        .ok_or_else(|| anyhow!("Plan file has no parent directory"))?;
+    let mut files: HashSet<PathBuf> = plan
         .import
         .profiles
         .iter()
-        .map(|profile| profile.filename.clone())
+        .map(|profile| normalise_watch_path(&parent_dir.join(&profile.filename)))
         .collect();
+    files.insert(normalise_watch_path(plan_file_path));
+    Ok(files)
+}
+
+/// Absolute path that still resolves while the file is briefly missing
+/// mid-save, by canonicalising the directory rather than the file.
+fn normalise_watch_path(path: &Path) -> PathBuf {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let parent = parent
+        .canonicalize()
+        .unwrap_or_else(|_| parent.to_path_buf());
+    match path.file_name() {
+        Some(name) => parent.join(name),
+        None => parent,
+    }
+}
+
+/// Collect `first` plus any items arriving within `window` of the previous one.
+fn debounce_batch<T>(rx: &Receiver<T>, first: T, window: Duration) -> Vec<T> {
+    let mut batch = vec![first];
+    while let Ok(next) = rx.recv_timeout(window) {
+        batch.push(next);
+    }
+    batch
+}
+
+fn is_relevant_event(event: &notify::Event, files: &HashSet<PathBuf>) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) && event
+        .paths
+        .iter()
+        .any(|path| files.contains(&normalise_watch_path(path)))
+}
+
+/// Sets up file watching for the plan and its input files to re-run the plan
+/// on changes. Directories are watched rather than files so editors that save
+/// atomically (write temp file, rename over) don't drop the watch.
+fn watch_for_changes(plan: Plan, plan_file_path: &Path, debounce: Duration) -> Result<()> {
+    info!("Watching for changes");
+    let mut plan = plan;
+    let mut files = watched_files(&plan, plan_file_path)?;
 
     let (tx, rx) = channel();
     let mut watcher = RecommendedWatcher::new(tx, Config::default())?;
-    for file in &files {
-        let parent_dir = plan_file_path
-            .parent()
-            .ok_or_else(|| anyhow!("Plan file has no parent directory"))?;
-        let path = parent_dir.join(file);
-        watcher.watch(&path, RecursiveMode::NonRecursive)?;
-    }
+    let mut watched_dirs: HashSet<PathBuf> = HashSet::new();
+    let mut watch_dirs = |watcher: &mut RecommendedWatcher, files: &HashSet<PathBuf>| {
+        for dir in files.iter().filter_map(|file| file.parent()) {
+            if watched_dirs.contains(dir) {
+                continue;
+            }
+            match watcher.watch(dir, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    watched_dirs.insert(dir.to_path_buf());
+                }
+                Err(e) => warn!("Unable to watch {}: {}", dir.display(), e),
+            }
+        }
+    };
+    watch_dirs(&mut watcher, &files);
 
     loop {
-        match rx.recv() {
-            Ok(event) => {
-                // debug!("Event: {:?}", event);
-                if let Ok(event) = event {
-                    if let EventKind::Modify(_) = event.kind {
-                        debug!("File modified {:?}", event.paths);
-                        info!("Change detected, re-executing plan");
-                        run_plan(plan.clone(), plan_file_path)?;
-                    }
+        let first = match rx.recv() {
+            Ok(event) => event,
+            Err(_) => return Err(anyhow!("File watcher stopped unexpectedly")),
+        };
+
+        let batch = debounce_batch(&rx, first, debounce);
+        let mut changed = false;
+        for event in batch {
+            match event {
+                Ok(event) if is_relevant_event(&event, &files) => {
+                    debug!("File changed {:?}", event.paths);
+                    changed = true;
                 }
+                Ok(_) => {}
+                // Transient errors (e.g. a file vanishing mid-save) shouldn't end the watch
+                Err(e) => warn!("Watch error: {:?}", e),
+            }
+        }
+        if !changed {
+            continue;
+        }
+
+        info!("Rebuilding…");
+        match load_plan(plan_file_path) {
+            Ok(updated) => {
+                plan = updated;
+                files = watched_files(&plan, plan_file_path)?;
+                watch_dirs(&mut watcher, &files);
             }
-            Err(e) => error!("Watch error: {:?}", e),
+            Err(e) => warn!("Unable to reload plan, re-running previous version: {}", e),
         }
+
+        if let Err(e) = run_plan(plan.clone(), plan_file_path) {
+            error!("Plan execution failed: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn debounce_batch_coalesces_rapid_events() {
+        let (tx, rx) = channel();
+        for event in ["temp", "rename", "write"] {
+            tx.send(event).unwrap();
+        }
+        let first = rx.recv().unwrap();
+
+        let started = Instant::now();
+        let batch = debounce_batch(&rx, first, Duration::from_millis(50));
+        assert_eq!(batch, vec!["temp", "rename", "write"]);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn relevant_events_match_watched_files_through_atomic_saves() {
+        let dir = std::env::temp_dir();
+        let plan_path = dir.join("plan.yaml");
+        let nodes = normalise_watch_path(&dir.join("nodes.csv"));
+        let files: HashSet<PathBuf> = [normalise_watch_path(&plan_path), nodes.clone()]
+            .into_iter()
+            .collect();
+
+        // nodes.csv need not exist (mid atomic save) to match.
+        let event = notify::Event::new(EventKind::Create(notify::event::CreateKind::File))
+            .add_path(dir.join("nodes.csv"));
+        assert!(is_relevant_event(&event, &files));
+
+        let unrelated = notify::Event::new(EventKind::Modify(notify::event::ModifyKind::Any))
+            .add_path(dir.join(".nodes.csv.swp"));
+        assert!(!is_relevant_event(&unrelated, &files));
     }
 }
@@ -998,6 +998,7 @@ mod graph_source_tests {
         projects::ActiveModel {
             id: Set(1), name: Set("P".into()), description: Set(None), tags: Set("[]".into()),
             import_export_path: Set(None), created_at: Set(Utc::now().into()), updated_at: Set(Utc::now().into()),
+            deleted_at: Set(None),
         }.insert(&db).await.unwrap();
 
         // A computed graph (graph_data id 500) with 2 nodes + 1 edge.
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+
+use crate::database::entities::collaboration_events::{self, Entity as CollaborationEvents};
+use crate::errors::{CoreError, CoreResult};
+
+/// Service for the persisted collaboration event log
+///
+/// Events are stored as opaque serialised payloads keyed by their broadcast
+/// channel (`plan_id`) so subscribers can replay recent history on connect.
+pub struct CollaborationEventService {
+    db: DatabaseConnection,
+}
+
+impl CollaborationEventService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Store one event. `created_at` should be the event's own timestamp so
+    /// replay order matches broadcast order.
+    pub async fn record(
+        &self,
+        event_id: String,
+        plan_id: String,
+        user_id: String,
+        event_type: String,
+        payload: String,
+        created_at: DateTime<Utc>,
+    ) -> CoreResult<collaboration_events::Model> {
+        collaboration_events::ActiveModel {
+            id: ActiveValue::NotSet,
+            event_id: Set(event_id),
+            plan_id: Set(plan_id),
+            user_id: Set(user_id),
+            event_type: Set(event_type),
+            payload: Set(payload),
+            created_at: Set(created_at),
+        }
+        .insert(&self.db)
+        .await
+        .map_err(|e| CoreError::internal(format!("Failed to record collaboration event: {}", e)))
+    }
+
+    /// The last `limit` events for a plan, oldest first.
+    pub async fn recent(
+        &self,
+        plan_id: &str,
+        limit: u64,
+    ) -> CoreResult<Vec<collaboration_events::Model>> {
+        let mut events = CollaborationEvents::find()
+            .filter(collaboration_events::Column::PlanId.eq(plan_id))
+            .order_by_desc(collaboration_events::Column::CreatedAt)
+            .order_by_desc(collaboration_events::Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .map_err(|e| {
+                CoreError::internal(format!("Failed to load collaboration events: {}", e))
+            })?;
+        events.reverse();
+        Ok(events)
+    }
+
+    /// Delete events created before `cutoff`, returning how many were removed.
+    pub async fn prune_older_than(&self, cutoff: DateTime<Utc>) -> CoreResult<u64> {
+        let result = CollaborationEvents::delete_many()
+            .filter(collaboration_events::Column::CreatedAt.lt(cutoff))
+            .exec(&self.db)
+            .await
+            .map_err(|e| {
+                CoreError::internal(format!("Failed to prune collaboration events: {}", e))
+            })?;
+        Ok(result.rows_affected)
+    }
+}
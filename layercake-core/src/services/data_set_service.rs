@@ -1,14 +1,17 @@
+use chrono::{DateTime, Utc};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
-    TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
 };
 use std::collections::{HashMap, HashSet};
 
 use crate::app_context::DataSetValidationSummary;
+use crate::data_loader::RowError;
 use crate::database::entities::common_types::{DataType, FileFormat};
 use crate::database::entities::data_sets::{self};
 use crate::database::entities::{graph_data, graph_data_edges, graph_data_nodes};
-use crate::database::entities::{plan_dag_edges, plan_dag_nodes, projects};
+use crate::database::entities::{plan_dag_edges, plan_dag_nodes, plans, projects};
+use crate::dataset_schema::DatasetSchema;
 use crate::errors::{CoreError, CoreResult};
 use crate::graph::{Edge, Graph, Layer, Node};
 use crate::services::{file_type_detection, source_processing};
@@ -101,6 +104,36 @@ impl DataSetService {
         file_data: Vec<u8>,
         tabular_data_type: Option<DataType>,
     ) -> CoreResult<data_sets::Model> {
+        self.create_from_file_with_validation(
+            project_id,
+            name,
+            description,
+            filename,
+            file_format,
+            file_data,
+            tabular_data_type,
+            None,
+        )
+        .await
+        .map(|(data_set, _)| data_set)
+    }
+
+    /// Create a new DataSet from uploaded file data, checking delimited node
+    /// and edge rows one by one when `strict` is given. Strict uploads fail on
+    /// any bad row; otherwise bad rows are skipped, returned, and recorded in
+    /// a "Skipped rows" annotation on the data set.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_from_file_with_validation(
+        &self,
+        project_id: i32,
+        name: String,
+        description: Option<String>,
+        filename: String,
+        file_format: FileFormat,
+        file_data: Vec<u8>,
+        tabular_data_type: Option<DataType>,
+        strict: Option<bool>,
+    ) -> CoreResult<(data_sets::Model, Vec<RowError>)> {
         // Validate project exists
         let _project = projects::Entity::find_by_id(project_id)
             .one(&self.db)
@@ -149,37 +182,65 @@ impl DataSetService {
             .map_err(|e| CoreError::internal(format!("Failed to create data set: {}", e)))?;
 
         // Process the file
-        let updated_data_set =
-            match source_processing::process_file(&file_format, &resolved_data_type, &file_data)
+        let processed = match strict {
+            Some(strict) => {
+                source_processing::process_file_validated(
+                    &file_format,
+                    &resolved_data_type,
+                    &file_data,
+                    strict,
+                )
                 .await
-            {
-                Ok(graph_json) => {
-                    // Update with successful processing
-                    let mut active_model: data_sets::ActiveModel = data_set.into();
-                    active_model.graph_json = Set(graph_json);
-                    active_model.status = Set("active".to_string());
-                    active_model.processed_at = Set(Some(chrono::Utc::now()));
-                    active_model.updated_at = Set(chrono::Utc::now());
-
-                    active_model.update(&self.db).await.map_err(|e| {
-                        CoreError::internal(format!("Failed to update data set: {}", e))
-                    })?
+            }
+            None => source_processing::process_file(&file_format, &resolved_data_type, &file_data)
+                .await
+                .map(|graph_json| (graph_json, Vec::new())),
+        };
+        let (updated_data_set, row_errors) = match processed {
+            Ok((graph_json, row_errors)) => {
+                // Update with successful processing
+                let mut active_model: data_sets::ActiveModel = data_set.into();
+                active_model.graph_json = Set(graph_json);
+                if !row_errors.is_empty() {
+                    let body = row_errors
+                        .iter()
+                        .map(|error| format!("- {}", error))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    let annotations = vec![DataSetAnnotation {
+                        title: "Skipped rows".to_string(),
+                        date: chrono::Utc::now(),
+                        body,
+                    }];
+                    active_model.annotations =
+                        Set(Some(serde_json::to_string(&annotations).map_err(|e| {
+                            CoreError::internal(format!("Failed to serialize annotations: {}", e))
+                        })?));
                 }
-                Err(e) => {
-                    // Update with error
-                    let mut active_model: data_sets::ActiveModel = data_set.into();
-                    active_model.status = Set("error".to_string());
-                    active_model.error_message = Set(Some(e.to_string()));
-                    active_model.updated_at = Set(chrono::Utc::now());
+                active_model.status = Set("active".to_string());
+                active_model.processed_at = Set(Some(chrono::Utc::now()));
+                active_model.updated_at = Set(chrono::Utc::now());
 
-                    let _updated = active_model.update(&self.db).await.map_err(|e| {
-                        CoreError::internal(format!("Failed to update data set: {}", e))
-                    })?;
-                    return Err(CoreError::validation(e.to_string()));
-                }
-            };
+                let updated = active_model.update(&self.db).await.map_err(|e| {
+                    CoreError::internal(format!("Failed to update data set: {}", e))
+                })?;
+                (updated, row_errors)
+            }
+            Err(e) => {
+                // Update with error
+                let mut active_model: data_sets::ActiveModel = data_set.into();
+                active_model.status = Set("error".to_string());
+                active_model.error_message = Set(Some(e.to_string()));
+                active_model.updated_at = Set(chrono::Utc::now());
 
-        Ok(updated_data_set)
+                let _updated = active_model.update(&self.db).await.map_err(|e| {
+                    CoreError::internal(format!("Failed to update data set: {}", e))
+                })?;
+                return Err(CoreError::validation(e.to_string()));
+            }
+        };
+
+        Ok((updated_data_set, row_errors))
     }
 
     /// Get DataSet by ID
@@ -454,6 +515,29 @@ impl DataSetService {
         Ok(updated)
     }
 
+    /// Replace all annotations (graph_json untouched), e.g. when restoring an import
+    pub async fn replace_annotations(
+        &self,
+        id: i32,
+        annotations: Vec<DataSetAnnotation>,
+    ) -> CoreResult<data_sets::Model> {
+        let data_set = self
+            .get_by_id(id)
+            .await?
+            .ok_or_else(|| CoreError::not_found("DataSet", id.to_string()))?;
+
+        let mut active_model: data_sets::ActiveModel = data_set.into();
+        active_model.annotations = Set(Some(serde_json::to_string(&annotations).map_err(|e| {
+            CoreError::internal(format!("Failed to serialize annotations: {}", e))
+        })?));
+        active_model.updated_at = Set(chrono::Utc::now());
+
+        active_model
+            .update(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to update data set: {}", e)))
+    }
+
     pub async fn get_graph_summary(&self, dataset_id: i32) -> CoreResult<GraphSummaryData> {
         // Dataset graph content is stored as the parsed graph on the data_sets row
         // (`graph_json`); the legacy dataset_graph_* tables have been dropped.
@@ -662,6 +746,101 @@ impl DataSetService {
         Ok(())
     }
 
+    /// DataSets in a project that no plan DAG node references through a
+    /// `dataSetId` in its config, across all of the project's plans. DataSets
+    /// created at or after `created_before` are skipped so freshly uploaded
+    /// data that is not wired up yet is never reported.
+    pub async fn find_orphaned(
+        &self,
+        project_id: i32,
+        created_before: DateTime<Utc>,
+    ) -> CoreResult<Vec<data_sets::Model>> {
+        Self::orphaned_in(&self.db, project_id, created_before).await
+    }
+
+    /// Delete exactly the DataSets `find_orphaned` reports, returning their IDs.
+    pub async fn delete_orphaned(
+        &self,
+        project_id: i32,
+        created_before: DateTime<Utc>,
+    ) -> CoreResult<Vec<i32>> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to begin transaction: {}", e)))?;
+
+        let ids: Vec<i32> = Self::orphaned_in(&txn, project_id, created_before)
+            .await?
+            .into_iter()
+            .map(|data_set| data_set.id)
+            .collect();
+
+        if !ids.is_empty() {
+            data_sets::Entity::delete_many()
+                .filter(data_sets::Column::Id.is_in(ids.clone()))
+                .exec(&txn)
+                .await
+                .map_err(|e| {
+                    CoreError::internal(format!("Failed to delete orphaned data sets: {}", e))
+                })?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(ids)
+    }
+
+    async fn orphaned_in<C: ConnectionTrait>(
+        conn: &C,
+        project_id: i32,
+        created_before: DateTime<Utc>,
+    ) -> CoreResult<Vec<data_sets::Model>> {
+        let plan_ids: Vec<i32> = plans::Entity::find()
+            .filter(plans::Column::ProjectId.eq(project_id))
+            .all(conn)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load plans: {}", e)))?
+            .into_iter()
+            .map(|plan| plan.id)
+            .collect();
+
+        let referenced: HashSet<i32> = plan_dag_nodes::Entity::find()
+            .filter(plan_dag_nodes::Column::PlanId.is_in(plan_ids))
+            .all(conn)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load plan DAG nodes: {}", e)))?
+            .iter()
+            .filter_map(|node| {
+                serde_json::from_str::<serde_json::Value>(&node.config_json)
+                    .ok()?
+                    .get("dataSetId")?
+                    .as_i64()
+            })
+            .map(|id| id as i32)
+            .collect();
+
+        let candidates = data_sets::Entity::find()
+            .filter(data_sets::Column::ProjectId.eq(project_id))
+            .filter(data_sets::Column::CreatedAt.lt(created_before))
+            .order_by_asc(data_sets::Column::Name)
+            .all(conn)
+            .await
+            .map_err(|e| {
+                CoreError::internal(format!(
+                    "Failed to load data sets for project {}: {}",
+                    project_id, e
+                ))
+            })?;
+
+        Ok(candidates
+            .into_iter()
+            .filter(|data_set| !referenced.contains(&data_set.id))
+            .collect())
+    }
+
     /// Reprocess existing DataSet file
     pub async fn reprocess(&self, id: i32) -> CoreResult<data_sets::Model> {
         let data_set = self
@@ -736,7 +915,56 @@ impl DataSetService {
             .get_by_id(id)
             .await?
             .ok_or_else(|| CoreError::not_found("DataSet", id.to_string()))?;
+        let graph = Self::validation_graph(&model)?;
+
+        let mut errors = Vec::new();
+        let warnings = graph.edge_integrity_warnings();
+
+        if let Err(mut validation_errors) = graph.verify_graph_integrity() {
+            errors.append(&mut validation_errors);
+        }
+
+        let partition_lookup: HashMap<_, _> = graph
+            .nodes
+            .iter()
+            .map(|node| (node.id.clone(), node.is_partition))
+            .collect();
 
+        for node in &graph.nodes {
+            if let Some(parent_id) = &node.belongs_to {
+                if let Some(is_partition) = partition_lookup.get(parent_id) {
+                    if !is_partition {
+                        errors.push(format!(
+                            "Node id:[{}] belongs_to {} but parent is not marked as a partition node",
+                            node.id, parent_id
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(Self::validation_summary(&model, &graph, errors, warnings))
+    }
+
+    /// Check a dataset's nodes and edges against `schema` without modifying it
+    pub async fn validate_against_schema(
+        &self,
+        id: i32,
+        schema: &DatasetSchema,
+    ) -> CoreResult<DataSetValidationSummary> {
+        let model = self
+            .get_by_id(id)
+            .await?
+            .ok_or_else(|| CoreError::not_found("DataSet", id.to_string()))?;
+        let graph = Self::validation_graph(&model)?;
+        let errors = schema.validate(&graph);
+
+        Ok(Self::validation_summary(&model, &graph, errors, Vec::new()))
+    }
+
+    /// Parse the stored graph, filling in the dataset name and any layers the
+    /// nodes reference when the JSON has none.
+    fn validation_graph(model: &data_sets::Model) -> CoreResult<Graph> {
         let mut graph: Graph = if model.graph_json.trim().is_empty() {
             Graph::default()
         } else {
@@ -766,33 +994,16 @@ impl DataSetService {
             }
         }
 
-        let mut errors = Vec::new();
-        let warnings = Vec::new();
-
-        if let Err(mut validation_errors) = graph.verify_graph_integrity() {
-            errors.append(&mut validation_errors);
-        }
-
-        let partition_lookup: HashMap<_, _> = graph
-            .nodes
-            .iter()
-            .map(|node| (node.id.clone(), node.is_partition))
-            .collect();
-
-        for node in &graph.nodes {
-            if let Some(parent_id) = &node.belongs_to {
-                if let Some(is_partition) = partition_lookup.get(parent_id) {
-                    if !is_partition {
-                        errors.push(format!(
-                            "Node id:[{}] belongs_to {} but parent is not marked as a partition node",
-                            node.id, parent_id
-                        ));
-                    }
-                }
-            }
-        }
+        Ok(graph)
+    }
 
-        Ok(DataSetValidationSummary {
+    fn validation_summary(
+        model: &data_sets::Model,
+        graph: &Graph,
+        errors: Vec<String>,
+        warnings: Vec<String>,
+    ) -> DataSetValidationSummary {
+        DataSetValidationSummary {
             data_set_id: model.id,
             project_id: model.project_id,
             is_valid: errors.is_empty(),
@@ -802,7 +1013,7 @@ impl DataSetService {
             edge_count: graph.edges.len(),
             layer_count: graph.layers.len(),
             checked_at: chrono::Utc::now(),
-        })
+        }
     }
 
     fn determine_data_type(
@@ -846,7 +1057,7 @@ impl DataSetService {
                     }
                 }
             }
-            FileFormat::Json => Ok(DataType::Graph),
+            FileFormat::Json | FileFormat::Dot => Ok(DataType::Graph),
             other => Err(CoreError::validation(format!(
                 "{} uploads are not supported for this operation",
                 other.as_ref()
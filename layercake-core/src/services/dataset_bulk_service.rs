@@ -1,4 +1,5 @@
 use icu_locale_core::locale;
+use indexmap::IndexMap;
 use rust_xlsxwriter::*;
 use sea_orm::{DatabaseConnection, EntityTrait};
 use spreadsheet_ods::{Sheet, Value, WorkBook};
@@ -7,12 +8,32 @@ use std::collections::HashSet;
 use crate::database::entities::common_types::{DataType, FileFormat};
 use crate::database::entities::data_sets;
 use crate::errors::{CoreError, CoreResult};
-use crate::services::data_set_service::DataSetService;
+use crate::services::data_set_service::{DataSetAnnotation, DataSetService};
+use crate::services::source_processing::NODE_COLUMNS;
+
+/// Header row of the per-dataset annotations sheet. The hidden `sheets` column
+/// lists the exact names of the dataset's data sheets, since sheet names may be
+/// truncated or de-duplicated and cannot be matched back by prefix.
+const ANNOTATION_HEADERS: [&str; 4] = ["title", "date", "body", "sheets"];
+const ANNOTATION_SHEET_SUFFIX: &str = "Annotations";
+/// Fixed sheets of the per-layer XLSX export
+const LAYER_SUMMARY_SHEET: &str = "summary";
+const LAYER_EDGES_SHEET: &str = "edges";
+/// Characters Excel rejects in worksheet names
+const INVALID_SHEET_NAME_CHARS: [char; 7] = ['[', ']', ':', '*', '?', '/', '\\'];
 
 pub struct DataSetBulkService {
     db: DatabaseConnection,
 }
 
+/// An annotations sheet read during import, applied once its datasets exist
+struct PendingAnnotations {
+    sheet_name: String,
+    /// Data sheets the annotations belong to
+    data_sheets: Vec<String>,
+    annotations: Vec<DataSetAnnotation>,
+}
+
 impl DataSetBulkService {
     pub fn new(db: DatabaseConnection) -> Self {
         Self { db }
@@ -47,10 +68,21 @@ impl DataSetBulkService {
         None
     }
 
-    /// Convert calamine range to CSV string with proper escaping and consistent column counts
-    fn range_to_csv(range: &calamine::Range<calamine::Data>) -> CoreResult<Vec<u8>> {
+    fn cell_to_string(cell: Option<&calamine::Data>) -> String {
         use calamine::Data;
 
+        match cell {
+            Some(Data::String(s)) => s.clone(),
+            Some(Data::Int(i)) => i.to_string(),
+            Some(Data::Float(f)) => f.to_string(),
+            Some(Data::Bool(b)) => b.to_string(),
+            Some(Data::Empty) | None => String::new(),
+            _ => String::new(),
+        }
+    }
+
+    /// Convert calamine range to CSV string with proper escaping and consistent column counts
+    fn range_to_csv(range: &calamine::Range<calamine::Data>) -> CoreResult<Vec<u8>> {
         let mut csv_data = Vec::new();
 
         // Create a scope for the CSV writer to ensure it's dropped before we return csv_data
@@ -65,16 +97,7 @@ impl DataSetBulkService {
 
                 // Always write exactly 'width' columns to ensure consistency
                 for col_idx in 0..width {
-                    let cell = range.get((row_idx, col_idx));
-                    let value = match cell {
-                        Some(Data::String(s)) => s.clone(),
-                        Some(Data::Int(i)) => i.to_string(),
-                        Some(Data::Float(f)) => f.to_string(),
-                        Some(Data::Bool(b)) => b.to_string(),
-                        Some(Data::Empty) | None => String::new(),
-                        _ => String::new(),
-                    };
-                    row_values.push(value);
+                    row_values.push(Self::cell_to_string(range.get((row_idx, col_idx))));
                 }
 
                 // Write CSV row with proper escaping
@@ -91,50 +114,201 @@ impl DataSetBulkService {
         Ok(csv_data)
     }
 
-    /// Convert graph_json to CSV rows
-    fn json_array_to_csv_rows(array: &[serde_json::Value]) -> CoreResult<Vec<Vec<String>>> {
+    /// Convert a graph_json array to sheet rows, headers first. With
+    /// `attribute_columns`, each key of the items' `attributes` objects gets its
+    /// own column after the regular fields; keys that clash with a node column
+    /// stay behind in an `attributes` JSON cell.
+    fn json_array_to_rows(
+        array: &[serde_json::Value],
+        attribute_columns: bool,
+    ) -> CoreResult<Vec<Vec<serde_json::Value>>> {
+        use serde_json::Value as Json;
+
         if array.is_empty() {
             return Ok(Vec::new());
         }
 
-        // Extract all unique keys for headers
-        let mut all_keys = std::collections::BTreeSet::new();
+        let mut items = Vec::with_capacity(array.len());
+        let mut extra_items = Vec::with_capacity(array.len());
         for item in array {
-            if let Some(obj) = item.as_object() {
-                for key in obj.keys() {
-                    all_keys.insert(key.clone());
+            let Some(obj) = item.as_object() else {
+                continue;
+            };
+            let mut obj = obj.clone();
+            let mut extra = serde_json::Map::new();
+            if attribute_columns {
+                match obj.remove("attributes") {
+                    Some(Json::Object(attributes)) => {
+                        let mut clashing = serde_json::Map::new();
+                        for (key, value) in attributes {
+                            if NODE_COLUMNS.contains(&key.as_str()) {
+                                clashing.insert(key, value);
+                            } else {
+                                extra.insert(key, value);
+                            }
+                        }
+                        if !clashing.is_empty() {
+                            obj.insert(
+                                "attributes".to_string(),
+                                Json::String(Json::Object(clashing).to_string()),
+                            );
+                        }
+                    }
+                    Some(Json::Null) | None => {}
+                    Some(other) => {
+                        obj.insert("attributes".to_string(), Json::String(other.to_string()));
+                    }
                 }
             }
+            items.push(obj);
+            extra_items.push(extra);
         }
 
-        let headers: Vec<String> = all_keys.into_iter().collect();
-        let mut rows = vec![headers.clone()];
+        // Extract all unique keys for headers
+        let keys: std::collections::BTreeSet<&String> =
+            items.iter().flat_map(|obj| obj.keys()).collect();
+        let extra_keys: std::collections::BTreeSet<&String> =
+            extra_items.iter().flat_map(|obj| obj.keys()).collect();
 
-        // Convert each object to a row
-        for item in array {
-            if let Some(obj) = item.as_object() {
-                let mut row = Vec::new();
-                for header in &headers {
-                    let value = obj
-                        .get(header)
-                        .map(|v| match v {
-                            serde_json::Value::String(s) => s.clone(),
-                            serde_json::Value::Number(n) => n.to_string(),
-                            serde_json::Value::Bool(b) => b.to_string(),
-                            serde_json::Value::Null => String::new(),
-                            _ => serde_json::to_string(v).unwrap_or_default(),
-                        })
-                        .unwrap_or_default();
-                    row.push(value);
+        let mut rows = vec![keys
+            .iter()
+            .chain(extra_keys.iter())
+            .map(|key| Json::String((*key).clone()))
+            .collect()];
+        for (obj, extra) in items.iter().zip(&extra_items) {
+            rows.push(
+                keys.iter()
+                    .map(|key| obj.get(*key))
+                    .chain(extra_keys.iter().map(|key| extra.get(*key)))
+                    .map(|value| value.cloned().unwrap_or(Json::Null))
+                    .collect(),
+            );
+        }
+
+        Ok(rows)
+    }
+
+    /// Write one cell keeping its JSON type; nested values become JSON text.
+    fn write_xlsx_cell(
+        worksheet: &mut Worksheet,
+        row: u32,
+        col: u16,
+        value: &serde_json::Value,
+    ) -> CoreResult<()> {
+        use serde_json::Value as Json;
+
+        let result = match value {
+            Json::Null => return Ok(()),
+            Json::Bool(b) => worksheet.write_boolean(row, col, *b),
+            Json::Number(n) => worksheet.write_number(row, col, n.as_f64().unwrap_or_default()),
+            Json::String(s) => worksheet.write_string(row, col, s),
+            other => worksheet.write_string(row, col, other.to_string()),
+        };
+        result
+            .map(|_| ())
+            .map_err(|e| CoreError::internal("Failed to write worksheet cell").with_source(e))
+    }
+
+    fn ods_value(value: &serde_json::Value) -> Option<Value> {
+        use serde_json::Value as Json;
+
+        match value {
+            Json::Null => None,
+            Json::Bool(b) => Some(Value::Boolean(*b)),
+            Json::Number(n) => Some(Value::Number(n.as_f64().unwrap_or_default())),
+            Json::String(s) => Some(Value::Text(s.clone())),
+            other => Some(Value::Text(other.to_string())),
+        }
+    }
+
+    /// Typed JSON for an attribute cell; whole numbers come back as integers.
+    fn cell_to_json(cell: Option<&calamine::Data>) -> Option<serde_json::Value> {
+        use calamine::Data;
+
+        match cell {
+            Some(Data::Empty) | None => None,
+            Some(Data::String(s)) if s.is_empty() => None,
+            Some(Data::String(s)) => Some(serde_json::Value::String(s.clone())),
+            Some(Data::Int(i)) => Some(serde_json::Value::from(*i)),
+            Some(Data::Float(f)) if f.fract() == 0.0 && f.abs() < 9.0e15 => {
+                Some(serde_json::Value::from(*f as i64))
+            }
+            Some(Data::Float(f)) => serde_json::Number::from_f64(*f).map(serde_json::Value::Number),
+            Some(Data::Bool(b)) => Some(serde_json::Value::Bool(*b)),
+            other => Some(serde_json::Value::String(Self::cell_to_string(other))),
+        }
+    }
+
+    /// Like `range_to_csv` for a nodes sheet, but folds every column outside
+    /// the known node columns into a typed `attributes` JSON column so cell
+    /// types survive the trip through CSV.
+    fn node_range_to_csv(range: &calamine::Range<calamine::Data>) -> CoreResult<Vec<u8>> {
+        let headers: Vec<String> = (0..range.width())
+            .map(|col_idx| Self::cell_to_string(range.get((0, col_idx))))
+            .collect();
+        let (known, extra): (Vec<usize>, Vec<usize>) = (0..headers.len())
+            .filter(|&idx| !headers[idx].is_empty())
+            .partition(|&idx| NODE_COLUMNS.contains(&headers[idx].as_str()));
+        if extra.is_empty() {
+            return Self::range_to_csv(range);
+        }
+        let blob_idx = known
+            .iter()
+            .copied()
+            .find(|&idx| headers[idx] == "attributes");
+
+        let mut csv_data = Vec::new();
+        {
+            let mut csv_writer = csv::Writer::from_writer(&mut csv_data);
+            let mut header_row: Vec<&str> = known
+                .iter()
+                .filter(|&&idx| Some(idx) != blob_idx)
+                .map(|&idx| headers[idx].as_str())
+                .collect();
+            header_row.push("attributes");
+            csv_writer
+                .write_record(&header_row)
+                .map_err(|e| CoreError::internal("Failed to write CSV record").with_source(e))?;
+
+            for row_idx in 1..range.height() {
+                let mut row: Vec<String> = known
+                    .iter()
+                    .filter(|&&idx| Some(idx) != blob_idx)
+                    .map(|&idx| Self::cell_to_string(range.get((row_idx, idx))))
+                    .collect();
+                let mut attributes = blob_idx
+                    .and_then(|idx| {
+                        serde_json::from_str::<serde_json::Value>(&Self::cell_to_string(
+                            range.get((row_idx, idx)),
+                        ))
+                        .ok()
+                    })
+                    .and_then(|value| value.as_object().cloned())
+                    .unwrap_or_default();
+                for &idx in &extra {
+                    if let Some(value) = Self::cell_to_json(range.get((row_idx, idx))) {
+                        attributes.insert(headers[idx].clone(), value);
+                    }
                 }
-                rows.push(row);
+                row.push(if attributes.is_empty() {
+                    String::new()
+                } else {
+                    serde_json::Value::Object(attributes).to_string()
+                });
+                csv_writer.write_record(&row).map_err(|e| {
+                    CoreError::internal("Failed to write CSV record").with_source(e)
+                })?;
             }
+
+            csv_writer
+                .flush()
+                .map_err(|e| CoreError::internal("Failed to flush CSV writer").with_source(e))?;
         }
 
-        Ok(rows)
+        Ok(csv_data)
     }
 
-    fn ensure_layer_alias_column(rows: &mut Vec<Vec<String>>) {
+    fn ensure_layer_alias_column(rows: &mut Vec<Vec<serde_json::Value>>) {
         if rows.is_empty() {
             return;
         }
@@ -153,14 +327,126 @@ impl DataSetBulkService {
             row.insert(
                 insert_idx,
                 if row_idx == 0 {
-                    "alias".to_string()
+                    serde_json::Value::from("alias")
                 } else {
-                    String::new()
+                    serde_json::Value::Null
                 },
             );
         }
     }
 
+    /// Rows for a dataset's annotations sheet, or None when it has no annotations.
+    /// `data_sheets` fills the `sheets` column, one name per row.
+    fn annotation_rows(
+        dataset: &data_sets::Model,
+        data_sheets: &[String],
+    ) -> Option<Vec<Vec<String>>> {
+        let annotations: Vec<DataSetAnnotation> = dataset
+            .annotations
+            .as_deref()
+            .and_then(|raw| serde_json::from_str(raw).ok())
+            .unwrap_or_default();
+        if annotations.is_empty() {
+            return None;
+        }
+
+        let mut rows: Vec<Vec<String>> =
+            vec![ANNOTATION_HEADERS.iter().map(|h| h.to_string()).collect()];
+        let mut annotations = annotations.into_iter();
+        let mut data_sheets = data_sheets.iter();
+        loop {
+            let annotation = annotations.next();
+            let data_sheet = data_sheets.next();
+            if annotation.is_none() && data_sheet.is_none() {
+                break;
+            }
+            let mut row = match annotation {
+                Some(a) => vec![a.title, a.date.to_rfc3339(), a.body],
+                None => vec![String::new(); 3],
+            };
+            row.push(data_sheet.cloned().unwrap_or_default());
+            rows.push(row);
+        }
+        Some(rows)
+    }
+
+    fn is_annotation_sheet(headers: &[String]) -> bool {
+        headers.iter().map(String::as_str).eq(ANNOTATION_HEADERS)
+    }
+
+    fn parse_annotation_sheet(
+        sheet_name: String,
+        range: &calamine::Range<calamine::Data>,
+    ) -> PendingAnnotations {
+        let data_sheets = (1..range.height())
+            .map(|row_idx| Self::cell_to_string(range.get((row_idx, 3))))
+            .filter(|name| !name.is_empty())
+            .collect();
+        let annotations = (1..range.height())
+            .filter_map(|row_idx| {
+                let cell = |col_idx| Self::cell_to_string(range.get((row_idx, col_idx)));
+                let (title, date, body) = (cell(0), cell(1), cell(2));
+                if title.is_empty() && body.is_empty() {
+                    return None;
+                }
+                let date = chrono::DateTime::parse_from_rfc3339(&date)
+                    .map(|d| d.with_timezone(&chrono::Utc))
+                    .unwrap_or_else(|_| {
+                        tracing::warn!("Invalid annotation date '{}', using now", date);
+                        chrono::Utc::now()
+                    });
+                Some(DataSetAnnotation { title, date, body })
+            })
+            .collect();
+        PendingAnnotations {
+            sheet_name,
+            data_sheets,
+            annotations,
+        }
+    }
+
+    /// Attach annotations to the datasets imported from the data sheets their
+    /// sheet lists.
+    async fn restore_annotations(
+        &self,
+        service: &DataSetService,
+        pending: Vec<PendingAnnotations>,
+        imported_ids: &[i32],
+    ) -> CoreResult<()> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        use sea_orm::ColumnTrait;
+        use sea_orm::QueryFilter;
+        let imported = data_sets::Entity::find()
+            .filter(data_sets::Column::Id.is_in(imported_ids.to_vec()))
+            .all(&self.db)
+            .await
+            .map_err(|e| CoreError::internal("Failed to load datasets").with_source(e))?;
+
+        for sheet in pending {
+            let targets: Vec<i32> = imported
+                .iter()
+                .filter(|ds| sheet.data_sheets.contains(&ds.name))
+                .map(|ds| ds.id)
+                .collect();
+            if targets.is_empty() {
+                tracing::warn!(
+                    "No imported dataset matches annotations sheet '{}'",
+                    sheet.sheet_name
+                );
+            }
+            for id in targets {
+                service
+                    .replace_annotations(id, sheet.annotations.clone())
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn build_sheet_name(
         base: &str,
         suffix: &str,
@@ -175,9 +461,10 @@ impl DataSetBulkService {
         if let Some(limit) = max_len {
             raw = Self::truncate_to_len(&raw, limit);
         }
+        // Excel compares sheet names case-insensitively.
         let mut candidate = raw.clone();
         let mut counter = 2;
-        while used.contains(&candidate) {
+        while used.contains(&candidate.to_lowercase()) {
             let appendix = format!(" ({})", counter);
             counter += 1;
             let prefix_len = max_len
@@ -187,10 +474,48 @@ impl DataSetBulkService {
             prefix.push_str(&appendix);
             candidate = prefix;
         }
-        used.insert(candidate.clone());
+        used.insert(candidate.to_lowercase());
         candidate
     }
 
+    /// Replace characters Excel forbids in sheet names and strip the
+    /// apostrophes it rejects at either end.
+    fn sanitize_sheet_name(name: &str) -> String {
+        let cleaned: String = name
+            .chars()
+            .map(|c| {
+                if INVALID_SHEET_NAME_CHARS.contains(&c) {
+                    '_'
+                } else {
+                    c
+                }
+            })
+            .collect();
+        let trimmed = cleaned.trim().trim_matches('\'');
+        if trimmed.is_empty() {
+            "Layer".to_string()
+        } else {
+            trimmed.to_string()
+        }
+    }
+
+    fn write_xlsx_sheet(
+        workbook: &mut Workbook,
+        sheet_name: &str,
+        rows: &[Vec<serde_json::Value>],
+    ) -> CoreResult<()> {
+        let worksheet = workbook.add_worksheet();
+        worksheet
+            .set_name(sheet_name)
+            .map_err(|e| CoreError::internal("Failed to set worksheet name").with_source(e))?;
+        for (row_idx, row_data) in rows.iter().enumerate() {
+            for (col_idx, value) in row_data.iter().enumerate() {
+                Self::write_xlsx_cell(worksheet, row_idx as u32, col_idx as u16, value)?;
+            }
+        }
+        Ok(())
+    }
+
     fn truncate_to_len(name: &str, limit: usize) -> String {
         if name.chars().count() <= limit {
             return name.to_string();
@@ -243,14 +568,14 @@ impl DataSetBulkService {
                     CoreError::internal("Failed to parse graph_json during export").with_source(e)
                 })?;
             let sections = [("nodes", "Nodes"), ("edges", "Edges"), ("layers", "Layers")];
-            let mut section_written = false;
+            let mut data_sheets = Vec::new();
             // Create a sheet named with the dataset name
             for (key, label) in sections {
                 if let Some(array) = parsed.get(key).and_then(|v| v.as_array()) {
                     if array.is_empty() {
                         continue;
                     }
-                    let mut rows = Self::json_array_to_csv_rows(array)?;
+                    let mut rows = Self::json_array_to_rows(array, key == "nodes")?;
                     if key == "layers" {
                         Self::ensure_layer_alias_column(&mut rows);
                     }
@@ -267,21 +592,12 @@ impl DataSetBulkService {
 
                     for (row_idx, row_data) in rows.iter().enumerate() {
                         for (col_idx, value) in row_data.iter().enumerate() {
-                            if let Ok(num) = value.parse::<f64>() {
-                                worksheet
-                                    .write_number(row_idx as u32, col_idx as u16, num)
-                                    .map_err(|e| {
-                                        CoreError::internal("Failed to write worksheet number")
-                                            .with_source(e)
-                                    })?;
-                            } else {
-                                worksheet
-                                    .write_string(row_idx as u32, col_idx as u16, value)
-                                    .map_err(|e| {
-                                        CoreError::internal("Failed to write worksheet string")
-                                            .with_source(e)
-                                    })?;
-                            }
+                            Self::write_xlsx_cell(
+                                worksheet,
+                                row_idx as u32,
+                                col_idx as u16,
+                                value,
+                            )?;
                         }
                     }
                     tracing::info!(
@@ -290,11 +606,11 @@ impl DataSetBulkService {
                         sheet_name,
                         label
                     );
-                    section_written = true;
+                    data_sheets.push(sheet_name);
                 }
             }
 
-            if !section_written {
+            if data_sheets.is_empty() {
                 let sheet_name =
                     Self::build_sheet_name(&dataset.name, "Empty", &mut used_sheet_names, Some(31));
                 let worksheet = workbook.add_worksheet();
@@ -306,6 +622,34 @@ impl DataSetBulkService {
                     .map_err(|e| {
                         CoreError::internal("Failed to write worksheet string").with_source(e)
                     })?;
+                data_sheets.push(sheet_name);
+            }
+
+            if let Some(rows) = Self::annotation_rows(&dataset, &data_sheets) {
+                let sheet_name = Self::build_sheet_name(
+                    &dataset.name,
+                    ANNOTATION_SHEET_SUFFIX,
+                    &mut used_sheet_names,
+                    Some(31),
+                );
+                let worksheet = workbook.add_worksheet();
+                worksheet.set_name(&sheet_name).map_err(|e| {
+                    CoreError::internal("Failed to set worksheet name").with_source(e)
+                })?;
+                worksheet.set_column_hidden(3).map_err(|e| {
+                    CoreError::internal("Failed to hide worksheet column").with_source(e)
+                })?;
+                // Always text: titles like "2024" must not turn into numbers.
+                for (row_idx, row_data) in rows.iter().enumerate() {
+                    for (col_idx, value) in row_data.iter().enumerate() {
+                        worksheet
+                            .write_string(row_idx as u32, col_idx as u16, value)
+                            .map_err(|e| {
+                                CoreError::internal("Failed to write worksheet string")
+                                    .with_source(e)
+                            })?;
+                    }
+                }
             }
         }
 
@@ -315,6 +659,113 @@ impl DataSetBulkService {
             .map_err(|e| CoreError::internal("Failed to generate XLSX").with_source(e))
     }
 
+    /// Export datasets to XLSX with one sheet per layer
+    ///
+    /// Nodes from all requested datasets are grouped by layer into sheets named
+    /// after the layer label. An `edges` sheet holds every edge and a `summary`
+    /// sheet lists each layer with its sheet name and node/edge counts.
+    pub async fn export_to_xlsx_by_layer(&self, dataset_ids: &[i32]) -> CoreResult<Vec<u8>> {
+        let datasets = data_sets::Entity::find()
+            .all(&self.db)
+            .await
+            .map_err(|e| CoreError::internal("Failed to fetch datasets").with_source(e))?
+            .into_iter()
+            .filter(|ds| dataset_ids.contains(&ds.id))
+            .collect::<Vec<_>>();
+
+        tracing::info!("Exporting {} datasets to XLSX by layer", datasets.len());
+
+        // Layer id -> label, in declaration order; layers only referenced by
+        // nodes follow in first-seen order and are labelled with their id.
+        let mut layer_labels: IndexMap<String, String> = IndexMap::new();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        for dataset in &datasets {
+            let mut parsed: serde_json::Value =
+                serde_json::from_str(&dataset.graph_json).map_err(|e| {
+                    CoreError::internal("Failed to parse graph_json during export").with_source(e)
+                })?;
+            for layer in parsed["layers"].as_array().into_iter().flatten() {
+                if let Some(id) = layer["id"].as_str() {
+                    let label = layer["label"].as_str().unwrap_or(id);
+                    layer_labels
+                        .entry(id.to_string())
+                        .or_insert_with(|| label.to_string());
+                }
+            }
+            if let Some(array) = parsed.get_mut("nodes").and_then(|v| v.as_array_mut()) {
+                nodes.append(array);
+            }
+            if let Some(array) = parsed.get_mut("edges").and_then(|v| v.as_array_mut()) {
+                edges.append(array);
+            }
+        }
+
+        let layer_of = |item: &serde_json::Value| item["layer"].as_str().unwrap_or("").to_string();
+        let mut nodes_by_layer: IndexMap<String, Vec<serde_json::Value>> = IndexMap::new();
+        for node in nodes {
+            let layer = layer_of(&node);
+            layer_labels
+                .entry(layer.clone())
+                .or_insert_with(|| layer.clone());
+            nodes_by_layer.entry(layer).or_default().push(node);
+        }
+        let mut edge_counts: IndexMap<String, usize> = IndexMap::new();
+        for edge in &edges {
+            *edge_counts.entry(layer_of(edge)).or_default() += 1;
+        }
+
+        let mut used_sheet_names: HashSet<String> = [LAYER_SUMMARY_SHEET, LAYER_EDGES_SHEET]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        let mut layer_sheets = Vec::new();
+        let mut summary = vec![["layer", "label", "sheet", "nodes", "edges"]
+            .iter()
+            .map(|header| serde_json::Value::from(*header))
+            .collect::<Vec<_>>()];
+        for (layer, label) in &layer_labels {
+            let layer_nodes = nodes_by_layer.get(layer).map(Vec::as_slice).unwrap_or(&[]);
+            let sheet_name = if layer_nodes.is_empty() {
+                None
+            } else {
+                Some(Self::build_sheet_name(
+                    &Self::sanitize_sheet_name(label),
+                    "",
+                    &mut used_sheet_names,
+                    Some(31),
+                ))
+            };
+            summary.push(vec![
+                serde_json::Value::from(layer.as_str()),
+                serde_json::Value::from(label.as_str()),
+                sheet_name
+                    .clone()
+                    .map_or(serde_json::Value::Null, Into::into),
+                serde_json::Value::from(layer_nodes.len()),
+                serde_json::Value::from(edge_counts.get(layer).copied().unwrap_or(0)),
+            ]);
+            if let Some(sheet_name) = sheet_name {
+                layer_sheets.push((sheet_name, Self::json_array_to_rows(layer_nodes, true)?));
+            }
+        }
+
+        let mut workbook = Workbook::new();
+        Self::write_xlsx_sheet(&mut workbook, LAYER_SUMMARY_SHEET, &summary)?;
+        for (sheet_name, rows) in &layer_sheets {
+            Self::write_xlsx_sheet(&mut workbook, sheet_name, rows)?;
+        }
+        Self::write_xlsx_sheet(
+            &mut workbook,
+            LAYER_EDGES_SHEET,
+            &Self::json_array_to_rows(&edges, false)?,
+        )?;
+
+        workbook
+            .save_to_buffer()
+            .map_err(|e| CoreError::internal("Failed to generate XLSX").with_source(e))
+    }
+
     /// Export datasets to ODS format
     /// Each dataset becomes a separate sheet named with its name containing CSV data
     pub async fn export_to_ods(&self, dataset_ids: &[i32]) -> CoreResult<Vec<u8>> {
@@ -360,14 +811,14 @@ impl DataSetBulkService {
                     CoreError::internal("Failed to parse graph_json during export").with_source(e)
                 })?;
             let sections = [("nodes", "Nodes"), ("edges", "Edges"), ("layers", "Layers")];
-            let mut section_written = false;
+            let mut data_sheets = Vec::new();
 
             for (key, label) in sections {
                 if let Some(array) = parsed.get(key).and_then(|v| v.as_array()) {
                     if array.is_empty() {
                         continue;
                     }
-                    let mut rows = Self::json_array_to_csv_rows(array)?;
+                    let mut rows = Self::json_array_to_rows(array, key == "nodes")?;
                     if key == "layers" {
                         Self::ensure_layer_alias_column(&mut rows);
                     }
@@ -377,24 +828,18 @@ impl DataSetBulkService {
 
                     for (row_idx, row_data) in rows.iter().enumerate() {
                         for (col_idx, value) in row_data.iter().enumerate() {
-                            if let Ok(num) = value.parse::<f64>() {
-                                sheet.set_value(row_idx as u32, col_idx as u32, Value::Number(num));
-                            } else {
-                                sheet.set_value(
-                                    row_idx as u32,
-                                    col_idx as u32,
-                                    Value::Text(value.clone()),
-                                );
+                            if let Some(value) = Self::ods_value(value) {
+                                sheet.set_value(row_idx as u32, col_idx as u32, value);
                             }
                         }
                     }
 
                     workbook.push_sheet(sheet);
-                    section_written = true;
+                    data_sheets.push(sheet_name);
                 }
             }
 
-            if !section_written {
+            if data_sheets.is_empty() {
                 let sheet_name =
                     Self::build_sheet_name(&dataset.name, "Empty", &mut used_sheet_names, None);
                 let mut sheet = Sheet::new(&sheet_name);
@@ -404,6 +849,24 @@ impl DataSetBulkService {
                     Value::Text("Dataset contains no nodes, edges, or layers".to_string()),
                 );
                 workbook.push_sheet(sheet);
+                data_sheets.push(sheet_name);
+            }
+
+            if let Some(rows) = Self::annotation_rows(&dataset, &data_sheets) {
+                let sheet_name = Self::build_sheet_name(
+                    &dataset.name,
+                    ANNOTATION_SHEET_SUFFIX,
+                    &mut used_sheet_names,
+                    None,
+                );
+                let mut sheet = Sheet::new(&sheet_name);
+                for (row_idx, row_data) in rows.into_iter().enumerate() {
+                    for (col_idx, value) in row_data.into_iter().enumerate() {
+                        sheet.set_value(row_idx as u32, col_idx as u32, Value::Text(value));
+                    }
+                }
+                sheet.set_col_visible(3, spreadsheet_ods::sheet::Visibility::Collapsed);
+                workbook.push_sheet(sheet);
             }
         }
 
@@ -437,6 +900,7 @@ impl DataSetBulkService {
         let mut imported_ids = Vec::new();
 
         let service = DataSetService::new(self.db.clone());
+        let mut pending_annotations = Vec::new();
 
         // Iterate through all sheets
         let sheet_names = workbook.sheet_names();
@@ -469,6 +933,11 @@ impl DataSetBulkService {
 
                 tracing::info!("Sheet headers: {:?}", headers);
 
+                if Self::is_annotation_sheet(&headers) {
+                    pending_annotations.push(Self::parse_annotation_sheet(sheet_name, &range));
+                    continue;
+                }
+
                 // Infer data type
                 let data_type = Self::infer_data_type(&sheet_name, &headers).ok_or_else(|| {
                     CoreError::validation(format!(
@@ -480,7 +949,11 @@ impl DataSetBulkService {
                 tracing::info!("Inferred data type: {:?}", data_type);
 
                 // Convert sheet to CSV
-                let csv_data = Self::range_to_csv(&range)?;
+                let csv_data = if data_type == DataType::Nodes {
+                    Self::node_range_to_csv(&range)?
+                } else {
+                    Self::range_to_csv(&range)?
+                };
                 tracing::info!("Converted sheet to {} bytes of CSV", csv_data.len());
 
                 // Try to find existing dataset by name
@@ -529,6 +1002,9 @@ impl DataSetBulkService {
             }
         }
 
+        self.restore_annotations(&service, pending_annotations, &imported_ids)
+            .await?;
+
         Ok(DataSetImportResult {
             created_count,
             updated_count,
@@ -559,6 +1035,7 @@ impl DataSetBulkService {
         let mut imported_ids = Vec::new();
 
         let service = DataSetService::new(self.db.clone());
+        let mut pending_annotations = Vec::new();
 
         // Iterate through all sheets
         let sheet_names = workbook.sheet_names();
@@ -591,6 +1068,11 @@ impl DataSetBulkService {
 
                 tracing::info!("Sheet headers: {:?}", headers);
 
+                if Self::is_annotation_sheet(&headers) {
+                    pending_annotations.push(Self::parse_annotation_sheet(sheet_name, &range));
+                    continue;
+                }
+
                 // Infer data type
                 let data_type = Self::infer_data_type(&sheet_name, &headers).ok_or_else(|| {
                     CoreError::validation(format!(
@@ -602,7 +1084,11 @@ impl DataSetBulkService {
                 tracing::info!("Inferred data type: {:?}", data_type);
 
                 // Convert sheet to CSV
-                let csv_data = Self::range_to_csv(&range)?;
+                let csv_data = if data_type == DataType::Nodes {
+                    Self::node_range_to_csv(&range)?
+                } else {
+                    Self::range_to_csv(&range)?
+                };
                 tracing::info!("Converted sheet to {} bytes of CSV", csv_data.len());
 
                 // Try to find existing dataset by name
@@ -651,6 +1137,9 @@ impl DataSetBulkService {
             }
         }
 
+        self.restore_annotations(&service, pending_annotations, &imported_ids)
+            .await?;
+
         Ok(DataSetImportResult {
             created_count,
             updated_count,
@@ -0,0 +1,103 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+
+use crate::database::entities::dataset_schemas::{self, Entity as DatasetSchemas};
+use crate::dataset_schema::DatasetSchema;
+use crate::errors::{CoreError, CoreResult};
+
+/// Service for the named dataset schemas stored per project
+pub struct DatasetSchemaService {
+    db: DatabaseConnection,
+}
+
+impl DatasetSchemaService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn list(&self, project_id: i32) -> CoreResult<Vec<dataset_schemas::Model>> {
+        DatasetSchemas::find()
+            .filter(dataset_schemas::Column::ProjectId.eq(project_id))
+            .order_by_asc(dataset_schemas::Column::Name)
+            .all(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to list dataset schemas: {}", e)))
+    }
+
+    pub async fn get(&self, id: i32) -> CoreResult<dataset_schemas::Model> {
+        DatasetSchemas::find_by_id(id)
+            .one(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load dataset schema: {}", e)))?
+            .ok_or_else(|| CoreError::not_found("DatasetSchema", id.to_string()))
+    }
+
+    /// Store `schema` under `name`, replacing any schema of that name in the
+    /// project.
+    pub async fn save(
+        &self,
+        project_id: i32,
+        name: &str,
+        schema: &DatasetSchema,
+    ) -> CoreResult<dataset_schemas::Model> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(CoreError::validation("Dataset schema name cannot be empty"));
+        }
+        let schema_json = serde_json::to_string(schema).map_err(|e| {
+            CoreError::internal(format!("Failed to serialize dataset schema: {}", e))
+        })?;
+
+        let existing = DatasetSchemas::find()
+            .filter(dataset_schemas::Column::ProjectId.eq(project_id))
+            .filter(dataset_schemas::Column::Name.eq(name))
+            .one(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load dataset schema: {}", e)))?;
+
+        let now = Utc::now();
+        let result = match existing {
+            Some(model) => {
+                let mut active: dataset_schemas::ActiveModel = model.into();
+                active.schema_json = Set(schema_json);
+                active.updated_at = Set(now);
+                active.update(&self.db).await
+            }
+            None => {
+                dataset_schemas::ActiveModel {
+                    id: ActiveValue::NotSet,
+                    project_id: Set(project_id),
+                    name: Set(name.to_string()),
+                    schema_json: Set(schema_json),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&self.db)
+                .await
+            }
+        };
+
+        result.map_err(|e| CoreError::internal(format!("Failed to save dataset schema: {}", e)))
+    }
+
+    pub async fn delete(&self, id: i32) -> CoreResult<()> {
+        DatasetSchemas::delete_by_id(id)
+            .exec(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to delete dataset schema: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Parse the stored schema JSON of a saved dataset schema.
+pub fn parse_schema(model: &dataset_schemas::Model) -> CoreResult<DatasetSchema> {
+    serde_json::from_str(&model.schema_json).map_err(|e| {
+        CoreError::internal(format!(
+            "Stored dataset schema {} is not valid: {}",
+            model.id, e
+        ))
+    })
+}
@@ -1,32 +1,55 @@
+use std::collections::HashSet;
+
 use sea_orm::DatabaseConnection;
 
 use crate::errors::{CoreError, CoreResult};
 use crate::export::{
-    to_csv_edges, to_csv_nodes, to_dot, to_gml, to_json, to_mermaid, to_mermaid_mindmap,
-    to_mermaid_treemap, to_plantuml, to_plantuml_mindmap, to_plantuml_wbs,
+    to_csv_adjacency, to_csv_edges, to_csv_matrix, to_csv_nodes, to_dot, to_dot_hierarchy, to_gml,
+    to_jsgraph, to_json, to_json_hierarchy, to_jsonl, to_mermaid, to_mermaid_mindmap,
+    to_mermaid_treemap, to_plantuml, to_plantuml_mindmap, to_plantuml_wbs, to_svg,
 };
 use crate::graph::Graph;
 use crate::plan::{
     ExportFileType, NotePosition, Plan, RenderConfig, RenderConfigBuiltInStyle,
     RenderConfigOrientation, RenderTargetOptions,
 };
+use crate::services::GraphService;
 pub struct ExportService {
-    _db: DatabaseConnection,
+    db: DatabaseConnection,
 }
 
 impl ExportService {
     pub fn new(db: DatabaseConnection) -> Self {
-        Self { _db: db }
+        Self { db }
     }
 
-    pub fn export_to_string(
+    /// Export a stored graph restricted to the given layers.
+    ///
+    /// Nodes outside the layer set are pruned along with their edges (see
+    /// `Graph::filter_by_layers`); the rendered output matches `export_to_string`.
+    pub async fn export_graph_filtered(
         &self,
-        graph: &Graph,
+        graph_id: i32,
         format: &ExportFileType,
-        render_config_override: Option<RenderConfig>,
+        layers: Vec<String>,
     ) -> CoreResult<String> {
-        // Default render config
-        let default_render_config = RenderConfig {
+        if layers.is_empty() {
+            return Err(CoreError::validation(
+                "At least one layer is required for a filtered export",
+            ));
+        }
+
+        let graph = GraphService::new(self.db.clone())
+            .build_graph_from_dag_graph(graph_id)
+            .await?;
+        let layer_set: HashSet<String> = layers.into_iter().collect();
+        let filtered = graph.filter_by_layers(&layer_set);
+
+        self.export_to_string(&filtered, format, None)
+    }
+
+    fn default_render_config() -> RenderConfig {
+        RenderConfig {
             contain_nodes: true,
             orientation: RenderConfigOrientation::TB,
             apply_layers: true,
@@ -34,14 +57,34 @@ impl ExportService {
             target_options: RenderTargetOptions {
                 graphviz: Some(crate::plan::GraphvizRenderOptions::default()),
                 mermaid: None,
+                svg: None,
+                plantuml: None,
             },
             add_node_comments_as_notes: false,
             note_position: NotePosition::Left,
             use_node_weight: true,
             use_edge_weight: true,
+            normalize_edge_weights: false,
+            render_partitions_as_nodes: false,
+            directed: None,
+            precompute_layout: false,
+            prettify: false,
+            timestamp_format: crate::plan::TimestampFormat::Rfc3339,
             layer_source_styles: Vec::new(),
-        };
-        let render_config = render_config_override.unwrap_or(default_render_config);
+            color_by_attribute: None,
+        }
+    }
+
+    pub fn export_to_string(
+        &self,
+        graph: &Graph,
+        format: &ExportFileType,
+        render_config_override: Option<RenderConfig>,
+    ) -> CoreResult<String> {
+        let render_config = render_config_override.unwrap_or_else(Self::default_render_config);
+        render_config
+            .check_supported_by(format)
+            .map_err(CoreError::validation)?;
 
         match format {
             ExportFileType::DOT => Ok(to_dot::render(graph, &render_config)
@@ -50,6 +93,14 @@ impl ExportService {
                 .map_err(|e| CoreError::internal(format!("GML render failed: {}", e)))?),
             ExportFileType::JSON => Ok(to_json::render(graph, &render_config)
                 .map_err(|e| CoreError::internal(format!("JSON render failed: {}", e)))?),
+            ExportFileType::JSONHierarchy => Ok(to_json_hierarchy::render(graph, &render_config)
+                .map_err(|e| {
+                CoreError::internal(format!("JSON hierarchy render failed: {}", e))
+            })?),
+            ExportFileType::JSONL => Ok(to_jsonl::render(graph, &render_config)
+                .map_err(|e| CoreError::internal(format!("JSONL render failed: {}", e)))?),
+            ExportFileType::SVG => Ok(to_svg::render(graph, &render_config)
+                .map_err(|e| CoreError::internal(format!("SVG render failed: {}", e)))?),
             ExportFileType::Mermaid => Ok(to_mermaid::render(graph, &render_config)
                 .map_err(|e| CoreError::internal(format!("Mermaid render failed: {}", e)))?),
             ExportFileType::PlantUML => Ok(to_plantuml::render(graph, &render_config)
@@ -73,12 +124,42 @@ impl ExportService {
                 .map_err(|e| CoreError::internal(format!("CSV nodes render failed: {}", e)))?),
             ExportFileType::CSVEdges => Ok(to_csv_edges::render(graph, &render_config)
                 .map_err(|e| CoreError::internal(format!("CSV edges render failed: {}", e)))?),
+            ExportFileType::CSVAdjacency => Ok(to_csv_adjacency::render(graph, &render_config)
+                .map_err(|e| CoreError::internal(format!("CSV adjacency render failed: {}", e)))?),
+            ExportFileType::CSVMatrix => Ok(to_csv_matrix::render(graph, &render_config)
+                .map_err(|e| CoreError::internal(format!("CSV matrix render failed: {}", e)))?),
+            ExportFileType::DOTHierarchy => Ok(to_dot_hierarchy::render(graph, &render_config)
+                .map_err(|e| CoreError::internal(format!("DOT hierarchy render failed: {}", e)))?),
+            ExportFileType::JSGraph => Ok(to_jsgraph::render(graph, &render_config)
+                .map_err(|e| CoreError::internal(format!("JSGraph render failed: {}", e)))?),
             _ => Err(CoreError::validation(
                 "Export format not implemented for string output",
             )),
         }
     }
 
+    /// Export to raw bytes, covering binary formats such as Parquet as well as
+    /// every text format `export_to_string` supports.
+    pub fn export_to_bytes(
+        &self,
+        graph: &Graph,
+        format: &ExportFileType,
+        render_config_override: Option<RenderConfig>,
+    ) -> CoreResult<Vec<u8>> {
+        if !format.format_info().is_some_and(|info| info.binary) {
+            return self
+                .export_to_string(graph, format, render_config_override)
+                .map(String::into_bytes);
+        }
+
+        let render_config = render_config_override.unwrap_or_else(Self::default_render_config);
+        render_config
+            .check_supported_by(format)
+            .map_err(CoreError::validation)?;
+        crate::export::render_bytes(graph, format, &render_config)
+            .map_err(|e| CoreError::internal(format!("{:?} render failed: {}", format, e)))
+    }
+
     #[allow(dead_code)] // Reserved for future plan export execution
     pub async fn execute_plan_exports(
         &self,
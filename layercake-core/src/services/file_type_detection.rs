@@ -11,6 +11,7 @@ pub fn detect_data_type(file_format: &FileFormat, file_data: &[u8]) -> CoreResul
         FileFormat::Csv => detect_from_csv(file_data, b','),
         FileFormat::Tsv => detect_from_csv(file_data, b'\t'),
         FileFormat::Json => detect_from_json(file_data),
+        FileFormat::Dot => Ok(DataType::Graph),
         FileFormat::Xlsx | FileFormat::Ods | FileFormat::Pdf | FileFormat::Xml => {
             Err(CoreError::validation(format!(
                 "File format {:?} is not supported for data type detection",
@@ -1,10 +1,17 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Mutex;
 
+use indexmap::IndexMap;
 use serde::Serialize;
 
-use crate::errors::CoreResult;
-use crate::graph::Graph;
-use crate::services::GraphService;
+use crate::database::entities::graph_data;
+use crate::errors::{CoreError, CoreResult};
+use crate::graph::{Graph, PathAlgorithm, WeightNormalization};
+use crate::graph_diff::{diff_graphs_with, GraphDiff, NodeComparison};
+use crate::plan_dag::transforms::{GraphTransform, GraphTransformKind, GraphTransformParams};
+use crate::services::{GraphDataService, GraphService};
 use sea_orm::DatabaseConnection;
 
 #[derive(Clone, Serialize)]
@@ -16,13 +23,227 @@ pub struct GraphConnectivityReport {
     pub components: Vec<Vec<String>>,
 }
 
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KCoreReport {
+    pub graph_id: i32,
+    pub k: usize,
+    pub core_nodes: Vec<String>,
+    pub coreness: IndexMap<String, usize>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentSplitReport {
+    pub graph_id: i32,
+    pub graphs: Vec<Graph>,
+    /// Ids of the computed graphs created for each component, in the same order
+    /// as `graphs`; empty unless persistence was requested.
+    pub persisted_graph_ids: Vec<i32>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortestPathReport {
+    pub graph_id: i32,
+    /// False when no directed path leads from source to target.
+    pub reachable: bool,
+    /// Node ids from source to target; empty when unreachable.
+    pub path: Vec<String>,
+    pub cost: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrossingEdge {
+    pub id: String,
+    pub source: String,
+    pub target: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayerConnectivityReport {
+    pub graph_id: i32,
+    pub layer_a: String,
+    pub layer_b: String,
+    /// False when no path, direct or multi-hop, joins the two layers.
+    pub connected: bool,
+    /// Edges with one endpoint in each layer, in either direction.
+    pub direct_edges: Vec<CrossingEdge>,
+    /// Fewest-hop node path from a `layer_a` node to a `layer_b` node; empty
+    /// when not connected.
+    pub path: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphSummaryMetrics {
+    pub graph_id: i32,
+    pub node_count: usize,
+    pub edge_count: usize,
+    /// Directed density E / (N * (N - 1)); 0 for graphs with fewer than two nodes.
+    pub density: f64,
+    /// Mean of in-degree plus out-degree, i.e. 2E / N.
+    pub average_degree: f64,
+    /// Weakly connected components, counting isolated nodes.
+    pub component_count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentSummary {
+    pub graph_id: i32,
+    /// Strongly connected components when true, weakly connected otherwise.
+    pub directed: bool,
+    pub component_count: usize,
+    pub largest_component_size: usize,
+    /// Size of every component, largest first; isolated nodes count as 1.
+    pub component_sizes: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeScore {
+    pub node_id: String,
+    pub score: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BetweennessReport {
+    pub graph_id: i32,
+    pub weighted: bool,
+    pub directed: bool,
+    pub normalized: bool,
+    /// Every flow node, highest score first; ties are ordered by node ID.
+    pub scores: Vec<NodeScore>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DegreeCount {
+    pub degree: usize,
+    pub count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopologyReport {
+    pub graph_id: i32,
+    /// Whether `density` uses the directed formula E / (N * (N - 1)) or the
+    /// undirected 2E / (N * (N - 1)).
+    pub directed: bool,
+    pub node_count: usize,
+    /// Edges whose endpoints are both nodes of the graph.
+    pub edge_count: usize,
+    pub density: f64,
+    /// Degree is in-degree plus out-degree, so this is 2E / N.
+    pub average_degree: f64,
+    pub max_degree: usize,
+    pub min_degree: usize,
+    /// Number of nodes with each degree that occurs, lowest degree first.
+    pub degree_histogram: Vec<DegreeCount>,
+}
+
+/// Graph state a cached analysis was computed from. Any edit bumps at least
+/// one of these, so a mismatch means the cached result is stale.
+type GraphCacheKey = (chrono::DateTime<chrono::Utc>, i32, i32, i32);
+
+/// Betweenness options a cached report was computed with: weighted, directed,
+/// normalized.
+type BetweennessOptions = (bool, bool, bool);
+
+/// Results kept per analysis kind before the least recently used is evicted.
+const ANALYSIS_CACHE_CAPACITY: usize = 64;
+
+/// Results of one kind of analysis keyed by graph id and options `K`. An
+/// entry is served only while its graph is unchanged, and once more than
+/// `capacity` entries are held the least recently used one is dropped.
+struct AnalysisCache<K, T> {
+    capacity: usize,
+    entries: Mutex<IndexMap<(i32, K), (GraphCacheKey, T)>>,
+}
+
+impl<K: Hash + Eq, T: Clone> AnalysisCache<K, T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(IndexMap::new()),
+        }
+    }
+
+    /// The cached result for `graph_id` and `options` if it was computed from
+    /// `graph_key`, otherwise the result of `compute`. Graphs without a key
+    /// are computed every time and never stored.
+    async fn get_or_compute<F>(
+        &self,
+        graph_id: i32,
+        options: K,
+        graph_key: Option<GraphCacheKey>,
+        compute: F,
+    ) -> CoreResult<T>
+    where
+        F: Future<Output = CoreResult<T>>,
+    {
+        let key = (graph_id, options);
+        let Some(graph_key) = graph_key else {
+            return compute.await;
+        };
+        {
+            let mut entries = self.entries.lock().expect("analysis cache poisoned");
+            // Re-inserting moves a hit to the most recently used end; a stale
+            // entry is simply dropped.
+            if let Some((cached_key, value)) = entries.shift_remove(&key) {
+                if cached_key == graph_key {
+                    entries.insert(key, (cached_key, value.clone()));
+                    return Ok(value);
+                }
+            }
+        }
+
+        let value = compute.await?;
+        let mut entries = self.entries.lock().expect("analysis cache poisoned");
+        entries.shift_remove(&key);
+        entries.insert(key, (graph_key, value.clone()));
+        while entries.len() > self.capacity {
+            entries.shift_remove_index(0);
+        }
+        Ok(value)
+    }
+}
+
 pub struct GraphAnalysisService {
     db: DatabaseConnection,
+    component_cache: AnalysisCache<bool, ComponentSummary>,
+    betweenness_cache: AnalysisCache<BetweennessOptions, BetweennessReport>,
+    topology_cache: AnalysisCache<bool, TopologyReport>,
 }
 
 impl GraphAnalysisService {
     pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+        Self {
+            db,
+            component_cache: AnalysisCache::new(ANALYSIS_CACHE_CAPACITY),
+            betweenness_cache: AnalysisCache::new(ANALYSIS_CACHE_CAPACITY),
+            topology_cache: AnalysisCache::new(ANALYSIS_CACHE_CAPACITY),
+        }
+    }
+
+    /// Current state of a persisted graph, or `None` for graphs that are not
+    /// in `graph_data` and so are never cached.
+    async fn graph_cache_key(&self, graph_id: i32) -> CoreResult<Option<GraphCacheKey>> {
+        Ok(GraphDataService::new(self.db.clone())
+            .get_by_id(graph_id)
+            .await?
+            .map(|gd| {
+                (
+                    gd.updated_at,
+                    gd.last_edit_sequence,
+                    gd.node_count,
+                    gd.edge_count,
+                )
+            }))
     }
 
     pub async fn analyze_connectivity(&self, graph_id: i32) -> CoreResult<GraphConnectivityReport> {
@@ -52,6 +273,509 @@ impl GraphAnalysisService {
 
         Ok(find_all_paths(&adjacency, source, target, max_paths))
     }
+
+    /// Cheapest path between two nodes. Missing endpoints are a validation
+    /// error; an unreachable target is reported with `reachable: false`.
+    pub async fn shortest_path(
+        &self,
+        graph_id: i32,
+        source: &str,
+        target: &str,
+        algorithm: PathAlgorithm,
+        weighted: bool,
+    ) -> CoreResult<ShortestPathReport> {
+        let graph_service = GraphService::new(self.db.clone());
+        let graph = graph_service.build_graph_from_dag_graph(graph_id).await?;
+        let found = graph
+            .shortest_path(source, target, algorithm, weighted)
+            .map_err(CoreError::validation)?;
+
+        Ok(match found {
+            Some(found) => ShortestPathReport {
+                graph_id,
+                reachable: true,
+                path: found.path,
+                cost: Some(found.cost),
+            },
+            None => ShortestPathReport {
+                graph_id,
+                reachable: false,
+                path: Vec::new(),
+                cost: None,
+            },
+        })
+    }
+
+    /// How two layers of a graph connect. Unknown or identical layers are a
+    /// validation error; unconnected layers report `connected: false`.
+    pub async fn layer_connectivity(
+        &self,
+        graph_id: i32,
+        layer_a: &str,
+        layer_b: &str,
+    ) -> CoreResult<LayerConnectivityReport> {
+        let graph_service = GraphService::new(self.db.clone());
+        let graph = graph_service.build_graph_from_dag_graph(graph_id).await?;
+        let found = graph
+            .layer_connectivity(layer_a, layer_b)
+            .map_err(CoreError::validation)?;
+
+        Ok(LayerConnectivityReport {
+            graph_id,
+            layer_a: layer_a.to_string(),
+            layer_b: layer_b.to_string(),
+            connected: found.path.is_some(),
+            direct_edges: found
+                .direct_edges
+                .into_iter()
+                .map(|edge| CrossingEdge {
+                    id: edge.id,
+                    source: edge.source,
+                    target: edge.target,
+                })
+                .collect(),
+            path: found.path.unwrap_or_default(),
+        })
+    }
+
+    /// Structural diff from graph `graph_id_a` to graph `graph_id_b`, e.g. the
+    /// same plan node before and after a re-run. Nodes count as changed only
+    /// when their label, layer or attributes differ.
+    pub async fn diff_graphs(&self, graph_id_a: i32, graph_id_b: i32) -> CoreResult<GraphDiff> {
+        let graph_service = GraphService::new(self.db.clone());
+        let from = graph_service.build_graph_from_dag_graph(graph_id_a).await?;
+        let to = graph_service.build_graph_from_dag_graph(graph_id_b).await?;
+        Ok(diff_graphs_with(&from, &to, NodeComparison::Content))
+    }
+
+    pub async fn summary_metrics(&self, graph_id: i32) -> CoreResult<GraphSummaryMetrics> {
+        let graph_service = GraphService::new(self.db.clone());
+        let graph = graph_service.build_graph_from_dag_graph(graph_id).await?;
+        Ok(compute_summary_metrics(graph_id, &graph))
+    }
+
+    /// Component sizes of a graph, cached per graph until the graph changes.
+    pub async fn connected_components(
+        &self,
+        graph_id: i32,
+        directed: bool,
+    ) -> CoreResult<ComponentSummary> {
+        let cache_key = self.graph_cache_key(graph_id).await?;
+        self.component_cache
+            .get_or_compute(graph_id, directed, cache_key, async {
+                let graph_service = GraphService::new(self.db.clone());
+                let graph = graph_service.build_graph_from_dag_graph(graph_id).await?;
+                Ok(compute_component_summary(graph_id, &graph, directed))
+            })
+            .await
+    }
+
+    /// Betweenness centrality of every flow node (see
+    /// [`Graph::betweenness_centrality`]), cached per graph and options until
+    /// the graph changes.
+    pub async fn betweenness_centrality(
+        &self,
+        graph_id: i32,
+        weighted: bool,
+        directed: bool,
+        normalized: bool,
+    ) -> CoreResult<BetweennessReport> {
+        let options = (weighted, directed, normalized);
+        let cache_key = self.graph_cache_key(graph_id).await?;
+        self.betweenness_cache
+            .get_or_compute(graph_id, options, cache_key, async {
+                let graph_service = GraphService::new(self.db.clone());
+                let graph = graph_service.build_graph_from_dag_graph(graph_id).await?;
+                let mut scores: Vec<NodeScore> = graph
+                    .betweenness_centrality(weighted, directed, normalized)
+                    .map_err(CoreError::validation)?
+                    .into_iter()
+                    .map(|(node_id, score)| NodeScore { node_id, score })
+                    .collect();
+                scores.sort_by(|a, b| {
+                    b.score
+                        .total_cmp(&a.score)
+                        .then_with(|| a.node_id.cmp(&b.node_id))
+                });
+                Ok(BetweennessReport {
+                    graph_id,
+                    weighted,
+                    directed,
+                    normalized,
+                    scores,
+                })
+            })
+            .await
+    }
+
+    /// Density and degree distribution of a graph, cached per graph until the
+    /// graph changes.
+    pub async fn topology(&self, graph_id: i32, directed: bool) -> CoreResult<TopologyReport> {
+        let cache_key = self.graph_cache_key(graph_id).await?;
+        self.topology_cache
+            .get_or_compute(graph_id, directed, cache_key, async {
+                let graph_service = GraphService::new(self.db.clone());
+                let graph = graph_service.build_graph_from_dag_graph(graph_id).await?;
+                Ok(compute_topology(graph_id, &graph, directed))
+            })
+            .await
+    }
+
+    pub async fn k_core(&self, graph_id: i32, k: usize) -> CoreResult<KCoreReport> {
+        let graph_service = GraphService::new(self.db.clone());
+        let graph = graph_service.build_graph_from_dag_graph(graph_id).await?;
+        let coreness = graph.core_numbers();
+        let core_nodes = coreness
+            .iter()
+            .filter(|(_, value)| **value >= k)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        Ok(KCoreReport {
+            graph_id,
+            k,
+            core_nodes,
+            coreness,
+        })
+    }
+
+    /// Score every flow node with weighted PageRank, stored in its `pagerank`
+    /// attribute, and persist the result as a new computed graph in the same
+    /// project.
+    pub async fn annotate_pagerank(
+        &self,
+        graph_id: i32,
+        damping: f64,
+        iterations: usize,
+    ) -> CoreResult<graph_data::Model> {
+        let graph_service = GraphService::new(self.db.clone());
+        let mut graph = graph_service.build_graph_from_dag_graph(graph_id).await?;
+        GraphTransform {
+            kind: GraphTransformKind::PageRank,
+            params: GraphTransformParams {
+                pagerank_damping: Some(damping),
+                pagerank_iterations: Some(iterations),
+                ..Default::default()
+            },
+        }
+        .apply_to(&mut graph)
+        .map_err(|e| CoreError::validation(e.to_string()))?;
+        graph.name = format!("{} (PageRank)", graph.name);
+
+        let graph_data_service = GraphDataService::new(self.db.clone());
+        let source = graph_data_service
+            .get_by_id(graph_id)
+            .await?
+            .ok_or_else(|| CoreError::not_found("GraphData", graph_id.to_string()))?;
+        let metadata = serde_json::json!({
+            "pagerankOfGraphId": graph_id,
+            "damping": damping,
+            "iterations": iterations,
+        });
+        graph_data_service
+            .create_computed_from_graph(source.project_id, &graph, Some(metadata))
+            .await
+    }
+
+    /// Rescale edge weights (see [`Graph::normalize_edge_weights`]) and
+    /// persist the result as a new computed graph in the same project.
+    pub async fn normalize_edge_weights(
+        &self,
+        graph_id: i32,
+        method: WeightNormalization,
+        range: (i32, i32),
+        preserve_zero: bool,
+    ) -> CoreResult<graph_data::Model> {
+        let graph_service = GraphService::new(self.db.clone());
+        let mut graph = graph_service.build_graph_from_dag_graph(graph_id).await?;
+        GraphTransform {
+            kind: GraphTransformKind::NormalizeEdgeWeights,
+            params: GraphTransformParams {
+                weight_normalization: Some(method),
+                weight_range_min: Some(range.0),
+                weight_range_max: Some(range.1),
+                preserve_zero_weights: Some(preserve_zero),
+                ..Default::default()
+            },
+        }
+        .apply_to(&mut graph)
+        .map_err(|e| CoreError::validation(e.to_string()))?;
+        graph.name = format!("{} (normalized weights)", graph.name);
+
+        let graph_data_service = GraphDataService::new(self.db.clone());
+        let source = graph_data_service
+            .get_by_id(graph_id)
+            .await?
+            .ok_or_else(|| CoreError::not_found("GraphData", graph_id.to_string()))?;
+        let metadata = serde_json::json!({
+            "normalizedFromGraphId": graph_id,
+            "method": method,
+            "range": [range.0, range.1],
+            "preserveZero": preserve_zero,
+        });
+        graph_data_service
+            .create_computed_from_graph(source.project_id, &graph, Some(metadata))
+            .await
+    }
+
+    /// Split a graph into one graph per connected component, optionally
+    /// persisting each as a separate computed graph in the same project.
+    pub async fn split_by_component(
+        &self,
+        graph_id: i32,
+        persist: bool,
+    ) -> CoreResult<ComponentSplitReport> {
+        let graph_service = GraphService::new(self.db.clone());
+        let graph = graph_service.build_graph_from_dag_graph(graph_id).await?;
+        let graphs = graph.split_by_component();
+
+        let mut persisted_graph_ids = Vec::new();
+        if persist {
+            let graph_data_service = GraphDataService::new(self.db.clone());
+            let source = graph_data_service
+                .get_by_id(graph_id)
+                .await?
+                .ok_or_else(|| CoreError::not_found("GraphData", graph_id.to_string()))?;
+            for (idx, component) in graphs.iter().enumerate() {
+                let metadata = serde_json::json!({
+                    "splitFromGraphId": graph_id,
+                    "componentIndex": idx,
+                });
+                let record = graph_data_service
+                    .create_computed_from_graph(source.project_id, component, Some(metadata))
+                    .await?;
+                persisted_graph_ids.push(record.id);
+            }
+        }
+
+        Ok(ComponentSplitReport {
+            graph_id,
+            graphs,
+            persisted_graph_ids,
+        })
+    }
+}
+
+/// Counts, density, degree and components in one pass over nodes and edges,
+/// using union-find for the components.
+fn compute_summary_metrics(graph_id: i32, graph: &Graph) -> GraphSummaryMetrics {
+    let index: HashMap<&str, usize> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(idx, node)| (node.id.as_str(), idx))
+        .collect();
+    let mut parent: Vec<usize> = (0..graph.nodes.len()).collect();
+
+    let mut component_count = graph.nodes.len();
+    for edge in &graph.edges {
+        if let (Some(&source), Some(&target)) = (
+            index.get(edge.source.as_str()),
+            index.get(edge.target.as_str()),
+        ) {
+            let (a, b) = (
+                union_find_root(&mut parent, source),
+                union_find_root(&mut parent, target),
+            );
+            if a != b {
+                parent[a] = b;
+                component_count -= 1;
+            }
+        }
+    }
+
+    let nodes = graph.nodes.len() as f64;
+    let edges = graph.edges.len() as f64;
+    GraphSummaryMetrics {
+        graph_id,
+        node_count: graph.nodes.len(),
+        edge_count: graph.edges.len(),
+        density: if graph.nodes.len() < 2 {
+            0.0
+        } else {
+            edges / (nodes * (nodes - 1.0))
+        },
+        average_degree: if graph.nodes.is_empty() {
+            0.0
+        } else {
+            2.0 * edges / nodes
+        },
+        component_count,
+    }
+}
+
+/// Degree statistics over all nodes. Edges with an unknown endpoint are left
+/// out of every figure, so `average_degree` is the mean of the histogram.
+/// Graphs too small for a ratio report zeros.
+fn compute_topology(graph_id: i32, graph: &Graph, directed: bool) -> TopologyReport {
+    let mut degrees: HashMap<&str, usize> = graph
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), 0))
+        .collect();
+    let mut edge_count = 0;
+    for edge in &graph.edges {
+        let (source, target) = (edge.source.as_str(), edge.target.as_str());
+        if !degrees.contains_key(source) || !degrees.contains_key(target) {
+            continue;
+        }
+        edge_count += 1;
+        for endpoint in [source, target] {
+            *degrees.get_mut(endpoint).expect("known endpoint") += 1;
+        }
+    }
+
+    let mut histogram: BTreeMap<usize, usize> = BTreeMap::new();
+    for degree in degrees.values() {
+        *histogram.entry(*degree).or_default() += 1;
+    }
+
+    let nodes = graph.nodes.len() as f64;
+    let edges = edge_count as f64;
+    let density = if graph.nodes.len() < 2 {
+        0.0
+    } else if directed {
+        edges / (nodes * (nodes - 1.0))
+    } else {
+        2.0 * edges / (nodes * (nodes - 1.0))
+    };
+    TopologyReport {
+        graph_id,
+        directed,
+        node_count: graph.nodes.len(),
+        edge_count,
+        density,
+        average_degree: if graph.nodes.is_empty() {
+            0.0
+        } else {
+            2.0 * edges / nodes
+        },
+        max_degree: histogram.keys().next_back().copied().unwrap_or(0),
+        min_degree: histogram.keys().next().copied().unwrap_or(0),
+        degree_histogram: histogram
+            .into_iter()
+            .map(|(degree, count)| DegreeCount { degree, count })
+            .collect(),
+    }
+}
+
+/// Union-find root of `idx`, halving the path on the way. Shared by every
+/// union-find over node indices (components, spanning trees).
+pub(crate) fn union_find_root(parent: &mut [usize], mut idx: usize) -> usize {
+    while parent[idx] != idx {
+        parent[idx] = parent[parent[idx]];
+        idx = parent[idx];
+    }
+    idx
+}
+
+/// Weakly connected components via union-find, or strongly connected ones via
+/// Tarjan's algorithm when `directed`. Edges to unknown nodes are ignored.
+fn compute_component_summary(graph_id: i32, graph: &Graph, directed: bool) -> ComponentSummary {
+    let index: HashMap<&str, usize> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(idx, node)| (node.id.as_str(), idx))
+        .collect();
+    let edges = graph.edges.iter().filter_map(|edge| {
+        Some((
+            *index.get(edge.source.as_str())?,
+            *index.get(edge.target.as_str())?,
+        ))
+    });
+
+    let mut component_sizes = if directed {
+        let mut successors = vec![Vec::new(); graph.nodes.len()];
+        for (source, target) in edges {
+            successors[source].push(target);
+        }
+        strongly_connected_component_sizes(&successors)
+    } else {
+        let mut parent: Vec<usize> = (0..graph.nodes.len()).collect();
+        for (source, target) in edges {
+            let (a, b) = (
+                union_find_root(&mut parent, source),
+                union_find_root(&mut parent, target),
+            );
+            if a != b {
+                parent[a] = b;
+            }
+        }
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        for idx in 0..graph.nodes.len() {
+            *sizes.entry(union_find_root(&mut parent, idx)).or_default() += 1;
+        }
+        sizes.into_values().collect()
+    };
+    component_sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+    ComponentSummary {
+        graph_id,
+        directed,
+        component_count: component_sizes.len(),
+        largest_component_size: component_sizes.first().copied().unwrap_or(0),
+        component_sizes,
+    }
+}
+
+/// Sizes of the strongly connected components of `successors`, using an
+/// iterative form of Tarjan's algorithm so deep graphs cannot overflow.
+fn strongly_connected_component_sizes(successors: &[Vec<usize>]) -> Vec<usize> {
+    let count = successors.len();
+    let mut index = vec![usize::MAX; count];
+    let mut low = vec![0; count];
+    let mut on_stack = vec![false; count];
+    let mut stack = Vec::new();
+    let mut sizes = Vec::new();
+    let mut next_index = 0;
+
+    for start in 0..count {
+        if index[start] != usize::MAX {
+            continue;
+        }
+        let mut calls = vec![(start, 0)];
+        index[start] = next_index;
+        low[start] = next_index;
+        next_index += 1;
+        stack.push(start);
+        on_stack[start] = true;
+
+        while let Some((node, next)) = calls.last_mut() {
+            let node = *node;
+            if let Some(&successor) = successors[node].get(*next) {
+                *next += 1;
+                if index[successor] == usize::MAX {
+                    index[successor] = next_index;
+                    low[successor] = next_index;
+                    next_index += 1;
+                    stack.push(successor);
+                    on_stack[successor] = true;
+                    calls.push((successor, 0));
+                } else if on_stack[successor] {
+                    low[node] = low[node].min(index[successor]);
+                }
+                continue;
+            }
+            calls.pop();
+            if low[node] == index[node] {
+                let mut size = 0;
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    size += 1;
+                    if member == node {
+                        break;
+                    }
+                }
+                sizes.push(size);
+            }
+            if let Some((caller, _)) = calls.last() {
+                low[*caller] = low[*caller].min(low[node]);
+            }
+        }
+    }
+
+    sizes
 }
 
 fn build_adjacency(graph: &Graph) -> HashMap<String, Vec<String>> {
@@ -153,3 +877,257 @@ fn find_all_paths(
 
     paths
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Edge, Node};
+
+    fn node(id: &str) -> Node {
+        Node {
+            id: id.to_string(),
+            label: id.to_string(),
+            layer: "app".to_string(),
+            weight: 1,
+            ..Default::default()
+        }
+    }
+
+    fn edge(source: &str, target: &str) -> Edge {
+        weighted_edge(source, target, 1)
+    }
+
+    fn weighted_edge(source: &str, target: &str, weight: i32) -> Edge {
+        Edge {
+            id: format!("{}_{}", source, target),
+            source: source.to_string(),
+            target: target.to_string(),
+            layer: "app".to_string(),
+            weight,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn analysis_cache_evicts_least_recently_used_and_stale_entries() {
+        let cache: AnalysisCache<bool, usize> = AnalysisCache::new(2);
+        let at = |seconds: i64| {
+            Some((
+                chrono::DateTime::from_timestamp(seconds, 0).unwrap(),
+                0,
+                0,
+                0,
+            ))
+        };
+        let get = |graph_id: i32, key, value: usize| {
+            cache.get_or_compute(graph_id, false, key, async move { Ok(value) })
+        };
+
+        assert_eq!(get(1, at(0), 10).await.unwrap(), 10);
+        assert_eq!(get(2, at(0), 20).await.unwrap(), 20);
+        // A hit keeps the cached value and makes graph 1 the most recent
+        assert_eq!(get(1, at(0), 11).await.unwrap(), 10);
+        assert_eq!(get(3, at(0), 30).await.unwrap(), 30);
+        // Graph 2 was least recently used, so inserting graph 3 evicted it;
+        // bringing it back in turn evicts graph 1
+        assert_eq!(get(2, at(0), 21).await.unwrap(), 21);
+        assert_eq!(get(1, at(0), 12).await.unwrap(), 12);
+        // A changed graph recomputes, and unpersisted graphs are never stored
+        assert_eq!(get(1, at(5), 13).await.unwrap(), 13);
+        assert_eq!(get(4, None, 40).await.unwrap(), 40);
+        assert_eq!(get(4, None, 41).await.unwrap(), 41);
+        assert_eq!(cache.entries.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn summary_metrics_on_small_directed_graph() {
+        // a -> b -> c, a -> c, plus d -> e and an isolated f.
+        let graph = Graph {
+            name: "Metrics".to_string(),
+            nodes: ["a", "b", "c", "d", "e", "f"].map(node).to_vec(),
+            edges: vec![
+                edge("a", "b"),
+                edge("b", "c"),
+                edge("a", "c"),
+                edge("d", "e"),
+            ],
+            layers: Vec::new(),
+            annotations: None,
+        };
+
+        let metrics = compute_summary_metrics(7, &graph);
+        assert_eq!(metrics.graph_id, 7);
+        assert_eq!(metrics.node_count, 6);
+        assert_eq!(metrics.edge_count, 4);
+        assert!((metrics.density - 4.0 / 30.0).abs() < 1e-12);
+        assert!((metrics.average_degree - 8.0 / 6.0).abs() < 1e-12);
+        assert_eq!(metrics.component_count, 3);
+
+        let single = Graph {
+            nodes: vec![node("solo")],
+            ..Default::default()
+        };
+        let metrics = compute_summary_metrics(1, &single);
+        assert_eq!(metrics.density, 0.0);
+        assert_eq!(metrics.component_count, 1);
+    }
+
+    #[test]
+    fn topology_of_star_graph() {
+        let leaves = ["a", "b", "c", "d", "e"];
+        let mut nodes = vec![node("hub")];
+        nodes.extend(leaves.map(node));
+        let star = Graph {
+            nodes,
+            edges: leaves.iter().map(|leaf| edge("hub", leaf)).collect(),
+            ..Default::default()
+        };
+
+        let directed = compute_topology(3, &star, true);
+        assert_eq!(directed.max_degree, leaves.len());
+        assert_eq!(directed.min_degree, 1);
+        assert_eq!(
+            directed.degree_histogram,
+            vec![
+                DegreeCount {
+                    degree: 1,
+                    count: leaves.len()
+                },
+                DegreeCount {
+                    degree: leaves.len(),
+                    count: 1
+                },
+            ]
+        );
+        assert!((directed.density - 5.0 / 30.0).abs() < 1e-12);
+        assert!((directed.average_degree - 10.0 / 6.0).abs() < 1e-12);
+        let undirected = compute_topology(3, &star, false);
+        assert!((undirected.density - 10.0 / 30.0).abs() < 1e-12);
+
+        // A dangling edge changes neither the degrees nor their average.
+        let mut dangling = star.clone();
+        dangling.edges.push(edge("hub", "missing"));
+        let report = compute_topology(3, &dangling, true);
+        assert_eq!(report.edge_count, leaves.len());
+        assert_eq!(report.max_degree, leaves.len());
+        assert_eq!(report.average_degree, directed.average_degree);
+
+        let empty = compute_topology(4, &Graph::default(), false);
+        assert_eq!(empty.density, 0.0);
+        assert_eq!(empty.average_degree, 0.0);
+        assert_eq!(empty.max_degree, 0);
+        assert!(empty.degree_histogram.is_empty());
+    }
+
+    #[test]
+    fn component_summary_weak_and_strong() {
+        // A directed cycle a -> b -> c -> a, so fully connected either way.
+        let cycle = Graph {
+            nodes: ["a", "b", "c"].map(node).to_vec(),
+            edges: vec![edge("a", "b"), edge("b", "c"), edge("c", "a")],
+            ..Default::default()
+        };
+        for directed in [false, true] {
+            let summary = compute_component_summary(1, &cycle, directed);
+            assert_eq!(summary.component_count, 1);
+            assert_eq!(summary.component_sizes, vec![3]);
+        }
+
+        let isolated = Graph {
+            nodes: ["a", "b", "c", "d"].map(node).to_vec(),
+            ..Default::default()
+        };
+        let summary = compute_component_summary(2, &isolated, false);
+        assert_eq!(summary.component_count, 4);
+        assert_eq!(summary.largest_component_size, 1);
+        assert_eq!(summary.component_sizes, vec![1, 1, 1, 1]);
+
+        // The cycle plus a tail c -> d -> e: one weak component, but d and e
+        // are their own strong components.
+        let mut tailed = cycle.clone();
+        tailed.nodes.extend(["d", "e"].map(node));
+        tailed.edges.extend([edge("c", "d"), edge("d", "e")]);
+        assert_eq!(
+            compute_component_summary(3, &tailed, false).component_sizes,
+            vec![5]
+        );
+        let strong = compute_component_summary(3, &tailed, true);
+        assert_eq!(strong.component_sizes, vec![3, 1, 1]);
+        assert_eq!(strong.largest_component_size, 3);
+
+        let empty = compute_component_summary(4, &Graph::default(), true);
+        assert_eq!(empty.component_count, 0);
+        assert_eq!(empty.largest_component_size, 0);
+    }
+
+    #[test]
+    fn betweenness_peaks_at_bottleneck_and_follows_weights() {
+        // Two triangles joined only through `hub`.
+        let bridged = Graph {
+            nodes: ["a", "b", "c", "hub", "d", "e", "f"].map(node).to_vec(),
+            edges: vec![
+                edge("a", "b"),
+                edge("b", "c"),
+                edge("c", "a"),
+                edge("c", "hub"),
+                edge("hub", "d"),
+                edge("d", "e"),
+                edge("e", "f"),
+                edge("f", "d"),
+            ],
+            ..Default::default()
+        };
+        let scores = bridged.betweenness_centrality(false, false, false).unwrap();
+        // Every path between the triangles crosses c, hub and d; hub also
+        // sits between c and d themselves.
+        assert_eq!(scores["hub"], 9.0);
+        assert_eq!(scores["c"], 8.0);
+        assert_eq!(scores["d"], 8.0);
+        assert_eq!(scores["a"], 0.0);
+        let top = scores
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(id, _)| id.as_str());
+        assert_eq!(top, Some("hub"));
+
+        let normalized = bridged.betweenness_centrality(false, false, true).unwrap();
+        assert!((normalized["hub"] - 9.0 / 15.0).abs() < 1e-12);
+
+        // s reaches t through x or y; by hops both routes tie, by weight only
+        // the route through x is shortest.
+        let diamond = Graph {
+            nodes: ["s", "x", "y", "t"].map(node).to_vec(),
+            edges: vec![
+                weighted_edge("s", "x", 1),
+                weighted_edge("x", "t", 1),
+                weighted_edge("s", "y", 5),
+                weighted_edge("y", "t", 5),
+            ],
+            ..Default::default()
+        };
+        let hops = diamond.betweenness_centrality(false, true, false).unwrap();
+        assert_eq!((hops["x"], hops["y"]), (0.5, 0.5));
+        let weighted = diamond.betweenness_centrality(true, true, false).unwrap();
+        assert_eq!((weighted["x"], weighted["y"]), (1.0, 0.0));
+        // Undirected, x and y also reach each other through s or t at equal
+        // cost, splitting that pair between them.
+        let undirected = diamond.betweenness_centrality(true, false, false).unwrap();
+        assert_eq!((undirected["x"], undirected["y"]), (1.0, 0.0));
+        assert_eq!((undirected["s"], undirected["t"]), (0.5, 0.5));
+
+        let mut negative = diamond.clone();
+        negative.edges[0].weight = -1;
+        assert!(negative.betweenness_centrality(true, true, false).is_err());
+
+        // Zero-cost edges can reach a node at its own distance after it is
+        // settled, so weighted scores reject them; hop counts still work.
+        let mut zero = diamond.clone();
+        zero.edges[1].weight = 0;
+        let err = zero.betweenness_centrality(true, true, false).unwrap_err();
+        assert!(err.contains("non-positive weight 0"), "{err}");
+        assert_eq!(
+            zero.betweenness_centrality(false, true, false).unwrap(),
+            hops
+        );
+    }
+}
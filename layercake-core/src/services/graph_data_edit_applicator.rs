@@ -247,14 +247,26 @@ impl GraphDataEditApplicator {
             .map_err(|e| CoreError::internal("Failed to delete graph_data node").with_source(e))?;
 
         if result.rows_affected == 0 {
-            Ok(ApplyResult::Skipped {
+            return Ok(ApplyResult::Skipped {
                 reason: format!("Node {} not found", edit.target_id),
-            })
-        } else {
-            Ok(ApplyResult::Success {
-                message: format!("Deleted node {}", edit.target_id),
-            })
+            });
         }
+
+        // Edges cannot outlive either endpoint
+        GraphDataEdges::delete_many()
+            .filter(graph_data_edges::Column::GraphDataId.eq(edit.graph_id))
+            .filter(
+                graph_data_edges::Column::Source
+                    .eq(&edit.target_id)
+                    .or(graph_data_edges::Column::Target.eq(&edit.target_id)),
+            )
+            .exec(conn)
+            .await
+            .map_err(|e| CoreError::internal("Failed to delete graph_data edges").with_source(e))?;
+
+        Ok(ApplyResult::Success {
+            message: format!("Deleted node {}", edit.target_id),
+        })
     }
 
     /// Apply edit to a graph_data edge
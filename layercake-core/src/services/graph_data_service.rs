@@ -6,14 +6,20 @@ use crate::database::entities::{
     plan_dag_nodes, plans,
 };
 use crate::errors::{CoreError, CoreResult};
+use crate::graph::Graph;
+use crate::pipeline::graph_data_persist_utils::{
+    edges_to_graph_data_inputs, nodes_to_graph_data_inputs,
+};
 use crate::services::graph_data_edit_applicator::{ApplyResult, GraphDataEditApplicator};
+use crate::services::graph_snapshot_service::GraphSnapshotService;
 use chrono::Utc;
 use sea_orm::ActiveValue::Set;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
     QueryFilter, QueryOrder, TransactionTrait,
 };
-use serde_json::Value;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 use tracing::{debug, info, warn};
 
 pub struct GraphDataService {
@@ -142,21 +148,32 @@ impl GraphDataService {
             CoreError::internal("Failed to begin graph_data transaction").with_source(e)
         })?;
 
+        Self::replace_contents_in_txn(&txn, graph_data_id, &nodes, &edges).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| CoreError::internal("Failed to commit graph_data contents").with_source(e))
+    }
+
+    /// `replace_contents` within a caller-owned transaction, so the caller can
+    /// commit other changes atomically with the new contents.
+    pub(crate) async fn replace_contents_in_txn(
+        txn: &DatabaseTransaction,
+        graph_data_id: i32,
+        nodes: &[GraphDataNodeInput],
+        edges: &[GraphDataEdgeInput],
+    ) -> CoreResult<()> {
         let now = Utc::now();
-        Self::replace_nodes_in_txn(&txn, graph_data_id, &nodes, now).await?;
-        Self::replace_edges_in_txn(&txn, graph_data_id, &edges, now).await?;
+        Self::replace_nodes_in_txn(txn, graph_data_id, nodes, now).await?;
+        Self::replace_edges_in_txn(txn, graph_data_id, edges, now).await?;
         Self::update_counts_in_txn(
-            &txn,
+            txn,
             graph_data_id,
             nodes.len() as i32,
             edges.len() as i32,
             now,
         )
-        .await?;
-
-        txn.commit()
-            .await
-            .map_err(|e| CoreError::internal("Failed to commit graph_data contents").with_source(e))
+        .await
     }
 
     /// Delete existing nodes (and their edges) and insert the given nodes.
@@ -500,6 +517,52 @@ impl GraphDataService {
         .await
     }
 
+    /// Persist an in-memory graph as a computed graph_data entry that is not
+    /// tied to a DAG node (e.g. the result of an API-level merge or split).
+    pub async fn create_computed_from_graph(
+        &self,
+        project_id: i32,
+        graph: &Graph,
+        metadata: Option<Value>,
+    ) -> CoreResult<graph_data::Model> {
+        let record = self
+            .create(GraphDataCreate {
+                project_id,
+                name: graph.name.clone(),
+                source_type: "computed".to_string(),
+                dag_node_id: None,
+                file_format: None,
+                origin: None,
+                filename: None,
+                blob: None,
+                file_size: None,
+                processed_at: None,
+                source_hash: None,
+                computed_date: None,
+                last_edit_sequence: Some(0),
+                has_pending_edits: Some(false),
+                last_replay_at: None,
+                metadata,
+                annotations: graph.annotations.as_ref().map(|a| json!([a])),
+                status: Some(GraphDataStatus::Processing),
+            })
+            .await?;
+
+        // The conversion keeps node and edge attributes alongside layer/comment
+        let node_inputs = nodes_to_graph_data_inputs(&graph.nodes);
+        let edge_inputs = edges_to_graph_data_inputs(&graph.edges);
+        self.replace_contents(record.id, node_inputs, edge_inputs)
+            .await?;
+
+        let serialized = serde_json::to_string(graph)
+            .map_err(|e| CoreError::internal("Failed to serialise graph").with_source(e))?;
+        let hash = format!("{:x}", Sha256::digest(serialized.as_bytes()));
+        self.mark_complete(record.id, hash).await?;
+        self.get_by_id(record.id)
+            .await?
+            .ok_or_else(|| CoreError::not_found("GraphData", record.id.to_string()))
+    }
+
     /// Create a dataset graph_data entry from JSON (convenience wrapper)
     pub async fn create_from_json(
         &self,
@@ -645,7 +708,7 @@ impl GraphDataService {
 
     /// Mark an edit as applied against the given connection (which may be a
     /// transaction), so it can commit atomically with the edit's own mutation.
-    async fn mark_edit_applied_on<C: sea_orm::ConnectionTrait>(
+    pub(crate) async fn mark_edit_applied_on<C: sea_orm::ConnectionTrait>(
         &self,
         conn: &C,
         edit_id: i32,
@@ -904,6 +967,7 @@ impl GraphDataService {
             .exec(&txn)
             .await
             .map_err(|e| CoreError::internal("Failed to delete graph_data row").with_source(e))?;
+        GraphSnapshotService::delete_unreferenced_blobs(&txn).await?;
 
         txn.commit()
             .await
@@ -1020,6 +1084,7 @@ mod prune_tests {
             import_export_path: Set(None),
             created_at: Set(Utc::now().into()),
             updated_at: Set(Utc::now().into()),
+            deleted_at: Set(None),
         }
         .insert(&db)
         .await
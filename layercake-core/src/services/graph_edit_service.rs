@@ -1,10 +1,12 @@
 use crate::database::entities::graph_data;
 use crate::database::entities::graph_edits::{self, Entity as GraphEdits};
-use crate::errors::{CoreError, CoreResult};
-use crate::services::GraphDataService;
+use crate::errors::{CoreError, CoreErrorKind, CoreResult};
+use crate::graph::Graph;
+use crate::services::graph_data_edit_applicator::{ApplyResult, GraphDataEditApplicator};
+use crate::services::{GraphDataService, GraphService};
 use sea_orm::{
     ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, Set,
+    QueryFilter, QueryOrder, Set, TransactionTrait,
 };
 
 /// Service for managing graph edit operations
@@ -252,6 +254,75 @@ impl GraphEditService {
         Ok(())
     }
 
+    /// Fold the graph's unapplied edits over its stored contents and return the
+    /// resulting graph.
+    ///
+    /// Edits are applied oldest first (`created_at`, then `sequence_number`)
+    /// with `GraphDataEditApplicator`, all in a single transaction that also
+    /// marks them applied, so a second call finds nothing left to fold and
+    /// returns the same graph. Edits the applicator skips (e.g. an update to a
+    /// deleted node) stay unapplied and are reported in `skipped`.
+    pub async fn apply_edits(&self, graph_id: i32) -> CoreResult<AppliedEdits> {
+        let graph_service = GraphService::new(self.db.clone());
+        let graph_data_service = GraphDataService::new(self.db.clone());
+        let applicator = GraphDataEditApplicator::new(self.db.clone());
+
+        let mut edits = self.get_edits_for_graph(graph_id, true).await?;
+        edits.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then(a.sequence_number.cmp(&b.sequence_number))
+        });
+
+        let txn = self.db.begin().await.map_err(|e| {
+            CoreError::internal("Failed to begin graph edit transaction").with_source(e)
+        })?;
+        let mut applied = 0;
+        let mut skipped = Vec::new();
+        for edit in &edits {
+            let reason = match applicator.apply_edit_on(&txn, edit).await {
+                Ok(ApplyResult::Success { .. }) => {
+                    graph_data_service
+                        .mark_edit_applied_on(&txn, edit.id)
+                        .await?;
+                    applied += 1;
+                    continue;
+                }
+                Ok(ApplyResult::Skipped { reason }) | Ok(ApplyResult::Error { reason }) => reason,
+                // A malformed edit payload is reported rather than aborting the fold.
+                Err(e) if e.kind() == CoreErrorKind::Validation => e.message().to_string(),
+                Err(e) => return Err(e),
+            };
+            skipped.push(SkippedEdit {
+                edit_id: edit.id,
+                sequence_number: edit.sequence_number,
+                target_type: edit.target_type.clone(),
+                target_id: edit.target_id.clone(),
+                operation: edit.operation.clone(),
+                reason,
+            });
+        }
+        txn.commit().await.map_err(|e| {
+            CoreError::internal("Failed to commit graph edit transaction").with_source(e)
+        })?;
+
+        if applied > 0 {
+            graph_data_service.mark_replayed(graph_id).await?;
+            if skipped.is_empty() {
+                graph_data_service
+                    .set_pending_state(graph_id, false)
+                    .await?;
+            }
+        }
+
+        let graph = graph_service.build_graph_from_dag_graph(graph_id).await?;
+        Ok(AppliedEdits {
+            graph,
+            applied,
+            skipped,
+        })
+    }
+
     /// Get edit count for a graph
     pub async fn get_edit_count(&self, graph_id: i32, unapplied_only: bool) -> CoreResult<u64> {
         let mut query = GraphEdits::find().filter(graph_edits::Column::GraphId.eq(graph_id));
@@ -289,6 +360,25 @@ pub struct EditResult {
     pub message: String,
 }
 
+/// Result of folding a graph's unapplied edits with `apply_edits`
+#[derive(Debug, Clone)]
+pub struct AppliedEdits {
+    pub graph: Graph,
+    pub applied: usize,
+    pub skipped: Vec<SkippedEdit>,
+}
+
+/// An edit left unapplied because it conflicted with the graph
+#[derive(Debug, Clone)]
+pub struct SkippedEdit {
+    pub edit_id: i32,
+    pub sequence_number: i32,
+    pub target_type: String,
+    pub target_id: String,
+    pub operation: String,
+    pub reason: String,
+}
+
 /// Detect a unique-constraint violation across backends. SeaORM surfaces the
 /// underlying driver error; for SQLite the message contains "UNIQUE constraint
 /// failed", for Postgres "duplicate key value violates unique constraint".
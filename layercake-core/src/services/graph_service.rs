@@ -5,6 +5,7 @@ use crate::database::entities::{
 };
 use crate::errors::{CoreError, CoreResult};
 use crate::graph::{Edge, Graph, Layer, Node};
+use crate::services::graph_snapshot_service::GraphSnapshotService;
 use crate::services::GraphDataService;
 use chrono::Utc;
 use indexmap::IndexMap;
@@ -14,7 +15,7 @@ use sea_orm::{
     QueryOrder,
 };
 use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 pub struct GraphService {
     db: DatabaseConnection,
@@ -392,12 +393,18 @@ impl GraphService {
         let graph = self.build_graph_from_dag_graph(graph_id).await?;
 
         let mut errors = Vec::new();
-        let warnings = Vec::new();
+        let mut warnings = Vec::new();
 
         if let Err(mut validation_errors) = graph.verify_graph_integrity() {
             errors.append(&mut validation_errors);
         }
 
+        warnings.extend(graph.edge_integrity_warnings());
+
+        for cycle in self.validate_layer_aliases(gd.project_id).await? {
+            warnings.push(format!("Layer alias cycle: {}", cycle.join(" -> ")));
+        }
+
         Ok(GraphValidationSummary {
             graph_id,
             project_id: gd.project_id,
@@ -526,11 +533,12 @@ impl GraphService {
             }
         }
 
-        // Delete the graph itself (cascades to nodes/edges)
+        // Delete the graph itself (cascades to nodes/edges and snapshots)
         graph_data::Entity::delete_by_id(graph.id)
             .exec(&self.db)
             .await
             .map_err(|e| CoreError::internal(format!("Database error: {}", e)))?;
+        GraphSnapshotService::delete_unreferenced_blobs(&self.db).await?;
 
         Ok(())
     }
@@ -738,6 +746,130 @@ impl GraphService {
         }
     }
 
+    /// Rename and recolour a layer across the whole project palette: every
+    /// `project_layers` row for `layer_id` (project-level and dataset-scoped)
+    /// is updated, so resolved layers pick up the change on the next read.
+    /// Fields left as `None` are kept. An `alias` registers another layer id
+    /// that resolves to this layer through `layer_aliases`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_project_layer(
+        &self,
+        project_id: i32,
+        layer_id: &str,
+        name: Option<String>,
+        background_color: Option<String>,
+        text_color: Option<String>,
+        border_color: Option<String>,
+        alias: Option<String>,
+    ) -> CoreResult<Vec<project_layers::Model>> {
+        use sea_orm::{ActiveModelTrait, Set, TransactionTrait};
+
+        let background_color = background_color
+            .map(|color| normalize_layer_color("backgroundColor", &color))
+            .transpose()?;
+        let text_color = text_color
+            .map(|color| normalize_layer_color("textColor", &color))
+            .transpose()?;
+        let border_color = border_color
+            .map(|color| normalize_layer_color("borderColor", &color))
+            .transpose()?;
+        let name = name.map(|name| name.trim().to_string());
+        if name.as_deref() == Some("") {
+            return Err(CoreError::validation("Layer name cannot be empty")
+                .with_fields(BTreeMap::from([("field".to_string(), "name".to_string())])));
+        }
+        let alias = Self::normalize_alias(alias);
+
+        let rows = project_layers::Entity::find()
+            .filter(project_layers::Column::ProjectId.eq(project_id))
+            .filter(project_layers::Column::LayerId.eq(layer_id))
+            .order_by_asc(project_layers::Column::SourceDatasetId)
+            .all(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Database error: {}", e)))?;
+        // Project-level rows (no source dataset) sort first
+        let Some(target) = rows.first().cloned() else {
+            return Err(CoreError::not_found("ProjectLayer", layer_id.to_string()));
+        };
+
+        if let Some(alias) = alias.as_deref() {
+            let shadows_layer = alias == layer_id
+                || project_layers::Entity::find()
+                    .filter(project_layers::Column::ProjectId.eq(project_id))
+                    .filter(project_layers::Column::LayerId.eq(alias))
+                    .count(&self.db)
+                    .await
+                    .map_err(|e| CoreError::internal(format!("Database error: {}", e)))?
+                    > 0;
+            if shadows_layer {
+                return Err(CoreError::validation(format!(
+                    "Alias '{}' is already a layer in project {}",
+                    alias, project_id
+                ))
+                .with_fields(BTreeMap::from([
+                    ("field".to_string(), "alias".to_string()),
+                    ("value".to_string(), alias.to_string()),
+                ])));
+            }
+        }
+
+        // Row updates and the alias repoint land together or not at all
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to begin layer update: {}", e)))?;
+        let now = chrono::Utc::now();
+        let mut updated = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut active: project_layers::ActiveModel = row.into();
+            if let Some(name) = &name {
+                active.name = Set(name.clone());
+            }
+            if let Some(color) = &background_color {
+                active.background_color = Set(color.clone());
+            }
+            if let Some(color) = &text_color {
+                active.text_color = Set(color.clone());
+            }
+            if let Some(color) = &border_color {
+                active.border_color = Set(color.clone());
+            }
+            active.updated_at = Set(now);
+            updated.push(
+                active
+                    .update(&txn)
+                    .await
+                    .map_err(|e| CoreError::internal(format!("Database error: {}", e)))?,
+            );
+        }
+
+        if let Some(alias) = alias {
+            // An alias id maps to a single layer, so repoint any existing entry
+            layer_aliases::Entity::delete_many()
+                .filter(layer_aliases::Column::ProjectId.eq(project_id))
+                .filter(layer_aliases::Column::AliasLayerId.eq(alias.clone()))
+                .exec(&txn)
+                .await
+                .map_err(|e| CoreError::internal(format!("Database error: {}", e)))?;
+            layer_aliases::ActiveModel {
+                id: sea_orm::ActiveValue::NotSet,
+                project_id: Set(project_id),
+                alias_layer_id: Set(alias),
+                target_layer_id: Set(target.id),
+                created_at: Set(now),
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| CoreError::internal(format!("Database error: {}", e)))?;
+        }
+        txn.commit()
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to commit layer update: {}", e)))?;
+
+        Ok(updated)
+    }
+
     pub async fn delete_project_layer(
         &self,
         project_id: i32,
@@ -955,6 +1087,34 @@ impl GraphService {
         Ok(None)
     }
 
+    /// Find alias cycles (A -> B -> A) across `project_layers.alias` and the
+    /// `layer_aliases` table. Each cycle is returned once, starting from its
+    /// smallest layer id. Resolution breaks these cycles, so this is a report only.
+    pub async fn validate_layer_aliases(&self, project_id: i32) -> CoreResult<Vec<Vec<String>>> {
+        let mut links: Vec<(String, String)> = project_layers::Entity::find()
+            .filter(project_layers::Column::ProjectId.eq(project_id))
+            .all(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Database error: {}", e)))?
+            .into_iter()
+            .filter_map(|layer| {
+                Self::normalize_alias(layer.alias).map(|alias| (layer.layer_id, alias))
+            })
+            .collect();
+
+        let aliases = layer_aliases::Entity::find()
+            .filter(layer_aliases::Column::ProjectId.eq(project_id))
+            .find_also_related(project_layers::Entity)
+            .all(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Database error: {}", e)))?;
+        links.extend(aliases.into_iter().filter_map(|(alias, target)| {
+            target.map(|target| (alias.alias_layer_id, target.layer_id))
+        }));
+
+        Ok(find_alias_cycles(&links))
+    }
+
     /// Get all layers for a project, including aliases as separate layer entries
     /// Only returns enabled layers (or aliases pointing to enabled layers)
     pub async fn get_all_resolved_layers(&self, project_id: i32) -> CoreResult<Vec<Layer>> {
@@ -1038,7 +1198,8 @@ impl GraphService {
         let mut border_color = sanitize_hex(&layer.border_color, "000000");
 
         let mut current_alias = layer.alias.clone();
-        let mut visited = HashSet::new();
+        // Seed with the starting layer so A -> B -> A stops at B's colours.
+        let mut visited = HashSet::from([layer.layer_id.clone()]);
 
         while let Some(alias_layer_id) = current_alias {
             if !visited.insert(alias_layer_id.clone()) {
@@ -1104,6 +1265,55 @@ impl GraphService {
     }
 }
 
+/// Detect cycles in alias links (`from` layer id -> `to` layer id).
+fn find_alias_cycles(links: &[(String, String)]) -> Vec<Vec<String>> {
+    let mut targets: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (from, to) in links {
+        targets
+            .entry(from.as_str())
+            .or_default()
+            .insert(to.as_str());
+    }
+
+    let mut cycles: BTreeSet<Vec<String>> = BTreeSet::new();
+    let mut done: HashSet<&str> = HashSet::new();
+    for &start in targets.keys() {
+        if done.contains(start) {
+            continue;
+        }
+        // Iterative DFS; `path` holds the current chain of layer ids.
+        let mut path: Vec<&str> = vec![start];
+        let mut stack = vec![targets[start].iter()];
+        while let Some(next) = stack.last_mut().map(|iter| iter.next()) {
+            let Some(&next) = next else {
+                stack.pop();
+                if let Some(finished) = path.pop() {
+                    done.insert(finished);
+                }
+                continue;
+            };
+            if let Some(pos) = path.iter().position(|id| *id == next) {
+                let mut cycle: Vec<String> = path[pos..].iter().map(|id| id.to_string()).collect();
+                let min = cycle
+                    .iter()
+                    .enumerate()
+                    .min_by(|a, b| a.1.cmp(b.1))
+                    .map(|(i, _)| i)
+                    .unwrap_or(0);
+                cycle.rotate_left(min);
+                cycles.insert(cycle);
+            } else if !done.contains(next) {
+                if let Some(children) = targets.get(next) {
+                    path.push(next);
+                    stack.push(children.iter());
+                }
+            }
+        }
+    }
+
+    cycles.into_iter().collect()
+}
+
 fn sanitize_hex(value: &str, fallback: &str) -> String {
     let trimmed = value.trim();
     let source = if trimmed.is_empty() {
@@ -1113,3 +1323,20 @@ fn sanitize_hex(value: &str, fallback: &str) -> String {
     };
     source.trim_start_matches('#').to_string()
 }
+
+/// Check a user-supplied layer colour is six hex digits (an optional leading
+/// `#` is allowed) and return it without the `#`, as palette colours are stored.
+fn normalize_layer_color(field: &str, value: &str) -> CoreResult<String> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Ok(hex.to_string());
+    }
+    Err(CoreError::validation(format!(
+        "{} must be a 6-digit hex colour, got '{}'",
+        field, value
+    ))
+    .with_fields(BTreeMap::from([
+        ("field".to_string(), field.to_string()),
+        ("value".to_string(), value.to_string()),
+    ])))
+}
@@ -0,0 +1,357 @@
+use chrono::Utc;
+use sea_orm::sea_query::{OnConflict, Query};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::database::entities::{
+    graph_data, graph_data_edges, graph_data_nodes, graph_snapshot_blobs,
+    graph_snapshots::{self, Entity as GraphSnapshots},
+    project_layers,
+};
+use crate::errors::{CoreError, CoreResult};
+use crate::services::graph_data_service::{
+    GraphDataEdgeInput, GraphDataNodeInput, GraphDataService,
+};
+
+/// Service for named, immutable snapshots of graph_data contents
+///
+/// A snapshot copies a graph's nodes, edges and annotations plus the project's
+/// layer palette at that moment. Node, edge and layer sets are stored as
+/// content-addressed blobs, so repeated snapshots of unchanged contents only
+/// add a snapshot row; deleting a snapshot drops the blobs no other snapshot
+/// still uses. Restoring replaces the graph's nodes, edges and
+/// annotations; layers are shared by every graph in the project and are kept
+/// for reference only.
+pub struct GraphSnapshotService {
+    db: DatabaseConnection,
+}
+
+/// Node fields captured by a snapshot, without database ids or timestamps so
+/// identical contents hash identically.
+#[derive(Serialize, Deserialize)]
+struct SnapshotNode {
+    external_id: String,
+    label: Option<String>,
+    layer: Option<String>,
+    weight: Option<f64>,
+    is_partition: bool,
+    belongs_to: Option<String>,
+    comment: Option<String>,
+    source_dataset_id: Option<i32>,
+    attributes: Option<serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotEdge {
+    external_id: String,
+    source: String,
+    target: String,
+    label: Option<String>,
+    layer: Option<String>,
+    weight: Option<f64>,
+    comment: Option<String>,
+    source_dataset_id: Option<i32>,
+    attributes: Option<serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotLayer {
+    layer_id: String,
+    name: String,
+    background_color: String,
+    text_color: String,
+    border_color: String,
+    alias: Option<String>,
+    source_dataset_id: Option<i32>,
+    enabled: bool,
+}
+
+impl GraphSnapshotService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Snapshot the current contents of `graph_data_id` under `label`.
+    pub async fn create(
+        &self,
+        graph_data_id: i32,
+        label: &str,
+    ) -> CoreResult<graph_snapshots::Model> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err(CoreError::validation("Snapshot label cannot be empty"));
+        }
+
+        let (graph, nodes, edges) = GraphDataService::new(self.db.clone())
+            .load_full(graph_data_id)
+            .await?;
+        let layers = project_layers::Entity::find()
+            .filter(project_layers::Column::ProjectId.eq(graph.project_id))
+            .order_by_asc(project_layers::Column::LayerId)
+            .order_by_asc(project_layers::Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load project layers: {}", e)))?;
+
+        let mut nodes: Vec<SnapshotNode> = nodes.into_iter().map(SnapshotNode::from).collect();
+        nodes.sort_by(|a, b| a.external_id.cmp(&b.external_id));
+        let mut edges: Vec<SnapshotEdge> = edges.into_iter().map(SnapshotEdge::from).collect();
+        edges.sort_by(|a, b| a.external_id.cmp(&b.external_id));
+        let layers: Vec<SnapshotLayer> = layers.into_iter().map(SnapshotLayer::from).collect();
+
+        let txn = self.db.begin().await.map_err(|e| {
+            CoreError::internal(format!("Failed to begin snapshot transaction: {}", e))
+        })?;
+        let nodes_hash = store_blob(&txn, &nodes).await?;
+        let edges_hash = store_blob(&txn, &edges).await?;
+        let layers_hash = store_blob(&txn, &layers).await?;
+        let snapshot = graph_snapshots::ActiveModel {
+            id: ActiveValue::NotSet,
+            graph_data_id: Set(graph_data_id),
+            label: Set(label.to_string()),
+            nodes_hash: Set(nodes_hash),
+            edges_hash: Set(edges_hash),
+            layers_hash: Set(layers_hash),
+            annotations: Set(graph.annotations),
+            node_count: Set(nodes.len() as i32),
+            edge_count: Set(edges.len() as i32),
+            created_at: Set(Utc::now()),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| CoreError::internal(format!("Failed to save graph snapshot: {}", e)))?;
+        txn.commit()
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to save graph snapshot: {}", e)))?;
+
+        Ok(snapshot)
+    }
+
+    /// Snapshots of a graph, newest first.
+    pub async fn list(&self, graph_data_id: i32) -> CoreResult<Vec<graph_snapshots::Model>> {
+        GraphSnapshots::find()
+            .filter(graph_snapshots::Column::GraphDataId.eq(graph_data_id))
+            .order_by_desc(graph_snapshots::Column::CreatedAt)
+            .order_by_desc(graph_snapshots::Column::Id)
+            .all(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to list graph snapshots: {}", e)))
+    }
+
+    pub async fn get(&self, id: i32) -> CoreResult<graph_snapshots::Model> {
+        GraphSnapshots::find_by_id(id)
+            .one(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to load graph snapshot: {}", e)))?
+            .ok_or_else(|| CoreError::not_found("GraphSnapshot", id.to_string()))
+    }
+
+    /// Delete a snapshot along with any of its blobs that no remaining
+    /// snapshot references.
+    pub async fn delete(&self, id: i32) -> CoreResult<()> {
+        self.get(id).await?;
+
+        let txn = self.db.begin().await.map_err(|e| {
+            CoreError::internal(format!("Failed to begin snapshot transaction: {}", e))
+        })?;
+        GraphSnapshots::delete_by_id(id)
+            .exec(&txn)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to delete graph snapshot: {}", e)))?;
+
+        Self::delete_unreferenced_blobs(&txn).await?;
+
+        txn.commit()
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to delete graph snapshot: {}", e)))
+    }
+
+    /// Delete the blobs no snapshot references any more, returning how many
+    /// were removed. Snapshot rows also go away with their graph (and its
+    /// project) through `ON DELETE CASCADE`, so graph and project deletion
+    /// call this too.
+    pub async fn delete_unreferenced_blobs<C: ConnectionTrait>(db: &C) -> CoreResult<u64> {
+        let referenced = |column: graph_snapshots::Column| {
+            Query::select()
+                .column(column)
+                .from(GraphSnapshots)
+                .to_owned()
+        };
+        let result = graph_snapshot_blobs::Entity::delete_many()
+            .filter(
+                graph_snapshot_blobs::Column::Hash
+                    .not_in_subquery(referenced(graph_snapshots::Column::NodesHash)),
+            )
+            .filter(
+                graph_snapshot_blobs::Column::Hash
+                    .not_in_subquery(referenced(graph_snapshots::Column::EdgesHash)),
+            )
+            .filter(
+                graph_snapshot_blobs::Column::Hash
+                    .not_in_subquery(referenced(graph_snapshots::Column::LayersHash)),
+            )
+            .exec(db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to delete snapshot blobs: {}", e)))?;
+        Ok(result.rows_affected)
+    }
+
+    /// Replace the snapshotted graph's nodes, edges and annotations with the
+    /// snapshot's, returning the updated graph. The snapshot itself is kept.
+    pub async fn restore(&self, snapshot_id: i32) -> CoreResult<graph_data::Model> {
+        let snapshot = self.get(snapshot_id).await?;
+        let nodes: Vec<SnapshotNode> = load_blob(&self.db, &snapshot.nodes_hash).await?;
+        let edges: Vec<SnapshotEdge> = load_blob(&self.db, &snapshot.edges_hash).await?;
+        let node_inputs: Vec<GraphDataNodeInput> = nodes.into_iter().map(Into::into).collect();
+        let edge_inputs: Vec<GraphDataEdgeInput> = edges.into_iter().map(Into::into).collect();
+
+        let txn = self.db.begin().await.map_err(|e| {
+            CoreError::internal(format!("Failed to begin restore transaction: {}", e))
+        })?;
+        GraphDataService::replace_contents_in_txn(
+            &txn,
+            snapshot.graph_data_id,
+            &node_inputs,
+            &edge_inputs,
+        )
+        .await?;
+        graph_data::ActiveModel {
+            id: Set(snapshot.graph_data_id),
+            annotations: Set(snapshot.annotations.clone()),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await
+        .map_err(|e| CoreError::internal(format!("Failed to restore graph snapshot: {}", e)))?;
+        txn.commit()
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to restore graph snapshot: {}", e)))?;
+
+        GraphDataService::new(self.db.clone())
+            .get_by_id(snapshot.graph_data_id)
+            .await?
+            .ok_or_else(|| CoreError::not_found("GraphData", snapshot.graph_data_id.to_string()))
+    }
+}
+
+/// Store `value` as JSON under its SHA-256 hash, returning the hash. An
+/// existing blob with that hash is left as is, including one inserted
+/// concurrently by another snapshot.
+async fn store_blob<C: ConnectionTrait, T: Serialize>(db: &C, value: &T) -> CoreResult<String> {
+    let content = serde_json::to_string(value).map_err(|e| {
+        CoreError::internal(format!("Failed to serialize snapshot contents: {}", e))
+    })?;
+    let hash = format!("{:x}", Sha256::digest(content.as_bytes()));
+
+    graph_snapshot_blobs::Entity::insert(graph_snapshot_blobs::ActiveModel {
+        hash: Set(hash.clone()),
+        content: Set(content),
+        created_at: Set(Utc::now()),
+    })
+    .on_conflict(
+        OnConflict::column(graph_snapshot_blobs::Column::Hash)
+            .do_nothing()
+            .to_owned(),
+    )
+    .do_nothing()
+    .exec_without_returning(db)
+    .await
+    .map_err(|e| CoreError::internal(format!("Failed to save snapshot blob: {}", e)))?;
+
+    Ok(hash)
+}
+
+async fn load_blob<C: ConnectionTrait, T: DeserializeOwned>(db: &C, hash: &str) -> CoreResult<T> {
+    let blob = graph_snapshot_blobs::Entity::find_by_id(hash.to_string())
+        .one(db)
+        .await
+        .map_err(|e| CoreError::internal(format!("Failed to load snapshot blob: {}", e)))?
+        .ok_or_else(|| CoreError::not_found("GraphSnapshotBlob", hash.to_string()))?;
+    serde_json::from_str(&blob.content)
+        .map_err(|e| CoreError::internal(format!("Snapshot blob {} is not valid: {}", hash, e)))
+}
+
+impl From<graph_data_nodes::Model> for SnapshotNode {
+    fn from(node: graph_data_nodes::Model) -> Self {
+        Self {
+            external_id: node.external_id,
+            label: node.label,
+            layer: node.layer,
+            weight: node.weight,
+            is_partition: node.is_partition,
+            belongs_to: node.belongs_to,
+            comment: node.comment,
+            source_dataset_id: node.source_dataset_id,
+            attributes: node.attributes,
+        }
+    }
+}
+
+impl From<SnapshotNode> for GraphDataNodeInput {
+    fn from(node: SnapshotNode) -> Self {
+        Self {
+            external_id: node.external_id,
+            label: node.label,
+            layer: node.layer,
+            weight: node.weight,
+            is_partition: Some(node.is_partition),
+            belongs_to: node.belongs_to,
+            comment: node.comment,
+            source_dataset_id: node.source_dataset_id,
+            attributes: node.attributes,
+            created_at: None,
+        }
+    }
+}
+
+impl From<graph_data_edges::Model> for SnapshotEdge {
+    fn from(edge: graph_data_edges::Model) -> Self {
+        Self {
+            external_id: edge.external_id,
+            source: edge.source,
+            target: edge.target,
+            label: edge.label,
+            layer: edge.layer,
+            weight: edge.weight,
+            comment: edge.comment,
+            source_dataset_id: edge.source_dataset_id,
+            attributes: edge.attributes,
+        }
+    }
+}
+
+impl From<SnapshotEdge> for GraphDataEdgeInput {
+    fn from(edge: SnapshotEdge) -> Self {
+        Self {
+            external_id: edge.external_id,
+            source: edge.source,
+            target: edge.target,
+            label: edge.label,
+            layer: edge.layer,
+            weight: edge.weight,
+            comment: edge.comment,
+            source_dataset_id: edge.source_dataset_id,
+            attributes: edge.attributes,
+            created_at: None,
+        }
+    }
+}
+
+impl From<project_layers::Model> for SnapshotLayer {
+    fn from(layer: project_layers::Model) -> Self {
+        Self {
+            layer_id: layer.layer_id,
+            name: layer.name,
+            background_color: layer.background_color,
+            text_color: layer.text_color,
+            border_color: layer.border_color,
+            alias: layer.alias,
+            source_dataset_id: layer.source_dataset_id,
+            enabled: layer.enabled,
+        }
+    }
+}
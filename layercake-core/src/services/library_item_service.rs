@@ -608,6 +608,7 @@ impl LibraryItemService {
             FileFormat::Ods => "application/vnd.oasis.opendocument.spreadsheet",
             FileFormat::Pdf => "application/pdf",
             FileFormat::Xml => "application/xml",
+            FileFormat::Dot => "text/vnd.graphviz",
         }
         .to_string()
     }
@@ -679,7 +680,7 @@ impl LibraryItemService {
 
                 infer_data_type(file_name, file_format, file_bytes)
             }
-            FileFormat::Json => Ok(DataType::Graph),
+            FileFormat::Json | FileFormat::Dot => Ok(DataType::Graph),
             _ => infer_data_type(file_name, file_format, file_bytes),
         }
     }
@@ -708,7 +709,7 @@ pub fn infer_data_type(
     }
 
     match file_format {
-        FileFormat::Json => Ok(DataType::Graph),
+        FileFormat::Json | FileFormat::Dot => Ok(DataType::Graph),
         FileFormat::Csv | FileFormat::Tsv => infer_data_type_from_headers(file_format, file_data),
         FileFormat::Xlsx | FileFormat::Ods | FileFormat::Pdf | FileFormat::Xml => {
             Err(CoreError::validation(format!(
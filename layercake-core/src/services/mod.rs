@@ -3,9 +3,11 @@
 pub mod auth_service;
 pub mod authorization;
 pub mod cli_graphql_helpers;
+pub mod collaboration_event_service;
 pub mod collaboration_service;
 pub mod data_set_service;
 pub mod dataset_bulk_service;
+pub mod dataset_schema_service;
 pub mod export_service;
 pub mod file_type_detection;
 pub mod graph_analysis_service;
@@ -13,6 +15,7 @@ pub mod graph_data_edit_applicator;
 pub mod graph_data_service;
 pub mod graph_edit_service;
 pub mod graph_service;
+pub mod graph_snapshot_service;
 pub mod import_service;
 pub mod layer_palette_service;
 pub mod library_item_service;
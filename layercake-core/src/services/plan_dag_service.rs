@@ -1529,6 +1529,7 @@ mod tests {
             import_export_path: Set(None),
             created_at: Set(Utc::now().into()),
             updated_at: Set(Utc::now().into()),
+            deleted_at: Set(None),
         }
         .insert(db)
         .await
@@ -65,7 +65,7 @@ impl PlanService {
         })
     }
 
-    fn generate_node_id(node_type: &str) -> String {
+    pub(crate) fn generate_node_id(node_type: &str) -> String {
         let prefix = match node_type {
             "DataSetNode" => "dataset",
             "GraphNode" => "graph",
@@ -81,7 +81,7 @@ impl PlanService {
         format!("{}_{}", prefix, short_uuid)
     }
 
-    fn generate_edge_id() -> String {
+    pub(crate) fn generate_edge_id() -> String {
         let uuid = Uuid::new_v4().simple().to_string();
         let short_uuid: String = uuid.chars().take(12).collect();
         format!("edge_{}", short_uuid)
@@ -122,22 +122,27 @@ impl ProjectService {
         Ok(updated_project)
     }
 
-    /// Delete a project (owner only)
+    /// Soft-delete a project (owner only); collaborators and data are kept
+    /// until the project is purged
     pub async fn delete_project(&self, user_id: i32, project_id: i32) -> CoreResult<()> {
         // Check admin access
         self.auth_service
             .check_project_admin_access(user_id, project_id)
             .await?;
 
-        // Delete project (cascading deletes should handle collaborators)
-        let result = projects::Entity::delete_by_id(project_id)
-            .exec(&self.db)
+        let project = projects::Entity::find_by_id(project_id)
+            .filter(projects::Column::DeletedAt.is_null())
+            .one(&self.db)
             .await
-            .map_err(|e| CoreError::internal(format!("Failed to delete project: {}", e)))?;
+            .map_err(|e| CoreError::internal(format!("Database error: {}", e)))?
+            .ok_or_else(|| CoreError::not_found("Project", project_id.to_string()))?;
 
-        if result.rows_affected == 0 {
-            return Err(CoreError::not_found("Project", project_id.to_string()));
-        }
+        let mut project: projects::ActiveModel = project.into();
+        project.deleted_at = Set(Some(Utc::now()));
+        project
+            .update(&self.db)
+            .await
+            .map_err(|e| CoreError::internal(format!("Failed to delete project: {}", e)))?;
 
         Ok(())
     }
@@ -175,6 +180,7 @@ impl ProjectService {
         // Get projects
         let projects = projects::Entity::find()
             .filter(projects::Column::Id.is_in(project_ids))
+            .filter(projects::Column::DeletedAt.is_null())
             .order_by_desc(projects::Column::UpdatedAt)
             .all(&self.db)
             .await
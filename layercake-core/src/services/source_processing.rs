@@ -1,11 +1,28 @@
-use csv::ReaderBuilder;
+use csv::{ReaderBuilder, StringRecord};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 
+use crate::data_loader::{self, RowError};
 use crate::database::entities::common_types::{DataType, FileFormat};
 use crate::errors::{CoreError, CoreResult};
 use crate::graph::Graph;
 
+/// Node columns with a dedicated meaning; any other column is a node attribute
+pub(crate) const NODE_COLUMNS: [&str; 12] = [
+    "id",
+    "label",
+    "layer",
+    "is_partition",
+    "isPartition",
+    "belongs_to",
+    "weight",
+    "comment",
+    "dataset",
+    "attributes",
+    "x",
+    "y",
+];
+
 /// Shared routines for processing dataset files into graph JSON payloads
 pub async fn process_file(
     file_format: &FileFormat,
@@ -20,10 +37,51 @@ pub async fn process_file(
         (FileFormat::Tsv, DataType::Edges) => process_delimited_edges(file_data, b'\t').await,
         (FileFormat::Tsv, DataType::Layers) => process_delimited_layers(file_data, b'\t').await,
         (FileFormat::Json, DataType::Graph) => process_json_graph(file_data).await,
+        (FileFormat::Dot, DataType::Graph) => process_dot_graph(file_data).await,
         _ => Err(CoreError::validation("Invalid format/type combination")),
     }
 }
 
+/// Like [`process_file`], but delimited node and edge files are checked row by
+/// row with [`data_loader::validate_delimited_rows`]. With `strict` any bad
+/// row fails the import; otherwise the valid rows are imported and the
+/// skipped ones are returned alongside the graph JSON.
+pub async fn process_file_validated(
+    file_format: &FileFormat,
+    data_type: &DataType,
+    file_data: &[u8],
+    strict: bool,
+) -> CoreResult<(String, Vec<RowError>)> {
+    let delimiter = match file_format {
+        FileFormat::Csv => b',',
+        FileFormat::Tsv => b'\t',
+        _ => {
+            return Ok((
+                process_file(file_format, data_type, file_data).await?,
+                Vec::new(),
+            ))
+        }
+    };
+    let required: &[&str] = match data_type {
+        DataType::Nodes => &["id", "label"],
+        DataType::Edges => &["id", "source", "target"],
+        _ => {
+            return Ok((
+                process_file(file_format, data_type, file_data).await?,
+                Vec::new(),
+            ))
+        }
+    };
+
+    let validated = data_loader::validate_delimited_rows(file_data, delimiter, required, strict)
+        .map_err(|e| CoreError::validation(e.to_string()))?;
+    let graph_json = match data_type {
+        DataType::Nodes => nodes_graph_json(&validated.headers, &validated.rows)?,
+        _ => edges_graph_json(&validated.headers, &validated.rows)?,
+    };
+    Ok((graph_json, validated.errors))
+}
+
 async fn process_delimited_nodes(file_data: &[u8], delimiter: u8) -> CoreResult<String> {
     let content = String::from_utf8(file_data.to_vec())
         .map_err(|e| CoreError::validation(format!("Invalid UTF-8 data: {}", e)))?;
@@ -36,12 +94,6 @@ async fn process_delimited_nodes(file_data: &[u8], delimiter: u8) -> CoreResult<
         .headers()
         .map_err(|e| CoreError::validation(format!("Failed to read CSV headers: {}", e)))?
         .clone();
-    let mut nodes = Vec::new();
-
-    let parse_bool = |value: &str| {
-        let lowered = value.trim().to_lowercase();
-        matches!(lowered.as_str(), "true" | "1" | "y" | "yes")
-    };
 
     if !headers.iter().any(|h| h == "id") || !headers.iter().any(|h| h == "label") {
         return Err(CoreError::validation(
@@ -49,10 +101,25 @@ async fn process_delimited_nodes(file_data: &[u8], delimiter: u8) -> CoreResult<
         ));
     }
 
-    for result in reader.records() {
-        let record = result
-            .map_err(|e| CoreError::validation(format!("Failed to read CSV record: {}", e)))?;
+    let records = reader
+        .records()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| CoreError::validation(format!("Failed to read CSV record: {}", e)))?;
+    nodes_graph_json(&headers, &records)
+}
+
+fn nodes_graph_json(headers: &StringRecord, records: &[StringRecord]) -> CoreResult<String> {
+    let mut nodes = Vec::new();
+
+    let parse_bool = |value: &str| {
+        let lowered = value.trim().to_lowercase();
+        matches!(lowered.as_str(), "true" | "1" | "y" | "yes")
+    };
+
+    for record in records {
         let mut node = HashMap::new();
+        let mut attributes = serde_json::Map::new();
+        let mut raw_attributes = None;
 
         for (i, field) in record.iter().enumerate() {
             if let Some(header) = headers.get(i) {
@@ -90,15 +157,41 @@ async fn process_delimited_nodes(file_data: &[u8], delimiter: u8) -> CoreResult<
                             node.insert("y".to_string(), json!(y));
                         }
                     }
-                    _ => {
+                    "belongs_to" | "comment" => {
                         if !field.is_empty() {
                             node.insert(header.to_string(), json!(field));
                         }
                     }
+                    "dataset" => {
+                        if let Ok(dataset) = field.parse::<i32>() {
+                            node.insert("dataset".to_string(), json!(dataset));
+                        }
+                    }
+                    // A JSON blob of attributes; columns named after a key win.
+                    "attributes" => match serde_json::from_str::<Value>(field) {
+                        Ok(Value::Object(map)) => {
+                            for (key, value) in map {
+                                attributes.entry(key).or_insert(value);
+                            }
+                        }
+                        Ok(value) if !value.is_null() => raw_attributes = Some(value),
+                        _ => {}
+                    },
+                    _ => {
+                        if !field.is_empty() {
+                            attributes.insert(header.to_string(), json!(field));
+                        }
+                    }
                 };
             }
         }
 
+        if !attributes.is_empty() {
+            node.insert("attributes".to_string(), Value::Object(attributes));
+        } else if let Some(value) = raw_attributes {
+            node.insert("attributes".to_string(), value);
+        }
+
         nodes.push(json!(node));
     }
 
@@ -125,7 +218,6 @@ async fn process_delimited_edges(file_data: &[u8], delimiter: u8) -> CoreResult<
         .headers()
         .map_err(|e| CoreError::validation(format!("Failed to read CSV headers: {}", e)))?
         .clone();
-    let mut edges = Vec::new();
 
     let required_headers = ["id", "source", "target"];
     for required in &required_headers {
@@ -137,9 +229,17 @@ async fn process_delimited_edges(file_data: &[u8], delimiter: u8) -> CoreResult<
         }
     }
 
-    for result in reader.records() {
-        let record = result
-            .map_err(|e| CoreError::validation(format!("Failed to read CSV record: {}", e)))?;
+    let records = reader
+        .records()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| CoreError::validation(format!("Failed to read CSV record: {}", e)))?;
+    edges_graph_json(&headers, &records)
+}
+
+fn edges_graph_json(headers: &StringRecord, records: &[StringRecord]) -> CoreResult<String> {
+    let mut edges = Vec::new();
+
+    for record in records {
         let mut edge = HashMap::new();
 
         for (i, field) in record.iter().enumerate() {
@@ -302,6 +402,17 @@ async fn process_json_graph(file_data: &[u8]) -> CoreResult<String> {
         .map_err(|e| CoreError::internal("Failed to serialize graph JSON").with_source(e))
 }
 
+/// Convert a Graphviz DOT file with [`data_loader::parse_dot`]
+async fn process_dot_graph(file_data: &[u8]) -> CoreResult<String> {
+    let content = String::from_utf8(file_data.to_vec())
+        .map_err(|e| CoreError::validation(format!("Invalid UTF-8 data: {}", e)))?;
+    let mut graph = data_loader::parse_dot(&content)
+        .map_err(|e| CoreError::validation(format!("Invalid DOT: {}", e)))?;
+    graph.sanitize_labels();
+    serde_json::to_string(&graph)
+        .map_err(|e| CoreError::internal("Failed to serialize graph JSON").with_source(e))
+}
+
 fn sanitize_graph_json(graph_json: String) -> CoreResult<String> {
     let mut graph: Graph = serde_json::from_str(&graph_json)
         .map_err(|e| CoreError::validation(format!("Invalid graph JSON: {}", e)))?;
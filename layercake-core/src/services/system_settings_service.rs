@@ -193,6 +193,39 @@ static DESCRIPTOR_LIST: &[SettingDescriptor] = &[
         is_secret: true,
         is_read_only: false,
     },
+    SettingDescriptor {
+        key: "LAYERCAKE_MAX_REQUEST_BODY_MB",
+        label: "Upload Request Size Limit (MB)",
+        category: "Server",
+        description: "Largest request body accepted by upload routes; bigger requests are rejected with 413 before being buffered. Takes effect on restart.",
+        value_type: SettingValueType::Integer,
+        allowed_values: &[],
+        default_value: "64",
+        is_secret: false,
+        is_read_only: false,
+    },
+    SettingDescriptor {
+        key: "LAYERCAKE_COLLABORATION_EVENT_RETENTION_HOURS",
+        label: "Collaboration History Retention (hours)",
+        category: "Server",
+        description: "How long persisted collaboration events are kept for replay to rejoining subscribers; older events are pruned in the background.",
+        value_type: SettingValueType::Integer,
+        allowed_values: &[],
+        default_value: "24",
+        is_secret: false,
+        is_read_only: false,
+    },
+    SettingDescriptor {
+        key: "LAYERCAKE_DELETED_PROJECT_RETENTION_DAYS",
+        label: "Deleted Project Retention (days)",
+        category: "Server",
+        description: "How long deleted projects can be restored before they are purged permanently in the background.",
+        value_type: SettingValueType::Integer,
+        allowed_values: &[],
+        default_value: "30",
+        is_secret: false,
+        is_read_only: false,
+    },
 ];
 
 /// Canonical metadata describing a configurable runtime setting.
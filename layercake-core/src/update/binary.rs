@@ -25,8 +25,8 @@ impl DefaultBinaryManager {
         backup_path
     }
 
-    #[allow(dead_code)] // Reserved for future hash verification
-    async fn calculate_sha256(path: &PathBuf) -> Result<String, UpdateError> {
+    /// Lowercase hex SHA-256 of the file at `path`.
+    pub async fn calculate_sha256(path: &PathBuf) -> Result<String, UpdateError> {
         let contents = tokio::fs::read(path).await?;
         let mut hasher = Sha256::new();
         hasher.update(&contents);
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 
 use super::{
     BinaryManager, DefaultBinaryManager, DefaultPlatformDetector, DefaultUpdater,
-    GitHubVersionManager, UpdateError, Updater,
+    GitHubVersionManager, ReleaseInfo, UpdateError, Updater,
 };
 
 pub struct UpdateCommand {
@@ -18,7 +18,7 @@ pub struct UpdateCommand {
     pub backup: bool,
     pub rollback: bool,
     pub dry_run: bool,
-    #[allow(dead_code)] // Reserved for future signature verification
+    /// Install the download without checking its published SHA-256.
     pub skip_verify: bool,
 }
 
@@ -67,6 +67,9 @@ impl UpdateCommand {
             }
 
             if self.dry_run {
+                if let Some(release) = &update_info.release {
+                    self.print_checksum_plan(updater, release).await;
+                }
                 println!(
                     "\n{}",
                     "This is a dry run - no changes will be made.".yellow()
@@ -82,10 +85,39 @@ impl UpdateCommand {
         Ok(())
     }
 
+    /// Show the checksum a real update would verify against. Nothing is
+    /// downloaded during a dry run, so there is no computed hash to compare.
+    async fn print_checksum_plan(&self, updater: &DefaultUpdater, release: &ReleaseInfo) {
+        if self.skip_verify {
+            println!(
+                "\n{}",
+                "Checksum verification would be skipped (--skip-verify).".yellow()
+            );
+            return;
+        }
+
+        match updater.published_sha256(release).await {
+            Ok((asset, digest)) => {
+                println!("\nAsset:            {}", asset);
+                println!("Expected SHA-256: {}", digest.cyan());
+                println!(
+                    "Computed SHA-256: {}",
+                    "not computed (dry run downloads nothing)".yellow()
+                );
+            }
+            Err(e) => {
+                println!("\n{} {}", "Checksum unavailable:".red(), e);
+            }
+        }
+    }
+
     async fn perform_update(&self, updater: &DefaultUpdater) -> Result<()> {
         println!("{}", "Starting update process...".blue());
 
-        match updater.perform_update(self.force, self.backup).await {
+        match updater
+            .perform_update(self.force, self.backup, !self.skip_verify)
+            .await
+        {
             Ok(()) => {
                 println!("\n{}", "✓ Update completed successfully!".green().bold());
                 println!("You may need to restart any running layercake processes.");
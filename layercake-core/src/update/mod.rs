@@ -160,7 +160,14 @@ pub trait Updater {
         release: &ReleaseInfo,
         platform: &PlatformInfo,
     ) -> Result<PathBuf, UpdateError>;
-    async fn perform_update(&self, force: bool, backup: bool) -> Result<(), UpdateError>;
+    /// Install the latest release. With `verify`, the download must match
+    /// its published SHA-256 before the installed binary is touched.
+    async fn perform_update(
+        &self,
+        force: bool,
+        backup: bool,
+        verify: bool,
+    ) -> Result<(), UpdateError>;
 }
 
 pub use binary::DefaultBinaryManager;
@@ -1,6 +1,6 @@
 use super::{
-    BinaryManager, PlatformDetector, PlatformInfo, ReleaseInfo, UpdateError, UpdateInfo, Updater,
-    VersionComparison, VersionManager,
+    BinaryManager, DefaultBinaryManager, PlatformDetector, PlatformInfo, ReleaseAsset, ReleaseInfo,
+    UpdateError, UpdateInfo, Updater, VersionComparison, VersionManager,
 };
 use async_trait::async_trait;
 use reqwest::Client;
@@ -15,6 +15,34 @@ fn is_sidecar(name: &str) -> bool {
         .any(|suffix| name.ends_with(suffix))
 }
 
+/// The SHA-256 sidecar published alongside `asset`, if any.
+fn checksum_asset_for<'a>(
+    release: &'a ReleaseInfo,
+    asset: &ReleaseAsset,
+) -> Option<&'a ReleaseAsset> {
+    let name = format!("{}.sha256", asset.name);
+    release
+        .assets
+        .iter()
+        .find(|candidate| candidate.name == name)
+}
+
+/// The digest from a `sha256sum`-style checksum file (`<digest>  <file>`).
+fn parse_sha256(contents: &str) -> Result<String, UpdateError> {
+    let digest = contents
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(UpdateError::VerificationError(format!(
+            "Malformed SHA-256 checksum: '{}'",
+            contents.trim()
+        )));
+    }
+    Ok(digest)
+}
+
 /// A downloadable release archive we know how to extract.
 fn is_release_archive(name: &str) -> bool {
     name.ends_with(".tar.gz") || name.ends_with(".tgz") || name.ends_with(".zip")
@@ -146,6 +174,100 @@ impl DefaultUpdater {
         Ok(())
     }
 
+    /// Name of the release asset for this platform and its published SHA-256.
+    /// Fails when the release publishes no checksum for the asset.
+    pub async fn published_sha256(
+        &self,
+        release: &ReleaseInfo,
+    ) -> Result<(String, String), UpdateError> {
+        let platform = self.platform_detector.detect_platform()?;
+        let asset = self.find_asset_for_platform(release, &platform)?;
+        let checksum = checksum_asset_for(release, asset).ok_or_else(|| {
+            UpdateError::VerificationError(format!(
+                "Release {} publishes no SHA-256 checksum for {}; use --skip-verify to install it unverified",
+                release.tag_name, asset.name
+            ))
+        })?;
+
+        let response = self
+            .client
+            .get(&checksum.download_url)
+            .header("User-Agent", "layercake-updater")
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(UpdateError::NetworkError(
+                response.error_for_status().unwrap_err(),
+            ));
+        }
+        let digest = parse_sha256(&response.text().await?)?;
+
+        Ok((asset.name.clone(), digest))
+    }
+
+    /// Replace `target` with the downloaded binary, backing it up first when
+    /// `backup` is set. With `expected_sha256`, the download is checked
+    /// before anything else: a mismatch discards it and fails without taking a
+    /// backup or touching `target`.
+    async fn install_download(
+        &self,
+        download: &PathBuf,
+        expected_sha256: Option<&str>,
+        target: &PathBuf,
+        backup: bool,
+    ) -> Result<(), UpdateError> {
+        if let Some(expected) = expected_sha256 {
+            let actual = DefaultBinaryManager::calculate_sha256(download).await?;
+            if actual != expected {
+                if let Err(e) = tokio::fs::remove_file(download).await {
+                    tracing::warn!("Failed to clean up rejected download: {}", e);
+                }
+                return Err(UpdateError::VerificationError(format!(
+                    "Checksum mismatch for {}: expected {}, got {}",
+                    download.display(),
+                    expected,
+                    actual
+                )));
+            }
+        }
+
+        // Create backup if requested
+        let backup_path = if backup {
+            Some(self.binary_manager.create_backup(target).await?)
+        } else {
+            None
+        };
+
+        // Install the new binary
+        match self.binary_manager.install_binary(download, target).await {
+            Ok(()) => {
+                // Clean up temporary file
+                if let Err(e) = tokio::fs::remove_file(download).await {
+                    tracing::warn!("Failed to clean up temporary file: {}", e);
+                }
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Installation failed: {}", e);
+
+                // Attempt rollback if we have a backup
+                if let Some(backup) = backup_path {
+                    tracing::info!("Attempting rollback...");
+                    if let Err(rollback_err) = self.binary_manager.rollback(&backup, target).await {
+                        tracing::error!("Rollback also failed: {}", rollback_err);
+                        return Err(UpdateError::InstallationError(format!(
+                            "Installation failed and rollback failed: {} (rollback error: {})",
+                            e, rollback_err
+                        )));
+                    }
+                    tracing::info!("Successfully rolled back to previous version");
+                }
+
+                Err(e)
+            }
+        }
+    }
+
     fn get_current_executable() -> Result<PathBuf, UpdateError> {
         std::env::current_exe().map_err(|e| {
             UpdateError::InstallationError(format!(
@@ -207,7 +329,12 @@ impl Updater for DefaultUpdater {
         Ok(temp_path)
     }
 
-    async fn perform_update(&self, force: bool, backup: bool) -> Result<(), UpdateError> {
+    async fn perform_update(
+        &self,
+        force: bool,
+        backup: bool,
+        verify: bool,
+    ) -> Result<(), UpdateError> {
         let platform = self.platform_detector.detect_platform()?;
         let update_info = self.check_for_updates(false).await?;
 
@@ -226,54 +353,28 @@ impl Updater for DefaultUpdater {
             update_info.latest_version
         );
 
-        // Download the new binary
-        let new_binary_path = self.download_release(release, &platform).await?;
-
-        // Get current executable path
-        let current_exe = Self::get_current_executable()?;
-
-        // Create backup if requested
-        let backup_path = if backup {
-            Some(self.binary_manager.create_backup(&current_exe).await?)
+        // Fetch the checksum first so a release without one fails before downloading
+        let expected_sha256 = if verify {
+            Some(self.published_sha256(release).await?.1)
         } else {
+            tracing::warn!("Skipping checksum verification of the downloaded release");
             None
         };
 
-        // Install the new binary
-        match self
-            .binary_manager
-            .install_binary(&new_binary_path, &current_exe)
-            .await
-        {
-            Ok(()) => {
-                tracing::info!("Successfully updated to {}", update_info.latest_version);
-
-                // Clean up temporary file
-                if let Err(e) = tokio::fs::remove_file(&new_binary_path).await {
-                    tracing::warn!("Failed to clean up temporary file: {}", e);
-                }
-            }
-            Err(e) => {
-                tracing::error!("Installation failed: {}", e);
+        // Download the new binary
+        let new_binary_path = self.download_release(release, &platform).await?;
 
-                // Attempt rollback if we have a backup
-                if let Some(backup) = backup_path {
-                    tracing::info!("Attempting rollback...");
-                    if let Err(rollback_err) =
-                        self.binary_manager.rollback(&backup, &current_exe).await
-                    {
-                        tracing::error!("Rollback also failed: {}", rollback_err);
-                        return Err(UpdateError::InstallationError(format!(
-                            "Installation failed and rollback failed: {} (rollback error: {})",
-                            e, rollback_err
-                        )));
-                    }
-                    tracing::info!("Successfully rolled back to previous version");
-                }
+        // Get current executable path
+        let current_exe = Self::get_current_executable()?;
 
-                return Err(e);
-            }
-        }
+        self.install_download(
+            &new_binary_path,
+            expected_sha256.as_deref(),
+            &current_exe,
+            backup,
+        )
+        .await?;
+        tracing::info!("Successfully updated to {}", update_info.latest_version);
 
         Ok(())
     }
@@ -282,10 +383,7 @@ impl Updater for DefaultUpdater {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::update::{
-        DefaultBinaryManager, DefaultPlatformDetector, GitHubVersionManager, ReleaseAsset,
-        ReleaseInfo,
-    };
+    use crate::update::{DefaultPlatformDetector, GitHubVersionManager};
 
     #[tokio::test]
     async fn test_updater_creation() {
@@ -310,6 +408,74 @@ mod tests {
         assert!(!is_release_archive("layercake-v0.4.0-linux-x86_64.tar.gz.sha256"));
     }
 
+    #[test]
+    fn parse_sha256_accepts_sha256sum_output() {
+        let digest = "a".repeat(64);
+        assert_eq!(
+            parse_sha256(&format!("{}  layercake.tar.gz\n", digest.to_uppercase())).unwrap(),
+            digest
+        );
+        assert!(parse_sha256("not-a-digest  layercake.tar.gz").is_err());
+        assert!(parse_sha256("").is_err());
+    }
+
+    #[tokio::test]
+    async fn corrupted_download_is_not_installed() {
+        let dir =
+            std::env::temp_dir().join(format!("layercake-update-verify-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let target = dir.join("layercake");
+        let download = dir.join("layercake-download");
+        tokio::fs::write(&target, b"installed").await.unwrap();
+        tokio::fs::write(&download, b"corrupted").await.unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            for path in [&target, &download] {
+                tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let genuine = dir.join("genuine");
+        tokio::fs::write(&genuine, b"genuine").await.unwrap();
+        let expected = DefaultBinaryManager::calculate_sha256(&genuine)
+            .await
+            .unwrap();
+        tokio::fs::remove_file(&genuine).await.unwrap();
+
+        let updater = DefaultUpdater::new(
+            Box::new(GitHubVersionManager::new(
+                "michiel/layercake-tool".to_string(),
+            )),
+            Box::new(DefaultPlatformDetector::new()),
+            Box::new(DefaultBinaryManager::new()),
+        );
+        let result = updater
+            .install_download(&download, Some(&expected), &target, true)
+            .await;
+
+        assert!(
+            matches!(&result, Err(UpdateError::VerificationError(msg)) if msg.contains(&expected)),
+            "{result:?}"
+        );
+        assert_eq!(tokio::fs::read(&target).await.unwrap(), b"installed");
+        assert!(!download.exists());
+        let mut entries = tokio::fs::read_dir(&dir).await.unwrap();
+        let mut remaining = Vec::new();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            remaining.push(entry.file_name());
+        }
+        assert_eq!(
+            remaining,
+            vec!["layercake"],
+            "no backup is taken before verifying"
+        );
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
     fn asset(name: &str) -> ReleaseAsset {
         ReleaseAsset {
             name: name.to_string(),
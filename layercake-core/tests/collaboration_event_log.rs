@@ -0,0 +1,52 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use layercake::database::migrations::Migrator;
+use layercake::services::collaboration_event_service::CollaborationEventService;
+use sea_orm::Database;
+use sea_orm_migration::MigratorTrait;
+
+#[tokio::test]
+async fn recent_events_replay_in_order_and_old_ones_are_pruned() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    Migrator::up(&db, None).await?;
+    let service = CollaborationEventService::new(db.clone());
+
+    let now = Utc::now();
+    for (event_id, plan_id, age_hours) in [
+        ("stale", "project_1", 48),
+        ("first", "project_1", 3),
+        ("second", "project_1", 2),
+        ("third", "project_1", 1),
+        ("other", "project_2", 1),
+    ] {
+        service
+            .record(
+                event_id.to_string(),
+                plan_id.to_string(),
+                "user_1".to_string(),
+                "NodeUpdated".to_string(),
+                "{}".to_string(),
+                now - Duration::hours(age_hours),
+            )
+            .await?;
+    }
+
+    let ids = |events: Vec<layercake::database::entities::collaboration_events::Model>| {
+        events.into_iter().map(|e| e.event_id).collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        ids(service.recent("project_1", 2).await?),
+        vec!["second", "third"]
+    );
+
+    let pruned = service.prune_older_than(now - Duration::hours(24)).await?;
+    assert_eq!(pruned, 1);
+    assert_eq!(
+        ids(service.recent("project_1", 10).await?),
+        vec!["first", "second", "third"]
+    );
+    assert_eq!(ids(service.recent("project_2", 10).await?), vec!["other"]);
+
+    Ok(())
+}
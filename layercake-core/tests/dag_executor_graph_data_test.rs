@@ -164,6 +164,7 @@ async fn seed_project_and_palette(db: &DatabaseConnection) -> i32 {
         import_export_path: Set(None),
         created_at: Set(Utc::now()),
         updated_at: Set(Utc::now()),
+        deleted_at: Set(None),
     };
     project.insert(db).await.unwrap();
 
@@ -757,3 +758,175 @@ async fn test_dag_executor_affected_nodes() {
     assert_eq!(final_graph1.dag_node_id, Some("graph1-node".to_string()));
     assert_eq!(final_graph2.dag_node_id, Some("graph2-node".to_string()));
 }
+
+/// A TransformNode plan_dag_nodes model applying a single transform `kind`.
+fn transform_node(id: &str, kind: &str) -> plan_dag_nodes::Model {
+    plan_dag_nodes::Model {
+        id: id.to_string(),
+        plan_id: 1,
+        node_type: "TransformNode".to_string(),
+        position_x: 200.0,
+        position_y: 0.0,
+        source_position: None,
+        target_position: None,
+        metadata_json: json!({"label": id}).to_string(),
+        config_json: json!({"transforms": [{"kind": kind, "params": {}}]}).to_string(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+/// Run the whole DAG and return the ids of the nodes that were recomputed
+/// rather than reused from the previous run.
+async fn recomputed_nodes(
+    executor: &DagExecutor,
+    project_id: i32,
+    nodes: &[plan_dag_nodes::Model],
+    edges: &[(String, String)],
+) -> Vec<String> {
+    executor
+        .execute_dag(project_id, 1, nodes, edges)
+        .await
+        .unwrap();
+    let mut ids: Vec<String> = executor
+        .take_node_records()
+        .into_iter()
+        .filter(|record| !record.cached)
+        .map(|record| record.node_id)
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[tokio::test]
+async fn test_dag_executor_reuses_nodes_with_unchanged_source_hashes() {
+    use layercake_core::database::entities::data_sets;
+
+    let db = setup_db().await;
+    let project_id = seed_project_and_palette(&db).await;
+    let data_set_id = create_data_set(&db, project_id, "Shared", 3).await;
+
+    // One import feeding two exports.
+    let mut nodes = vec![
+        dataset_node("import", data_set_id),
+        transform_node("export-a", "AggregateEdges"),
+        transform_node("export-b", "AggregateEdges"),
+    ];
+    let edges = vec![
+        ("import".to_string(), "export-a".to_string()),
+        ("import".to_string(), "export-b".to_string()),
+    ];
+    let all = vec!["export-a", "export-b", "import"];
+
+    let executor = DagExecutor::new(db.clone());
+    assert_eq!(
+        recomputed_nodes(&executor, project_id, &nodes, &edges).await,
+        all
+    );
+    assert!(recomputed_nodes(&executor, project_id, &nodes, &edges)
+        .await
+        .is_empty());
+
+    // Reconfiguring one export leaves the shared import and its sibling alone.
+    nodes[2] = transform_node("export-b", "ComputeHierarchyDepth");
+    assert_eq!(
+        recomputed_nodes(&executor, project_id, &nodes, &edges).await,
+        vec!["export-b"]
+    );
+
+    // New source data changes every source hash downstream of the import.
+    let mut data_set: data_sets::ActiveModel = data_sets::Entity::find_by_id(data_set_id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .into();
+    data_set.graph_json = Set(json!({
+        "nodes": [{"id": "n1", "label": "Only", "layer": "L1", "weight": 1}],
+        "edges": [],
+        "layers": [],
+    })
+    .to_string());
+    data_set.update(&db).await.unwrap();
+    assert_eq!(
+        recomputed_nodes(&executor, project_id, &nodes, &edges).await,
+        all
+    );
+
+    let forced = DagExecutor::new(db.clone()).with_force(true);
+    assert_eq!(
+        recomputed_nodes(&forced, project_id, &nodes, &edges).await,
+        all
+    );
+}
+
+#[tokio::test]
+async fn test_transform_attributes_are_persisted() {
+    let db = setup_db().await;
+    let project_id = seed_project_and_palette(&db).await;
+    let service = GraphDataService::new(db.clone());
+
+    let node = |id: &str| GraphDataNodeInput {
+        external_id: id.to_string(),
+        label: Some(id.to_uppercase()),
+        layer: Some("L1".to_string()),
+        weight: Some(1.0),
+        is_partition: Some(false),
+        belongs_to: None,
+        comment: None,
+        source_dataset_id: None,
+        attributes: None,
+        created_at: None,
+    };
+    let edge = |id: &str, source: &str, target: &str| GraphDataEdgeInput {
+        external_id: id.to_string(),
+        source: source.to_string(),
+        target: target.to_string(),
+        label: None,
+        layer: Some("L1".to_string()),
+        weight: Some(1.0),
+        comment: None,
+        source_dataset_id: None,
+        attributes: None,
+        created_at: None,
+    };
+    let data_set_id = create_data_set_from_inputs(
+        &db,
+        project_id,
+        "Pair",
+        &[node("a"), node("b")],
+        &[edge("ab", "a", "b"), edge("ba", "b", "a")],
+    )
+    .await;
+
+    let mut transform = transform_node("analyse", "PageRank");
+    transform.config_json = json!({"transforms": [
+        {"kind": "PageRank", "params": {}},
+        {"kind": "MergeReciprocalEdges", "params": {}},
+    ]})
+    .to_string();
+    let nodes = vec![dataset_node("import", data_set_id), transform];
+    let edges = vec![("import".to_string(), "analyse".to_string())];
+    DagExecutor::new(db.clone())
+        .execute_dag(project_id, 1, &nodes, &edges)
+        .await
+        .unwrap();
+
+    let graph = service
+        .get_by_dag_node("analyse")
+        .await
+        .unwrap()
+        .expect("TransformNode should produce graph_data");
+    for node in service.load_nodes(graph.id).await.unwrap() {
+        let attributes = node.attributes.expect("node attributes");
+        assert!(attributes["pagerank"].is_number(), "{attributes}");
+        assert_eq!(attributes["layer"], "L1");
+    }
+    let persisted_edges = service.load_edges(graph.id).await.unwrap();
+    assert_eq!(persisted_edges.len(), 1);
+    let attributes = persisted_edges[0]
+        .attributes
+        .clone()
+        .expect("edge attributes");
+    assert_eq!(attributes["bidirectional"], true);
+}
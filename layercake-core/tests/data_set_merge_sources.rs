@@ -0,0 +1,59 @@
+use anyhow::Result;
+use layercake::app_context::AppContext;
+use layercake::auth::SystemActor;
+use layercake::database::migrations::Migrator;
+use layercake::graph_merge::{ConflictResolution, MergeStrategy};
+use layercake::services::data_set_service::DataSetService;
+use layercake::CoreErrorKind;
+use sea_orm::Database;
+use sea_orm_migration::MigratorTrait;
+
+#[tokio::test]
+async fn merging_rejects_repeated_source_data_sets() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    Migrator::up(&db, None).await?;
+    let app = AppContext::new(db.clone());
+    let actor = SystemActor::internal();
+
+    let project = app
+        .create_project(&actor, "Merging".to_string(), None, None)
+        .await?;
+    let data_sets = DataSetService::new(db.clone());
+    let first = data_sets
+        .create_empty(project.id, "First".to_string(), None)
+        .await?;
+    let second = data_sets
+        .create_empty(project.id, "Second".to_string(), None)
+        .await?;
+
+    for source_ids in [
+        vec![first.id, first.id],
+        vec![first.id, second.id, first.id],
+    ] {
+        let err = app
+            .merge_data_sets_into_graph(
+                &actor,
+                project.id,
+                source_ids,
+                MergeStrategy::Union,
+                ConflictResolution::PreferFirst,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), CoreErrorKind::Validation);
+        assert!(err.to_string().contains("more than once"), "{err}");
+    }
+
+    let merged = app
+        .merge_data_sets_into_graph(
+            &actor,
+            project.id,
+            vec![first.id, second.id],
+            MergeStrategy::Union,
+            ConflictResolution::PreferFirst,
+        )
+        .await?;
+    assert_eq!(merged.project_id, project.id);
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use layercake::database::entities::{data_sets, projects};
+use layercake::services::data_set_service::{DataSetAnnotation, DataSetService};
 use layercake::services::dataset_bulk_service::DataSetBulkService;
 use sea_orm::{ActiveModelTrait, Database, DatabaseConnection, EntityTrait, Set};
 use serde_json::json;
@@ -46,6 +47,296 @@ async fn dataset_layers_alias_roundtrip_xlsx_and_ods() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn dataset_annotations_roundtrip_xlsx_and_ods() -> Result<()> {
+    let db = setup_in_memory_db().await.expect("test database");
+    let service = DataSetBulkService::new(db.clone());
+    let data_set_service = DataSetService::new(db.clone());
+
+    let source_project = insert_project(&db, "Annotated Project").await?;
+    let dataset = insert_layer_dataset(&db, source_project.id, "Annotated").await?;
+    data_set_service
+        .update_annotation(
+            dataset.id,
+            "2024".to_string(),
+            "Imported from *CMDB*".to_string(),
+        )
+        .await?;
+    data_set_service
+        .update_annotation(
+            dataset.id,
+            "Review".to_string(),
+            "Checked aliases".to_string(),
+        )
+        .await?;
+    let expected = annotations_of(&db, dataset.id).await?;
+
+    let xlsx_bytes = service.export_to_xlsx(&[dataset.id]).await?;
+    let project_xlsx = insert_project(&db, "Annotated XLSX Import").await?;
+    let xlsx_result = service
+        .import_from_xlsx(project_xlsx.id, &xlsx_bytes)
+        .await?;
+    assert_eq!(
+        xlsx_result.created_count, 1,
+        "annotations sheet must not become a dataset"
+    );
+    assert_eq!(
+        annotations_of(&db, xlsx_result.imported_ids[0]).await?,
+        expected
+    );
+
+    let ods_bytes = service.export_to_ods(&[dataset.id]).await?;
+    let project_ods = insert_project(&db, "Annotated ODS Import").await?;
+    let ods_result = service.import_from_ods(project_ods.id, &ods_bytes).await?;
+    assert_eq!(ods_result.created_count, 1);
+    assert_eq!(
+        annotations_of(&db, ods_result.imported_ids[0]).await?,
+        expected
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn annotations_follow_long_dataset_names_sharing_a_prefix() -> Result<()> {
+    let db = setup_in_memory_db().await.expect("test database");
+    let service = DataSetBulkService::new(db.clone());
+    let data_set_service = DataSetService::new(db.clone());
+
+    // Both names truncate to the same 31-char XLSX sheet prefix, and the
+    // first is a prefix of the second. Node sheets are used because their
+    // type is still recognised from the headers once the name is truncated.
+    let graph_json = json!({
+        "nodes": [{
+            "id": "n1",
+            "label": "Node 1",
+            "layer": "app",
+            "is_partition": false,
+            "belongs_to": null,
+            "weight": 1,
+            "comment": null
+        }],
+        "edges": [],
+        "layers": []
+    });
+    let project = insert_project(&db, "Long Names").await?;
+    let primary = insert_dataset(
+        &db,
+        project.id,
+        "Infrastructure Inventory Primary",
+        graph_json.clone(),
+    )
+    .await?;
+    let backup = insert_dataset(
+        &db,
+        project.id,
+        "Infrastructure Inventory Primary Backup",
+        graph_json,
+    )
+    .await?;
+    data_set_service
+        .update_annotation(primary.id, "Primary".to_string(), "Live".to_string())
+        .await?;
+    data_set_service
+        .update_annotation(backup.id, "Backup".to_string(), "Nightly".to_string())
+        .await?;
+    let expected_primary = annotations_of(&db, primary.id).await?;
+    let expected_backup = annotations_of(&db, backup.id).await?;
+
+    let xlsx_bytes = service.export_to_xlsx(&[primary.id, backup.id]).await?;
+    let project_xlsx = insert_project(&db, "Long Names XLSX Import").await?;
+    let xlsx_result = service
+        .import_from_xlsx(project_xlsx.id, &xlsx_bytes)
+        .await?;
+    let ods_bytes = service.export_to_ods(&[primary.id, backup.id]).await?;
+    let project_ods = insert_project(&db, "Long Names ODS Import").await?;
+    let ods_result = service.import_from_ods(project_ods.id, &ods_bytes).await?;
+
+    for imported_ids in [xlsx_result.imported_ids, ods_result.imported_ids] {
+        assert_eq!(imported_ids.len(), 2);
+        // Datasets are exported, and so imported, in id order.
+        assert_eq!(
+            annotations_of(&db, imported_ids[0]).await?,
+            expected_primary
+        );
+        assert_eq!(annotations_of(&db, imported_ids[1]).await?, expected_backup);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn dataset_node_attributes_roundtrip_xlsx_and_ods() -> Result<()> {
+    let db = setup_in_memory_db().await.expect("test database");
+    let service = DataSetBulkService::new(db.clone());
+
+    let node = |id: &str, attributes: Option<serde_json::Value>| {
+        let mut node = json!({
+            "id": id,
+            "label": format!("Node {id}"),
+            "layer": "app",
+            "is_partition": false,
+            "belongs_to": null,
+            "weight": 1,
+            "comment": null,
+        });
+        if let Some(attributes) = attributes {
+            node["attributes"] = attributes;
+        }
+        node
+    };
+    let graph_json = json!({
+        "name": "",
+        "nodes": [
+            node("n1", Some(json!({
+                "owner": "ops",
+                "rank": 3,
+                "score": 1.5,
+                "active": true,
+                "code": "007",
+                "label": "shadowed by the label column"
+            }))),
+            // Missing keys must not come back as empty strings.
+            node("n2", Some(json!({ "owner": "dev" }))),
+            node("n3", None),
+        ],
+        "edges": [],
+        "layers": [],
+        "annotations": null
+    });
+
+    for format in ["xlsx", "ods"] {
+        let project = insert_project(&db, &format!("Attributes {format}")).await?;
+        let dataset = insert_dataset(
+            &db,
+            project.id,
+            &format!("Attrs {format}"),
+            graph_json.clone(),
+        )
+        .await?;
+        let target = insert_project(&db, &format!("Attributes {format} import")).await?;
+        let result = if format == "xlsx" {
+            let bytes = service.export_to_xlsx(&[dataset.id]).await?;
+            service.import_from_xlsx(target.id, &bytes).await?
+        } else {
+            let bytes = service.export_to_ods(&[dataset.id]).await?;
+            service.import_from_ods(target.id, &bytes).await?
+        };
+        assert_eq!(result.created_count, 1, "{format}: one nodes sheet");
+
+        let imported = data_sets::Entity::find_by_id(result.imported_ids[0])
+            .one(&db)
+            .await?
+            .expect("imported dataset");
+        let imported: serde_json::Value = serde_json::from_str(&imported.graph_json)?;
+        assert_eq!(
+            imported, graph_json,
+            "{format}: graph_json should round-trip"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn xlsx_export_grouped_by_layer_puts_nodes_on_layer_sheets() -> Result<()> {
+    use calamine::{open_workbook_from_rs, Data, Reader, Xlsx};
+
+    let db = setup_in_memory_db().await.expect("test database");
+    let service = DataSetBulkService::new(db.clone());
+    let project = insert_project(&db, "Layer sheets").await?;
+
+    let layer = |id: &str, label: &str| {
+        json!({
+            "id": id,
+            "label": label,
+            "background_color": "#ffffff",
+            "text_color": "#000000",
+            "border_color": "#000000"
+        })
+    };
+    let node = |id: &str, layer: &str| json!({ "id": id, "label": id, "layer": layer });
+    let edge = |id: &str, source: &str, target: &str, layer: &str| json!({ "id": id, "source": source, "target": target, "label": "", "layer": layer });
+    let graph_json = json!({
+        "nodes": [
+            node("n1", "app"),
+            node("n2", "db"),
+            node("n3", "app"),
+            node("n4", "edge_layer"),
+            node("n5", "long"),
+        ],
+        "edges": [edge("e1", "n1", "n2", "app"), edge("e2", "n3", "n2", "app")],
+        "layers": [
+            layer("app", "Apps: Core/Edge"),
+            // Same label after sanitising, and a clash with the fixed edges sheet.
+            layer("db", "Apps? Core*Edge"),
+            layer("edge_layer", "Edges"),
+            layer("long", "A very long layer label that exceeds Excel limits"),
+            layer("unused", "Unused"),
+        ]
+    });
+    let dataset = insert_dataset(&db, project.id, "Layered", graph_json).await?;
+
+    let bytes = service.export_to_xlsx_by_layer(&[dataset.id]).await?;
+    let mut workbook: Xlsx<_> = open_workbook_from_rs(std::io::Cursor::new(bytes))?;
+    assert_eq!(
+        workbook.sheet_names(),
+        vec![
+            "summary",
+            "Apps_ Core_Edge",
+            "Apps_ Core_Edge (2)",
+            "Edges (2)",
+            "A very long layer label that ex",
+            "edges",
+        ]
+    );
+
+    let column = |range: &calamine::Range<Data>, header: &str| -> Vec<String> {
+        let index = (0..range.width())
+            .find(|&col| range.get((0, col)) == Some(&Data::String(header.to_string())))
+            .expect("header column");
+        (1..range.height())
+            .map(|row| range.get((row, index)).unwrap().to_string())
+            .collect()
+    };
+    for (sheet, expected_ids) in [
+        ("Apps_ Core_Edge", vec!["n1", "n3"]),
+        ("Apps_ Core_Edge (2)", vec!["n2"]),
+        ("Edges (2)", vec!["n4"]),
+        ("A very long layer label that ex", vec!["n5"]),
+        ("edges", vec!["e1", "e2"]),
+    ] {
+        let range = workbook.worksheet_range(sheet)?;
+        assert_eq!(column(&range, "id"), expected_ids, "sheet {sheet}");
+    }
+
+    let summary = workbook.worksheet_range("summary")?;
+    assert_eq!(
+        column(&summary, "layer"),
+        vec!["app", "db", "edge_layer", "long", "unused"]
+    );
+    assert_eq!(column(&summary, "nodes"), vec!["2", "1", "1", "1", "0"]);
+    assert_eq!(column(&summary, "edges"), vec!["2", "0", "0", "0", "0"]);
+
+    Ok(())
+}
+
+async fn annotations_of(
+    db: &DatabaseConnection,
+    dataset_id: i32,
+) -> Result<Vec<(String, String, String)>> {
+    let dataset = data_sets::Entity::find_by_id(dataset_id)
+        .one(db)
+        .await?
+        .expect("dataset should exist");
+    let annotations: Vec<DataSetAnnotation> =
+        serde_json::from_str(dataset.annotations.as_deref().unwrap_or("[]"))?;
+    Ok(annotations
+        .into_iter()
+        .map(|a| (a.title, a.date.timestamp().to_string(), a.body))
+        .collect())
+}
+
 async fn insert_project(db: &DatabaseConnection, name: &str) -> Result<projects::Model> {
     let mut project = projects::ActiveModel::new();
     project.name = Set(name.to_string());
@@ -58,8 +349,6 @@ async fn insert_layer_dataset(
     project_id: i32,
     name: &str,
 ) -> Result<data_sets::Model> {
-    use chrono::Utc;
-
     let graph_json = json!({
         "nodes": [],
         "edges": [],
@@ -82,6 +371,17 @@ async fn insert_layer_dataset(
         ]
     });
 
+    insert_dataset(db, project_id, name, graph_json).await
+}
+
+async fn insert_dataset(
+    db: &DatabaseConnection,
+    project_id: i32,
+    name: &str,
+    graph_json: serde_json::Value,
+) -> Result<data_sets::Model> {
+    use chrono::Utc;
+
     let mut dataset = data_sets::ActiveModel::new();
     dataset.project_id = Set(project_id);
     dataset.name = Set(name.to_string());
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use layercake::database::entities::projects;
+use layercake::graph::Graph;
+use layercake::services::data_set_service::DataSetService;
+use sea_orm::{ActiveModelTrait, Database, DatabaseConnection, Set};
+
+const NETWORK_GV: &[u8] = br#"digraph network {
+    subgraph cluster_web {
+        label = "Web";
+        lb [label="Load balancer"];
+        app [label="App server", color=blue];
+    }
+    db [label="Database"];
+    lb -> app -> db [label="query"];
+}
+"#;
+
+#[tokio::test]
+async fn dot_upload_is_detected_and_stored_as_graph() -> Result<()> {
+    let db = setup_in_memory_db().await?;
+    let mut project = projects::ActiveModel::new();
+    project.name = Set("DOT project".to_string());
+    let project = project.insert(&db).await?;
+    let service = DataSetService::new(db.clone());
+
+    let data_set = service
+        .create_with_auto_detect(
+            project.id,
+            "Network".to_string(),
+            None,
+            "network.gv".to_string(),
+            NETWORK_GV.to_vec(),
+        )
+        .await?;
+    assert_eq!(data_set.status, "active");
+    assert_eq!(data_set.file_format, "dot");
+    assert_eq!(data_set.data_type, "graph");
+
+    let graph: Graph = serde_json::from_str(&data_set.graph_json)?;
+    let app = graph.get_node_by_id("app").expect("app node");
+    assert_eq!(app.label, "App server");
+    assert_eq!(app.layer, "web");
+    assert_eq!(app.belongs_to.as_deref(), Some("cluster_web"));
+    assert_eq!(
+        graph
+            .edges
+            .iter()
+            .map(|e| (e.source.as_str(), e.target.as_str()))
+            .collect::<Vec<_>>(),
+        vec![("lb", "app"), ("app", "db")]
+    );
+
+    let err = service
+        .create_with_auto_detect(
+            project.id,
+            "Broken".to_string(),
+            None,
+            "broken.dot".to_string(),
+            b"digraph { a -> }".to_vec(),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Invalid DOT"), "{err}");
+
+    Ok(())
+}
+
+async fn setup_in_memory_db() -> Result<DatabaseConnection> {
+    let db = Database::connect("sqlite::memory:").await?;
+    use sea_orm_migration::MigratorTrait;
+    layercake::database::migrations::Migrator::up(&db, None).await?;
+    Ok(db)
+}
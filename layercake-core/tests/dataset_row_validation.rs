@@ -0,0 +1,71 @@
+use anyhow::Result;
+use layercake::database::entities::common_types::FileFormat;
+use layercake::database::entities::projects;
+use layercake::services::data_set_service::{DataSetAnnotation, DataSetService};
+use sea_orm::{ActiveModelTrait, Database, DatabaseConnection, Set};
+
+const NODES_CSV: &[u8] = b"id,label,layer,weight\na,A,app,1\nb,B,app,x\na,Dup,app,2\nc,C,app,3\n";
+
+#[tokio::test]
+async fn lenient_upload_skips_bad_rows_and_annotates_them() -> Result<()> {
+    let db = setup_in_memory_db().await?;
+    let mut project = projects::ActiveModel::new();
+    project.name = Set("Upload project".to_string());
+    let project = project.insert(&db).await?;
+    let service = DataSetService::new(db.clone());
+
+    let (data_set, row_errors) = service
+        .create_from_file_with_validation(
+            project.id,
+            "Nodes".to_string(),
+            None,
+            "nodes.csv".to_string(),
+            FileFormat::Csv,
+            NODES_CSV.to_vec(),
+            None,
+            Some(false),
+        )
+        .await?;
+    assert_eq!(data_set.status, "active");
+    let lines: Vec<u64> = row_errors.iter().map(|e| e.line).collect();
+    assert_eq!(lines, vec![3, 4]);
+
+    let graph: serde_json::Value = serde_json::from_str(&data_set.graph_json)?;
+    let ids: Vec<&str> = graph["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["a", "c"]);
+
+    let annotations: Vec<DataSetAnnotation> =
+        serde_json::from_str(data_set.annotations.as_deref().unwrap())?;
+    assert_eq!(annotations.len(), 1);
+    assert_eq!(annotations[0].title, "Skipped rows");
+    assert!(annotations[0].body.contains("line 3, column 'weight'"));
+
+    let err = service
+        .create_from_file_with_validation(
+            project.id,
+            "Strict".to_string(),
+            None,
+            "nodes.csv".to_string(),
+            FileFormat::Csv,
+            NODES_CSV.to_vec(),
+            None,
+            Some(true),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("2 invalid row(s)"), "{err}");
+
+    Ok(())
+}
+
+async fn setup_in_memory_db() -> Result<DatabaseConnection> {
+    let db = Database::connect("sqlite::memory:").await?;
+    use sea_orm_migration::MigratorTrait;
+    layercake::database::migrations::Migrator::up(&db, None).await?;
+    Ok(db)
+}
@@ -0,0 +1,120 @@
+use anyhow::Result;
+use layercake::app_context::AppContext;
+use layercake::auth::SystemActor;
+use layercake::database::migrations::Migrator;
+use layercake::dataset_schema::{DatasetSchema, FieldConstraint, FieldType, RecordSchema};
+use layercake::errors::CoreErrorKind;
+use layercake::services::data_set_service::DataSetService;
+use sea_orm::Database;
+use sea_orm_migration::MigratorTrait;
+use serde_json::json;
+
+#[tokio::test]
+async fn data_set_is_checked_against_saved_schema_without_being_modified() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    Migrator::up(&db, None).await?;
+    let app = AppContext::new(db.clone());
+    let actor = SystemActor::internal();
+
+    let project = app
+        .create_project(&actor, "Contracts".to_string(), None, None)
+        .await?;
+    let data_sets = DataSetService::new(db.clone());
+    let data_set = data_sets
+        .create_empty(project.id, "Services".to_string(), None)
+        .await?;
+    let graph_json = json!({
+        "nodes": [
+            { "id": "api", "label": "API", "layer": "service", "is_partition": false,
+              "weight": 1, "attributes": { "owner": "platform", "replicas": "3" } },
+            { "id": "db", "label": "Database", "layer": "store", "is_partition": false,
+              "weight": 1, "attributes": { "replicas": 2 } },
+            { "id": "queue", "label": "Queue", "layer": "service", "is_partition": false,
+              "weight": 1, "attributes": { "owner": "data", "replicas": "many" } }
+        ],
+        "edges": [
+            { "id": "e1", "source": "api", "target": "db", "label": "", "layer": "service",
+              "weight": 1 }
+        ],
+        "layers": []
+    })
+    .to_string();
+    let stored_json = data_sets
+        .update_graph_data(data_set.id, graph_json)
+        .await?
+        .graph_json;
+
+    let schema = DatasetSchema {
+        nodes: RecordSchema {
+            required: vec!["label".to_string(), "owner".to_string()],
+            fields: vec![FieldConstraint {
+                name: "replicas".to_string(),
+                field_type: FieldType::Integer,
+            }],
+        },
+        edges: RecordSchema::default(),
+        allowed_layers: Some(vec!["service".to_string(), "store".to_string()]),
+    };
+    let saved = app
+        .save_dataset_schema(&actor, project.id, "services", &schema)
+        .await?;
+    let stored = app.get_dataset_schema(&actor, saved.id).await?;
+    assert_eq!(stored.schema, schema);
+
+    let summary = app
+        .validate_data_set_against_schema(data_set.id, &stored.schema)
+        .await?;
+    assert!(!summary.is_valid);
+    assert_eq!(
+        summary.errors,
+        vec![
+            r#"nodes[1] (id "db"): missing required field "owner""#,
+            r#"nodes[2] (id "queue"): field "replicas" expected integer, got "many""#,
+        ]
+    );
+
+    let unchanged = data_sets.get_by_id(data_set.id).await?.unwrap();
+    assert_eq!(unchanged.graph_json, stored_json);
+
+    // Saving under the same name replaces the stored schema.
+    let relaxed = DatasetSchema::default();
+    let replaced = app
+        .save_dataset_schema(&actor, project.id, "services", &relaxed)
+        .await?;
+    assert_eq!(replaced.id, saved.id);
+    let listed = app.list_dataset_schemas(&actor, project.id).await?;
+    assert_eq!(listed.len(), 1);
+    assert!(
+        app.validate_data_set_against_schema(data_set.id, &listed[0].schema)
+            .await?
+            .is_valid
+    );
+
+    assert!(
+        app.validate_data_set_against_saved_schema(&actor, data_set.id, saved.id)
+            .await?
+            .is_valid
+    );
+
+    // A schema saved in another project cannot be used.
+    let other = app
+        .create_project(&actor, "Other".to_string(), None, None)
+        .await?;
+    let foreign = app
+        .save_dataset_schema(&actor, other.id, "services", &relaxed)
+        .await?;
+    let err = app
+        .validate_data_set_against_saved_schema(&actor, data_set.id, foreign.id)
+        .await
+        .err()
+        .expect("schema from another project should be rejected");
+    assert_eq!(err.kind(), CoreErrorKind::Validation);
+
+    app.delete_dataset_schema(&actor, saved.id).await?;
+    assert!(app
+        .list_dataset_schemas(&actor, project.id)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
@@ -595,3 +595,143 @@ async fn replay_marks_and_applies_each_edit_atomically() {
         .len();
     assert_eq!(count, 1, "re-replay did not duplicate or alter data");
 }
+
+#[tokio::test]
+async fn apply_edits_folds_in_order_and_skips_conflicts() {
+    let db = setup_test_db().await.unwrap();
+    ensure_project(&db, 1).await;
+    let gd_service = GraphDataService::new(db.clone());
+    let gd = create_graph_data(&gd_service, 1).await;
+    gd_service
+        .replace_contents(
+            gd.id,
+            vec![node("n1"), node("n2")],
+            vec![edge("e1", "n1", "n2")],
+        )
+        .await
+        .unwrap();
+
+    insert_edit(
+        &db,
+        gd.id,
+        1,
+        "n3",
+        "create",
+        None,
+        json!({ "label": "Third" }),
+    )
+    .await
+    .unwrap();
+    insert_edit(
+        &db,
+        gd.id,
+        2,
+        "n1",
+        "update",
+        Some("label"),
+        json!("Renamed"),
+    )
+    .await
+    .unwrap();
+    insert_edit(&db, gd.id, 3, "n2", "delete", None, json!({}))
+        .await
+        .unwrap();
+    insert_edit(
+        &db,
+        gd.id,
+        5,
+        "n1",
+        "update",
+        Some("layer"),
+        json!("storage"),
+    )
+    .await
+    .unwrap();
+    // Conflicts with the delete above, so it must be skipped.
+    let ghost = insert_edit(&db, gd.id, 4, "n2", "update", Some("label"), json!("Ghost"))
+        .await
+        .unwrap();
+
+    let edit_service = GraphEditService::new(db.clone());
+    let result = edit_service.apply_edits(gd.id).await.unwrap();
+    assert_eq!(result.applied, 4);
+    assert_eq!(result.skipped.len(), 1);
+    assert_eq!(result.skipped[0].edit_id, ghost.id);
+
+    let labels = |graph: &layercake_core::graph::Graph| {
+        let mut labels: Vec<(String, String)> = graph
+            .nodes
+            .iter()
+            .map(|n| (n.id.clone(), n.label.clone()))
+            .collect();
+        labels.sort();
+        labels
+    };
+    assert_eq!(
+        labels(&result.graph),
+        vec![
+            ("n1".to_string(), "Renamed".to_string()),
+            ("n3".to_string(), "Third".to_string()),
+        ]
+    );
+    assert!(
+        result.graph.edges.is_empty(),
+        "deleting n2 dropped its edge"
+    );
+    let n1 = result.graph.nodes.iter().find(|n| n.id == "n1").unwrap();
+    assert_eq!(n1.layer, "storage");
+
+    // Applied edits are persisted, so folding again changes nothing.
+    let again = edit_service.apply_edits(gd.id).await.unwrap();
+    assert_eq!(again.applied, 0);
+    assert_eq!(again.skipped.len(), 1);
+    assert_eq!(labels(&again.graph), labels(&result.graph));
+    assert_eq!(edit_service.get_edit_count(gd.id, true).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn computed_graphs_keep_node_and_edge_attributes() {
+    use layercake_core::graph::{Edge, Graph, Node};
+
+    let db = setup_test_db().await.unwrap();
+    ensure_project(&db, 1).await;
+    let service = GraphDataService::new(db.clone());
+
+    let graph = Graph {
+        name: "Analysed".to_string(),
+        nodes: ["a", "b"]
+            .map(|id| Node {
+                id: id.to_string(),
+                label: id.to_string(),
+                layer: "app".to_string(),
+                weight: 1,
+                attributes: Some(json!({"community": 2})),
+                ..Default::default()
+            })
+            .to_vec(),
+        edges: vec![Edge {
+            id: "a_b".to_string(),
+            source: "a".to_string(),
+            target: "b".to_string(),
+            layer: "app".to_string(),
+            weight: 1,
+            attributes: Some(json!({"is_bridge": true})),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let created = service
+        .create_computed_from_graph(1, &graph, None)
+        .await
+        .unwrap();
+
+    let (_, nodes, edges) = service.load_full(created.id).await.unwrap();
+    assert_eq!(nodes.len(), 2);
+    for node in nodes {
+        assert_eq!(node.attributes.unwrap()["community"], 2);
+    }
+    assert_eq!(edges.len(), 1);
+    let attributes = edges[0].attributes.clone().unwrap();
+    assert_eq!(attributes["is_bridge"], true);
+    assert_eq!(attributes["layer"], "app");
+}
@@ -27,6 +27,7 @@ async fn seed_project_and_palette(db: &DatabaseConnection) -> i32 {
         import_export_path: Set(None),
         created_at: Set(Utc::now()),
         updated_at: Set(Utc::now()),
+        deleted_at: Set(None),
     };
     project.insert(db).await.unwrap();
 
@@ -0,0 +1,226 @@
+use layercake as layercake_core;
+use layercake_core::app_context::AppContext;
+use layercake_core::auth::SystemActor;
+use layercake_core::database::entities::graph_data::GraphDataStatus;
+use layercake_core::database::entities::{graph_snapshot_blobs, projects};
+use layercake_core::database::migrations::Migrator;
+use layercake_core::services::graph_snapshot_service::GraphSnapshotService;
+use layercake_core::services::{
+    GraphDataCreate, GraphDataEdgeInput, GraphDataNodeInput, GraphDataService, GraphService,
+};
+use sea_orm::{ActiveModelTrait, Database, DatabaseConnection, EntityTrait, PaginatorTrait, Set};
+use sea_orm_migration::MigratorTrait;
+use serde_json::json;
+
+fn node(external_id: &str, label: &str) -> GraphDataNodeInput {
+    GraphDataNodeInput {
+        external_id: external_id.to_string(),
+        label: Some(label.to_string()),
+        layer: Some("service".to_string()),
+        weight: Some(1.0),
+        is_partition: None,
+        belongs_to: None,
+        comment: None,
+        source_dataset_id: None,
+        attributes: Some(json!({"owner": "platform"})),
+        created_at: None,
+    }
+}
+
+fn edge(external_id: &str, source: &str, target: &str) -> GraphDataEdgeInput {
+    GraphDataEdgeInput {
+        external_id: external_id.to_string(),
+        source: source.to_string(),
+        target: target.to_string(),
+        label: None,
+        layer: None,
+        weight: Some(2.0),
+        comment: None,
+        source_dataset_id: None,
+        attributes: None,
+        created_at: None,
+    }
+}
+
+async fn create_graph(
+    graphs: &GraphDataService,
+    project_id: i32,
+    name: &str,
+    nodes: Vec<GraphDataNodeInput>,
+) -> i32 {
+    let graph = graphs
+        .create(GraphDataCreate {
+            project_id,
+            name: name.to_string(),
+            source_type: "computed".to_string(),
+            dag_node_id: None,
+            file_format: None,
+            origin: None,
+            filename: None,
+            blob: None,
+            file_size: None,
+            processed_at: None,
+            source_hash: None,
+            computed_date: None,
+            last_edit_sequence: None,
+            has_pending_edits: None,
+            last_replay_at: None,
+            metadata: None,
+            annotations: None,
+            status: Some(GraphDataStatus::Active),
+        })
+        .await
+        .unwrap();
+    graphs
+        .replace_contents(graph.id, nodes, Vec::new())
+        .await
+        .unwrap();
+    graph.id
+}
+
+async fn blob_count(db: &DatabaseConnection) -> u64 {
+    graph_snapshot_blobs::Entity::find()
+        .count(db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn restoring_a_snapshot_brings_back_the_original_graph() {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    Migrator::up(&db, None).await.unwrap();
+
+    let mut project = projects::ActiveModel::new();
+    project.name = Set("Snapshots".to_string());
+    let project = project.insert(&db).await.unwrap();
+
+    let graphs = GraphDataService::new(db.clone());
+    let graph = graphs
+        .create(GraphDataCreate {
+            project_id: project.id,
+            name: "architecture".to_string(),
+            source_type: "computed".to_string(),
+            dag_node_id: None,
+            file_format: None,
+            origin: None,
+            filename: None,
+            blob: None,
+            file_size: None,
+            processed_at: None,
+            source_hash: None,
+            computed_date: None,
+            last_edit_sequence: None,
+            has_pending_edits: None,
+            last_replay_at: None,
+            metadata: None,
+            annotations: Some(json!(["reviewed"])),
+            status: Some(GraphDataStatus::Active),
+        })
+        .await
+        .unwrap();
+    graphs
+        .replace_contents(
+            graph.id,
+            vec![node("api", "API"), node("db", "Database")],
+            vec![edge("api-db", "api", "db")],
+        )
+        .await
+        .unwrap();
+
+    let snapshots = GraphSnapshotService::new(db.clone());
+    let before = snapshots.create(graph.id, "before cleanup").await.unwrap();
+    assert_eq!((before.node_count, before.edge_count), (2, 1));
+
+    // An unchanged graph reuses the stored node, edge and layer blobs
+    let blobs = graph_snapshot_blobs::Entity::find()
+        .count(&db)
+        .await
+        .unwrap();
+    let again = snapshots.create(graph.id, "unchanged").await.unwrap();
+    assert_eq!(again.nodes_hash, before.nodes_hash);
+    assert_eq!(
+        graph_snapshot_blobs::Entity::find()
+            .count(&db)
+            .await
+            .unwrap(),
+        blobs
+    );
+
+    graphs
+        .replace_contents(graph.id, vec![node("cache", "Cache")], Vec::new())
+        .await
+        .unwrap();
+
+    let restored = snapshots.restore(before.id).await.unwrap();
+    assert_eq!((restored.node_count, restored.edge_count), (2, 1));
+    assert_eq!(restored.annotations, Some(json!(["reviewed"])));
+
+    let (_, nodes, edges) = graphs.load_full(graph.id).await.unwrap();
+    let mut labels: Vec<_> = nodes
+        .iter()
+        .map(|n| (n.external_id.as_str(), n.label.as_deref()))
+        .collect();
+    labels.sort();
+    assert_eq!(labels, vec![("api", Some("API")), ("db", Some("Database"))]);
+    assert_eq!(nodes[0].attributes, Some(json!({"owner": "platform"})));
+    assert_eq!(edges.len(), 1);
+    assert_eq!(
+        (edges[0].source.as_str(), edges[0].target.as_str()),
+        ("api", "db")
+    );
+    assert_eq!(edges[0].weight, Some(2.0));
+
+    let listed = snapshots.list(graph.id).await.unwrap();
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[0].label, "unchanged");
+
+    // Blobs survive while another snapshot still references them
+    snapshots.delete(again.id).await.unwrap();
+    assert_eq!(
+        graph_snapshot_blobs::Entity::find()
+            .count(&db)
+            .await
+            .unwrap(),
+        blobs
+    );
+    snapshots.delete(before.id).await.unwrap();
+    assert_eq!(
+        graph_snapshot_blobs::Entity::find()
+            .count(&db)
+            .await
+            .unwrap(),
+        0
+    );
+    assert!(snapshots.list(graph.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn deleting_graphs_and_projects_drops_their_snapshot_blobs() {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    Migrator::up(&db, None).await.unwrap();
+
+    let mut project = projects::ActiveModel::new();
+    project.name = Set("Snapshots".to_string());
+    let project = project.insert(&db).await.unwrap();
+
+    let graphs = GraphDataService::new(db.clone());
+    let api = create_graph(&graphs, project.id, "api", vec![node("api", "API")]).await;
+    let db_graph = create_graph(&graphs, project.id, "db", vec![node("db", "Database")]).await;
+    let snapshots = GraphSnapshotService::new(db.clone());
+    snapshots.create(api, "api").await.unwrap();
+    snapshots.create(db_graph, "db").await.unwrap();
+    // One node blob per graph plus the shared empty list of edges and layers
+    assert_eq!(blob_count(&db).await, 3);
+
+    GraphService::new(db.clone())
+        .delete_graph(api)
+        .await
+        .unwrap();
+    assert_eq!(blob_count(&db).await, 2);
+
+    AppContext::new(db.clone())
+        .purge_project(&SystemActor::internal(), project.id)
+        .await
+        .unwrap();
+    assert_eq!(blob_count(&db).await, 0);
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use chrono::Utc;
+use layercake::database::entities::{project_layers, projects};
+use layercake::services::GraphService;
+use sea_orm::{ActiveModelTrait, Database, DatabaseConnection, Set};
+
+#[tokio::test]
+async fn alias_cycle_is_reported_and_resolution_terminates() -> Result<()> {
+    let db = setup_in_memory_db().await?;
+    let mut project = projects::ActiveModel::new();
+    project.name = Set("Alias cycle project".to_string());
+    let project = project.insert(&db).await?;
+
+    insert_layer(&db, project.id, "layer_a", "layer_b", "ff0000").await?;
+    insert_layer(&db, project.id, "layer_b", "layer_a", "00ff00").await?;
+
+    let service = GraphService::new(db.clone());
+    let cycles = service.validate_layer_aliases(project.id).await?;
+    assert_eq!(
+        cycles,
+        vec![vec!["layer_a".to_string(), "layer_b".to_string()]]
+    );
+
+    // Resolution must stop at the first repeat instead of looping forever.
+    let layers = service.get_all_resolved_layers(project.id).await?;
+    assert_eq!(layers.len(), 2);
+    let layer_a = layers.iter().find(|l| l.id == "layer_a").unwrap();
+    assert_eq!(layer_a.background_color, "00ff00");
+
+    Ok(())
+}
+
+async fn insert_layer(
+    db: &DatabaseConnection,
+    project_id: i32,
+    layer_id: &str,
+    alias: &str,
+    background: &str,
+) -> Result<project_layers::Model> {
+    let now = Utc::now();
+    let layer = project_layers::ActiveModel {
+        project_id: Set(project_id),
+        layer_id: Set(layer_id.to_string()),
+        name: Set(layer_id.to_string()),
+        background_color: Set(background.to_string()),
+        text_color: Set("000000".to_string()),
+        border_color: Set("000000".to_string()),
+        alias: Set(Some(alias.to_string())),
+        source_dataset_id: Set(None),
+        enabled: Set(true),
+        created_at: Set(now),
+        updated_at: Set(now),
+        ..Default::default()
+    };
+    Ok(layer.insert(db).await?)
+}
+
+async fn setup_in_memory_db() -> Result<DatabaseConnection> {
+    let db = Database::connect("sqlite::memory:").await?;
+    use sea_orm_migration::MigratorTrait;
+    layercake::database::migrations::Migrator::up(&db, None).await?;
+    Ok(db)
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use layercake::app_context::{AppContext, PlanDagNodeRequest};
+use layercake::auth::SystemActor;
+use layercake::database::migrations::Migrator;
+use layercake::plan_dag::{PlanDagNodeType, Position};
+use layercake::services::data_set_service::DataSetService;
+use sea_orm::Database;
+use sea_orm_migration::MigratorTrait;
+use serde_json::json;
+
+#[tokio::test]
+async fn only_unreferenced_data_sets_are_orphaned() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    Migrator::up(&db, None).await?;
+    let app = AppContext::new(db.clone());
+    let actor = SystemActor::internal();
+
+    let project = app
+        .create_project(&actor, "Orphans".to_string(), None, None)
+        .await?;
+    let plan = app
+        .plan_service()
+        .get_default_plan(project.id)
+        .await?
+        .unwrap();
+
+    let data_sets = DataSetService::new(db.clone());
+    let wired = data_sets
+        .create_empty(project.id, "Wired".to_string(), None)
+        .await?;
+    let unwired = data_sets
+        .create_empty(project.id, "Unwired".to_string(), None)
+        .await?;
+    app.create_plan_dag_node(
+        &actor,
+        project.id,
+        Some(plan.id),
+        PlanDagNodeRequest {
+            node_type: PlanDagNodeType::DataSet,
+            position: Position { x: 0.0, y: 0.0 },
+            metadata: json!({ "label": "Wired" }),
+            config: json!({ "dataSetId": wired.id }),
+        },
+    )
+    .await?;
+
+    // Both were just created, so the default grace period hides them.
+    assert!(app.orphaned_data_sets(project.id, None).await?.is_empty());
+
+    let orphaned = app.orphaned_data_sets(project.id, Some(0)).await?;
+    assert_eq!(
+        orphaned.iter().map(|ds| ds.id).collect::<Vec<_>>(),
+        vec![unwired.id]
+    );
+
+    assert!(app
+        .delete_orphaned_data_sets(&actor, project.id, None)
+        .await?
+        .is_empty());
+    let deleted = app
+        .delete_orphaned_data_sets(&actor, project.id, Some(0))
+        .await?;
+    assert_eq!(deleted, vec![unwired.id]);
+    assert!(data_sets.get_by_id(unwired.id).await?.is_none());
+    assert!(data_sets.get_by_id(wired.id).await?.is_some());
+
+    Ok(())
+}
@@ -0,0 +1,383 @@
+use anyhow::Result;
+use chrono::Utc;
+use layercake as layercake_core;
+use layercake_core::app_context::{AppContext, PlanDagEdgeRequest, PlanDagNodeRequest};
+use layercake_core::auth::SystemActor;
+use layercake_core::database::entities::{
+    data_sets, dataset_schemas, graph_data, graph_data_nodes, graph_snapshots, layer_aliases,
+    plan_dag_annotations, plan_dag_edges, plan_dag_nodes, plans, project_collaborators,
+    project_layers, users,
+};
+use layercake_core::database::migrations::Migrator;
+use layercake_core::errors::CoreErrorKind;
+use layercake_core::plan_dag::{PlanDagNodeType, Position};
+use layercake_core::services::data_set_service::DataSetService;
+use layercake_core::services::graph_data_service::{GraphDataNodeInput, GraphDataService};
+use layercake_core::services::graph_snapshot_service::GraphSnapshotService;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, Database, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, Set,
+};
+use sea_orm_migration::MigratorTrait;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn clone_project_copies_assets_with_remapped_ids() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    Migrator::up(&db, None).await?;
+    let app = AppContext::new(db.clone());
+    let actor = SystemActor::internal();
+
+    let source = app
+        .create_project(
+            &actor,
+            "Template".to_string(),
+            Some("reusable".to_string()),
+            Some(vec!["template".to_string()]),
+        )
+        .await?;
+    let plan = app
+        .plan_service()
+        .get_default_plan(source.id)
+        .await?
+        .unwrap();
+
+    let dataset = DataSetService::new(db.clone())
+        .create_empty(source.id, "Nodes".to_string(), None)
+        .await?;
+    let dataset_node = app
+        .create_plan_dag_node(
+            &actor,
+            source.id,
+            Some(plan.id),
+            PlanDagNodeRequest {
+                node_type: PlanDagNodeType::DataSet,
+                position: Position { x: 0.0, y: 0.0 },
+                metadata: json!({ "label": "Nodes" }),
+                config: json!({ "dataSetId": dataset.id }),
+            },
+        )
+        .await?;
+    let graph_node = app
+        .create_plan_dag_node(
+            &actor,
+            source.id,
+            Some(plan.id),
+            PlanDagNodeRequest {
+                node_type: PlanDagNodeType::Graph,
+                position: Position { x: 200.0, y: 0.0 },
+                metadata: json!({ "label": "Graph" }),
+                config: json!({}),
+            },
+        )
+        .await?;
+    let dag_edge = app
+        .create_plan_dag_edge(
+            &actor,
+            source.id,
+            Some(plan.id),
+            PlanDagEdgeRequest {
+                source: dataset_node.id.clone(),
+                target: graph_node.id.clone(),
+                metadata: json!({}),
+            },
+        )
+        .await?;
+    let now = Utc::now();
+    for (target_id, target_type) in [(&graph_node.id, "node"), (&dag_edge.id, "edge")] {
+        plan_dag_annotations::ActiveModel {
+            id: NotSet,
+            project_id: Set(source.id),
+            plan_id: Set(plan.id),
+            target_id: Set(target_id.clone()),
+            target_type: Set(target_type.to_string()),
+            key: Set("owner".to_string()),
+            value: Set("platform".to_string()),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&db)
+        .await?;
+    }
+
+    dataset_schemas::ActiveModel {
+        id: NotSet,
+        project_id: Set(source.id),
+        name: Set("Nodes".to_string()),
+        schema_json: Set(json!({ "required": ["id"] }).to_string()),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(&db)
+    .await?;
+
+    let graph_service = GraphDataService::new(db.clone());
+    let computed = graph_service
+        .create_computed(source.id, graph_node.id.clone(), "Graph".to_string())
+        .await?;
+    graph_service
+        .replace_nodes(
+            computed.id,
+            vec![GraphDataNodeInput {
+                external_id: "n1".to_string(),
+                label: Some("Node 1".to_string()),
+                layer: Some("layer-1".to_string()),
+                weight: None,
+                is_partition: None,
+                belongs_to: None,
+                comment: None,
+                source_dataset_id: Some(dataset.id),
+                attributes: None,
+                created_at: None,
+            }],
+        )
+        .await?;
+    GraphSnapshotService::new(db.clone())
+        .create(computed.id, "seeded")
+        .await?;
+    let mut downstream: graph_data::ActiveModel = graph_service
+        .create_computed(
+            source.id,
+            "downstream".to_string(),
+            "Downstream".to_string(),
+        )
+        .await?
+        .into();
+    downstream.metadata = Set(Some(json!({ "upstreamGraphId": computed.id })));
+    downstream.update(&db).await?;
+
+    let layer = project_layers::ActiveModel {
+        id: NotSet,
+        project_id: Set(source.id),
+        layer_id: Set("layer-1".to_string()),
+        name: Set("Layer 1".to_string()),
+        background_color: Set("111111".to_string()),
+        text_color: Set("eeeeee".to_string()),
+        border_color: Set("222222".to_string()),
+        alias: Set(None),
+        source_dataset_id: Set(Some(dataset.id)),
+        enabled: Set(true),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(&db)
+    .await?;
+    layer_aliases::ActiveModel {
+        id: NotSet,
+        project_id: Set(source.id),
+        alias_layer_id: Set("layer-alias".to_string()),
+        target_layer_id: Set(layer.id),
+        created_at: Set(now),
+    }
+    .insert(&db)
+    .await?;
+
+    let mut user = users::ActiveModel::new();
+    user.email = Set("owner@example.com".to_string());
+    user.username = Set("owner".to_string());
+    user.display_name = Set("Owner".to_string());
+    user.password_hash = Set("x".to_string());
+    let user = user.insert(&db).await?;
+    project_collaborators::ActiveModel {
+        id: NotSet,
+        project_id: Set(source.id),
+        user_id: Set(user.id),
+        role: Set("owner".to_string()),
+        permissions: Set("[]".to_string()),
+        invited_by: Set(None),
+        invitation_status: Set("accepted".to_string()),
+        invited_at: Set(now),
+        joined_at: Set(Some(now)),
+        last_active_at: Set(None),
+        is_active: Set(true),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(&db)
+    .await?;
+
+    let clone = app
+        .clone_project(&actor, source.id, "  Copy  ".to_string())
+        .await?;
+    assert_ne!(clone.id, source.id);
+    assert_eq!(clone.name, "Copy");
+    assert_eq!(clone.tags, vec!["template".to_string()]);
+
+    for (label, source_count, clone_count) in [
+        count::<data_sets::Entity, _>(&db, data_sets::Column::ProjectId, source.id, clone.id)
+            .await?,
+        count::<plans::Entity, _>(&db, plans::Column::ProjectId, source.id, clone.id).await?,
+        count::<graph_data::Entity, _>(&db, graph_data::Column::ProjectId, source.id, clone.id)
+            .await?,
+        count::<project_layers::Entity, _>(
+            &db,
+            project_layers::Column::ProjectId,
+            source.id,
+            clone.id,
+        )
+        .await?,
+        count::<layer_aliases::Entity, _>(
+            &db,
+            layer_aliases::Column::ProjectId,
+            source.id,
+            clone.id,
+        )
+        .await?,
+        count::<dataset_schemas::Entity, _>(
+            &db,
+            dataset_schemas::Column::ProjectId,
+            source.id,
+            clone.id,
+        )
+        .await?,
+        count::<plan_dag_annotations::Entity, _>(
+            &db,
+            plan_dag_annotations::Column::ProjectId,
+            source.id,
+            clone.id,
+        )
+        .await?,
+    ] {
+        assert_eq!(source_count, clone_count, "{label} count differs");
+        assert!(source_count > 0, "{label} should be seeded");
+    }
+
+    let collaborators = project_collaborators::Entity::find()
+        .filter(project_collaborators::Column::ProjectId.eq(clone.id))
+        .count(&db)
+        .await?;
+    assert_eq!(collaborators, 0);
+
+    // Plan DAG topology is preserved with fresh ids and remapped dataset references
+    let cloned_plan = plans::Entity::find()
+        .filter(plans::Column::ProjectId.eq(clone.id))
+        .one(&db)
+        .await?
+        .unwrap();
+    let cloned_nodes = plan_dag_nodes::Entity::find()
+        .filter(plan_dag_nodes::Column::PlanId.eq(cloned_plan.id))
+        .all(&db)
+        .await?;
+    assert_eq!(cloned_nodes.len(), 2);
+    assert!(cloned_nodes
+        .iter()
+        .all(|n| n.id != dataset_node.id && n.id != graph_node.id));
+    let cloned_dataset = data_sets::Entity::find()
+        .filter(data_sets::Column::ProjectId.eq(clone.id))
+        .one(&db)
+        .await?
+        .unwrap();
+    let cloned_dataset_node = cloned_nodes
+        .iter()
+        .find(|n| n.node_type == "DataSetNode")
+        .unwrap();
+    let config: Value = serde_json::from_str(&cloned_dataset_node.config_json)?;
+    assert_eq!(config["dataSetId"], json!(cloned_dataset.id));
+    let cloned_graph_node = cloned_nodes
+        .iter()
+        .find(|n| n.node_type == "GraphNode")
+        .unwrap();
+
+    let cloned_edges = plan_dag_edges::Entity::find()
+        .filter(plan_dag_edges::Column::PlanId.eq(cloned_plan.id))
+        .all(&db)
+        .await?;
+    assert_eq!(cloned_edges.len(), 1);
+    assert_eq!(cloned_edges[0].source_node_id, cloned_dataset_node.id);
+    assert_eq!(cloned_edges[0].target_node_id, cloned_graph_node.id);
+
+    // Annotations follow their regenerated node and edge ids
+    let cloned_annotations = plan_dag_annotations::Entity::find()
+        .filter(plan_dag_annotations::Column::ProjectId.eq(clone.id))
+        .all(&db)
+        .await?;
+    assert!(cloned_annotations
+        .iter()
+        .all(|a| a.plan_id == cloned_plan.id && a.value == "platform"));
+    let annotated: Vec<(&str, &str)> = cloned_annotations
+        .iter()
+        .map(|a| (a.target_type.as_str(), a.target_id.as_str()))
+        .collect();
+    assert!(annotated.contains(&("node", cloned_graph_node.id.as_str())));
+    assert!(annotated.contains(&("edge", cloned_edges[0].id.as_str())));
+
+    // Computed graph follows its DAG node and keeps its contents
+    let cloned_graph = graph_data::Entity::find()
+        .filter(graph_data::Column::ProjectId.eq(clone.id))
+        .filter(graph_data::Column::DagNodeId.eq(cloned_graph_node.id.clone()))
+        .one(&db)
+        .await?
+        .unwrap();
+    assert_eq!(
+        cloned_graph.dag_node_id.as_deref(),
+        Some(cloned_graph_node.id.as_str())
+    );
+    let cloned_graph_nodes = graph_data_nodes::Entity::find()
+        .filter(graph_data_nodes::Column::GraphDataId.eq(cloned_graph.id))
+        .all(&db)
+        .await?;
+    assert_eq!(cloned_graph_nodes.len(), 1);
+    assert_eq!(
+        cloned_graph_nodes[0].source_dataset_id,
+        Some(cloned_dataset.id)
+    );
+
+    // Snapshots stay with the source project's history
+    let cloned_snapshots = graph_snapshots::Entity::find()
+        .filter(graph_snapshots::Column::GraphDataId.eq(cloned_graph.id))
+        .count(&db)
+        .await?;
+    assert_eq!(cloned_snapshots, 0);
+    let source_snapshots = graph_snapshots::Entity::find()
+        .filter(graph_snapshots::Column::GraphDataId.eq(computed.id))
+        .count(&db)
+        .await?;
+    assert_eq!(source_snapshots, 1);
+
+    let cloned_downstream = graph_data::Entity::find()
+        .filter(graph_data::Column::ProjectId.eq(clone.id))
+        .filter(graph_data::Column::DagNodeId.eq("downstream"))
+        .one(&db)
+        .await?
+        .unwrap();
+    assert_eq!(
+        cloned_downstream.metadata,
+        Some(json!({ "upstreamGraphId": cloned_graph.id }))
+    );
+
+    let cloned_layer = project_layers::Entity::find()
+        .filter(project_layers::Column::ProjectId.eq(clone.id))
+        .one(&db)
+        .await?
+        .unwrap();
+    assert_eq!(cloned_layer.source_dataset_id, Some(cloned_dataset.id));
+    let cloned_alias = layer_aliases::Entity::find()
+        .filter(layer_aliases::Column::ProjectId.eq(clone.id))
+        .one(&db)
+        .await?
+        .unwrap();
+    assert_eq!(cloned_alias.target_layer_id, cloned_layer.id);
+
+    let missing = app
+        .clone_project(&actor, source.id + 100, "Missing".to_string())
+        .await;
+    assert!(matches!(missing, Err(err) if err.kind() == CoreErrorKind::NotFound));
+
+    Ok(())
+}
+
+async fn count<E, C>(
+    db: &DatabaseConnection,
+    column: C,
+    source_id: i32,
+    clone_id: i32,
+) -> Result<(String, u64, u64)>
+where
+    E: EntityTrait,
+    E::Model: Sync,
+    C: ColumnTrait,
+{
+    let source = E::find().filter(column.eq(source_id)).count(db).await?;
+    let clone = E::find().filter(column.eq(clone_id)).count(db).await?;
+    Ok((E::default().table_name().to_string(), source, clone))
+}
@@ -0,0 +1,86 @@
+use anyhow::Result;
+use layercake::database::entities::projects;
+use layercake::errors::CoreErrorKind;
+use layercake::services::GraphService;
+use sea_orm::{ActiveModelTrait, Database, DatabaseConnection, Set};
+
+#[tokio::test]
+async fn update_project_layer_restyles_resolved_layers_and_alias() -> Result<()> {
+    let db = setup_in_memory_db().await?;
+    let mut project = projects::ActiveModel::new();
+    project.name = Set("Palette project".to_string());
+    let project = project.insert(&db).await?;
+
+    let service = GraphService::new(db.clone());
+    service
+        .upsert_project_layer(
+            project.id,
+            "infra".to_string(),
+            "Infra".to_string(),
+            "ffffff".to_string(),
+            "000000".to_string(),
+            "000000".to_string(),
+            None,
+            None,
+            true,
+        )
+        .await?;
+
+    let updated = service
+        .update_project_layer(
+            project.id,
+            "infra",
+            Some("Infrastructure".to_string()),
+            Some("#1a2B3c".to_string()),
+            None,
+            None,
+            Some("infrastructure".to_string()),
+        )
+        .await?;
+    assert_eq!(updated.len(), 1);
+    assert_eq!(updated[0].name, "Infrastructure");
+    assert_eq!(updated[0].background_color, "1a2B3c");
+    assert_eq!(updated[0].text_color, "000000");
+
+    let layers = service.get_all_resolved_layers(project.id).await?;
+    assert_eq!(layers.len(), 2);
+    let alias = layers.iter().find(|l| l.id == "infrastructure").unwrap();
+    assert_eq!(alias.label, "Infrastructure");
+    assert_eq!(alias.background_color, "1a2B3c");
+    assert_eq!(alias.alias.as_deref(), Some("infra"));
+
+    let err = service
+        .update_project_layer(
+            project.id,
+            "infra",
+            None,
+            None,
+            Some("red".to_string()),
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), CoreErrorKind::Validation);
+    assert_eq!(
+        err.fields()
+            .and_then(|f| f.get("field"))
+            .map(String::as_str),
+        Some("textColor")
+    );
+
+    let err = service
+        .update_project_layer(project.id, "missing", None, None, None, None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), CoreErrorKind::NotFound);
+
+    Ok(())
+}
+
+async fn setup_in_memory_db() -> Result<DatabaseConnection> {
+    let db = Database::connect("sqlite::memory:").await?;
+    use sea_orm_migration::MigratorTrait;
+    layercake::database::migrations::Migrator::up(&db, None).await?;
+    Ok(db)
+}
@@ -0,0 +1,95 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use layercake as layercake_core;
+use layercake_core::app_context::AppContext;
+use layercake_core::auth::SystemActor;
+use layercake_core::database::entities::data_sets;
+use layercake_core::database::migrations::Migrator;
+use layercake_core::errors::CoreErrorKind;
+use layercake_core::services::data_set_service::DataSetService;
+use sea_orm::{ColumnTrait, Database, EntityTrait, PaginatorTrait, QueryFilter};
+use sea_orm_migration::MigratorTrait;
+
+#[tokio::test]
+async fn deleted_project_is_hidden_until_restored_and_keeps_its_data() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    Migrator::up(&db, None).await?;
+    let app = AppContext::new(db.clone());
+    let actor = SystemActor::internal();
+
+    let kept = app
+        .create_project(&actor, "Kept".to_string(), None, None)
+        .await?;
+    let project = app
+        .create_project(&actor, "Trashed".to_string(), None, None)
+        .await?;
+    DataSetService::new(db.clone())
+        .create_empty(project.id, "Nodes".to_string(), None)
+        .await?;
+
+    app.delete_project(&actor, project.id).await?;
+
+    let listed: Vec<i32> = app.list_projects().await?.iter().map(|p| p.id).collect();
+    assert_eq!(listed, vec![kept.id]);
+    let deleted = app.list_deleted_projects().await?;
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0].id, project.id);
+    assert!(deleted[0].deleted_at.is_some());
+    let data_set_count = data_sets::Entity::find()
+        .filter(data_sets::Column::ProjectId.eq(project.id))
+        .count(&db)
+        .await?;
+    assert_eq!(data_set_count, 1);
+
+    let err = app.delete_project(&actor, project.id).await.unwrap_err();
+    assert_eq!(err.kind(), CoreErrorKind::NotFound);
+
+    let restored = app.restore_project(&actor, project.id).await?;
+    assert!(restored.deleted_at.is_none());
+    let mut listed: Vec<i32> = app.list_projects().await?.iter().map(|p| p.id).collect();
+    listed.sort();
+    assert_eq!(listed, vec![kept.id, project.id]);
+    assert!(app.list_deleted_projects().await?.is_empty());
+
+    let err = match app.restore_project(&actor, project.id).await {
+        Ok(_) => panic!("restoring a live project should fail"),
+        Err(err) => err,
+    };
+    assert_eq!(err.kind(), CoreErrorKind::Validation);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn purge_removes_projects_past_the_retention_window() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    Migrator::up(&db, None).await?;
+    let app = AppContext::new(db.clone());
+    let actor = SystemActor::internal();
+
+    let project = app
+        .create_project(&actor, "Old".to_string(), None, None)
+        .await?;
+    app.delete_project(&actor, project.id).await?;
+
+    let purged = app
+        .purge_projects_deleted_before(Utc::now() - Duration::days(30))
+        .await?;
+    assert_eq!(purged, 0);
+    assert_eq!(app.list_deleted_projects().await?.len(), 1);
+
+    let purged = app
+        .purge_projects_deleted_before(Utc::now() + Duration::seconds(1))
+        .await?;
+    assert_eq!(purged, 1);
+    assert!(app.list_deleted_projects().await?.is_empty());
+    assert!(app.get_project(project.id).await?.is_none());
+
+    let live = app
+        .create_project(&actor, "Live".to_string(), None, None)
+        .await?;
+    app.purge_project(&actor, live.id).await?;
+    assert!(app.get_project(live.id).await?.is_none());
+
+    Ok(())
+}
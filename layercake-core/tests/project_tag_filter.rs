@@ -0,0 +1,54 @@
+use anyhow::Result;
+use layercake::app_context::{AppContext, TagMatchMode};
+use layercake::auth::SystemActor;
+use layercake::database::migrations::Migrator;
+use sea_orm::Database;
+use sea_orm_migration::MigratorTrait;
+
+fn tags(values: &[&str]) -> Option<Vec<String>> {
+    Some(values.iter().map(|value| value.to_string()).collect())
+}
+
+#[tokio::test]
+async fn list_projects_filtered_honours_tag_match_mode() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    Migrator::up(&db, None).await?;
+    let app = AppContext::new(db.clone());
+
+    let tagged = app
+        .create_project(
+            &SystemActor::internal(),
+            "Tagged".to_string(),
+            None,
+            tags(&["a", "B"]),
+        )
+        .await?;
+    app.create_project(&SystemActor::internal(), "Untagged".to_string(), None, None)
+        .await?;
+
+    let ids = |projects: Vec<layercake::app_context::ProjectSummary>| {
+        projects.into_iter().map(|p| p.id).collect::<Vec<_>>()
+    };
+
+    let all_ab = app
+        .list_projects_filtered(tags(&["a", " b "]), TagMatchMode::All)
+        .await?;
+    assert_eq!(ids(all_ab), vec![tagged.id]);
+
+    let all_ac = app
+        .list_projects_filtered(tags(&["a", "c"]), TagMatchMode::All)
+        .await?;
+    assert!(all_ac.is_empty());
+
+    let any_cb = app
+        .list_projects_filtered(tags(&["c", "B"]), TagMatchMode::Any)
+        .await?;
+    assert_eq!(ids(any_cb), vec![tagged.id]);
+
+    for mode in [TagMatchMode::Any, TagMatchMode::All] {
+        assert_eq!(app.list_projects_filtered(tags(&[]), mode).await?.len(), 2);
+        assert_eq!(app.list_projects_filtered(None, mode).await?.len(), 2);
+    }
+
+    Ok(())
+}
@@ -5,6 +5,7 @@ use crate::graphql::types::*;
 /// This module provides convenience functions to publish execution status
 /// updates via GraphQL subscriptions when datasets or graphs change state.
 use layercake_core::database::entities::datasets;
+use layercake_core::pipeline::CancelledNode;
 use sea_orm::DatabaseConnection;
 
 /// Publish dataset execution status change event
@@ -48,3 +49,39 @@ pub async fn publish_dataset_status(
     });
 }
 
+/// Publish the terminal status of a node skipped by a cancelled plan run
+///
+/// Nodes with a computed graph_data row report it with execution state
+/// `cancelled`; nodes without one report no graph metadata.
+pub fn publish_cancelled_node_status(
+    project_id: i32,
+    node: &CancelledNode,
+    node_type: PlanDagNodeType,
+) {
+    let event = NodeExecutionStatusEvent {
+        project_id,
+        node_id: node.node_id.clone(),
+        node_type,
+        dataset_execution: None,
+        graph_execution: node.graph_data.as_ref().map(|gd| GraphExecutionMetadata {
+            graph_id: gd.id,
+            graph_data_id: Some(gd.id),
+            node_count: gd.node_count,
+            edge_count: gd.edge_count,
+            execution_state: gd.status.clone(),
+            computed_date: gd.computed_date.map(|d| d.to_rfc3339()),
+            error_message: gd.error_message.clone(),
+            annotations: gd
+                .annotations
+                .as_ref()
+                .and_then(|v| v.as_str().map(|s| s.to_string())),
+        }),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+
+    tokio::spawn(async move {
+        if let Err(e) = publish_execution_status_event(event).await {
+            tracing::debug!("Failed to publish cancelled node status: {}", e);
+        }
+    });
+}
@@ -0,0 +1,163 @@
+use async_graphql::Context;
+use layercake_core::graph::Layer;
+use layercake_core::services::GraphService;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+use crate::graphql::context::GraphQLContext;
+
+/// Resolved layer palette of a project, keyed by layer id.
+pub type LayerPalette = Arc<HashMap<String, Layer>>;
+
+/// Request-scoped loader for project layer palettes.
+///
+/// `graphql_handler` attaches a fresh loader to every request, so all fields
+/// resolving layers for the same project share a single palette load, even
+/// when they run concurrently. Nothing is cached across requests.
+#[derive(Clone)]
+pub struct LayerPaletteLoader {
+    graph_service: Arc<GraphService>,
+    palettes: Arc<Mutex<HashMap<i32, Arc<OnceCell<LayerPalette>>>>>,
+    loads: Arc<AtomicUsize>,
+}
+
+impl LayerPaletteLoader {
+    pub fn new(graph_service: Arc<GraphService>) -> Self {
+        Self {
+            graph_service,
+            palettes: Arc::new(Mutex::new(HashMap::new())),
+            loads: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Palette for `project_id`, loading it on first use. A failed load yields
+    /// an empty palette, matching the resolvers' previous behaviour.
+    pub async fn load(&self, project_id: i32) -> LayerPalette {
+        let cell = self
+            .palettes
+            .lock()
+            .expect("palette cache poisoned")
+            .entry(project_id)
+            .or_default()
+            .clone();
+
+        cell.get_or_init(|| async {
+            self.loads.fetch_add(1, Ordering::Relaxed);
+            let layers = self
+                .graph_service
+                .get_all_resolved_layers(project_id)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to resolve layers for project {}: {}", project_id, e);
+                    Vec::new()
+                });
+            Arc::new(
+                layers
+                    .into_iter()
+                    .map(|layer| (layer.id.clone(), layer))
+                    .collect(),
+            )
+        })
+        .await
+        .clone()
+    }
+
+    /// Number of palettes actually loaded from the database.
+    pub fn load_count(&self) -> usize {
+        self.loads.load(Ordering::Relaxed)
+    }
+}
+
+/// Palette for `project_id` through the request's loader, falling back to a
+/// one-off load when the request has none (e.g. subscriptions).
+pub async fn project_layer_palette(
+    ctx: &Context<'_>,
+    project_id: i32,
+) -> async_graphql::Result<LayerPalette> {
+    if let Some(loader) = ctx.data_opt::<LayerPaletteLoader>() {
+        return Ok(loader.load(project_id).await);
+    }
+    let context = ctx.data::<GraphQLContext>()?;
+    Ok(LayerPaletteLoader::new(context.graph_service.clone())
+        .load(project_id)
+        .await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphql::mutations::Mutation;
+    use crate::graphql::queries::Query;
+    use crate::graphql::subscriptions::Subscription;
+    use async_graphql::{Request, Schema};
+    use layercake_core::app_context::AppContext;
+    use layercake_core::database::entities::projects;
+    use layercake_core::database::migrations::Migrator;
+    use layercake_core::graph::{Graph, Node};
+    use layercake_core::services::{GraphDataService, SystemSettingsService};
+    use sea_orm::{ActiveModelTrait, Database, Set};
+    use sea_orm_migration::MigratorTrait;
+
+    #[tokio::test]
+    async fn layers_for_several_graphs_share_one_palette_load() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("Failed to connect to test database");
+        Migrator::up(&db, None)
+            .await
+            .expect("Failed to run migrations");
+
+        let mut project = projects::ActiveModel::new();
+        project.name = Set("Palette".to_string());
+        let project = project.insert(&db).await.expect("Failed to insert project");
+
+        let graph_data_service = GraphDataService::new(db.clone());
+        let mut graph_ids = Vec::new();
+        for layer in ["app", "db"] {
+            let graph = Graph {
+                name: format!("{layer} graph"),
+                nodes: vec![Node {
+                    id: format!("{layer}_node"),
+                    label: layer.to_string(),
+                    layer: layer.to_string(),
+                    weight: 1,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            };
+            let record = graph_data_service
+                .create_computed_from_graph(project.id, &graph, None)
+                .await
+                .expect("Failed to create graph");
+            graph_ids.push(record.id);
+        }
+
+        let app = Arc::new(AppContext::new(db.clone()));
+        let system_settings = Arc::new(
+            SystemSettingsService::new(db.clone())
+                .await
+                .expect("System settings init"),
+        );
+        let context = GraphQLContext::new(app, system_settings);
+        let loader = LayerPaletteLoader::new(context.graph_service.clone());
+        let schema = Schema::build(Query, Mutation::default(), Subscription)
+            .data(context)
+            .finish();
+
+        let query = format!(
+            "{{ a: graph(id: {}) {{ layers {{ layerId }} }} b: graph(id: {}) {{ layers {{ layerId }} }} }}",
+            graph_ids[0], graph_ids[1]
+        );
+        let response = schema
+            .execute(Request::new(query).data(loader.clone()))
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let data = response.data.into_json().expect("response data");
+        assert_eq!(data["a"]["layers"][0]["layerId"], "app");
+        assert_eq!(data["b"]["layers"][0]["layerId"], "db");
+        assert_eq!(loader.load_count(), 1);
+    }
+}
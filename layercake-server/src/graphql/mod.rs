@@ -5,6 +5,8 @@ pub mod errors;
 #[cfg(feature = "graphql")]
 pub mod execution_events;
 #[cfg(feature = "graphql")]
+pub mod loaders;
+#[cfg(feature = "graphql")]
 pub mod mutations;
 #[cfg(feature = "graphql")]
 pub mod queries;
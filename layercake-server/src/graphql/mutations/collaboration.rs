@@ -155,7 +155,7 @@ impl CollaborationMutation {
         );
 
         // Broadcast the event
-        match crate::graphql::subscriptions::publish_collaboration_event(event).await {
+        match crate::graphql::subscriptions::publish_collaboration_event(&context.db, event).await {
             Ok(()) => Ok(true),
             Err(_) => Ok(false),
         }
@@ -203,7 +203,7 @@ impl CollaborationMutation {
         );
 
         // Broadcast the event
-        match crate::graphql::subscriptions::publish_collaboration_event(event).await {
+        match crate::graphql::subscriptions::publish_collaboration_event(&context.db, event).await {
             Ok(()) => Ok(true),
             Err(_) => Ok(false),
         }
@@ -5,14 +5,17 @@ use crate::graphql::context::GraphQLContext;
 use crate::graphql::errors::StructuredError;
 use crate::graphql::types::{
     BulkUploadDataSetInput, CreateDataSetInput, CreateEmptyDataSetInput, DataSet,
-    DataSetValidationResult, ExportDataSetsInput, ExportDataSetsResult, ImportDataSetsInput,
-    ImportDataSetsResult, MergeDataSetsInput, UpdateDataSetInput,
+    DataSetConflictResolution, DataSetMergeStrategy, DataSetValidationResult,
+    DatasetSchemaDefinition, ExportDataSetsInput, ExportDataSetsResult, GraphData,
+    ImportDataSetsInput, ImportDataSetsResult, MergeDataSetsInput, SavedDatasetSchema,
+    UpdateDataSetInput,
 };
 use layercake_core::app_context::{
     BulkDataSetUpload, DataSetEmptyCreateRequest, DataSetExportFormat, DataSetExportRequest,
     DataSetFileCreateRequest, DataSetFileReplacement, DataSetImportFormat, DataSetImportRequest,
     DataSetUpdateRequest,
 };
+use layercake_core::dataset_schema::DatasetSchema;
 
 #[derive(Default)]
 pub struct DataSetMutation;
@@ -46,6 +49,7 @@ impl DataSetMutation {
                     file_format: input.file_format.into(),
                     tabular_data_type: input.tabular_data_type.map(Into::into),
                     file_bytes,
+                    strict: input.strict,
                 },
             )
             .await
@@ -177,6 +181,22 @@ impl DataSetMutation {
         Ok(true)
     }
 
+    /// Delete the DataSets `orphanedDataSets` reports, returning their IDs
+    async fn delete_orphaned_data_sets(
+        &self,
+        ctx: &Context<'_>,
+        project_id: i32,
+        min_age_hours: Option<i32>,
+    ) -> Result<Vec<i32>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        context
+            .app
+            .delete_orphaned_data_sets(&actor, project_id, min_age_hours.map(i64::from))
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)
+    }
+
     /// Reprocess existing DataSet file
     async fn reprocess_data_set(&self, ctx: &Context<'_>, id: i32) -> Result<DataSet> {
         let context = ctx.data::<GraphQLContext>()?;
@@ -208,20 +228,73 @@ impl DataSetMutation {
         Ok(DataSet::from(summary))
     }
 
-    /// Validate DataSet graph integrity
+    /// Validate DataSet graph integrity, or check it against a dataset schema
+    /// when `schema` or a saved `schemaId` is given. The DataSet is not modified.
     async fn validate_data_set(
         &self,
         ctx: &Context<'_>,
         id: i32,
+        schema: Option<DatasetSchemaDefinition>,
+        schema_id: Option<i32>,
     ) -> Result<DataSetValidationResult> {
         let context = ctx.data::<GraphQLContext>()?;
+        let summary = match (schema, schema_id) {
+            (Some(_), Some(_)) => {
+                return Err(StructuredError::bad_request(
+                    "Provide either schema or schemaId, not both",
+                ))
+            }
+            (Some(schema), None) => {
+                context
+                    .app
+                    .validate_data_set_against_schema(id, &DatasetSchema::from(schema))
+                    .await
+            }
+            (None, Some(schema_id)) => {
+                let actor = context.actor_for_request(ctx).await;
+                context
+                    .app
+                    .validate_data_set_against_saved_schema(&actor, id, schema_id)
+                    .await
+            }
+            (None, None) => context.app.validate_data_set(id).await,
+        }
+        .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(DataSetValidationResult::from(summary))
+    }
+
+    /// Save a dataset schema under `name` in a project, replacing any schema
+    /// with the same name
+    async fn save_dataset_schema(
+        &self,
+        ctx: &Context<'_>,
+        project_id: i32,
+        name: String,
+        schema: DatasetSchemaDefinition,
+    ) -> Result<SavedDatasetSchema> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
         let summary = context
             .app
-            .validate_data_set(id)
+            .save_dataset_schema(&actor, project_id, &name, &DatasetSchema::from(schema))
             .await
             .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
 
-        Ok(DataSetValidationResult::from(summary))
+        Ok(SavedDatasetSchema::from(summary))
+    }
+
+    /// Delete a saved dataset schema
+    async fn delete_dataset_schema(&self, ctx: &Context<'_>, id: i32) -> Result<bool> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        context
+            .app
+            .delete_dataset_schema(&actor, id)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(true)
     }
 
     /// Export data sources as spreadsheet (XLSX or ODS)
@@ -246,6 +319,7 @@ impl DataSetMutation {
                     project_id: input.project_id,
                     data_set_ids: input.data_set_ids,
                     format,
+                    group_by_layer: input.group_by_layer.unwrap_or(false),
                 },
             )
             .await
@@ -323,4 +397,35 @@ impl DataSetMutation {
 
         Ok(DataSet::from(summary))
     }
+
+    /// Join the graphs of several distinct data sets on node id into a new
+    /// computed graph; listing a data set twice is a validation error.
+    /// With `ERROR` conflict resolution, disagreeing values are reported as a
+    /// CONFLICT error whose extensions list the conflicting node and edge ids.
+    #[graphql(name = "mergeDataSetsIntoGraph")]
+    async fn merge_data_sets_into_graph(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "projectId")] project_id: i32,
+        #[graphql(name = "sourceIds")] source_ids: Vec<i32>,
+        strategy: DataSetMergeStrategy,
+        #[graphql(name = "conflictResolution")] conflict_resolution: DataSetConflictResolution,
+    ) -> Result<GraphData> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+
+        let graph_data = context
+            .app
+            .merge_data_sets_into_graph(
+                &actor,
+                project_id,
+                source_ids,
+                strategy.into(),
+                conflict_resolution.into(),
+            )
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(GraphData::from(graph_data))
+    }
 }
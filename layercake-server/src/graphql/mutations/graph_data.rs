@@ -1,6 +1,8 @@
 use async_graphql::*;
 
 use crate::graphql::context::GraphQLContext;
+use crate::graphql::types::graph_metrics::WeightNormalization;
+use crate::graphql::types::graph_snapshot::GraphSnapshot;
 use crate::graphql::types::{GraphData, UpdateGraphDataInput};
 
 #[derive(Default)]
@@ -46,6 +48,125 @@ impl GraphDataMutation {
             .map_err(crate::graphql::errors::core_error_to_graphql_error)
     }
 
+    /// Bookmark a graph's current nodes, edges and annotations under a label,
+    /// e.g. before a destructive transformation
+    #[graphql(name = "snapshotGraph")]
+    async fn snapshot_graph(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "graphId")] graph_id: i32,
+        label: String,
+    ) -> Result<GraphSnapshot> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+
+        let snapshot = context
+            .app
+            .snapshot_graph(&actor, graph_id, label)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(GraphSnapshot::from(snapshot))
+    }
+
+    /// Replace a graph's nodes, edges and annotations with a snapshot's
+    #[graphql(name = "restoreSnapshot")]
+    async fn restore_snapshot(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "snapshotId")] snapshot_id: i32,
+    ) -> Result<GraphData> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+
+        let graph_data = context
+            .app
+            .restore_graph_snapshot(&actor, snapshot_id)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(GraphData::from(graph_data))
+    }
+
+    /// Delete a snapshot; the graph it was taken from is left untouched
+    #[graphql(name = "deleteSnapshot")]
+    async fn delete_snapshot(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "snapshotId")] snapshot_id: i32,
+    ) -> Result<bool> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+
+        context
+            .app
+            .delete_graph_snapshot(&actor, snapshot_id)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(true)
+    }
+
+    /// Compute weighted PageRank for a graph and save it as a new computed
+    /// graph whose nodes carry the score in their `pagerank` attribute.
+    /// Defaults to a damping factor of 0.85 and at most 100 iterations.
+    #[graphql(name = "annotatePagerank")]
+    async fn annotate_pagerank(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "graphId")] graph_id: i32,
+        damping: Option<f64>,
+        iterations: Option<i32>,
+    ) -> Result<GraphData> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+
+        let graph_data = context
+            .app
+            .annotate_graph_pagerank(
+                &actor,
+                graph_id,
+                damping.unwrap_or(0.85),
+                iterations.unwrap_or(100).max(1) as usize,
+            )
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(GraphData::from(graph_data))
+    }
+
+    /// Rescale a graph's edge weights and save the result as a new computed
+    /// graph. MinMax maps onto rangeMin..rangeMax (default 1..100); ZScore and
+    /// Robust yield standard scores times 100. With preserveZero, zero-weight
+    /// edges stay zero.
+    #[graphql(name = "normalizeEdgeWeights")]
+    async fn normalize_edge_weights(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "graphId")] graph_id: i32,
+        method: WeightNormalization,
+        #[graphql(name = "rangeMin")] range_min: Option<i32>,
+        #[graphql(name = "rangeMax")] range_max: Option<i32>,
+        #[graphql(name = "preserveZero")] preserve_zero: Option<bool>,
+    ) -> Result<GraphData> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+
+        let graph_data = context
+            .app
+            .normalize_graph_edge_weights(
+                &actor,
+                graph_id,
+                method.into(),
+                (range_min.unwrap_or(1), range_max.unwrap_or(100)),
+                preserve_zero.unwrap_or(false),
+            )
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(GraphData::from(graph_data))
+    }
+
     /// Replay edits for a graph_data item
     async fn replay_graph_data_edits(
         &self,
@@ -8,8 +8,8 @@ use crate::graphql::errors::StructuredError;
 use layercake_core::database::entities::{project_collaborators, user_sessions, users};
 use layercake_core::plan::{
     ExportFileType, GraphvizLayout, GraphvizRenderOptions, MermaidDisplay, MermaidLook,
-    MermaidRenderOptions, RenderConfig, RenderConfigBuiltInStyle, RenderConfigOrientation,
-    RenderConfigTheme, RenderTargetOptions,
+    MermaidRenderOptions, PlantUmlRenderOptions, RenderConfig, RenderConfigBuiltInStyle,
+    RenderConfigOrientation, RenderConfigTheme, RenderTargetOptions, SvgRenderOptions,
 };
 
 /// Generate a unique node ID based on node type
@@ -124,7 +124,14 @@ pub struct StoredRenderConfig {
     pub note_position: Option<String>,
     pub use_node_weight: Option<bool>,
     pub use_edge_weight: Option<bool>,
+    pub normalize_edge_weights: Option<bool>,
+    pub render_partitions_as_nodes: Option<bool>,
+    pub directed: Option<bool>,
+    pub precompute_layout: Option<bool>,
+    pub prettify: Option<bool>,
+    pub timestamp_format: Option<String>,
     pub layer_source_styles: Option<Vec<layercake_core::plan::LayerSourceStyleOverride>>,
+    pub color_by_attribute: Option<StoredColorByAttribute>,
 }
 
 impl StoredRenderConfig {
@@ -176,16 +183,43 @@ impl StoredRenderConfig {
                 .unwrap_or(layercake_core::plan::NotePosition::Left),
             use_node_weight: self.use_node_weight.unwrap_or(true),
             use_edge_weight: self.use_edge_weight.unwrap_or(true),
+            normalize_edge_weights: self.normalize_edge_weights.unwrap_or(false),
+            render_partitions_as_nodes: self.render_partitions_as_nodes.unwrap_or(false),
+            directed: self.directed,
+            precompute_layout: self.precompute_layout.unwrap_or(false),
+            prettify: self.prettify.unwrap_or(false),
+            timestamp_format: self
+                .timestamp_format
+                .as_deref()
+                .map(parse_timestamp_format)
+                .unwrap_or_default(),
             layer_source_styles: self.layer_source_styles.unwrap_or_default(),
+            color_by_attribute: self.color_by_attribute.map(|color| {
+                layercake_core::plan::ColorByAttribute {
+                    key: color.key,
+                    min_color: color.min_color,
+                    max_color: color.max_color,
+                }
+            }),
         }
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredColorByAttribute {
+    pub key: String,
+    pub min_color: String,
+    pub max_color: String,
+}
+
 #[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StoredRenderTargetOptions {
     pub graphviz: Option<StoredGraphvizRenderOptions>,
     pub mermaid: Option<StoredMermaidRenderOptions>,
+    pub svg: Option<StoredSvgRenderOptions>,
+    pub plantuml: Option<StoredPlantUmlRenderOptions>,
 }
 
 impl StoredRenderTargetOptions {
@@ -193,6 +227,8 @@ impl StoredRenderTargetOptions {
         RenderTargetOptions {
             graphviz: self.graphviz.map(|opts| opts.into_graphviz_options()),
             mermaid: self.mermaid.map(|opts| opts.into_mermaid_options()),
+            svg: self.svg.map(|opts| opts.into_svg_options()),
+            plantuml: self.plantuml.map(|opts| opts.into_plantuml_options()),
         }
     }
 }
@@ -253,6 +289,42 @@ impl StoredMermaidRenderOptions {
     }
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredSvgRenderOptions {
+    pub label_max_width: Option<u32>,
+}
+
+impl StoredSvgRenderOptions {
+    pub fn into_svg_options(self) -> SvgRenderOptions {
+        let mut options = SvgRenderOptions::default();
+        if let Some(width) = self.label_max_width {
+            options.label_max_width = width;
+        }
+        options
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredPlantUmlRenderOptions {
+    pub background_color: Option<String>,
+    pub font_name: Option<String>,
+    pub arrow_color: Option<String>,
+    pub round_corner: Option<u32>,
+}
+
+impl StoredPlantUmlRenderOptions {
+    pub fn into_plantuml_options(self) -> PlantUmlRenderOptions {
+        PlantUmlRenderOptions {
+            background_color: self.background_color,
+            font_name: self.font_name,
+            arrow_color: self.arrow_color,
+            round_corner: self.round_corner,
+        }
+    }
+}
+
 /// Parse orientation string
 pub fn parse_orientation(value: &str) -> RenderConfigOrientation {
     match value {
@@ -273,10 +345,20 @@ pub fn parse_builtin_style(value: &str) -> RenderConfigBuiltInStyle {
     match value {
         "none" | "NONE" | "None" => RenderConfigBuiltInStyle::None,
         "dark" | "DARK" | "Dark" => RenderConfigBuiltInStyle::Dark,
+        "blueprint" | "BLUEPRINT" | "Blueprint" => RenderConfigBuiltInStyle::Blueprint,
         _ => RenderConfigBuiltInStyle::Light,
     }
 }
 
+pub fn parse_timestamp_format(value: &str) -> layercake_core::plan::TimestampFormat {
+    match value {
+        "epochMillis" | "EPOCH_MILLIS" | "epoch_millis" => {
+            layercake_core::plan::TimestampFormat::EpochMillis
+        }
+        _ => layercake_core::plan::TimestampFormat::Rfc3339,
+    }
+}
+
 pub fn parse_note_position(value: &str) -> layercake_core::plan::NotePosition {
     match value {
         "right" | "RIGHT" | "Right" => layercake_core::plan::NotePosition::Right,
@@ -446,58 +528,35 @@ pub async fn ensure_project_collaborator(
 
 /// Get file extension for render target format
 pub fn get_extension_for_format(format: &str) -> &str {
+    if let Some(info) = ExportFileType::from_format_id(format).and_then(|f| f.format_info()) {
+        return info.extension;
+    }
     match format {
-        "DOT" => "dot",
-        "GML" => "gml",
-        "JSON" => "json",
         "CSV" => "csv",
-        "CSVNodes" => "csv",
-        "CSVEdges" => "csv",
-        "PlantUML" => "puml",
-        "PlantUmlMindmap" => "puml",
-        "PlantUmlWbs" => "puml",
         "PlantUmlSequence" => "puml",
-        "Mermaid" => "mermaid",
-        "MermaidMindmap" => "mmd",
-        "MermaidTreemap" => "mmd",
         "MermaidSequence" => "mmd",
         _ => "txt",
     }
 }
 
-/// Get MIME type for render target format
+/// Get MIME type for render target format, from the export format registry.
+/// Render targets that are not export formats (e.g. sequence diagrams) are
+/// plain text.
 pub fn get_mime_type_for_format(format: &str) -> String {
-    match format {
-        "DOT" => "text/vnd.graphviz",
-        "GML" => "text/plain",
-        "JSON" => "application/json",
-        "CSV" | "CSVNodes" | "CSVEdges" => "text/csv",
-        "PlantUML" | "PlantUmlMindmap" | "PlantUmlWbs" | "PlantUmlSequence" => "text/plain",
-        "Mermaid" | "MermaidMindmap" | "MermaidTreemap" | "MermaidSequence" => "text/plain",
-        _ => "text/plain",
-    }
-    .to_string()
+    parse_export_format(format)
+        .ok()
+        .and_then(|format| format.format_info())
+        .map_or("text/plain", |info| info.mime_type)
+        .to_string()
 }
 
 /// Parse render target string to ExportFileType enum
 pub fn parse_export_format(format: &str) -> Result<ExportFileType> {
     match format {
-        "DOT" => Ok(ExportFileType::DOT),
-        "GML" => Ok(ExportFileType::GML),
-        "JSON" => Ok(ExportFileType::JSON),
-        "PlantUML" => Ok(ExportFileType::PlantUML),
-        "PlantUmlMindmap" => Ok(ExportFileType::PlantUmlMindmap),
-        "PlantUmlWbs" => Ok(ExportFileType::PlantUmlWbs),
-        "Mermaid" => Ok(ExportFileType::Mermaid),
-        "MermaidMindmap" => Ok(ExportFileType::MermaidMindmap),
-        "MermaidTreemap" => Ok(ExportFileType::MermaidTreemap),
-        "CSVNodes" => Ok(ExportFileType::CSVNodes),
-        "CSVEdges" => Ok(ExportFileType::CSVEdges),
         "CSV" => Ok(ExportFileType::CSVNodes), // Default CSV to nodes
-        _ => Err(StructuredError::bad_request(format!(
-            "Unsupported export format: {}",
-            format
-        ))),
+        _ => ExportFileType::from_format_id(format).ok_or_else(|| {
+            StructuredError::bad_request(format!("Unsupported export format: {}", format))
+        }),
     }
 }
 
@@ -541,6 +600,9 @@ pub struct NodeExecutionTiming {
     /// Whether the node ran during execution or renders lazily on export.
     /// Artefact nodes are `Render` (durationMs 0 here).
     pub phase: ExecutionPhase,
+    /// The node was skipped and its previous output reused because neither
+    /// its config nor anything upstream changed.
+    pub cached: bool,
 }
 
 impl From<layercake_core::pipeline::NodeExecutionRecord> for NodeExecutionTiming {
@@ -550,6 +612,7 @@ impl From<layercake_core::pipeline::NodeExecutionRecord> for NodeExecutionTiming
             node_type: r.node_type,
             duration_ms: r.duration_ms as i64,
             phase: r.phase.into(),
+            cached: r.cached,
         }
     }
 }
@@ -576,6 +639,53 @@ pub struct ExportNodeOutputResult {
     pub content: String, // Base64 encoded
     pub filename: String,
     pub mime_type: String,
+    /// `gzip` when `content` was compressed; `mime_type` describes the
+    /// decompressed payload.
+    pub content_encoding: Option<String>,
+}
+
+impl ExportNodeOutputResult {
+    /// Successful export result carrying `content`, gzipped when `compress`
+    /// is set (the filename then gains a `.gz` suffix).
+    pub fn exported(
+        content: &[u8],
+        filename: String,
+        render_target: &str,
+        compress: bool,
+    ) -> Result<Self> {
+        use base64::Engine;
+
+        let (content, filename, content_encoding) = if compress {
+            let compressed = gzip_export_content(content).map_err(|e| {
+                StructuredError::internal(format!("Failed to compress export: {}", e))
+            })?;
+            (
+                compressed,
+                format!("{}.gz", filename),
+                Some("gzip".to_string()),
+            )
+        } else {
+            (content.to_vec(), filename, None)
+        };
+
+        Ok(Self {
+            success: true,
+            message: format!("Successfully exported {} as {}", filename, render_target),
+            content: base64::engine::general_purpose::STANDARD.encode(content),
+            filename,
+            mime_type: get_mime_type_for_format(render_target),
+            content_encoding,
+        })
+    }
+}
+
+pub fn gzip_export_content(content: &[u8]) -> std::io::Result<Vec<u8>> {
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content)?;
+    encoder.finish()
 }
 
 #[cfg(test)]
@@ -605,3 +715,61 @@ mod contain_nodes_tests {
         assert_eq!(n.contain_nodes, None);
     }
 }
+
+#[cfg(test)]
+mod export_compression_tests {
+    use super::{gzip_export_content, ExportNodeOutputResult};
+    use base64::Engine;
+    use flate2::read::GzDecoder;
+    use layercake_core::graph::{Graph, Node};
+    use layercake_core::plan::ExportFileType;
+    use layercake_core::services::ExportService;
+    use std::io::Read;
+
+    #[test]
+    fn gzipped_export_decompresses_to_the_plain_export() {
+        let graph = Graph {
+            name: "Compressed".to_string(),
+            nodes: vec![Node {
+                id: "a".to_string(),
+                label: "A".to_string(),
+                layer: "layer1".to_string(),
+                weight: 1,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let export = ExportService::new(Default::default())
+            .export_to_string(&graph, &ExportFileType::JSON, None)
+            .expect("export");
+
+        let plain = ExportNodeOutputResult::exported(
+            export.as_bytes(),
+            "graph.json".to_string(),
+            "JSON",
+            false,
+        )
+        .unwrap();
+        let gzipped = ExportNodeOutputResult::exported(
+            export.as_bytes(),
+            "graph.json".to_string(),
+            "JSON",
+            true,
+        )
+        .unwrap();
+        assert_eq!(plain.content_encoding, None);
+        assert_eq!(gzipped.content_encoding.as_deref(), Some("gzip"));
+        assert_eq!(gzipped.filename, "graph.json.gz");
+        assert_eq!(plain.mime_type, "application/json");
+        assert_eq!(gzipped.mime_type, plain.mime_type);
+
+        let engine = base64::engine::general_purpose::STANDARD;
+        let compressed = engine.decode(&gzipped.content).unwrap();
+        assert_eq!(compressed, gzip_export_content(export.as_bytes()).unwrap());
+        let mut decompressed = Vec::new();
+        GzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, engine.decode(&plain.content).unwrap());
+    }
+}
@@ -39,6 +39,43 @@ impl LayerMutation {
         Ok(ProjectLayer::from(model))
     }
 
+    /// Rename and recolour a layer everywhere it appears in the project palette,
+    /// so previews and exports pick up the new styling. Omitted fields are left
+    /// unchanged; colours must be 6-digit hex. `alias` registers another layer id
+    /// that resolves to this layer. Returns every updated palette row.
+    #[graphql(name = "updateProjectLayer")]
+    #[allow(clippy::too_many_arguments)]
+    async fn update_project_layer(
+        &self,
+        ctx: &Context<'_>,
+        project_id: i32,
+        layer_id: String,
+        name: Option<String>,
+        background_color: Option<String>,
+        text_color: Option<String>,
+        border_color: Option<String>,
+        alias: Option<String>,
+    ) -> Result<Vec<ProjectLayer>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let models = context
+            .app
+            .update_project_layer(
+                &actor,
+                project_id,
+                layer_id,
+                name,
+                background_color,
+                text_color,
+                border_color,
+                alias,
+            )
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(models.into_iter().map(ProjectLayer::from).collect())
+    }
+
     /// Apply a curated palette preset in one call: upsert each of the preset's
     /// swatches as a project layer (keyed by swatch name). Returns the resulting
     /// project layers. Fails if `presetName` is not a known preset.
@@ -353,7 +353,7 @@ impl LibraryMutation {
         // Delete the old project
         context
             .app
-            .delete_project(&actor, project_id)
+            .purge_project(&actor, project_id)
             .await
             .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
 
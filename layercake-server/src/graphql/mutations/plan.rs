@@ -4,8 +4,11 @@ use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use super::helpers::PlanExecutionResult;
 use crate::graphql::context::GraphQLContext;
 use crate::graphql::errors::StructuredError;
+use crate::graphql::execution_events::publish_cancelled_node_status;
 use crate::graphql::types::plan::{CreatePlanInput, Plan, UpdatePlanInput};
+use crate::graphql::types::plan_dag::PlanDagNode;
 use layercake_core::database::entities::{plan_dag_edges, plan_dag_nodes};
+use layercake_core::pipeline::PlanExecutionCancelled;
 use layercake_core::services::plan_service::{PlanCreateRequest, PlanUpdateRequest};
 
 #[derive(Default)]
@@ -87,12 +90,15 @@ impl PlanMutation {
         Ok(true)
     }
 
-    /// Execute a plan (executes all nodes in the DAG in optimal topological order)
+    /// Execute a plan (executes all nodes in the DAG in optimal topological order).
+    /// Nodes whose config and upstream inputs are unchanged since the last run
+    /// reuse their stored graph unless `force` is set.
     async fn execute_plan(
         &self,
         ctx: &Context<'_>,
         #[graphql(name = "projectId")] project_id: i32,
         #[graphql(name = "planId")] plan_id: Option<i32>,
+        force: Option<bool>,
     ) -> Result<PlanExecutionResult> {
         let context = ctx.data::<GraphQLContext>()?;
 
@@ -158,13 +164,17 @@ impl PlanMutation {
             .map(|e| (e.source_node_id.clone(), e.target_node_id.clone()))
             .collect();
 
-        // Create executor and execute the entire DAG
-        let executor = layercake_core::pipeline::DagExecutor::new(context.db.clone());
+        // Create executor and execute the entire DAG; the registration lets
+        // `cancelPlanExecution` stop the run between nodes.
+        let execution = context.app.plan_executions().register(project_id, plan.id);
+        let executor = layercake_core::pipeline::DagExecutor::new(context.db.clone())
+            .with_cancellation(execution.token())
+            .with_force(force.unwrap_or(false));
 
-        executor
+        let outcome = executor
             .execute_dag(project_id, plan.id, &nodes, &edges)
-            .await
-            .map_err(|e| StructuredError::service("DagExecutor::execute_dag", e))?;
+            .await;
+        drop(execution);
 
         let warnings = executor.take_warnings();
         let node_results = executor
@@ -172,6 +182,26 @@ impl PlanMutation {
             .into_iter()
             .map(super::helpers::NodeExecutionTiming::from)
             .collect();
+
+        if let Err(e) = outcome {
+            let cancelled = e
+                .downcast::<PlanExecutionCancelled>()
+                .map_err(|e| StructuredError::service("DagExecutor::execute_dag", e))?;
+            for node in &cancelled.cancelled_nodes {
+                if let Some(model) = nodes.iter().find(|n| n.id == node.node_id) {
+                    let node_type = PlanDagNode::from(model.clone()).node_type;
+                    publish_cancelled_node_status(project_id, node, node_type);
+                }
+            }
+            return Ok(PlanExecutionResult {
+                success: false,
+                message: cancelled.to_string(),
+                output_files: vec![],
+                warnings,
+                node_results,
+            });
+        }
+
         Ok(PlanExecutionResult {
             success: true,
             message: format!("Executed {} nodes in topological order", nodes.len()),
@@ -181,6 +211,27 @@ impl PlanMutation {
         })
     }
 
+    /// Cancel the project's running plan executions. Each run stops before its
+    /// next DAG node; nodes already executed keep their results. Returns false
+    /// when nothing was running.
+    #[graphql(name = "cancelPlanExecution")]
+    async fn cancel_plan_execution(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "projectId")] project_id: i32,
+    ) -> Result<bool> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+
+        let cancelled = context
+            .app
+            .cancel_plan_execution(&actor, project_id)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(!cancelled.is_empty())
+    }
+
     /// Duplicate a plan with all DAG nodes and edges
     async fn duplicate_plan(&self, ctx: &Context<'_>, id: i32, name: String) -> Result<Plan> {
         let context = ctx.data::<GraphQLContext>()?;
@@ -1,18 +1,17 @@
 use anyhow::anyhow;
 use async_graphql::*;
-use base64::Engine;
 use chrono::Utc;
 use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
 
 use super::helpers::{
-    generate_edge_id, generate_node_id_from_ids, get_extension_for_format,
-    get_mime_type_for_format, parse_export_format, ExecutionActionResult, ExportNodeOutputResult,
-    StoredGraphArtefactNodeConfig, StoredSequenceArtefactNodeConfig, StoredSequenceRenderConfig,
-    StoredTreeArtefactNodeConfig,
+    generate_edge_id, generate_node_id_from_ids, get_extension_for_format, parse_export_format,
+    ExecutionActionResult, ExportNodeOutputResult, StoredGraphArtefactNodeConfig,
+    StoredSequenceArtefactNodeConfig, StoredSequenceRenderConfig, StoredTreeArtefactNodeConfig,
 };
 use crate::graphql::context::GraphQLContext;
 use crate::graphql::errors::StructuredError;
 use crate::graphql::types::plan_dag::{
+    config::ColorByAttribute as GraphQLColorByAttribute,
     config::GraphvizCommentStyle as GraphQLGraphvizCommentStyle,
     config::LayerSourceStyle as GraphQLLayerSourceStyle,
     config::LayerSourceStyleOverride as GraphQLLayerSourceStyleOverride,
@@ -20,8 +19,9 @@ use crate::graphql::types::plan_dag::{
     config::RenderBuiltinStyle as GraphQLRenderBuiltinStyle,
     config::RenderConfig as GraphQLRenderConfig,
     config::RenderTargetOptions as GraphQLRenderTargetOptions,
-    config::SequenceArtefactRenderTarget, config::StoryNodeConfig, PlanDag, PlanDagEdge,
-    PlanDagInput, PlanDagMigrationDetail, PlanDagMigrationResult, PlanDagNode,
+    config::SequenceArtefactRenderTarget, config::StoryNodeConfig,
+    config::TimestampFormat as GraphQLTimestampFormat, PlanDag, PlanDagEdge, PlanDagInput,
+    PlanDagMigrationDetail, PlanDagMigrationResult, PlanDagNode,
 };
 use layercake_core::database::entities::graph_data;
 use layercake_core::database::entities::{
@@ -34,11 +34,11 @@ use layercake_core::export::{
 use layercake_core::graph::{Edge, Graph, Layer, Node};
 use layercake_core::pipeline::DagExecutor;
 use layercake_core::plan::{
-    ExportFileType, GraphvizCommentStyle, GraphvizRenderOptions,
-    LayerSourceStyle as PlanLayerSourceStyle,
+    ColorByAttribute as PlanColorByAttribute, ExportFileType, GraphvizCommentStyle,
+    GraphvizRenderOptions, LayerSourceStyle as PlanLayerSourceStyle,
     LayerSourceStyleOverride as PlanLayerSourceStyleOverride, NotePosition as PlanNotePosition,
     RenderConfig as PlanRenderConfig, RenderConfigBuiltInStyle, RenderConfigOrientation,
-    RenderTargetOptions,
+    RenderTargetOptions, TimestampFormat as PlanTimestampFormat,
 };
 use layercake_core::sequence_context::{apply_render_config, SequenceStoryContext};
 use layercake_core::services::{GraphDataService, GraphService};
@@ -57,12 +57,21 @@ fn default_artefact_render_config() -> PlanRenderConfig {
         target_options: RenderTargetOptions {
             graphviz: Some(GraphvizRenderOptions::default()),
             mermaid: None,
+            svg: None,
+            plantuml: None,
         },
         add_node_comments_as_notes: false,
         note_position: PlanNotePosition::Left,
         use_node_weight: true,
         use_edge_weight: true,
+        normalize_edge_weights: false,
+        render_partitions_as_nodes: false,
+        directed: None,
+        precompute_layout: false,
+        prettify: false,
+        timestamp_format: PlanTimestampFormat::Rfc3339,
         layer_source_styles: Vec::new(),
+        color_by_attribute: None,
     }
 }
 
@@ -75,6 +84,13 @@ fn map_note_position(position: GraphQLNotePosition) -> PlanNotePosition {
     }
 }
 
+fn map_timestamp_format(format: GraphQLTimestampFormat) -> PlanTimestampFormat {
+    match format {
+        GraphQLTimestampFormat::Rfc3339 => PlanTimestampFormat::Rfc3339,
+        GraphQLTimestampFormat::EpochMillis => PlanTimestampFormat::EpochMillis,
+    }
+}
+
 /// Merge a (partial) GraphQL render config with defaults to the concrete plan RenderConfig
 fn render_config_from_graphql_input(
     input: &GraphQLRenderConfig,
@@ -92,6 +108,7 @@ fn render_config_from_graphql_input(
             GraphQLRenderBuiltinStyle::None => RenderConfigBuiltInStyle::None,
             GraphQLRenderBuiltinStyle::Light => RenderConfigBuiltInStyle::Light,
             GraphQLRenderBuiltinStyle::Dark => RenderConfigBuiltInStyle::Dark,
+            GraphQLRenderBuiltinStyle::Blueprint => RenderConfigBuiltInStyle::Blueprint,
         }
     }
 
@@ -127,6 +144,14 @@ fn render_config_from_graphql_input(
             .unwrap_or_else(|| defaults.to_vec())
     }
 
+    fn map_color_by_attribute(value: &GraphQLColorByAttribute) -> PlanColorByAttribute {
+        PlanColorByAttribute {
+            key: value.key.clone(),
+            min_color: value.min_color.clone(),
+            max_color: value.max_color.clone(),
+        }
+    }
+
     fn map_target_options(
         input: &GraphQLRenderTargetOptions,
         defaults: &RenderTargetOptions,
@@ -191,6 +216,27 @@ fn render_config_from_graphql_input(
                 }
             }
         }
+        if let Some(svg) = &input.svg {
+            let svg_opts = opts.svg.get_or_insert_with(Default::default);
+            if let Some(width) = svg.label_max_width {
+                svg_opts.label_max_width = width.max(1) as u32;
+            }
+        }
+        if let Some(plantuml) = &input.plantuml {
+            let plantuml_opts = opts.plantuml.get_or_insert_with(Default::default);
+            if let Some(color) = &plantuml.background_color {
+                plantuml_opts.background_color = Some(color.clone());
+            }
+            if let Some(font) = &plantuml.font_name {
+                plantuml_opts.font_name = Some(font.clone());
+            }
+            if let Some(color) = &plantuml.arrow_color {
+                plantuml_opts.arrow_color = Some(color.clone());
+            }
+            if let Some(radius) = plantuml.round_corner {
+                plantuml_opts.round_corner = Some(radius.max(0) as u32);
+            }
+        }
         opts
     }
 
@@ -219,10 +265,30 @@ fn render_config_from_graphql_input(
             .unwrap_or(defaults.note_position),
         use_node_weight: input.use_node_weight.unwrap_or(defaults.use_node_weight),
         use_edge_weight: input.use_edge_weight.unwrap_or(defaults.use_edge_weight),
+        normalize_edge_weights: input
+            .normalize_edge_weights
+            .unwrap_or(defaults.normalize_edge_weights),
+        render_partitions_as_nodes: input
+            .render_partitions_as_nodes
+            .unwrap_or(defaults.render_partitions_as_nodes),
+        directed: input.directed.or(defaults.directed),
+        precompute_layout: input
+            .precompute_layout
+            .unwrap_or(defaults.precompute_layout),
+        prettify: input.prettify.unwrap_or(defaults.prettify),
+        timestamp_format: input
+            .timestamp_format
+            .map(map_timestamp_format)
+            .unwrap_or(defaults.timestamp_format),
         layer_source_styles: map_layer_source_styles(
             input.layer_source_styles.as_ref(),
             &defaults.layer_source_styles,
         ),
+        color_by_attribute: input
+            .color_by_attribute
+            .as_ref()
+            .map(map_color_by_attribute)
+            .or_else(|| defaults.color_by_attribute.clone()),
     }
 }
 
@@ -272,7 +338,10 @@ fn apply_preview_limit(
 ) -> String {
     match (format, max_rows) {
         (
-            ExportFileType::CSVNodes | ExportFileType::CSVEdges | ExportFileType::CSVMatrix,
+            ExportFileType::CSVNodes
+            | ExportFileType::CSVEdges
+            | ExportFileType::CSVMatrix
+            | ExportFileType::CSVAdjacency,
             Some(limit),
         ) => {
             let mut limited_lines = Vec::new();
@@ -454,6 +523,7 @@ async fn export_sequence_artefact_node(
     project_id: i32,
     edges: &[plan_dag_edges::Model],
     all_nodes: &[plan_dag_nodes::Model],
+    compress: bool,
 ) -> Result<ExportNodeOutputResult> {
     let stored_config: StoredSequenceArtefactNodeConfig =
         serde_json::from_str(&artefact_node.config_json).map_err(|e| {
@@ -529,19 +599,12 @@ async fn export_sequence_artefact_node(
         }
     };
 
-    let encoded_content = base64::engine::general_purpose::STANDARD.encode(rendered.as_bytes());
-    let mime_type = get_mime_type_for_format(render_target_str.as_str());
-
-    Ok(ExportNodeOutputResult {
-        success: true,
-        message: format!(
-            "Successfully exported {} as {}",
-            filename, render_target_str
-        ),
-        content: encoded_content,
+    ExportNodeOutputResult::exported(
+        rendered.as_bytes(),
         filename,
-        mime_type,
-    })
+        render_target_str.as_str(),
+        compress,
+    )
 }
 
 #[Object]
@@ -720,6 +783,7 @@ impl PlanDagMutation {
     }
 
     /// Export a node's output (graph export to various formats)
+    #[allow(clippy::too_many_arguments)]
     async fn export_node_output(
         &self,
         ctx: &Context<'_>,
@@ -730,7 +794,9 @@ impl PlanDagMutation {
             GraphQLRenderConfig,
         >,
         preview_rows: Option<i32>,
+        compress: Option<bool>,
     ) -> Result<ExportNodeOutputResult> {
+        let compress = compress.unwrap_or(false);
         let context = ctx.data::<GraphQLContext>()?;
 
         // Find the plan for this project
@@ -792,6 +858,7 @@ impl PlanDagMutation {
                 project_id,
                 &edges,
                 &all_nodes,
+                compress,
             )
             .await?;
             return Ok(result);
@@ -896,6 +963,24 @@ impl PlanDagMutation {
             }
         });
 
+        if export_format
+            .format_info()
+            .is_some_and(|format| format.binary)
+        {
+            // Binary formats have no line-oriented preview; always return the full file.
+            let content = context
+                .app
+                .export_service()
+                .export_to_bytes(&graph, &export_format, Some(render_config))
+                .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+            return ExportNodeOutputResult::exported(
+                &content,
+                filename,
+                render_target.as_str(),
+                compress,
+            );
+        }
+
         let raw_content = context
             .app
             .export_service()
@@ -904,20 +989,12 @@ impl PlanDagMutation {
 
         let content = apply_preview_limit(raw_content, &export_format, preview_limit);
 
-        // Encode as base64
-        use base64::Engine;
-        let encoded_content = base64::engine::general_purpose::STANDARD.encode(content.as_bytes());
-
-        // Get MIME type
-        let mime_type = get_mime_type_for_format(render_target.as_str());
-
-        Ok(ExportNodeOutputResult {
-            success: true,
-            message: format!("Successfully exported {} as {}", filename, render_target),
-            content: encoded_content,
+        ExportNodeOutputResult::exported(
+            content.as_bytes(),
             filename,
-            mime_type,
-        })
+            render_target.as_str(),
+            compress,
+        )
     }
 
     /// Clear execution state for all nodes in a project (keeps edits, config, and datasets)
@@ -111,6 +111,62 @@ pub fn generate_edge_delete_patch(edge_id: &str, edges: &[PlanDagEdge]) -> Optio
     })
 }
 
+/// Plan DAG node IDs touched by `operations`, for `PlanDagDeltaEvent::node_ids`
+///
+/// Paths are resolved against the DAG state the operations were generated
+/// from: `/nodes/{i}/...` yields that node and `/edges/{i}/...` the edge's
+/// source and target. Whole-item adds are read from the operation value, since
+/// the new item is not in `nodes`/`edges` yet.
+pub fn touched_node_ids(
+    operations: &[PatchOperation],
+    nodes: &[PlanDagNode],
+    edges: &[PlanDagEdge],
+) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    let mut push = |id: &str| {
+        if !ids.iter().any(|existing| existing == id) {
+            ids.push(id.to_string());
+        }
+    };
+
+    for operation in operations {
+        let segments: Vec<&str> = operation.path.split('/').skip(1).collect();
+        let (collection, index) = match segments.as_slice() {
+            [collection, index, ..] => (*collection, index.parse::<usize>().ok()),
+            _ => continue,
+        };
+        let added = match (&operation.op, &operation.value) {
+            (PatchOp::Add, Some(value)) if segments.len() == 2 => Some(value),
+            _ => None,
+        };
+
+        match collection {
+            "nodes" => {
+                if let Some(id) = added.and_then(|value| value.get("id")?.as_str()) {
+                    push(id);
+                } else if let Some(node) = index.and_then(|i| nodes.get(i)) {
+                    push(&node.id);
+                }
+            }
+            "edges" => {
+                if let Some(value) = added {
+                    for key in ["source", "target"] {
+                        if let Some(id) = value.get(key).and_then(|v| v.as_str()) {
+                            push(id);
+                        }
+                    }
+                } else if let Some(edge) = index.and_then(|i| edges.get(i)) {
+                    push(&edge.source);
+                    push(&edge.target);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ids
+}
+
 /// Fetch current Plan DAG state for diff generation
 pub async fn fetch_current_plan_dag(
     db: &DatabaseConnection,
@@ -141,6 +197,7 @@ pub async fn publish_plan_dag_delta(
     user_id: String,
     client_id: String,
     operations: Vec<PatchOperation>,
+    node_ids: Vec<String>,
 ) -> Result<(), String> {
     let event = PlanDagDeltaEvent {
         project_id,
@@ -150,6 +207,7 @@ pub async fn publish_plan_dag_delta(
         client_id,
         timestamp: chrono::Utc::now().to_rfc3339(),
         operations,
+        node_ids,
     };
 
     crate::graphql::subscriptions::publish_delta_event(event).await
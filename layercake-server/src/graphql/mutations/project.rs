@@ -65,7 +65,26 @@ impl ProjectMutation {
         Ok(Project::from(project))
     }
 
-    /// Delete a project
+    /// Clone a project with its datasets, plans, graphs and layer palette.
+    /// Collaborators and graph snapshots are not copied.
+    async fn clone_project(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "sourceId")] source_id: i32,
+        #[graphql(name = "newName")] new_name: String,
+    ) -> Result<Project> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let project = context
+            .app
+            .clone_project(&actor, source_id, new_name)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(Project::from(project))
+    }
+
+    /// Delete a project; it can be restored until it is purged
     async fn delete_project(&self, ctx: &Context<'_>, id: i32) -> Result<bool> {
         let context = ctx.data::<GraphQLContext>()?;
         let actor = context.actor_for_request(ctx).await;
@@ -77,4 +96,30 @@ impl ProjectMutation {
 
         Ok(true)
     }
+
+    /// Restore a deleted project
+    async fn restore_project(&self, ctx: &Context<'_>, id: i32) -> Result<Project> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let project = context
+            .app
+            .restore_project(&actor, id)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(Project::from(project))
+    }
+
+    /// Permanently delete a project and all of its data
+    async fn purge_project(&self, ctx: &Context<'_>, id: i32) -> Result<bool> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        context
+            .app
+            .purge_project(&actor, id)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(true)
+    }
 }
@@ -10,13 +10,13 @@ use crate::graphql::types::graph::Graph;
 use crate::graphql::types::plan::Plan;
 use crate::graphql::types::plan_dag::DataSetReference;
 use crate::graphql::types::plan_dag::{PlanDag, PlanDagInput, ValidationResult};
-use crate::graphql::types::project::Project;
+use crate::graphql::types::project::{Project, TagMatchMode};
 use crate::graphql::types::sample_project::SampleProject;
 use crate::graphql::types::{
     DataSet, DataSetPreview, GraphData, GraphEdgePreview, GraphEdit, GraphNodePreview,
     GraphPreview, Layer, LayerAlias, LibraryItem, LibraryItemFilterInput, ProjectCollaborator,
-    ProjectLayer, Sequence, Story, SystemSetting, TableColumn, TableRow, User, UserFilter,
-    UserSession,
+    ProjectLayer, SavedDatasetSchema, Sequence, Story, SystemSetting, TableColumn, TableRow, User,
+    UserFilter, UserSession, PREVIEW_TYPE_SAMPLE_ROWS,
 };
 use crate::graphql::types::{GraphPage, GraphSummary};
 use layercake_core::database::entities::{
@@ -27,9 +27,7 @@ use layercake_core::database::entities::{
 use layercake_core::services::{
     graph_edit_service::GraphEditService, library_item_service::LibraryItemFilter,
     library_item_service::LibraryItemService, sample_project_service::SampleProjectService,
-    GraphService,
 };
-use std::collections::HashMap;
 
 pub struct Query;
 
@@ -43,12 +41,39 @@ impl Query {
             .collect())
     }
 
-    /// Get all projects
-    async fn projects(&self, ctx: &Context<'_>, tags: Option<Vec<String>>) -> Result<Vec<Project>> {
+    /// Built-in export formats with their file extensions and capabilities
+    async fn export_formats(&self) -> Vec<crate::graphql::types::export_format::ExportFormatInfo> {
+        layercake_core::plan::ExportFileType::built_in()
+            .iter()
+            .filter_map(|format| format.format_info())
+            .map(Into::into)
+            .collect()
+    }
+
+    /// Get all projects, optionally filtered by tags (any tag by default)
+    async fn projects(
+        &self,
+        ctx: &Context<'_>,
+        tags: Option<Vec<String>>,
+        #[graphql(name = "tagMatch", default)] tag_match: TagMatchMode,
+    ) -> Result<Vec<Project>> {
         let context = ctx.data::<GraphQLContext>()?;
         let projects = context
             .app
-            .list_projects_filtered(tags)
+            .list_projects_filtered(tags, tag_match.into())
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(projects.into_iter().map(Project::from).collect())
+    }
+
+    /// Get soft-deleted projects that can still be restored
+    #[graphql(name = "deletedProjects")]
+    async fn deleted_projects(&self, ctx: &Context<'_>) -> Result<Vec<Project>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let projects = context
+            .app
+            .list_deleted_projects()
             .await
             .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
 
@@ -111,6 +136,163 @@ impl Query {
         Ok(diff.into())
     }
 
+    /// Node/edge counts, density, average degree and component count in one call.
+    #[graphql(name = "graphSummaryMetrics")]
+    async fn graph_summary_metrics(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "graphId")] graph_id: i32,
+    ) -> Result<crate::graphql::types::graph_metrics::GraphSummaryMetrics> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let metrics = context
+            .app
+            .graph_summary_metrics(&actor, graph_id)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+        Ok(metrics.into())
+    }
+
+    /// Component count and sizes of a graph; `directed` selects strongly
+    /// rather than weakly connected components.
+    #[graphql(name = "connectedComponents")]
+    async fn connected_components(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "graphId")] graph_id: i32,
+        directed: bool,
+    ) -> Result<crate::graphql::types::graph_metrics::ComponentSummary> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let summary = context
+            .app
+            .graph_connected_components(&actor, graph_id, directed)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+        Ok(summary.into())
+    }
+
+    /// Density, min/max/average degree and degree histogram of a graph.
+    /// `directed` (the default) selects the directed density formula.
+    #[graphql(name = "graphTopology")]
+    async fn graph_topology(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "graphId")] graph_id: i32,
+        directed: Option<bool>,
+    ) -> Result<crate::graphql::types::graph_metrics::Topology> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let topology = context
+            .app
+            .graph_topology(&actor, graph_id, directed.unwrap_or(true))
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+        Ok(topology.into())
+    }
+
+    /// Betweenness centrality of every flow node. `weighted` uses edge weights
+    /// (which must be positive) as path costs instead of hop counts,
+    /// `directed` (the default) follows
+    /// edge direction, and `normalized` scales scores by `(n-1)(n-2)` (halved
+    /// when undirected).
+    #[graphql(name = "betweennessCentrality")]
+    async fn betweenness_centrality(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "graphId")] graph_id: i32,
+        weighted: Option<bool>,
+        directed: Option<bool>,
+        normalized: Option<bool>,
+    ) -> Result<crate::graphql::types::graph_metrics::BetweennessCentrality> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let report = context
+            .app
+            .graph_betweenness_centrality(
+                &actor,
+                graph_id,
+                weighted.unwrap_or(false),
+                directed.unwrap_or(true),
+                normalized.unwrap_or(false),
+            )
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+        Ok(report.into())
+    }
+
+    /// Structural diff between two computed graphs, e.g. one plan node's
+    /// output before and after a re-run. Unlike `diffDatasets`, nodes count as
+    /// changed only when their label, layer or attributes differ.
+    #[graphql(name = "graphDiff")]
+    async fn graph_diff(
+        &self,
+        ctx: &Context<'_>,
+        a: i32,
+        b: i32,
+    ) -> Result<crate::graphql::types::graph_diff::GraphDiff> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let diff = context
+            .app
+            .diff_computed_graphs(&actor, a, b)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+        Ok(diff.into())
+    }
+
+    /// Cheapest directed path between two nodes of a graph. Unweighted
+    /// queries count hops; weighted ones sum edge weights.
+    #[graphql(name = "shortestPath")]
+    async fn shortest_path(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "graphId")] graph_id: i32,
+        source: String,
+        target: String,
+        algorithm: Option<crate::graphql::types::graph_path::PathAlgorithm>,
+        weighted: Option<bool>,
+    ) -> Result<crate::graphql::types::graph_path::PathResult> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let algorithm = algorithm
+            .map(Into::into)
+            .unwrap_or(layercake_core::graph::PathAlgorithm::Dijkstra);
+        let report = context
+            .app
+            .shortest_graph_path(
+                &actor,
+                graph_id,
+                source,
+                target,
+                algorithm,
+                weighted.unwrap_or(false),
+            )
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+        Ok(report.into())
+    }
+
+    /// Edges running directly between two layers plus the fewest-hop path
+    /// joining them, ignoring edge direction.
+    #[graphql(name = "layerConnectivity")]
+    async fn layer_connectivity(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "graphId")] graph_id: i32,
+        #[graphql(name = "layerA")] layer_a: String,
+        #[graphql(name = "layerB")] layer_b: String,
+    ) -> Result<crate::graphql::types::graph_path::LayerConnectivity> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let report = context
+            .app
+            .graph_layer_connectivity(&actor, graph_id, layer_a, layer_b)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+        Ok(report.into())
+    }
+
     async fn graph_page(
         &self,
         ctx: &Context<'_>,
@@ -372,67 +554,7 @@ impl Query {
         _ctx: &Context<'_>,
         plan_dag: PlanDagInput,
     ) -> Result<ValidationResult> {
-        // TODO: Implement comprehensive Plan DAG validation
-        // For now, return a basic validation that always passes
-
-        let mut errors = Vec::new();
-        let mut warnings = Vec::new();
-
-        // Basic validation: check for orphaned nodes, cycles, etc.
-        let node_ids: std::collections::HashSet<String> =
-            plan_dag.nodes.iter().filter_map(|n| n.id.clone()).collect();
-
-        // Check for edges referencing non-existent nodes
-        for edge in &plan_dag.edges {
-            let edge_id_str = edge.id.clone().unwrap_or_else(|| "<unknown>".to_string());
-            if !node_ids.contains(&edge.source) {
-                errors.push(crate::graphql::types::ValidationError {
-                    node_id: None,
-                    edge_id: edge.id.clone(),
-                    error_type: crate::graphql::types::ValidationErrorType::InvalidConnection,
-                    message: format!(
-                        "Edge {} references non-existent source node {}",
-                        edge_id_str, edge.source
-                    ),
-                });
-            }
-            if !node_ids.contains(&edge.target) {
-                errors.push(crate::graphql::types::ValidationError {
-                    node_id: None,
-                    edge_id: edge.id.clone(),
-                    error_type: crate::graphql::types::ValidationErrorType::InvalidConnection,
-                    message: format!(
-                        "Edge {} references non-existent target node {}",
-                        edge_id_str, edge.target
-                    ),
-                });
-            }
-        }
-
-        // Check for isolated nodes (nodes with no connections)
-        for node in &plan_dag.nodes {
-            // Skip nodes without IDs (they will be generated)
-            if let Some(ref node_id) = node.id {
-                let has_connections = plan_dag
-                    .edges
-                    .iter()
-                    .any(|e| &e.source == node_id || &e.target == node_id);
-                if !has_connections && plan_dag.nodes.len() > 1 {
-                    warnings.push(crate::graphql::types::ValidationWarning {
-                        node_id: node.id.clone(),
-                        edge_id: None,
-                        warning_type: crate::graphql::types::ValidationWarningType::UnusedOutput,
-                        message: format!("Node {} has no connections", node_id),
-                    });
-                }
-            }
-        }
-
-        Ok(crate::graphql::types::plan_dag::ValidationResult {
-            is_valid: errors.is_empty(),
-            errors,
-            warnings,
-        })
+        Ok(plan_dag.validate())
     }
 
     // Authentication and User Management Queries
@@ -670,6 +792,44 @@ impl Query {
         Ok(summaries.into_iter().map(DataSet::from).collect())
     }
 
+    /// DataSets in a project that no plan DAG node references. DataSets created
+    /// within the last `minAgeHours` (default 24) are excluded.
+    async fn orphaned_data_sets(
+        &self,
+        ctx: &Context<'_>,
+        project_id: i32,
+        min_age_hours: Option<i32>,
+    ) -> Result<Vec<DataSet>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let summaries = context
+            .app
+            .orphaned_data_sets(project_id, min_age_hours.map(i64::from))
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(summaries.into_iter().map(DataSet::from).collect())
+    }
+
+    /// Dataset schemas saved in a project, by name
+    async fn dataset_schemas(
+        &self,
+        ctx: &Context<'_>,
+        project_id: i32,
+    ) -> Result<Vec<SavedDatasetSchema>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let summaries = context
+            .app
+            .list_dataset_schemas(&actor, project_id)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(summaries
+            .into_iter()
+            .map(SavedDatasetSchema::from)
+            .collect())
+    }
+
     /// Get GraphData by ID (unified query for datasets and computed graphs)
     async fn graph_data(&self, ctx: &Context<'_>, id: i32) -> Result<Option<GraphData>> {
         let context = ctx.data::<GraphQLContext>()?;
@@ -683,6 +843,24 @@ impl Query {
         Ok(model.map(GraphData::from))
     }
 
+    /// Snapshots of a graph, newest first
+    #[graphql(name = "graphSnapshots")]
+    async fn graph_snapshots(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "graphId")] graph_id: i32,
+    ) -> Result<Vec<crate::graphql::types::graph_snapshot::GraphSnapshot>> {
+        let context = ctx.data::<GraphQLContext>()?;
+        let actor = context.actor_for_request(ctx).await;
+        let snapshots = context
+            .app
+            .list_graph_snapshots(&actor, graph_id)
+            .await
+            .map_err(crate::graphql::errors::core_error_to_graphql_error)?;
+
+        Ok(snapshots.into_iter().map(Into::into).collect())
+    }
+
     /// Get all GraphData for a project (both datasets and computed graphs)
     async fn graph_data_list(
         &self,
@@ -934,18 +1112,7 @@ impl Query {
         };
 
         let columns = section_records
-            .and_then(|records| records.first())
-            .and_then(|value| value.as_object())
-            .map(|object| {
-                object
-                    .keys()
-                    .map(|key| TableColumn {
-                        name: key.clone(),
-                        data_type: "string".to_string(),
-                        nullable: true,
-                    })
-                    .collect::<Vec<_>>()
-            })
+            .map(|records| TableColumn::infer_from_records(records, PREVIEW_TYPE_SAMPLE_ROWS))
             .unwrap_or_default();
 
         let total_rows = section_records
@@ -1031,14 +1198,8 @@ impl Query {
                 .filter(graph_data_edges::Column::GraphDataId.eq(gd.id))
                 .all(&context.db)
                 .await?;
-            let palette_layers = GraphService::new(context.db.clone())
-                .get_all_resolved_layers(project_id)
-                .await
-                .unwrap_or_default();
-            let palette_map: HashMap<String, layercake_core::graph::Layer> = palette_layers
-                .into_iter()
-                .map(|layer| (layer.id.clone(), layer))
-                .collect();
+            let palette_map =
+                crate::graphql::loaders::project_layer_palette(ctx, project_id).await?;
 
             let mut layers = Vec::new();
             for (idx, layer_id) in nodes
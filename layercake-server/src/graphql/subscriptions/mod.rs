@@ -1,17 +1,21 @@
 use async_graphql::*;
 use futures_util::Stream;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::pin::Pin;
 
 use crate::graphql::context::GraphQLContext;
 use crate::graphql::types::{
     NodeExecutionStatusEvent, PlanDagDeltaEvent, PlanDagEdge, PlanDagNode,
 };
+use layercake_core::services::collaboration_event_service::CollaborationEventService;
 // REMOVED: CursorPosition import - user presence now handled via WebSocket only
 
 pub struct Subscription;
 
 /// Collaboration event types for real-time updates
-#[derive(Clone, Debug, SimpleObject)]
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 pub struct CollaborationEvent {
     pub event_id: String,
     pub plan_id: String,
@@ -21,7 +25,7 @@ pub struct CollaborationEvent {
     pub data: CollaborationEventData,
 }
 
-#[derive(Clone, Debug, Enum, Copy, PartialEq, Eq)]
+#[derive(Clone, Debug, Enum, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CollaborationEventType {
     NodeCreated,
     NodeUpdated,
@@ -33,7 +37,7 @@ pub enum CollaborationEventType {
     CursorMoved,
 }
 
-#[derive(Clone, Debug, SimpleObject)]
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 pub struct CollaborationEventData {
     pub node_event: Option<NodeEventData>,
     pub edge_event: Option<EdgeEventData>,
@@ -41,24 +45,24 @@ pub struct CollaborationEventData {
     pub cursor_event: Option<CursorEventData>,
 }
 
-#[derive(Clone, Debug, SimpleObject)]
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 pub struct NodeEventData {
     pub node: PlanDagNode,
 }
 
-#[derive(Clone, Debug, SimpleObject)]
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 pub struct EdgeEventData {
     pub edge: PlanDagEdge,
 }
 
-#[derive(Clone, Debug, SimpleObject)]
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 pub struct UserEventData {
     pub user_id: String,
     pub user_name: String,
     pub avatar_color: String,
 }
 
-#[derive(Clone, Debug, SimpleObject)]
+#[derive(Clone, Debug, SimpleObject, Serialize, Deserialize)]
 pub struct CursorEventData {
     pub user_id: String,
     pub user_name: String,
@@ -116,95 +120,25 @@ impl Subscription {
         ctx: &Context<'_>,
         plan_id: String,
     ) -> Result<Pin<Box<dyn Stream<Item = PlanDagUpdateEvent> + Send>>> {
-        let _context = ctx.data::<GraphQLContext>()?;
+        let context = ctx.data::<GraphQLContext>()?;
 
-        // Subscribe to collaboration events for this plan
+        // Subscribe before loading history so nothing published in between is lost
         let mut receiver = COLLABORATION_EVENTS.subscribe(plan_id.clone()).await;
+        let history = load_collaboration_history(&context.db, &plan_id).await;
 
-        // Filter events for Plan DAG updates only
+        // Replay recent history, then filter live events for Plan DAG updates only
         let stream = async_stream::stream! {
+            let mut replayed: HashSet<String> = HashSet::new();
+            for event in history {
+                replayed.insert(event.event_id.clone());
+                if let Some(update) = plan_dag_update_from(event) {
+                    yield update;
+                }
+            }
+
             while let Ok(event) = receiver.recv().await {
-                if event.plan_id == plan_id {
-                    let update_event = match event.event_type {
-                        CollaborationEventType::NodeCreated => {
-                            if let Some(node_data) = &event.data.node_event {
-                                Some(PlanDagUpdateEvent {
-                                    plan_id: event.plan_id,
-                                    update_type: PlanDagUpdateType::NodeAdded,
-                                    data: PlanDagUpdateData {
-                                        node: Some(node_data.node.clone()),
-                                        edge: None,
-                                        metadata: None,
-                                    },
-                                    user_id: event.user_id,
-                                    timestamp: event.timestamp,
-                                })
-                            } else { None }
-                        },
-                        CollaborationEventType::NodeUpdated => {
-                            if let Some(node_data) = &event.data.node_event {
-                                Some(PlanDagUpdateEvent {
-                                    plan_id: event.plan_id,
-                                    update_type: PlanDagUpdateType::NodeUpdated,
-                                    data: PlanDagUpdateData {
-                                        node: Some(node_data.node.clone()),
-                                        edge: None,
-                                        metadata: None,
-                                    },
-                                    user_id: event.user_id,
-                                    timestamp: event.timestamp,
-                                })
-                            } else { None }
-                        },
-                        CollaborationEventType::NodeDeleted => {
-                            if let Some(node_data) = &event.data.node_event {
-                                Some(PlanDagUpdateEvent {
-                                    plan_id: event.plan_id,
-                                    update_type: PlanDagUpdateType::NodeRemoved,
-                                    data: PlanDagUpdateData {
-                                        node: Some(node_data.node.clone()),
-                                        edge: None,
-                                        metadata: None,
-                                    },
-                                    user_id: event.user_id,
-                                    timestamp: event.timestamp,
-                                })
-                            } else { None }
-                        },
-                        CollaborationEventType::EdgeCreated => {
-                            if let Some(edge_data) = &event.data.edge_event {
-                                Some(PlanDagUpdateEvent {
-                                    plan_id: event.plan_id,
-                                    update_type: PlanDagUpdateType::EdgeAdded,
-                                    data: PlanDagUpdateData {
-                                        node: None,
-                                        edge: Some(edge_data.edge.clone()),
-                                        metadata: None,
-                                    },
-                                    user_id: event.user_id,
-                                    timestamp: event.timestamp,
-                                })
-                            } else { None }
-                        },
-                        CollaborationEventType::EdgeDeleted => {
-                            if let Some(edge_data) = &event.data.edge_event {
-                                Some(PlanDagUpdateEvent {
-                                    plan_id: event.plan_id,
-                                    update_type: PlanDagUpdateType::EdgeRemoved,
-                                    data: PlanDagUpdateData {
-                                        node: None,
-                                        edge: Some(edge_data.edge.clone()),
-                                        metadata: None,
-                                    },
-                                    user_id: event.user_id,
-                                    timestamp: event.timestamp,
-                                })
-                            } else { None }
-                        },
-                        _ => None,
-                    };
-
-                    if let Some(update) = update_event {
+                if event.plan_id == plan_id && !replayed.remove(&event.event_id) {
+                    if let Some(update) = plan_dag_update_from(event) {
                         yield update;
                     }
                 }
@@ -220,18 +154,26 @@ impl Subscription {
         ctx: &Context<'_>,
         plan_id: String,
     ) -> Result<Pin<Box<dyn Stream<Item = CollaborationEvent> + Send>>> {
-        let _context = ctx.data::<GraphQLContext>()?;
+        let context = ctx.data::<GraphQLContext>()?;
 
-        // Subscribe to collaboration events for this plan
+        // Subscribe before loading history so nothing published in between is lost
         let mut receiver = COLLABORATION_EVENTS.subscribe(plan_id.clone()).await;
+        let history = load_collaboration_history(&context.db, &plan_id).await;
 
-        // Stream all events for this plan with lag detection
+        // Replay recent history, then stream live events for this plan with lag
+        // detection, skipping any that were already part of the replayed tail
         let plan_id_clone = plan_id.clone();
         let stream = async_stream::stream! {
+            let mut replayed: HashSet<String> = HashSet::new();
+            for event in history {
+                replayed.insert(event.event_id.clone());
+                yield event;
+            }
+
             loop {
                 match receiver.recv().await {
                     Ok(event) => {
-                        if event.plan_id == plan_id_clone {
+                        if event.plan_id == plan_id_clone && !replayed.remove(&event.event_id) {
                             yield event;
                         }
                     }
@@ -264,11 +206,16 @@ impl Subscription {
     /// When `plan_id` is provided, only deltas for that plan are delivered, so a
     /// client editing one plan of a multi-plan project never receives (and
     /// misapplies) a patch generated for a different plan.
+    ///
+    /// When `node_id` is provided, only deltas touching that node are
+    /// delivered: changes to the node itself, including metadata-only updates,
+    /// and changes to edges it is the source or target of.
     async fn plan_dag_delta_changed(
         &self,
         ctx: &Context<'_>,
         project_id: i32,
         plan_id: Option<i32>,
+        node_id: Option<String>,
     ) -> Result<Pin<Box<dyn Stream<Item = PlanDagDeltaEvent> + Send>>> {
         let _context = ctx.data::<GraphQLContext>()?;
 
@@ -281,7 +228,10 @@ impl Subscription {
                 match receiver.recv().await {
                     Ok(event) => {
                         let plan_matches = plan_id.map_or(true, |pid| event.plan_id == pid);
-                        if event.project_id == project_id && plan_matches {
+                        let node_matches = node_id
+                            .as_ref()
+                            .is_none_or(|id| event.node_ids.contains(id));
+                        if event.project_id == project_id && plan_matches && node_matches {
                             yield event;
                         }
                     }
@@ -356,10 +306,28 @@ lazy_static::lazy_static! {
         layercake_core::utils::EventBroadcaster::new(1000);
 }
 
-/// Publish a collaboration event to all subscribers of a plan
-pub async fn publish_collaboration_event(event: CollaborationEvent) -> Result<(), String> {
+/// How many persisted events a new collaboration subscriber is replayed.
+const COLLABORATION_REPLAY_LIMIT: u64 = 100;
+
+/// Persist a collaboration event and publish it to all subscribers of a plan.
+/// Cursor moves are broadcast only; they are too frequent and short-lived to
+/// be worth replaying.
+pub async fn publish_collaboration_event(
+    db: &DatabaseConnection,
+    event: CollaborationEvent,
+) -> Result<(), String> {
     let plan_id = event.plan_id.clone();
 
+    if event.event_type != CollaborationEventType::CursorMoved {
+        if let Err(e) = persist_collaboration_event(db, &event).await {
+            tracing::warn!(
+                "Failed to persist collaboration event for plan {}: {}",
+                plan_id,
+                e
+            );
+        }
+    }
+
     // Check subscriber count for lag detection
     let receiver_count = COLLABORATION_EVENTS.receiver_count(&plan_id).await;
     if receiver_count > 50 {
@@ -382,6 +350,104 @@ pub async fn publish_collaboration_event(event: CollaborationEvent) -> Result<()
     Ok(())
 }
 
+async fn persist_collaboration_event(
+    db: &DatabaseConnection,
+    event: &CollaborationEvent,
+) -> Result<(), String> {
+    let payload = serde_json::to_string(event).map_err(|e| e.to_string())?;
+    let created_at = chrono::DateTime::parse_from_rfc3339(&event.timestamp)
+        .map(|timestamp| timestamp.with_timezone(&chrono::Utc))
+        .unwrap_or_else(|_| chrono::Utc::now());
+
+    CollaborationEventService::new(db.clone())
+        .record(
+            event.event_id.clone(),
+            event.plan_id.clone(),
+            event.user_id.clone(),
+            format!("{:?}", event.event_type),
+            payload,
+            created_at,
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Load the most recent persisted events for a plan, oldest first. Failures
+/// are logged and yield an empty history so live streaming still works.
+async fn load_collaboration_history(
+    db: &DatabaseConnection,
+    plan_id: &str,
+) -> Vec<CollaborationEvent> {
+    let records = match CollaborationEventService::new(db.clone())
+        .recent(plan_id, COLLABORATION_REPLAY_LIMIT)
+        .await
+    {
+        Ok(records) => records,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to load collaboration history for plan {}: {}",
+                plan_id,
+                e
+            );
+            return Vec::new();
+        }
+    };
+
+    records
+        .into_iter()
+        .filter_map(|record| match serde_json::from_str(&record.payload) {
+            Ok(event) => Some(event),
+            Err(e) => {
+                tracing::warn!(
+                    "Skipping unreadable collaboration event {}: {}",
+                    record.event_id,
+                    e
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+/// Map a node/edge collaboration event onto a Plan DAG update, if it is one.
+fn plan_dag_update_from(event: CollaborationEvent) -> Option<PlanDagUpdateEvent> {
+    let node = event.data.node_event.map(|node_data| node_data.node);
+    let edge = event.data.edge_event.map(|edge_data| edge_data.edge);
+    let (update_type, node, edge) = match event.event_type {
+        CollaborationEventType::NodeCreated => (PlanDagUpdateType::NodeAdded, Some(node?), None),
+        CollaborationEventType::NodeUpdated => (PlanDagUpdateType::NodeUpdated, Some(node?), None),
+        CollaborationEventType::NodeDeleted => (PlanDagUpdateType::NodeRemoved, Some(node?), None),
+        CollaborationEventType::EdgeCreated => (PlanDagUpdateType::EdgeAdded, None, Some(edge?)),
+        CollaborationEventType::EdgeDeleted => (PlanDagUpdateType::EdgeRemoved, None, Some(edge?)),
+        _ => return None,
+    };
+
+    Some(PlanDagUpdateEvent {
+        plan_id: event.plan_id,
+        update_type,
+        data: PlanDagUpdateData {
+            node,
+            edge,
+            metadata: None,
+        },
+        user_id: event.user_id,
+        timestamp: event.timestamp,
+    })
+}
+
+/// Delete persisted collaboration events older than the retention window.
+pub async fn prune_collaboration_events(
+    db: &DatabaseConnection,
+    retention_hours: i64,
+) -> Result<u64, String> {
+    let cutoff = chrono::Utc::now() - chrono::Duration::hours(retention_hours);
+    CollaborationEventService::new(db.clone())
+        .prune_older_than(cutoff)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Helper function to create collaboration events
 pub fn create_collaboration_event(
     plan_id: String,
@@ -444,3 +510,168 @@ pub async fn publish_execution_status_event(event: NodeExecutionStatusEvent) ->
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphql::mutations::plan_dag_delta::{publish_plan_dag_delta, touched_node_ids};
+    use crate::graphql::mutations::Mutation;
+    use crate::graphql::queries::Query;
+    use crate::graphql::types::plan_dag::{DataType, EdgeMetadata};
+    use crate::graphql::types::{PatchOp, PatchOperation};
+    use async_graphql::{Request, Schema};
+    use futures_util::StreamExt;
+    use layercake_core::app_context::AppContext;
+    use layercake_core::database::migrations::Migrator;
+    use layercake_core::services::SystemSettingsService;
+    use sea_orm::Database;
+    use sea_orm_migration::MigratorTrait;
+    use serde_json::json;
+    use std::sync::Arc;
+    use tokio::time::{sleep, timeout, Duration};
+
+    fn user_event(plan_id: &str, event_type: CollaborationEventType) -> CollaborationEvent {
+        create_collaboration_event(
+            plan_id.to_string(),
+            "user_1".to_string(),
+            event_type,
+            CollaborationEventData {
+                node_event: None,
+                edge_event: None,
+                user_event: Some(create_user_event_data(
+                    "user_1".to_string(),
+                    "Ada".to_string(),
+                    "#3B82F6".to_string(),
+                )),
+                cursor_event: None,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn published_events_are_replayed_except_cursor_moves() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+
+        let joined = user_event("project_7", CollaborationEventType::UserJoined);
+        let left = user_event("project_7", CollaborationEventType::UserLeft);
+        for event in [
+            joined.clone(),
+            user_event("project_7", CollaborationEventType::CursorMoved),
+            left.clone(),
+        ] {
+            publish_collaboration_event(&db, event).await.unwrap();
+        }
+
+        let history = load_collaboration_history(&db, "project_7").await;
+        let ids: Vec<_> = history.iter().map(|e| e.event_id.as_str()).collect();
+        assert_eq!(ids, vec![joined.event_id.as_str(), left.event_id.as_str()]);
+        assert_eq!(history[1].event_type, CollaborationEventType::UserLeft);
+        assert_eq!(
+            history[0].data.user_event.as_ref().unwrap().user_name,
+            "Ada"
+        );
+        assert!(load_collaboration_history(&db, "project_8")
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn node_scoped_delta_subscription_only_yields_deltas_touching_the_node() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("Failed to connect to test database");
+        Migrator::up(&db, None)
+            .await
+            .expect("Failed to run migrations");
+        let app = Arc::new(AppContext::new(db.clone()));
+        let system_settings = Arc::new(
+            SystemSettingsService::new(db.clone())
+                .await
+                .expect("System settings init"),
+        );
+        let schema = Schema::build(Query, Mutation::default(), Subscription)
+            .data(GraphQLContext::new(app, system_settings))
+            .finish();
+
+        // DELTA_EVENTS is process-wide, so use a project ID no other test publishes to.
+        let project_id = 2328;
+        let mut stream = schema.execute_stream(Request::new(format!(
+            r#"subscription {{ planDagDeltaChanged(projectId: {}, nodeId: "a") {{ nodeIds operations {{ path }} }} }}"#,
+            project_id
+        )));
+
+        let publisher = tokio::spawn(async move {
+            while DELTA_EVENTS.receiver_count(&project_id).await == 0 {
+                sleep(Duration::from_millis(10)).await;
+            }
+
+            let add = |path: &str, value: serde_json::Value| PatchOperation {
+                op: PatchOp::Add,
+                path: path.to_string(),
+                value: Some(value),
+                from: None,
+            };
+            let edge_b_to_a = PlanDagEdge {
+                id: "e_ba".to_string(),
+                source: "b".to_string(),
+                target: "a".to_string(),
+                metadata: EdgeMetadata {
+                    label: None,
+                    data_type: DataType::GraphData,
+                },
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            };
+            let metadata_update = PatchOperation {
+                op: PatchOp::Replace,
+                path: "/edges/0/metadata".to_string(),
+                value: Some(json!({ "label": "feeds" })),
+                from: None,
+            };
+
+            let deltas = vec![
+                vec![add("/nodes/0", json!({ "id": "b" }))],
+                vec![add("/nodes/1", json!({ "id": "a" }))],
+                vec![add("/edges/0", json!({ "source": "c", "target": "b" }))],
+                vec![metadata_update],
+            ];
+            for (version, operations) in deltas.into_iter().enumerate() {
+                let node_ids =
+                    touched_node_ids(&operations, &[], std::slice::from_ref(&edge_b_to_a));
+                publish_plan_dag_delta(
+                    project_id,
+                    1,
+                    version as i32 + 1,
+                    "user".to_string(),
+                    String::new(),
+                    operations,
+                    node_ids,
+                )
+                .await
+                .expect("publish delta");
+            }
+        });
+
+        let first = next_delta(&mut stream).await;
+        assert_eq!(first["nodeIds"], json!(["a"]));
+        assert_eq!(first["operations"][0]["path"], "/nodes/1");
+
+        let second = next_delta(&mut stream).await;
+        assert_eq!(second["nodeIds"], json!(["b", "a"]));
+        assert_eq!(second["operations"][0]["path"], "/edges/0/metadata");
+
+        publisher.await.expect("publisher task");
+    }
+
+    async fn next_delta(
+        stream: &mut (impl futures_util::Stream<Item = async_graphql::Response> + Unpin),
+    ) -> serde_json::Value {
+        let response = timeout(Duration::from_secs(2), stream.next())
+            .await
+            .expect("timed out waiting for a delta")
+            .expect("subscription ended");
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().expect("response data")["planDagDeltaChanged"].clone()
+    }
+}
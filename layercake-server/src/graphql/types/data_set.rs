@@ -7,8 +7,9 @@ use crate::graphql::context::GraphQLContext;
 use crate::graphql::errors::StructuredError;
 use crate::graphql::types::Project;
 use layercake_core::app_context::{
-    summarize_graph_counts, DataSetSummary, DataSetValidationSummary,
+    summarize_graph_counts, DataSetSummary, DataSetValidationSummary, DatasetSchemaSummary,
 };
+use layercake_core::dataset_schema::{DatasetSchema, FieldConstraint, FieldType, RecordSchema};
 use layercake_core::services::data_set_service::DataSetAnnotation;
 
 #[derive(SimpleObject, Serialize, Deserialize, Clone)]
@@ -83,6 +84,7 @@ impl DataSet {
             import_export_path: project.import_export_path,
             created_at: project.created_at,
             updated_at: project.updated_at,
+            deleted_at: project.deleted_at,
         })
     }
 
@@ -280,6 +282,9 @@ pub struct CreateDataSetInput {
     pub file_format: FileFormat,
     #[graphql(name = "tabularDataType")]
     pub tabular_data_type: Option<DataSetDataType>,
+    /// Check CSV/TSV rows individually. When true any bad row fails the upload;
+    /// when false bad rows are skipped and listed in a "Skipped rows" annotation.
+    pub strict: Option<bool>,
 }
 
 #[derive(InputObject)]
@@ -315,6 +320,7 @@ pub enum FileFormat {
     CSV,
     TSV,
     JSON,
+    DOT,
 }
 
 impl From<layercake_core::database::entities::common_types::FileFormat> for FileFormat {
@@ -323,6 +329,7 @@ impl From<layercake_core::database::entities::common_types::FileFormat> for File
             layercake_core::database::entities::common_types::FileFormat::Csv => FileFormat::CSV,
             layercake_core::database::entities::common_types::FileFormat::Tsv => FileFormat::TSV,
             layercake_core::database::entities::common_types::FileFormat::Json => FileFormat::JSON,
+            layercake_core::database::entities::common_types::FileFormat::Dot => FileFormat::DOT,
             _ => panic!("Unsupported file format for GraphQL conversion"),
         }
     }
@@ -334,6 +341,7 @@ impl From<FileFormat> for layercake_core::database::entities::common_types::File
             FileFormat::CSV => layercake_core::database::entities::common_types::FileFormat::Csv,
             FileFormat::TSV => layercake_core::database::entities::common_types::FileFormat::Tsv,
             FileFormat::JSON => layercake_core::database::entities::common_types::FileFormat::Json,
+            FileFormat::DOT => layercake_core::database::entities::common_types::FileFormat::Dot,
         }
     }
 }
@@ -408,6 +416,9 @@ pub struct ExportDataSetsInput {
     #[graphql(name = "dataSetIds")]
     pub data_set_ids: Vec<i32>,
     pub format: SpreadsheetFormat,
+    /// XLSX only: one sheet per layer plus `edges` and `summary` sheets
+    #[graphql(name = "groupByLayer")]
+    pub group_by_layer: Option<bool>,
 }
 
 #[derive(SimpleObject)]
@@ -449,3 +460,161 @@ pub struct MergeDataSetsInput {
     #[graphql(name = "deleteMerged")]
     pub delete_merged: bool,
 }
+
+/// How node ids are joined when merging data sets into a graph
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum DataSetMergeStrategy {
+    Union,
+    Intersection,
+    Left,
+    Right,
+}
+
+impl From<DataSetMergeStrategy> for layercake_core::graph_merge::MergeStrategy {
+    fn from(strategy: DataSetMergeStrategy) -> Self {
+        match strategy {
+            DataSetMergeStrategy::Union => Self::Union,
+            DataSetMergeStrategy::Intersection => Self::Intersection,
+            DataSetMergeStrategy::Left => Self::Left,
+            DataSetMergeStrategy::Right => Self::Right,
+        }
+    }
+}
+
+/// Which value wins when merged data sets disagree on a node or edge
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum DataSetConflictResolution {
+    PreferFirst,
+    PreferLast,
+    Error,
+}
+
+impl From<DataSetConflictResolution> for layercake_core::graph_merge::ConflictResolution {
+    fn from(resolution: DataSetConflictResolution) -> Self {
+        match resolution {
+            DataSetConflictResolution::PreferFirst => Self::PreferFirst,
+            DataSetConflictResolution::PreferLast => Self::PreferLast,
+            DataSetConflictResolution::Error => Self::Error,
+        }
+    }
+}
+
+/// Contract a DataSet's nodes and edges can be validated against
+#[derive(SimpleObject, InputObject, Clone, Debug)]
+#[graphql(input_name = "DatasetSchemaInput")]
+pub struct DatasetSchemaDefinition {
+    pub nodes: DatasetRecordSchema,
+    pub edges: DatasetRecordSchema,
+    /// Layers nodes and edges may use. Omit to allow any layer.
+    pub allowed_layers: Option<Vec<String>>,
+}
+
+#[derive(SimpleObject, InputObject, Clone, Debug)]
+#[graphql(input_name = "DatasetRecordSchemaInput")]
+pub struct DatasetRecordSchema {
+    /// Fields that must be present and non-empty on every row
+    pub required: Vec<String>,
+    /// Type constraints, checked where the field has a value
+    pub fields: Vec<DatasetFieldConstraint>,
+}
+
+#[derive(SimpleObject, InputObject, Clone, Debug)]
+#[graphql(input_name = "DatasetFieldConstraintInput")]
+pub struct DatasetFieldConstraint {
+    pub name: String,
+    pub field_type: DatasetFieldType,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum DatasetFieldType {
+    String,
+    Integer,
+    Number,
+    Boolean,
+}
+
+impl From<DatasetSchemaDefinition> for DatasetSchema {
+    fn from(input: DatasetSchemaDefinition) -> Self {
+        Self {
+            nodes: input.nodes.into(),
+            edges: input.edges.into(),
+            allowed_layers: input.allowed_layers,
+        }
+    }
+}
+
+impl From<DatasetRecordSchema> for RecordSchema {
+    fn from(input: DatasetRecordSchema) -> Self {
+        Self {
+            required: input.required,
+            fields: input
+                .fields
+                .into_iter()
+                .map(|field| FieldConstraint {
+                    name: field.name,
+                    field_type: match field.field_type {
+                        DatasetFieldType::String => FieldType::String,
+                        DatasetFieldType::Integer => FieldType::Integer,
+                        DatasetFieldType::Number => FieldType::Number,
+                        DatasetFieldType::Boolean => FieldType::Boolean,
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<DatasetSchema> for DatasetSchemaDefinition {
+    fn from(schema: DatasetSchema) -> Self {
+        Self {
+            nodes: schema.nodes.into(),
+            edges: schema.edges.into(),
+            allowed_layers: schema.allowed_layers,
+        }
+    }
+}
+
+impl From<RecordSchema> for DatasetRecordSchema {
+    fn from(schema: RecordSchema) -> Self {
+        Self {
+            required: schema.required,
+            fields: schema
+                .fields
+                .into_iter()
+                .map(|field| DatasetFieldConstraint {
+                    name: field.name,
+                    field_type: match field.field_type {
+                        FieldType::String => DatasetFieldType::String,
+                        FieldType::Integer => DatasetFieldType::Integer,
+                        FieldType::Number => DatasetFieldType::Number,
+                        FieldType::Boolean => DatasetFieldType::Boolean,
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A dataset schema saved under a name so it can be reused
+#[derive(SimpleObject, Clone, Debug)]
+pub struct SavedDatasetSchema {
+    pub id: i32,
+    pub project_id: i32,
+    pub name: String,
+    pub schema: DatasetSchemaDefinition,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<DatasetSchemaSummary> for SavedDatasetSchema {
+    fn from(summary: DatasetSchemaSummary) -> Self {
+        Self {
+            id: summary.id,
+            project_id: summary.project_id,
+            name: summary.name,
+            schema: summary.schema.into(),
+            created_at: summary.created_at,
+            updated_at: summary.updated_at,
+        }
+    }
+}
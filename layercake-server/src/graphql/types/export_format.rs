@@ -0,0 +1,28 @@
+use async_graphql::SimpleObject;
+
+#[derive(SimpleObject)]
+pub struct ExportFormatInfo {
+    pub id: String,
+    pub name: String,
+    pub extension: String,
+    pub mime_type: String,
+    pub supports_hierarchy: bool,
+    pub supports_layers: bool,
+    pub supports_weights: bool,
+    pub binary: bool,
+}
+
+impl From<layercake_core::plan::ExportFormatInfo> for ExportFormatInfo {
+    fn from(info: layercake_core::plan::ExportFormatInfo) -> Self {
+        Self {
+            id: info.id.to_string(),
+            name: info.name.to_string(),
+            extension: info.extension.to_string(),
+            mime_type: info.mime_type.to_string(),
+            supports_hierarchy: info.supports_hierarchy,
+            supports_layers: info.supports_layers,
+            supports_weights: info.supports_weights,
+            binary: info.binary,
+        }
+    }
+}
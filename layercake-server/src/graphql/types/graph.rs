@@ -1,14 +1,14 @@
 use async_graphql::*;
 use layercake_core::database::entities::{graph_data_edges, graph_data_nodes};
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use crate::graphql::context::GraphQLContext;
 use crate::graphql::errors::StructuredError;
+use crate::graphql::loaders::project_layer_palette;
 use crate::graphql::types::graph_edge::GraphEdge;
 use crate::graphql::types::graph_node::GraphNode;
 use crate::graphql::types::{Layer, Project};
-use layercake_core::services::GraphService;
 
 /// DEPRECATED: Use GraphData type instead.
 /// This type is maintained for backward compatibility but will be removed in a future version.
@@ -67,14 +67,7 @@ impl Graph {
     #[graphql(name = "layers")]
     async fn graph_layers(&self, ctx: &Context<'_>) -> Result<Vec<Layer>> {
         let context = ctx.data::<GraphQLContext>()?;
-        let palette_layers = GraphService::new(context.db.clone())
-            .get_all_resolved_layers(self.project_id)
-            .await
-            .unwrap_or_default();
-        let palette_map: HashMap<String, layercake_core::graph::Layer> = palette_layers
-            .into_iter()
-            .map(|layer| (layer.id.clone(), layer))
-            .collect();
+        let palette_map = project_layer_palette(ctx, self.project_id).await?;
         let nodes = graph_data_nodes::Entity::find()
             .filter(graph_data_nodes::Column::GraphDataId.eq(self.id))
             .all(&context.db)
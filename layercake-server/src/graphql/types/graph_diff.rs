@@ -5,6 +5,25 @@ use async_graphql::SimpleObject;
 pub struct GraphDiff {
     pub nodes: ItemDiff,
     pub edges: ItemDiff,
+    /// Source/target pairs connected in `to` but not in `from`.
+    pub added_edge_pairs: Vec<EdgePair>,
+    /// Source/target pairs connected in `from` but not in `to`.
+    pub removed_edge_pairs: Vec<EdgePair>,
+    /// Nodes present in both graphs whose layer was reassigned.
+    pub layer_changes: Vec<LayerChange>,
+}
+
+#[derive(SimpleObject)]
+pub struct EdgePair {
+    pub source: String,
+    pub target: String,
+}
+
+#[derive(SimpleObject)]
+pub struct LayerChange {
+    pub node_id: String,
+    pub from: String,
+    pub to: String,
 }
 
 /// Added/removed/changed ids for one item kind (nodes or edges).
@@ -36,6 +55,31 @@ impl From<layercake_core::graph_diff::GraphDiff> for GraphDiff {
         Self {
             nodes: d.nodes.into(),
             edges: d.edges.into(),
+            added_edge_pairs: d
+                .added_edge_pairs
+                .into_iter()
+                .map(|p| EdgePair {
+                    source: p.source,
+                    target: p.target,
+                })
+                .collect(),
+            removed_edge_pairs: d
+                .removed_edge_pairs
+                .into_iter()
+                .map(|p| EdgePair {
+                    source: p.source,
+                    target: p.target,
+                })
+                .collect(),
+            layer_changes: d
+                .layer_changes
+                .into_iter()
+                .map(|c| LayerChange {
+                    node_id: c.node_id,
+                    from: c.from,
+                    to: c.to,
+                })
+                .collect(),
         }
     }
 }
@@ -0,0 +1,153 @@
+use async_graphql::{Enum, SimpleObject};
+
+/// One-shot overview metrics for a graph.
+#[derive(SimpleObject)]
+pub struct GraphSummaryMetrics {
+    pub graph_id: i32,
+    pub node_count: i32,
+    pub edge_count: i32,
+    /// Directed density E / (N * (N - 1)); 0 for graphs with fewer than two nodes.
+    pub density: f64,
+    /// Mean of in-degree plus out-degree (2E / N).
+    pub average_degree: f64,
+    /// Weakly connected components, counting isolated nodes.
+    pub component_count: i32,
+}
+
+impl From<layercake_core::services::graph_analysis_service::GraphSummaryMetrics>
+    for GraphSummaryMetrics
+{
+    fn from(m: layercake_core::services::graph_analysis_service::GraphSummaryMetrics) -> Self {
+        Self {
+            graph_id: m.graph_id,
+            node_count: m.node_count as i32,
+            edge_count: m.edge_count as i32,
+            density: m.density,
+            average_degree: m.average_degree,
+            component_count: m.component_count as i32,
+        }
+    }
+}
+
+/// Connected component sizes of a graph.
+#[derive(SimpleObject)]
+pub struct ComponentSummary {
+    pub graph_id: i32,
+    /// Strongly connected components when true, weakly connected otherwise.
+    pub directed: bool,
+    pub component_count: i32,
+    pub largest_component_size: i32,
+    /// Size of every component, largest first; isolated nodes count as 1.
+    pub component_sizes: Vec<i32>,
+}
+
+impl From<layercake_core::services::graph_analysis_service::ComponentSummary> for ComponentSummary {
+    fn from(s: layercake_core::services::graph_analysis_service::ComponentSummary) -> Self {
+        Self {
+            graph_id: s.graph_id,
+            directed: s.directed,
+            component_count: s.component_count as i32,
+            largest_component_size: s.largest_component_size as i32,
+            component_sizes: s.component_sizes.into_iter().map(|n| n as i32).collect(),
+        }
+    }
+}
+
+/// Density and degree distribution of a graph.
+#[derive(SimpleObject)]
+pub struct Topology {
+    pub graph_id: i32,
+    /// Whether density uses the directed E / (N * (N - 1)) or undirected
+    /// 2E / (N * (N - 1)) formula.
+    pub directed: bool,
+    pub density: f64,
+    /// Mean of in-degree plus out-degree (2E / N).
+    pub avg_degree: f64,
+    pub max_degree: i32,
+    pub min_degree: i32,
+    /// Node count per degree that occurs, lowest degree first.
+    pub degree_histogram: Vec<DegreeCount>,
+}
+
+#[derive(SimpleObject)]
+pub struct DegreeCount {
+    pub degree: i32,
+    pub count: i32,
+}
+
+impl From<layercake_core::services::graph_analysis_service::TopologyReport> for Topology {
+    fn from(r: layercake_core::services::graph_analysis_service::TopologyReport) -> Self {
+        Self {
+            graph_id: r.graph_id,
+            directed: r.directed,
+            density: r.density,
+            avg_degree: r.average_degree,
+            max_degree: r.max_degree as i32,
+            min_degree: r.min_degree as i32,
+            degree_histogram: r
+                .degree_histogram
+                .into_iter()
+                .map(|bucket| DegreeCount {
+                    degree: bucket.degree as i32,
+                    count: bucket.count as i32,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Betweenness centrality scores of a graph's flow nodes.
+#[derive(SimpleObject)]
+pub struct BetweennessCentrality {
+    pub graph_id: i32,
+    pub weighted: bool,
+    pub directed: bool,
+    pub normalized: bool,
+    /// Every flow node, highest score first.
+    pub scores: Vec<NodeScore>,
+}
+
+#[derive(SimpleObject)]
+pub struct NodeScore {
+    pub node_id: String,
+    pub score: f64,
+}
+
+impl From<layercake_core::services::graph_analysis_service::BetweennessReport>
+    for BetweennessCentrality
+{
+    fn from(r: layercake_core::services::graph_analysis_service::BetweennessReport) -> Self {
+        Self {
+            graph_id: r.graph_id,
+            weighted: r.weighted,
+            directed: r.directed,
+            normalized: r.normalized,
+            scores: r
+                .scores
+                .into_iter()
+                .map(|s| NodeScore {
+                    node_id: s.node_id,
+                    score: s.score,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Scaling used by `normalizeEdgeWeights`
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum WeightNormalization {
+    MinMax,
+    ZScore,
+    Robust,
+}
+
+impl From<WeightNormalization> for layercake_core::graph::WeightNormalization {
+    fn from(method: WeightNormalization) -> Self {
+        match method {
+            WeightNormalization::MinMax => Self::MinMax,
+            WeightNormalization::ZScore => Self::ZScore,
+            WeightNormalization::Robust => Self::Robust,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use async_graphql::{Enum, SimpleObject};
+
+/// Search used for weighted shortest-path queries
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PathAlgorithm {
+    Dijkstra,
+    AStar,
+}
+
+impl From<PathAlgorithm> for layercake_core::graph::PathAlgorithm {
+    fn from(algorithm: PathAlgorithm) -> Self {
+        match algorithm {
+            PathAlgorithm::Dijkstra => Self::Dijkstra,
+            PathAlgorithm::AStar => Self::AStar,
+        }
+    }
+}
+
+/// Cheapest directed path between two nodes of a graph.
+#[derive(SimpleObject)]
+pub struct PathResult {
+    /// False when no path leads from source to target.
+    pub reachable: bool,
+    /// Node ids from source to target; empty when unreachable.
+    pub path: Vec<String>,
+    /// Sum of edge weights (or hop count when unweighted); null when unreachable.
+    pub cost: Option<i64>,
+}
+
+impl From<layercake_core::services::graph_analysis_service::ShortestPathReport> for PathResult {
+    fn from(report: layercake_core::services::graph_analysis_service::ShortestPathReport) -> Self {
+        Self {
+            reachable: report.reachable,
+            path: report.path,
+            cost: report.cost,
+        }
+    }
+}
+
+/// Edge with one endpoint in each of two layers.
+#[derive(SimpleObject)]
+pub struct CrossingEdge {
+    pub id: String,
+    pub source: String,
+    pub target: String,
+}
+
+/// How two layers of a graph connect. Paths ignore edge direction and may
+/// pass through other layers.
+#[derive(SimpleObject)]
+pub struct LayerConnectivity {
+    #[graphql(name = "layerA")]
+    pub layer_a: String,
+    #[graphql(name = "layerB")]
+    pub layer_b: String,
+    /// False when no path, direct or multi-hop, joins the two layers.
+    pub connected: bool,
+    /// Number of edges running directly between the two layers.
+    pub direct_edge_count: i32,
+    pub direct_edges: Vec<CrossingEdge>,
+    /// Fewest-hop node path from a node in layer A to a node in layer B;
+    /// empty when not connected.
+    pub path: Vec<String>,
+    /// Edges along `path`; null when not connected.
+    pub hop_count: Option<i32>,
+}
+
+impl From<layercake_core::services::graph_analysis_service::LayerConnectivityReport>
+    for LayerConnectivity
+{
+    fn from(
+        report: layercake_core::services::graph_analysis_service::LayerConnectivityReport,
+    ) -> Self {
+        Self {
+            layer_a: report.layer_a,
+            layer_b: report.layer_b,
+            connected: report.connected,
+            direct_edge_count: report.direct_edges.len() as i32,
+            direct_edges: report
+                .direct_edges
+                .into_iter()
+                .map(|edge| CrossingEdge {
+                    id: edge.id,
+                    source: edge.source,
+                    target: edge.target,
+                })
+                .collect(),
+            hop_count: report.connected.then(|| report.path.len() as i32 - 1),
+            path: report.path,
+        }
+    }
+}
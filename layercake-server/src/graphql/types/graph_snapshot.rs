@@ -0,0 +1,27 @@
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use layercake_core::database::entities::graph_snapshots;
+
+/// A named, immutable copy of a graph's nodes, edges and annotations.
+#[derive(SimpleObject)]
+pub struct GraphSnapshot {
+    pub id: i32,
+    pub graph_id: i32,
+    pub label: String,
+    pub node_count: i32,
+    pub edge_count: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<graph_snapshots::Model> for GraphSnapshot {
+    fn from(model: graph_snapshots::Model) -> Self {
+        Self {
+            id: model.id,
+            graph_id: model.graph_data_id,
+            label: model.label,
+            node_count: model.node_count,
+            edge_count: model.edge_count,
+            created_at: model.created_at,
+        }
+    }
+}
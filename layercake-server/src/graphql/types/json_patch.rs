@@ -81,6 +81,11 @@ pub struct PlanDagDeltaEvent {
 
     /// JSON Patch operations describing the changes
     pub operations: Vec<PatchOperation>,
+
+    /// IDs of the Plan DAG nodes the operations touch: the node itself for
+    /// node changes, or the source and target for edge changes. Lets
+    /// subscribers scoped to one node skip unrelated deltas.
+    pub node_ids: Vec<String>,
 }
 
 /// Result of applying a JSON Patch
@@ -6,17 +6,21 @@ pub mod sequence;
 pub mod story;
 // REMOVED: node, edge - dead code, GraphQL types not used
 pub mod data_set;
+pub mod export_format;
 pub mod graph;
 pub mod graph_data;
 pub mod graph_diff;
-pub mod palette;
 pub mod graph_edge;
 pub mod graph_edit;
+pub mod graph_metrics;
 pub mod graph_node;
 pub mod graph_paging;
+pub mod graph_path;
+pub mod graph_snapshot;
 pub mod json_patch;
 pub mod layer;
 pub mod library_item;
+pub mod palette;
 pub mod preview;
 pub mod sample_project;
 pub mod scalars;
@@ -200,7 +200,24 @@ pub struct RenderConfig {
     pub note_position: Option<NotePosition>,
     pub use_node_weight: Option<bool>,
     pub use_edge_weight: Option<bool>,
+    pub normalize_edge_weights: Option<bool>,
+    pub render_partitions_as_nodes: Option<bool>,
+    pub directed: Option<bool>,
+    pub precompute_layout: Option<bool>,
+    pub prettify: Option<bool>,
+    pub timestamp_format: Option<TimestampFormat>,
     pub layer_source_styles: Option<Vec<LayerSourceStyleOverride>>,
+    pub color_by_attribute: Option<ColorByAttribute>,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum TimestampFormat {
+    #[graphql(name = "RFC3339")]
+    #[serde(rename = "rfc3339")]
+    Rfc3339,
+    #[graphql(name = "EPOCH_MILLIS")]
+    #[serde(rename = "epochMillis")]
+    EpochMillis,
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
@@ -233,6 +250,9 @@ pub enum RenderBuiltinStyle {
     Light,
     #[graphql(name = "DARK")]
     Dark,
+    /// PlantUML only; other formats render it as LIGHT.
+    #[graphql(name = "BLUEPRINT")]
+    Blueprint,
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
@@ -256,11 +276,23 @@ pub struct LayerSourceStyleOverride {
     pub mode: LayerSourceStyle,
 }
 
+#[derive(SimpleObject, InputObject, Clone, Debug, Serialize, Deserialize)]
+#[graphql(input_name = "ColorByAttributeInput")]
+pub struct ColorByAttribute {
+    pub key: String,
+    #[graphql(name = "minColor")]
+    pub min_color: String,
+    #[graphql(name = "maxColor")]
+    pub max_color: String,
+}
+
 #[derive(SimpleObject, InputObject, Clone, Debug, Serialize, Deserialize)]
 #[graphql(input_name = "RenderTargetOptionsInput")]
 pub struct RenderTargetOptions {
     pub graphviz: Option<GraphvizRenderOptions>,
     pub mermaid: Option<MermaidRenderOptions>,
+    pub svg: Option<SvgRenderOptions>,
+    pub plantuml: Option<PlantUmlRenderOptions>,
 }
 
 #[derive(SimpleObject, InputObject, Clone, Debug, Serialize, Deserialize)]
@@ -300,6 +332,26 @@ pub enum GraphvizCommentStyle {
     Tooltip,
 }
 
+#[derive(SimpleObject, InputObject, Clone, Debug, Serialize, Deserialize)]
+#[graphql(input_name = "SvgRenderOptionsInput")]
+pub struct SvgRenderOptions {
+    /// Widest node label, in pixels, before it is truncated with an ellipsis.
+    pub label_max_width: Option<i32>,
+}
+
+/// Global PlantUML skinparam overrides applied on top of the built-in style.
+#[derive(SimpleObject, InputObject, Clone, Debug, Serialize, Deserialize)]
+#[graphql(input_name = "PlantUmlRenderOptionsInput")]
+pub struct PlantUmlRenderOptions {
+    /// Hex colour, with or without a leading `#`.
+    pub background_color: Option<String>,
+    pub font_name: Option<String>,
+    /// Hex colour, with or without a leading `#`.
+    pub arrow_color: Option<String>,
+    /// Corner radius for node shapes; 0 draws square corners.
+    pub round_corner: Option<i32>,
+}
+
 #[derive(SimpleObject, InputObject, Clone, Debug, Serialize, Deserialize)]
 #[graphql(input_name = "MermaidRenderOptionsInput")]
 pub struct MermaidRenderOptions {
@@ -80,4 +80,5 @@ pub enum ValidationWarningType {
     UnusedOutput,
     PerformanceImpact,
     ConfigurationSuggestion,
+    UnreachableNode,
 }
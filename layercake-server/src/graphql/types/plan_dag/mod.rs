@@ -14,6 +14,7 @@ pub mod metadata;
 pub mod node;
 pub mod position;
 pub mod transforms;
+pub mod validation;
 
 // Re-export commonly used types
 pub use config::*;
@@ -0,0 +1,265 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{
+    PlanDagInput, PlanDagNodeType, ValidationError, ValidationErrorType, ValidationResult,
+    ValidationWarning, ValidationWarningType,
+};
+
+impl PlanDagInput {
+    /// Check the DAG's structure: edges must reference existing nodes and
+    /// must not form cycles. Isolated nodes and nodes that no data set feeds
+    /// are reported as warnings.
+    pub fn validate(&self) -> ValidationResult {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        let node_ids: HashSet<&str> = self.nodes.iter().filter_map(|n| n.id.as_deref()).collect();
+
+        // Check for edges referencing non-existent nodes
+        for edge in &self.edges {
+            let edge_id_str = edge.id.clone().unwrap_or_else(|| "<unknown>".to_string());
+            if !node_ids.contains(edge.source.as_str()) {
+                errors.push(ValidationError {
+                    node_id: None,
+                    edge_id: edge.id.clone(),
+                    error_type: ValidationErrorType::InvalidConnection,
+                    message: format!(
+                        "Edge {} references non-existent source node {}",
+                        edge_id_str, edge.source
+                    ),
+                });
+            }
+            if !node_ids.contains(edge.target.as_str()) {
+                errors.push(ValidationError {
+                    node_id: None,
+                    edge_id: edge.id.clone(),
+                    error_type: ValidationErrorType::InvalidConnection,
+                    message: format!(
+                        "Edge {} references non-existent target node {}",
+                        edge_id_str, edge.target
+                    ),
+                });
+            }
+        }
+
+        // Only edges between known nodes take part in the graph checks below
+        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            if node_ids.contains(edge.source.as_str()) && node_ids.contains(edge.target.as_str()) {
+                adjacency
+                    .entry(edge.source.as_str())
+                    .or_default()
+                    .push(edge.target.as_str());
+            }
+        }
+        // Node order keeps the reported cycles stable between runs
+        let order: Vec<&str> = self.nodes.iter().filter_map(|n| n.id.as_deref()).collect();
+
+        for cycle in find_cycles(&order, &adjacency) {
+            errors.push(ValidationError {
+                node_id: cycle.first().map(|id| id.to_string()),
+                edge_id: None,
+                error_type: ValidationErrorType::CyclicDependency,
+                message: format!("Cycle detected between nodes: {}", cycle.join(" -> ")),
+            });
+        }
+
+        // Nodes fed, directly or transitively, by a data set node
+        let mut reachable: HashSet<&str> = self
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == PlanDagNodeType::DataSet)
+            .filter_map(|n| n.id.as_deref())
+            .collect();
+        let mut queue: VecDeque<&str> = reachable.iter().copied().collect();
+        while let Some(node_id) = queue.pop_front() {
+            for target in adjacency.get(node_id).into_iter().flatten() {
+                if reachable.insert(target) {
+                    queue.push_back(target);
+                }
+            }
+        }
+
+        for node in &self.nodes {
+            // Skip nodes without IDs (they will be generated)
+            let Some(node_id) = node.id.as_deref() else {
+                continue;
+            };
+            let has_connections = self
+                .edges
+                .iter()
+                .any(|e| e.source == node_id || e.target == node_id);
+            if !has_connections {
+                // Check for isolated nodes (nodes with no connections)
+                if self.nodes.len() > 1 {
+                    warnings.push(ValidationWarning {
+                        node_id: node.id.clone(),
+                        edge_id: None,
+                        warning_type: ValidationWarningType::UnusedOutput,
+                        message: format!("Node {} has no connections", node_id),
+                    });
+                }
+            } else if !reachable.contains(node_id) {
+                warnings.push(ValidationWarning {
+                    node_id: node.id.clone(),
+                    edge_id: None,
+                    warning_type: ValidationWarningType::UnreachableNode,
+                    message: format!("Node {} is not reachable from any data set node", node_id),
+                });
+            }
+        }
+
+        ValidationResult {
+            is_valid: errors.is_empty(),
+            errors,
+            warnings,
+        }
+    }
+}
+
+/// Depth-first search keeping the current path as a recursion stack; every
+/// edge back onto the stack closes a cycle, returned as the nodes along it.
+fn find_cycles<'a>(
+    order: &[&'a str],
+    adjacency: &HashMap<&'a str, Vec<&'a str>>,
+) -> Vec<Vec<&'a str>> {
+    let mut cycles = Vec::new();
+    let mut visited: HashSet<&str> = HashSet::new();
+
+    for &start in order {
+        if visited.contains(start) {
+            continue;
+        }
+        visited.insert(start);
+        // Each frame is a node on the current path and its next edge to follow
+        let mut stack: Vec<(&str, usize)> = vec![(start, 0)];
+        let mut on_stack: HashSet<&str> = HashSet::from([start]);
+
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            let Some(&target) = adjacency.get(node).and_then(|targets| targets.get(*next)) else {
+                on_stack.remove(node);
+                stack.pop();
+                continue;
+            };
+            *next += 1;
+
+            if on_stack.contains(target) {
+                let from = stack
+                    .iter()
+                    .position(|(id, _)| *id == target)
+                    .expect("node on stack");
+                cycles.push(stack[from..].iter().map(|(id, _)| *id).collect());
+            } else if visited.insert(target) {
+                on_stack.insert(target);
+                stack.push((target, 0));
+            }
+        }
+    }
+
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphql::types::plan_dag::{
+        DataType, EdgeMetadata, NodeMetadata, PlanDagEdgeInput, PlanDagMetadata, PlanDagNodeInput,
+        Position,
+    };
+
+    fn node(id: &str, node_type: PlanDagNodeType) -> PlanDagNodeInput {
+        PlanDagNodeInput {
+            id: Some(id.to_string()),
+            node_type,
+            position: Position { x: 0.0, y: 0.0 },
+            metadata: NodeMetadata {
+                label: id.to_string(),
+                description: None,
+            },
+            config: "{}".to_string(),
+        }
+    }
+
+    fn edge(source: &str, target: &str) -> PlanDagEdgeInput {
+        PlanDagEdgeInput {
+            id: Some(format!("{source}-{target}")),
+            source: source.to_string(),
+            target: target.to_string(),
+            metadata: EdgeMetadata {
+                label: None,
+                data_type: DataType::GraphData,
+            },
+        }
+    }
+
+    fn plan_dag(nodes: Vec<PlanDagNodeInput>, edges: Vec<PlanDagEdgeInput>) -> PlanDagInput {
+        PlanDagInput {
+            version: "1.0".to_string(),
+            nodes,
+            edges,
+            metadata: PlanDagMetadata {
+                version: "1.0".to_string(),
+                name: None,
+                description: None,
+                created: None,
+                last_modified: None,
+                author: None,
+            },
+        }
+    }
+
+    #[test]
+    fn linear_dag_is_valid() {
+        let result = plan_dag(
+            vec![
+                node("source", PlanDagNodeType::DataSet),
+                node("graph", PlanDagNodeType::Graph),
+                node("output", PlanDagNodeType::GraphArtefact),
+            ],
+            vec![edge("source", "graph"), edge("graph", "output")],
+        )
+        .validate();
+
+        assert!(result.is_valid);
+        assert!(result.errors.is_empty());
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn three_node_cycle_is_reported() {
+        let result = plan_dag(
+            vec![
+                node("source", PlanDagNodeType::DataSet),
+                node("a", PlanDagNodeType::Graph),
+                node("b", PlanDagNodeType::Transform),
+                node("c", PlanDagNodeType::Filter),
+                node("stray", PlanDagNodeType::Merge),
+                node("output", PlanDagNodeType::GraphArtefact),
+            ],
+            vec![
+                edge("source", "a"),
+                edge("a", "b"),
+                edge("b", "c"),
+                edge("c", "a"),
+                edge("stray", "output"),
+            ],
+        )
+        .validate();
+
+        assert!(!result.is_valid);
+        assert_eq!(result.errors.len(), 1);
+        let error = &result.errors[0];
+        assert_eq!(error.error_type, ValidationErrorType::CyclicDependency);
+        assert_eq!(error.node_id.as_deref(), Some("a"));
+        assert_eq!(error.message, "Cycle detected between nodes: a -> b -> c");
+
+        let unreachable: Vec<_> = result
+            .warnings
+            .iter()
+            .filter(|w| w.warning_type == ValidationWarningType::UnreachableNode)
+            .filter_map(|w| w.node_id.as_deref())
+            .collect();
+        assert_eq!(unreachable, vec!["stray", "output"]);
+    }
+}
@@ -25,6 +25,104 @@ pub struct TableColumn {
     pub nullable: bool,
 }
 
+/// Rows sampled when inferring preview column types
+pub const PREVIEW_TYPE_SAMPLE_ROWS: usize = 200;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Integer,
+    Number,
+    Boolean,
+    String,
+}
+
+impl ColumnType {
+    fn of(value: &serde_json::Value) -> Option<Self> {
+        match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::Bool(_) => Some(Self::Boolean),
+            serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => Some(Self::Integer),
+            serde_json::Value::Number(_) => Some(Self::Number),
+            // CSV-sourced values arrive as strings, so look inside them too
+            serde_json::Value::String(s) => {
+                let s = s.trim();
+                if s.is_empty() {
+                    None
+                } else if s.parse::<i64>().is_ok() {
+                    Some(Self::Integer)
+                } else if s.parse::<f64>().is_ok_and(f64::is_finite) {
+                    Some(Self::Number)
+                } else if s.eq_ignore_ascii_case("true") || s.eq_ignore_ascii_case("false") {
+                    Some(Self::Boolean)
+                } else {
+                    Some(Self::String)
+                }
+            }
+            _ => Some(Self::String),
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Integer, Self::Number) | (Self::Number, Self::Integer) => Self::Number,
+            _ => Self::String,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::String => "string",
+        }
+    }
+}
+
+impl TableColumn {
+    /// Infer columns from the first `sample_size` records. Columns appear in
+    /// the records' key order; a column is nullable when any sampled record omits
+    /// it or holds null/empty, and mixed types degrade to "string".
+    pub fn infer_from_records(records: &[serde_json::Value], sample_size: usize) -> Vec<Self> {
+        let sample: Vec<&serde_json::Map<String, serde_json::Value>> = records
+            .iter()
+            .take(sample_size)
+            .filter_map(|record| record.as_object())
+            .collect();
+
+        let mut names: Vec<&String> = Vec::new();
+        for record in &sample {
+            for key in record.keys() {
+                if !names.contains(&key) {
+                    names.push(key);
+                }
+            }
+        }
+
+        names
+            .into_iter()
+            .map(|name| {
+                let mut nullable = false;
+                let mut data_type: Option<ColumnType> = None;
+                for record in &sample {
+                    match record.get(name).and_then(ColumnType::of) {
+                        Some(value_type) => {
+                            data_type = Some(data_type.map_or(value_type, |t| t.merge(value_type)));
+                        }
+                        None => nullable = true,
+                    }
+                }
+                TableColumn {
+                    name: name.clone(),
+                    data_type: data_type.unwrap_or(ColumnType::String).as_str().to_string(),
+                    nullable,
+                }
+            })
+            .collect()
+    }
+}
+
 /// Row data for table preview
 #[derive(Clone, Debug, SimpleObject)]
 pub struct TableRow {
@@ -108,3 +206,41 @@ impl From<layercake_core::database::entities::graph_data_edges::Model> for Graph
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn infers_column_types_and_nullability() {
+        let records = vec![
+            json!({"id": "a", "weight": 1, "score": "1.5", "active": true, "note": "x"}),
+            json!({"id": "b", "weight": "2", "score": 3, "active": "FALSE"}),
+            json!({"id": "c", "weight": 3, "score": 2.25, "active": null, "note": 7}),
+        ];
+
+        let columns = TableColumn::infer_from_records(&records, PREVIEW_TYPE_SAMPLE_ROWS);
+        let summary: Vec<(&str, &str, bool)> = columns
+            .iter()
+            .map(|c| (c.name.as_str(), c.data_type.as_str(), c.nullable))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("active", "boolean", true),
+                ("id", "string", false),
+                ("note", "string", true),
+                ("score", "number", false),
+                ("weight", "integer", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn sampling_is_bounded() {
+        let records = vec![json!({"n": 1}), json!({"n": "not a number"})];
+        let columns = TableColumn::infer_from_records(&records, 1);
+        assert_eq!(columns[0].data_type, "integer");
+    }
+}
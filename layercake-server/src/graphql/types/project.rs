@@ -5,6 +5,23 @@ use chrono::{DateTime, Utc};
 use layercake_core::app_context::ProjectSummary;
 use layercake_core::database::entities::projects;
 
+/// How the `projects` query combines multiple tag filters.
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum TagMatchMode {
+    #[default]
+    Any,
+    All,
+}
+
+impl From<TagMatchMode> for layercake_core::app_context::TagMatchMode {
+    fn from(mode: TagMatchMode) -> Self {
+        match mode {
+            TagMatchMode::Any => Self::Any,
+            TagMatchMode::All => Self::All,
+        }
+    }
+}
+
 #[derive(SimpleObject)]
 #[graphql(complex)]
 pub struct Project {
@@ -18,6 +35,9 @@ pub struct Project {
     pub created_at: DateTime<Utc>,
     #[graphql(name = "updatedAt")]
     pub updated_at: DateTime<Utc>,
+    /// When the project was soft-deleted, if it is in the trash
+    #[graphql(name = "deletedAt")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<projects::Model> for Project {
@@ -31,6 +51,7 @@ impl From<projects::Model> for Project {
             import_export_path: model.import_export_path,
             created_at: model.created_at,
             updated_at: model.updated_at,
+            deleted_at: model.deleted_at,
         }
     }
 }
@@ -45,6 +66,7 @@ impl From<ProjectSummary> for Project {
             import_export_path: summary.import_export_path,
             created_at: summary.created_at,
             updated_at: summary.updated_at,
+            deleted_at: summary.deleted_at,
         }
     }
 }
@@ -1,7 +1,7 @@
 use anyhow::{anyhow, Result};
 use axum::routing::get_service;
 use axum::{
-    extract::State,
+    extract::{DefaultBodyLimit, State},
     routing::{get, post},
     Router,
 };
@@ -9,6 +9,7 @@ use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
+use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::services::{ServeDir, ServeFile};
 
 use crate::collaboration::{CollaborationCoordinator, CoordinatorHandle};
@@ -31,6 +32,18 @@ use layercake_projections::graphql::{
 };
 use layercake_projections::service::ProjectionService;
 
+/// Setting holding the upload request body limit, in megabytes.
+const MAX_REQUEST_BODY_SETTING: &str = "LAYERCAKE_MAX_REQUEST_BODY_MB";
+const DEFAULT_MAX_REQUEST_BODY_MB: usize = 64;
+
+/// Setting holding how long persisted collaboration events are kept, in hours.
+const COLLABORATION_RETENTION_SETTING: &str = "LAYERCAKE_COLLABORATION_EVENT_RETENTION_HOURS";
+const DEFAULT_COLLABORATION_RETENTION_HOURS: i64 = 24;
+
+/// Setting holding how long soft-deleted projects stay restorable, in days.
+const DELETED_PROJECT_RETENTION_SETTING: &str = "LAYERCAKE_DELETED_PROJECT_RETENTION_DAYS";
+const DEFAULT_DELETED_PROJECT_RETENTION_DAYS: i64 = 30;
+
 #[derive(Clone)]
 pub struct AppState {
     #[allow(dead_code)] // Reserved for future REST endpoints or middleware
@@ -108,6 +121,71 @@ pub async fn create_app(
             }
         });
 
+        // Spawn background task to prune persisted collaboration history; the
+        // retention window is re-read each tick so setting changes apply live
+        let prune_db = db.clone();
+        let prune_settings = system_settings.clone();
+        tokio::spawn(async move {
+            use std::time::Duration;
+
+            let mut interval = tokio::time::interval(Duration::from_secs(600));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                let hours = retention_setting(
+                    &prune_settings,
+                    COLLABORATION_RETENTION_SETTING,
+                    DEFAULT_COLLABORATION_RETENTION_HOURS,
+                )
+                .await;
+                match crate::graphql::subscriptions::prune_collaboration_events(&prune_db, hours)
+                    .await
+                {
+                    Ok(0) => {}
+                    Ok(pruned) => tracing::info!(
+                        "Pruned {} collaboration events older than {} hours",
+                        pruned,
+                        hours
+                    ),
+                    Err(e) => tracing::warn!("Failed to prune collaboration events: {}", e),
+                }
+            }
+        });
+
+        // Spawn background task to purge projects that have sat in the trash
+        // longer than the retention window
+        let purge_app = app_context.clone();
+        let purge_settings = system_settings.clone();
+        tokio::spawn(async move {
+            use std::time::Duration;
+
+            let mut interval = tokio::time::interval(Duration::from_secs(3600));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                let days = retention_setting(
+                    &purge_settings,
+                    DELETED_PROJECT_RETENTION_SETTING,
+                    DEFAULT_DELETED_PROJECT_RETENTION_DAYS,
+                )
+                .await;
+                let cutoff = chrono::Utc::now() - chrono::Duration::days(days);
+                match purge_app.purge_projects_deleted_before(cutoff).await {
+                    Ok(0) => {}
+                    Ok(purged) => tracing::info!(
+                        "Purged {} projects deleted more than {} days ago",
+                        purged,
+                        days
+                    ),
+                    Err(e) => tracing::warn!("Failed to purge deleted projects: {}", e),
+                }
+            }
+        });
+
         let graphql_context = GraphQLContext::new(app_context.clone(), system_settings.clone());
 
         let schema: Schema<Query, Mutation, Subscription> =
@@ -160,14 +238,27 @@ pub async fn create_app(
             .allow_credentials(false),
     };
 
+    let max_request_body_bytes = max_request_body_mb(&system_settings).await * 1024 * 1024;
+
     let mut app = Router::new()
         // Health check endpoint
         .route("/health", get(health::health_check))
+        .route("/healthz", get(health::liveness))
+        .route("/readyz", get(health::readiness))
         .route(
             "/api/library/{id}/download",
             get(library::download_library_item),
         )
-        .route("/api/library/upload", post(library::upload_library_item));
+        // Reject oversized uploads with 413 before the body is buffered. The
+        // extractor's own 2 MB default is replaced by the configured limit.
+        .route(
+            "/api/library/upload",
+            post(library::upload_library_item).layer(
+                ServiceBuilder::new()
+                    .layer(DefaultBodyLimit::disable())
+                    .layer(RequestBodyLimitLayer::new(max_request_body_bytes)),
+            ),
+        );
 
     // Serve projections build assets if available (relative to the cwd).
     let projections_path = std::env::current_dir()
@@ -231,12 +322,50 @@ pub async fn create_app(
     Ok(app)
 }
 
+async fn max_request_body_mb(system_settings: &SystemSettingsService) -> usize {
+    match system_settings.raw_value(MAX_REQUEST_BODY_SETTING).await {
+        Some(value) => value.trim().parse().unwrap_or_else(|_| {
+            tracing::warn!(
+                "Invalid {} value '{}'; using {} MB",
+                MAX_REQUEST_BODY_SETTING,
+                value,
+                DEFAULT_MAX_REQUEST_BODY_MB
+            );
+            DEFAULT_MAX_REQUEST_BODY_MB
+        }),
+        None => DEFAULT_MAX_REQUEST_BODY_MB,
+    }
+}
+
+/// Read a positive retention window from settings, falling back to `default`
+/// when the value is missing or invalid.
+async fn retention_setting(
+    system_settings: &SystemSettingsService,
+    key: &str,
+    default: i64,
+) -> i64 {
+    match system_settings.raw_value(key).await {
+        Some(value) => value
+            .trim()
+            .parse()
+            .ok()
+            .filter(|amount| *amount > 0)
+            .unwrap_or_else(|| {
+                tracing::warn!("Invalid {} value '{}'; using {}", key, value, default);
+                default
+            }),
+        None => default,
+    }
+}
+
 async fn graphql_handler(
     State(state): State<AppState>,
     headers: axum::http::HeaderMap,
     request: GraphQLRequest,
 ) -> AxumGraphQLResponse {
     use crate::graphql::context::RequestSession;
+    use crate::graphql::loaders::LayerPaletteLoader;
+    use layercake_core::services::GraphService;
 
     tracing::debug!("GraphQL request received");
     let mut req = request.into_inner();
@@ -247,6 +376,9 @@ async fn graphql_handler(
     {
         req = req.data(RequestSession(session_header.to_string()));
     }
+    req = req.data(LayerPaletteLoader::new(Arc::new(GraphService::new(
+        state.db.clone(),
+    ))));
 
     let mutation_log = capture_mutation_log_info(&mut req);
     let response = state.graphql_schema.execute(req).await;
@@ -494,3 +626,76 @@ async fn handle_graphql_ws<Q, M, S>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request as HttpRequest, StatusCode};
+    use layercake_core::database::migrations::Migrator;
+    use sea_orm::Database;
+    use sea_orm_migration::MigratorTrait;
+    use tower::ServiceExt;
+
+    fn upload_request(len: usize) -> HttpRequest<Body> {
+        HttpRequest::post("/api/library/upload")
+            .header("content-type", "multipart/form-data; boundary=x")
+            .header("content-length", len.to_string())
+            .body(Body::from(vec![b'a'; len]))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn upload_over_body_limit_is_rejected_with_413() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("Failed to connect to test database");
+        Migrator::up(&db, None)
+            .await
+            .expect("Failed to run migrations");
+        SystemSettingsService::new(db.clone())
+            .await
+            .expect("System settings init")
+            .update_setting(MAX_REQUEST_BODY_SETTING, "1".to_string())
+            .await
+            .expect("Failed to set body limit");
+
+        let app = create_app(db, None, ":memory:".to_string())
+            .await
+            .expect("Failed to create app");
+
+        let response = app
+            .clone()
+            .oneshot(upload_request(2 * 1024 * 1024))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // Within the limit the request reaches the handler, which rejects the
+        // malformed multipart body itself.
+        let response = app.oneshot(upload_request(1024)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn liveness_and_readiness_probes_report_ok() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("Failed to connect to test database");
+        Migrator::up(&db, None)
+            .await
+            .expect("Failed to run migrations");
+        let app = create_app(db, None, ":memory:".to_string())
+            .await
+            .expect("Failed to create app");
+
+        for path in ["/healthz", "/readyz"] {
+            let response = app
+                .clone()
+                .oneshot(HttpRequest::get(path).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{path}");
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use axum::{extract::State, http::StatusCode, response::Json};
+use sea_orm::DatabaseConnection;
 use serde_json::{json, Value};
 
 use crate::server::app::AppState;
@@ -11,3 +12,59 @@ pub async fn health_check(State(state): State<AppState>) -> Result<Json<Value>,
         "database": state.database_path,
     })))
 }
+
+/// Liveness probe: the process is up and serving requests. Never touches
+/// dependencies, so a slow database does not get the server restarted.
+pub async fn liveness() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+/// Readiness probe: 200 when every dependency answers, otherwise 503 with the
+/// failing checks so callers can tell "starting up" from "database down".
+pub async fn readiness(State(state): State<AppState>) -> (StatusCode, Json<Value>) {
+    let (ready, body) = readiness_report(&state.db).await;
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(body))
+}
+
+async fn readiness_report(db: &DatabaseConnection) -> (bool, Value) {
+    let database = match db.ping().await {
+        Ok(()) => json!({ "status": "ok" }),
+        Err(e) => json!({ "status": "error", "error": e.to_string() }),
+    };
+    let checks = json!({ "database": database });
+    let failed: Vec<&String> = checks
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(_, check)| check["status"] != "ok")
+        .map(|(name, _)| name)
+        .collect();
+
+    let ready = failed.is_empty();
+    (
+        ready,
+        json!({
+            "status": if ready { "ready" } else { "unavailable" },
+            "failed": failed,
+            "checks": checks,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn readiness_reports_failed_database_check() {
+        let (ready, body) = readiness_report(&DatabaseConnection::Disconnected).await;
+        assert!(!ready);
+        assert_eq!(body["failed"], json!(["database"]));
+        assert_eq!(body["checks"]["database"]["status"], "error");
+    }
+}
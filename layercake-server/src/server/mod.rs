@@ -122,6 +122,7 @@ fn open_in_browser(url: &str) {
 fn log_routes(port: u16) {
     info!("API Endpoints:");
     info!("  /health                     - Health check");
+    info!("  /healthz, /readyz           - Liveness and readiness probes");
 
     #[cfg(feature = "graphql")]
     {